@@ -17,7 +17,7 @@ mod database_args;
 pub use database_args::DatabaseArgs;
 
 mod secret_key;
-pub use secret_key::{get_secret_key, SecretKeyError};
+pub use secret_key::{get_secret_key, read_secret_key, SecretKeyError};
 
 /// PayloadBuilderArgs struct for configuring the payload builder
 mod payload_builder_args;
@@ -3,11 +3,16 @@
 use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
 use reth_config::Config;
+use reth_discv5::{Discv5Config, Discv5Enr, DEFAULT_DISCV5_PORT};
 use reth_net_nat::NatResolver;
 use reth_network::{HelloMessage, NetworkConfigBuilder};
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
 use secp256k1::SecretKey;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    net::{Ipv4Addr, SocketAddrV4},
+    path::PathBuf,
+    sync::Arc,
+};
 
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Args)]
@@ -32,8 +37,9 @@ pub struct NetworkArgs {
     #[arg(long, value_delimiter = ',')]
     pub bootnodes: Option<Vec<NodeRecord>>,
 
-    /// The path to the known peers file. Connected peers are dumped to this file on nodes
-    /// shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
+    /// The path to the known peers file. Known peers and their history are dumped to this file
+    /// periodically and on node shutdown, and read on startup. Cannot be used with
+    /// `--no-persist-peers`.
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
@@ -124,6 +130,28 @@ pub struct DiscoveryArgs {
     /// The UDP port to use for P2P discovery/networking. default: 30303
     #[arg(long = "discovery.port", name = "discovery.port", value_name = "DISCOVERY_PORT")]
     pub port: Option<u16>,
+
+    /// Enable Discv5 discovery.
+    #[arg(long, conflicts_with = "disable_discovery")]
+    pub enable_discv5_discovery: bool,
+
+    /// The UDP port to use for Discv5 discovery.
+    #[arg(
+        long = "discovery.v5.port",
+        name = "discovery.v5.port",
+        value_name = "DISCOVERY_V5_PORT",
+        default_value_t = DEFAULT_DISCV5_PORT
+    )]
+    pub discv5_port: u16,
+
+    /// Comma separated ENRs of the nodes to bootstrap Discv5 discovery with.
+    #[arg(
+        long = "discovery.v5.bootnodes",
+        name = "discovery.v5.bootnodes",
+        value_name = "ENRS",
+        value_delimiter = ','
+    )]
+    pub discv5_bootnodes: Vec<Discv5Enr>,
 }
 
 impl DiscoveryArgs {
@@ -139,6 +167,15 @@ impl DiscoveryArgs {
         if self.disable_discovery || self.disable_discv4_discovery {
             network_config_builder = network_config_builder.disable_discv4_discovery();
         }
+
+        if self.enable_discv5_discovery && !self.disable_discovery {
+            let config = Discv5Config::builder(
+                SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, self.discv5_port).into(),
+            )
+            .add_boot_nodes(self.discv5_bootnodes.clone())
+            .build();
+            network_config_builder = network_config_builder.discovery_v5(config);
+        }
         network_config_builder
     }
 }
@@ -163,4 +200,21 @@ mod tests {
             CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "extip:0.0.0.0"]).args;
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
     }
+
+    #[test]
+    fn parse_discv5_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.discovery.enable_discv5_discovery);
+        assert_eq!(args.discovery.discv5_port, DEFAULT_DISCV5_PORT);
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--discovery.v5.port",
+            "9300",
+        ])
+        .args;
+        assert!(args.discovery.enable_discv5_discovery);
+        assert_eq!(args.discovery.discv5_port, 9300);
+    }
 }
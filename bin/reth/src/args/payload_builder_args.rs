@@ -5,7 +5,8 @@ use clap::{
 };
 use reth_primitives::{bytes::BytesMut, constants::MAXIMUM_EXTRA_DATA_SIZE};
 use reth_rlp::Encodable;
-use std::{ffi::OsStr, time::Duration};
+use std::{ffi::OsStr, path::PathBuf, time::Duration};
+use url::Url;
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Args, PartialEq, Default)]
@@ -34,6 +35,21 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", help_heading = "Builder", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// The url of a MEV-Boost relay to submit built payloads to.
+    ///
+    /// Can be specified multiple times. Requires `--builder.relay-secret-key`.
+    #[arg(
+        long = "builder.relay",
+        help_heading = "Builder",
+        value_name = "URL",
+        requires = "relay_secret_key"
+    )]
+    pub relays: Vec<Url>,
+
+    /// Path to a file containing the hex encoded BLS secret key used to sign relay submissions.
+    #[arg(long = "builder.relay-secret-key", help_heading = "Builder", value_name = "PATH")]
+    pub relay_secret_key: Option<PathBuf>,
 }
 
 impl PayloadBuilderArgs {
@@ -114,6 +130,32 @@ mod tests {
         assert_eq!(args.extradata, extradata);
     }
 
+    #[test]
+    fn test_args_with_relays() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.relay",
+            "http://127.0.0.1:9062",
+            "--builder.relay",
+            "http://127.0.0.1:9063",
+            "--builder.relay-secret-key",
+            "builder.key",
+        ])
+        .args;
+        assert_eq!(args.relays.len(), 2);
+        assert_eq!(args.relay_secret_key, Some(PathBuf::from("builder.key")));
+    }
+
+    #[test]
+    fn test_relay_requires_secret_key() {
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.relay",
+            "http://127.0.0.1:9062",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_invalid_extradata() {
         let extradata = "x".repeat(MAXIMUM_EXTRA_DATA_SIZE + 1);
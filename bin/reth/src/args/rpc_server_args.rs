@@ -7,6 +7,7 @@ use clap::{
 };
 use futures::TryFutureExt;
 use reth_network_api::{NetworkInfo, Peers};
+use reth_primitives::Address;
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
     StateProviderFactory,
@@ -18,7 +19,7 @@ use reth_rpc::{
         },
         gas_oracle::GasPriceOracleConfig,
     },
-    JwtError, JwtSecret,
+    FlashbotsConfig, JwtError, JwtSecret,
 };
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
@@ -28,6 +29,7 @@ use reth_rpc_builder::{
     RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::{EngineApi, EngineApiServer};
+use reth_rpc_types::SyncProgress;
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use std::{
@@ -35,6 +37,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
 };
+use tokio::sync::watch;
 use tracing::{debug, info};
 
 /// Default max number of subscriptions per connection.
@@ -147,6 +150,11 @@ pub struct RpcServerArgs {
     /// Maximum number of env cache entries.
     #[arg(long, default_value_t = DEFAULT_ENV_CACHE_MAX_LEN)]
     pub env_cache_len: u32,
+
+    /// Comma separated list of accounts that blocks validated by the `flashbots` namespace must
+    /// not touch.
+    #[arg(long = "flashbots.blacklist", value_name = "ADDRESS", value_delimiter = ',')]
+    pub flashbots_blacklist: Vec<Address>,
 }
 
 impl RpcServerArgs {
@@ -177,6 +185,11 @@ impl RpcServerArgs {
             .gpo_config(self.gas_price_oracle_config())
     }
 
+    /// Extracts the [FlashbotsConfig] from the args.
+    pub fn flashbots_config(&self) -> FlashbotsConfig {
+        FlashbotsConfig { blacklist: self.flashbots_blacklist.iter().copied().collect() }
+    }
+
     /// Convenience function that returns whether ipc is enabled
     ///
     /// By default IPC is enabled therefor it is enabled if the `ipcdisable` is false.
@@ -228,6 +241,7 @@ impl RpcServerArgs {
         network: Network,
         executor: Tasks,
         events: Events,
+        sync_status: watch::Receiver<SyncProgress>,
         engine_api: Engine,
         jwt_secret: JwtSecret,
     ) -> Result<(RpcServerHandle, AuthServerHandle), RpcError>
@@ -257,6 +271,7 @@ impl RpcServerArgs {
             .with_network(network)
             .with_events(events)
             .with_executor(executor)
+            .with_sync_status(sync_status)
             .build_with_auth_server(module_config, engine_api);
 
         let server_config = self.rpc_server_config();
@@ -362,8 +377,12 @@ impl RpcServerArgs {
     /// This sets all the api modules, and configures additional settings like gas price oracle
     /// settings in the [TransportRpcModuleConfig].
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default()
-            .with_config(RpcModuleConfig::new(self.eth_config()));
+        let mut config = TransportRpcModuleConfig::default().with_config(
+            RpcModuleConfig::builder()
+                .eth(self.eth_config())
+                .flashbots(self.flashbots_config())
+                .build(),
+        );
 
         if self.http {
             config = config.with_http(
@@ -554,4 +573,18 @@ mod tests {
         );
         assert_eq!(config.ipc_endpoint().unwrap().path(), constants::DEFAULT_IPC_ENDPOINT);
     }
+
+    #[test]
+    fn test_flashbots_blacklist() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--flashbots.blacklist",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        let blacklist = args.flashbots_config().blacklist;
+        assert_eq!(blacklist.len(), 2);
+        assert!(blacklist.contains(&Address::from_low_u64_be(1)));
+        assert!(blacklist.contains(&Address::from_low_u64_be(2)));
+    }
 }
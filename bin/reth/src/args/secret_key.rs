@@ -27,11 +27,7 @@ pub fn get_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyErro
     let exists = secret_key_path.try_exists();
 
     match exists {
-        Ok(true) => {
-            let contents = fs::read_to_string(secret_key_path)?;
-            Ok((contents.as_str().parse::<SecretKey>())
-                .map_err(SecretKeyError::SecretKeyDecodeError)?)
-        }
+        Ok(true) => read_secret_key(secret_key_path),
         Ok(false) => {
             if let Some(dir) = secret_key_path.parent() {
                 // Create parent directory
@@ -49,3 +45,10 @@ pub fn get_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyErro
         }),
     }
 }
+
+/// Loads a [`SecretKey`] from a specified path. Unlike [`get_secret_key`], this fails if no file
+/// exists there.
+pub fn read_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyError> {
+    let contents = fs::read_to_string(secret_key_path)?;
+    Ok((contents.as_str().parse::<SecretKey>()).map_err(SecretKeyError::SecretKeyDecodeError)?)
+}
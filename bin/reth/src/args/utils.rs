@@ -104,6 +104,7 @@ pub fn parse_socket_address(value: &str) -> eyre::Result<SocketAddr, SocketAddre
 mod tests {
     use super::*;
     use proptest::prelude::Rng;
+    use reth_primitives::{ForkCondition, Hardfork};
     use secp256k1::rand::thread_rng;
 
     #[test]
@@ -114,6 +115,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_geth_genesis_with_cancun() {
+        let genesis = r#"{
+            "config": { "chainId": 1337, "shanghaiTime": 0, "cancunTime": 0 },
+            "difficulty": "0x0",
+            "gasLimit": "0x1c9c380",
+            "alloc": {}
+        }"#;
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), genesis).unwrap();
+
+        let chain_spec = genesis_value_parser(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(chain_spec.fork(Hardfork::Cancun), ForkCondition::Timestamp(0));
+        assert_eq!(chain_spec.fork_timestamps.cancun, Some(0));
+    }
+
     #[test]
     fn parse_socket_addresses() {
         for value in ["localhost:9000", ":9000", "9000"] {
@@ -67,6 +67,15 @@ pub struct ImportCommand {
     #[clap(flatten)]
     db: DatabaseArgs,
 
+    /// Fully verify the ethash proof-of-work of pre-merge blocks.
+    ///
+    /// This checks the difficulty, mix hash and nonce of every imported header, which is
+    /// recommended for files from untrusted sources but slow.
+    ///
+    /// Only supported on chains that were secured by ethash before the merge.
+    #[arg(long, verbatim_doc_comment)]
+    verify_pow: bool,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -80,6 +89,14 @@ impl ImportCommand {
     pub async fn execute(self) -> eyre::Result<()> {
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
 
+        let consensus = BeaconConsensus::new(self.chain.clone());
+        if self.verify_pow && !consensus.is_ethash_chain() {
+            eyre::bail!(
+                "cannot verify the proof-of-work of {}, it is not secured by ethash",
+                self.chain.chain
+            );
+        }
+
         // add network name to data dir
         let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
         let config_path = self.config.clone().unwrap_or(data_dir.config_path());
@@ -97,7 +114,7 @@ impl ImportCommand {
 
         init_genesis(db.clone(), self.chain.clone())?;
 
-        let consensus = Arc::new(BeaconConsensus::new(self.chain.clone()));
+        let consensus = Arc::new(consensus.with_ethash_verification(self.verify_pow));
         info!(target: "reth::cli", "Consensus engine initialized");
 
         // create a new FileClient
@@ -163,6 +180,8 @@ impl ImportCommand {
             .with_tip_sender(tip_tx)
             // we want to sync all blocks the file client provides or 0 if empty
             .with_max_block(file_client.max_block().unwrap_or(0))
+            .with_commit_interval(config.stages.commit_interval)
+            .with_max_dirty_size(config.stages.memory_budget)
             .add_stages(
                 DefaultStages::new(
                     HeaderSyncMode::Tip(tip_rx),
@@ -183,6 +202,7 @@ impl ImportCommand {
                     ExecutionStageThresholds {
                         max_blocks: config.stages.execution.max_blocks,
                         max_changes: config.stages.execution.max_changes,
+                        max_memory: config.stages.execution_memory_budget(),
                     },
                 )),
             )
@@ -211,4 +231,12 @@ mod tests {
             assert_eq!(args.chain.chain, chain.parse().unwrap());
         }
     }
+
+    #[test]
+    fn parse_import_verify_pow() {
+        let args: ImportCommand = ImportCommand::parse_from(["reth", "."]);
+        assert!(!args.verify_pow);
+        let args: ImportCommand = ImportCommand::parse_from(["reth", "--verify-pow", "."]);
+        assert!(args.verify_pow);
+    }
 }
@@ -0,0 +1,62 @@
+use crate::args::utils::genesis_value_parser;
+use clap::Parser;
+use reth_primitives::{ChainSpec, DisplayHardforks};
+use std::sync::Arc;
+
+/// Prints the fork schedule and the fork ids of a chain.
+#[derive(Debug, Parser)]
+pub struct InspectCommand {
+    /// The chain to inspect.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+}
+
+impl InspectCommand {
+    /// Execute the `inspect` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        println!("Chain:        {} ({})", self.chain.chain, self.chain.chain.id());
+        println!("Genesis hash: {:?}", self.chain.genesis_hash());
+        println!();
+        print!("{}", DisplayHardforks::from(self.chain.hardforks().clone()));
+        println!();
+        println!("Fork ids (EIP-2124):");
+        for (fork, fork_id) in self.chain.fork_ids() {
+            println!(
+                "- {:32} hash 0x{}, next {}",
+                fork.to_string(),
+                hex::encode(fork_id.hash.0),
+                fork_id.next
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::SEPOLIA;
+
+    #[test]
+    fn parse_inspect_command_chain_args() {
+        let args = InspectCommand::parse_from(["reth"]);
+        assert_eq!(args.chain.chain, reth_primitives::Chain::mainnet());
+
+        let args = InspectCommand::parse_from(["reth", "--chain", "sepolia"]);
+        assert_eq!(args.chain.genesis_hash(), SEPOLIA.genesis_hash());
+    }
+}
@@ -1,7 +1,33 @@
-//! Command line utilities for initializing a chain.
+//! Command line utilities for initializing and inspecting a chain.
+use clap::{Parser, Subcommand};
 
 mod import;
 mod init;
+mod inspect;
 
 pub use import::ImportCommand;
 pub use init::InitCommand;
+pub use inspect::InspectCommand;
+
+/// `reth chain` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth chain` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Print the resolved fork schedule and the fork ids of a chain.
+    Inspect(InspectCommand),
+}
+
+impl Command {
+    /// Execute `chain` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Inspect(command) => command.execute().await,
+        }
+    }
+}
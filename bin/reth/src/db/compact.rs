@@ -0,0 +1,99 @@
+use clap::Parser;
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{open_db_exclusive, open_db_read_only, version::create_db_version_file};
+use reth_interfaces::db::LogLevel;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db compact` command
+pub struct Command {
+    /// The directory to write the compacted database to.
+    ///
+    /// Defaults to `<DB_PATH>-compact` next to the database directory.
+    #[arg(long, value_name = "PATH")]
+    to: Option<PathBuf>,
+
+    /// Replace the original database with the compacted copy.
+    ///
+    /// The original database is moved to `<DB_PATH>-old` and has to be removed manually once the
+    /// node runs fine on the compacted copy.
+    #[arg(long)]
+    replace: bool,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// The database is opened exclusively, so the command fails if the node is running.
+    pub fn execute(self, db_path: &Path, log_level: Option<LogLevel>) -> eyre::Result<()> {
+        let dest = self.to.unwrap_or_else(|| with_suffix(db_path, "compact"));
+        if dest.exists() {
+            eyre::bail!("Destination {dest:?} already exists.")
+        }
+        let old = with_suffix(db_path, "old");
+        if self.replace && old.exists() {
+            eyre::bail!("{old:?} already exists, remove it before replacing the database.")
+        }
+
+        let db = open_db_exclusive(db_path, log_level)?;
+        let before = db.page_usage()?;
+        info!(
+            target: "reth::cli",
+            size = human_bytes(before.total_size() as f64),
+            free = human_bytes(before.free_size() as f64),
+            "Compacting database into {dest:?}"
+        );
+
+        std::fs::create_dir_all(&dest)
+            .wrap_err_with(|| format!("Could not create directory {dest:?}"))?;
+        db.copy_compacted(&dest)?;
+        create_db_version_file(&dest)?;
+        // the original database must be closed before it's moved
+        drop(db);
+
+        let after = open_db_read_only(&dest, None)?.page_usage()?;
+
+        println!(
+            "Compacted database from {} to {} ({} pages freed)",
+            human_bytes(before.total_size() as f64),
+            human_bytes(after.total_size() as f64),
+            before.total_pages.saturating_sub(after.total_pages),
+        );
+
+        if self.replace {
+            std::fs::rename(db_path, &old)
+                .wrap_err_with(|| format!("Could not move {db_path:?} to {old:?}"))?;
+            std::fs::rename(&dest, db_path)
+                .wrap_err_with(|| format!("Could not move {dest:?} to {db_path:?}"))?;
+            println!(
+                "Replaced {db_path:?} with the compacted database, the original database was moved to {old:?}."
+            );
+        } else {
+            println!("Compacted database written to {dest:?}");
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends `-<suffix>` to the last path component.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!("-{suffix}"));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixed_path() {
+        assert_eq!(
+            with_suffix(Path::new("/data/mainnet/db"), "compact"),
+            Path::new("/data/mainnet/db-compact")
+        );
+    }
+}
@@ -1,9 +1,14 @@
 use crate::utils::DbTool;
 use clap::Parser;
 
-use super::tui::DbListTUI;
+use super::tui::{DbListTUI, Page, PageQuery, TuiEntry};
 use eyre::WrapErr;
-use reth_db::{database::Database, table::Table, DatabaseEnvRO, TableType, TableViewer, Tables};
+use regex::Regex;
+use reth_db::{
+    database::Database,
+    table::{Compress, Encode, Table},
+    DatabaseEnvRO, TableType, TableViewer, Tables,
+};
 use tracing::error;
 
 const DEFAULT_NUM_ITEMS: &str = "5";
@@ -22,6 +27,9 @@ pub struct Command {
     /// How many items to take from the walker
     #[arg(long, short, default_value = DEFAULT_NUM_ITEMS)]
     len: usize,
+    /// Only list entries whose JSON encoded value matches the regex.
+    #[arg(long, short)]
+    filter: Option<Regex>,
     /// Dump as JSON instead of using TUI.
     #[arg(long, short)]
     json: bool,
@@ -34,47 +42,116 @@ impl Command {
             error!(target: "reth::cli", "Unsupported table.");
         }
 
-        self.table.view(&ListTableViewer { tool, args: &self })?;
+        let total_entries = table_entries(tool, self.table)?;
+        if self.skip > total_entries.saturating_sub(1) {
+            error!(
+                target: "reth::cli",
+                "Start index {start} is greater than the final entry index ({final_entry_idx}) in the table {table}",
+                start = self.skip,
+                final_entry_idx = total_entries.saturating_sub(1),
+                table = self.table.name()
+            );
+            return Ok(())
+        }
+
+        let query = PageQuery {
+            table: self.table,
+            skip: self.skip,
+            count: self.len,
+            reverse: self.reverse,
+            start_key: None,
+            end_key: None,
+            filter: self.filter,
+        };
+
+        if self.json {
+            let list_result = self.table.view(&JsonTableViewer { tool, query: &query })?;
+            println!("{}", serde_json::to_string_pretty(&list_result)?);
+            Ok(())
+        } else {
+            DbListTUI::new(|query| fetch_page(tool, query), query).run()
+        }
+    }
+}
+
+/// Returns the number of entries in the table.
+fn table_entries(tool: &DbTool<'_, DatabaseEnvRO>, table: Tables) -> eyre::Result<usize> {
+    tool.db.view(|tx| {
+        let table_db = tx.inner.open_db(Some(table.name())).wrap_err("Could not open db.")?;
+        let stats =
+            tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {table}"))?;
+        Ok::<_, eyre::Report>(stats.entries())
+    })?
+}
+
+/// Fetches the page of entries described by the query for the [DbListTUI].
+fn fetch_page(tool: &DbTool<'_, DatabaseEnvRO>, query: &PageQuery) -> eyre::Result<Page> {
+    let entries = query.table.view(&TuiTableViewer { tool, query })?;
+    // The total is only cheap to get if the whole table is listed.
+    let total_entries =
+        if query.is_restricted() { None } else { Some(table_entries(tool, query.table)?) };
+    Ok(Page { entries, total_entries })
+}
+
+/// Lists the entries matching the query in a type-aware way.
+fn list_entries<T: Table>(
+    tool: &DbTool<'_, DatabaseEnvRO>,
+    query: &PageQuery,
+) -> eyre::Result<Vec<(T::Key, T::Value)>> {
+    let parse_key = |key: &Option<String>| {
+        key.as_deref()
+            .map(serde_json::from_str::<T::Key>)
+            .transpose()
+            .map_err(|err| eyre::eyre!("Invalid key for table {}: {err}", T::NAME))
+    };
+    let (start_key, end_key) = (parse_key(&query.start_key)?, parse_key(&query.end_key)?);
+
+    tool.list_filtered::<T>(
+        start_key,
+        end_key,
+        query.skip,
+        query.count,
+        query.reverse,
+        |_, value| {
+            query.filter.as_ref().map_or(true, |filter| {
+                serde_json::to_string(value).map_or(false, |json| filter.is_match(&json))
+            })
+        },
+    )
+}
+
+struct JsonTableViewer<'a> {
+    tool: &'a DbTool<'a, DatabaseEnvRO>,
+    query: &'a PageQuery,
+}
+
+impl TableViewer<serde_json::Value> for JsonTableViewer<'_> {
+    type Error = eyre::Report;
 
-        Ok(())
+    fn view<T: Table>(&self) -> Result<serde_json::Value, Self::Error> {
+        Ok(serde_json::to_value(list_entries::<T>(self.tool, self.query)?)?)
     }
 }
 
-struct ListTableViewer<'a> {
+struct TuiTableViewer<'a> {
     tool: &'a DbTool<'a, DatabaseEnvRO>,
-    args: &'a Command,
+    query: &'a PageQuery,
 }
 
-impl TableViewer<()> for ListTableViewer<'_> {
+impl TableViewer<Vec<TuiEntry>> for TuiTableViewer<'_> {
     type Error = eyre::Report;
 
-    fn view<T: Table>(&self) -> Result<(), Self::Error> {
-        self.tool.db.view(|tx| {
-            let table_db = tx.inner.open_db(Some(self.args.table.name())).wrap_err("Could not open db.")?;
-            let stats = tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {}", stringify!($table)))?;
-            let total_entries = stats.entries();
-            if self.args.skip > total_entries - 1 {
-                error!(
-                    target: "reth::cli",
-                    "Start index {start} is greater than the final entry index ({final_entry_idx}) in the table {table}",
-                    start = self.args.skip,
-                    final_entry_idx = total_entries - 1,
-                    table = self.args.table.name()
-                );
-                return Ok(());
-            }
-
-            if self.args.json {
-                let list_result = self.tool.list::<T>(self.args.skip, self.args.len, self.args.reverse)?.into_iter().collect::<Vec<_>>();
-                println!("{}", serde_json::to_string_pretty(&list_result)?);
-                Ok(())
-            } else {
-                DbListTUI::<_, T>::new(|skip, count| {
-                    self.tool.list::<T>(skip, count, self.args.reverse).unwrap()
-                }, self.args.skip, self.args.len, total_entries).run()
-            }
-        })??;
-
-        Ok(())
+    fn view<T: Table>(&self) -> Result<Vec<TuiEntry>, Self::Error> {
+        list_entries::<T>(self.tool, self.query)?
+            .into_iter()
+            .map(|(key, value)| {
+                Ok::<_, eyre::Report>(TuiEntry {
+                    key: format!("{key:?}"),
+                    raw_key: key.encode().as_ref().to_vec(),
+                    value: serde_json::to_string_pretty(&value)?,
+                    raw_value: value.compress().as_ref().to_vec(),
+                })
+            })
+            .collect()
     }
 }
@@ -17,6 +17,7 @@ use reth_db::{
 use reth_primitives::ChainSpec;
 use std::sync::Arc;
 
+mod compact;
 mod get;
 mod list;
 /// DB List TUI
@@ -71,6 +72,10 @@ pub enum Subcommands {
     Get(get::Command),
     /// Deletes all database entries
     Drop,
+    /// Copies the database into a new directory, reclaiming all free pages.
+    ///
+    /// The node must be stopped while the database is compacted.
+    Compact(compact::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -152,6 +157,9 @@ impl Command {
                 let mut tool = DbTool::new(&db, self.chain.clone())?;
                 tool.drop(db_path)?;
             }
+            Subcommands::Compact(command) => {
+                command.execute(&db_path, self.db.log_level)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
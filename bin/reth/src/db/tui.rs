@@ -3,8 +3,11 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use reth_db::table::Table;
+use regex::Regex;
+use reth_db::{table::Decompress, BlockNumberList, Tables};
+use reth_primitives::trie::{BranchNodeCompact, StorageTrieEntry};
 use std::{
+    fmt::Write as _,
     io,
     time::{Duration, Instant},
 };
@@ -18,13 +21,17 @@ use tui::{
 };
 
 /// Available keybindings for the [DbListTUI]
-static CMDS: [(&str, &str); 6] = [
+static CMDS: [(&str, &str); 10] = [
     ("q", "Quit"),
     ("↑", "Entry above"),
     ("↓", "Entry below"),
     ("←", "Previous page"),
     ("→", "Next page"),
     ("G", "Go to a specific page"),
+    ("K", "Jump to key or range"),
+    ("/", "Filter values"),
+    ("T", "Switch table"),
+    ("C", "Clear key range and filter"),
 ];
 
 /// Modified version of the [ListState] struct that exposes the `offset` field.
@@ -40,54 +47,112 @@ pub(crate) enum ViewMode {
     Normal,
     /// Currently wanting to go to a page
     GoToPage,
+    /// Currently wanting to jump to a key or a key range
+    GoToKey,
+    /// Currently entering a regex to filter the values by
+    Filter,
+    /// Currently selecting the table to show
+    SelectTable,
 }
 
-#[derive(Default)]
-pub(crate) struct DbListTUI<F, T: Table>
+/// A table entry prepared for display in the [DbListTUI].
+pub(crate) struct TuiEntry {
+    /// The debug representation of the key.
+    pub(crate) key: String,
+    /// The key as encoded in the database.
+    pub(crate) raw_key: Vec<u8>,
+    /// The value serialized as pretty JSON.
+    pub(crate) value: String,
+    /// The value as compressed in the database.
+    pub(crate) raw_value: Vec<u8>,
+}
+
+/// The entries of the table that the [DbListTUI] should fetch.
+#[derive(Debug, Clone)]
+pub(crate) struct PageQuery {
+    /// The table to fetch the entries from.
+    pub(crate) table: Tables,
+    /// Skip N entries that are within the key range and match the filter.
+    pub(crate) skip: usize,
+    /// The amount of entries to fetch.
+    pub(crate) count: usize,
+    /// Whether to walk the table from the last entry.
+    pub(crate) reverse: bool,
+    /// The JSON encoded key to start at (inclusive).
+    pub(crate) start_key: Option<String>,
+    /// The JSON encoded key to end at (inclusive).
+    pub(crate) end_key: Option<String>,
+    /// Only fetch the entries whose JSON encoded value matches this regex.
+    pub(crate) filter: Option<Regex>,
+}
+
+impl PageQuery {
+    /// Returns `true` if only a subset of the table is queried.
+    pub(crate) fn is_restricted(&self) -> bool {
+        self.start_key.is_some() || self.end_key.is_some() || self.filter.is_some()
+    }
+}
+
+/// A page of entries fetched for the [DbListTUI].
+pub(crate) struct Page {
+    /// The fetched entries.
+    pub(crate) entries: Vec<TuiEntry>,
+    /// The total number of entries matching the query, if known.
+    pub(crate) total_entries: Option<usize>,
+}
+
+pub(crate) struct DbListTUI<F>
 where
-    F: FnMut(usize, usize) -> Vec<(T::Key, T::Value)>,
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
 {
     /// Fetcher for the next page of items.
     ///
-    /// The fetcher is passed the index of the first item to fetch, and the number of items to
-    /// fetch from that item.
+    /// The fetcher is passed the query describing the table, key range and filter, as well as
+    /// the number of entries to skip and to fetch.
     fetch: F,
-    /// Skip N indices of the key list in the DB.
-    skip: usize,
-    /// The amount of entries to show per page
-    count: usize,
-    /// The total number of entries in the database
-    total_entries: usize,
+    /// The currently shown table, page, key range and filter.
+    query: PageQuery,
+    /// The total number of entries matching the query, if known
+    total_entries: Option<usize>,
     /// The current view mode
     mode: ViewMode,
     /// The current state of the input buffer
     input: String,
+    /// The last error or notice to show in the footer
+    status: Option<String>,
     /// The state of the key list.
     list_state: ListState,
+    /// The state of the table list.
+    table_state: ListState,
     /// Entries to show in the TUI.
-    entries: Vec<(T::Key, T::Value)>,
+    entries: Vec<TuiEntry>,
 }
 
-impl<F, T: Table> DbListTUI<F, T>
+impl<F> DbListTUI<F>
 where
-    F: FnMut(usize, usize) -> Vec<(T::Key, T::Value)>,
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
 {
     /// Create a new database list TUI
-    pub(crate) fn new(fetch: F, skip: usize, count: usize, total_entries: usize) -> Self {
+    pub(crate) fn new(fetch: F, query: PageQuery) -> Self {
         Self {
             fetch,
-            skip,
-            count,
-            total_entries,
+            query,
+            total_entries: None,
             mode: ViewMode::Normal,
             input: String::new(),
+            status: None,
             list_state: ListState::default(),
+            table_state: ListState::default(),
             entries: Vec::new(),
         }
     }
 
     /// Move to the next list selection
     fn next(&mut self) {
+        if self.entries.is_empty() {
+            return
+        }
+
         let i = match self.list_state.selected() {
             Some(i) => {
                 if i >= self.entries.len() - 1 {
@@ -103,6 +168,10 @@ where
 
     /// Move to the previous list selection
     fn previous(&mut self) {
+        if self.entries.is_empty() {
+            return
+        }
+
         let i = match self.list_state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -116,39 +185,129 @@ where
         self.list_state.select(Some(i));
     }
 
+    /// Move the table selection by the given offset
+    fn move_table_selection(&mut self, forward: bool) {
+        let len = Tables::ALL.len();
+        let i = self.table_state.selected().unwrap_or_default();
+        let i = if forward { (i + 1) % len } else { (i + len - 1) % len };
+        self.table_state.select(Some(i));
+    }
+
     fn reset(&mut self) {
-        self.list_state.select(Some(0));
+        self.list_state.select(if self.entries.is_empty() { None } else { Some(0) });
     }
 
     /// Fetch the next page of items
     fn next_page(&mut self) {
-        if self.skip + self.count >= self.total_entries {
+        let has_more = match self.total_entries {
+            Some(total) => self.query.skip + self.query.count < total,
+            // Without a known total, assume there's more as long as the page was full
+            None => self.entries.len() == self.query.count,
+        };
+        if !has_more {
             return
         }
 
-        self.skip += self.count;
+        self.query.skip += self.query.count;
         self.fetch_page();
     }
 
     /// Fetch the previous page of items
     fn previous_page(&mut self) {
-        if self.skip == 0 {
+        if self.query.skip == 0 {
             return
         }
 
-        self.skip = self.skip.saturating_sub(self.count);
+        self.query.skip = self.query.skip.saturating_sub(self.query.count);
         self.fetch_page();
     }
 
     /// Go to a specific page.
     fn go_to_page(&mut self, page: usize) {
-        self.skip = (self.count * page).min(self.total_entries - self.count);
+        let skip = self.query.count * page;
+        self.query.skip = match self.total_entries {
+            Some(total) => skip.min(total.saturating_sub(self.query.count)),
+            None => skip,
+        };
+        self.fetch_page();
+    }
+
+    /// Jump to a key or a key range in the form of `start..end`, where either side may be empty.
+    fn go_to_key(&mut self, input: &str) {
+        let (start, end) = match input.split_once("..") {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (input.trim(), ""),
+        };
+        let parse = |key: &str| {
+            (!key.is_empty()).then(|| super::get::maybe_json_value_parser(key)).transpose()
+        };
+
+        match parse(start).and_then(|start| Ok((start, parse(end)?))) {
+            Ok((start_key, end_key)) => {
+                self.query.start_key = start_key;
+                self.query.end_key = end_key;
+                self.query.skip = 0;
+                self.fetch_page();
+            }
+            Err(err) => self.status = Some(format!("Invalid key: {err}")),
+        }
+    }
+
+    /// Filter the entries by a regex over their JSON encoded values.
+    fn filter(&mut self, input: &str) {
+        if input.is_empty() {
+            self.query.filter = None;
+        } else {
+            match Regex::new(input) {
+                Ok(regex) => self.query.filter = Some(regex),
+                Err(err) => {
+                    self.status = Some(format!("Invalid regex: {err}"));
+                    return
+                }
+            }
+        }
+        self.query.skip = 0;
         self.fetch_page();
     }
 
+    /// Switch to the table selected in the table list.
+    fn select_table(&mut self) {
+        let Some(table) = self.table_state.selected().map(|i| Tables::ALL[i]) else { return };
+        if table != self.query.table {
+            // Keys of one table can't be parsed as keys of another table.
+            self.query.table = table;
+            self.query.start_key = None;
+            self.query.end_key = None;
+            self.query.skip = 0;
+            self.fetch_page();
+        }
+    }
+
+    /// Clear the key range and the filter.
+    fn clear(&mut self) {
+        if self.query.is_restricted() {
+            self.query.start_key = None;
+            self.query.end_key = None;
+            self.query.filter = None;
+            self.query.skip = 0;
+            self.fetch_page();
+        }
+    }
+
     /// Fetch the current page
     fn fetch_page(&mut self) {
-        self.entries = (self.fetch)(self.skip, self.count);
+        match (self.fetch)(&self.query) {
+            Ok(page) => {
+                self.entries = page.entries;
+                self.total_entries = page.total_entries;
+                self.status = None;
+            }
+            Err(err) => {
+                self.entries.clear();
+                self.total_entries = None;
+                self.status = Some(format!("Error fetching entries: {err}"));
+            }
+        }
         self.reset();
     }
 
@@ -182,13 +341,13 @@ where
 }
 
 /// Run the event loop
-fn event_loop<B: Backend, F, T: Table>(
+fn event_loop<B: Backend, F>(
     terminal: &mut Terminal<B>,
-    app: &mut DbListTUI<F, T>,
+    app: &mut DbListTUI<F>,
     tick_rate: Duration,
 ) -> io::Result<()>
 where
-    F: FnMut(usize, usize) -> Vec<(T::Key, T::Value)>,
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
 {
     let mut last_tick = Instant::now();
     let mut running = true;
@@ -214,32 +373,61 @@ where
 }
 
 /// Handle incoming events
-fn handle_event<F, T: Table>(app: &mut DbListTUI<F, T>, event: Event) -> io::Result<bool>
+fn handle_event<F>(app: &mut DbListTUI<F>, event: Event) -> io::Result<bool>
 where
-    F: FnMut(usize, usize) -> Vec<(T::Key, T::Value)>,
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
 {
-    if app.mode == ViewMode::GoToPage {
-        if let Event::Key(key) = event {
-            match key.code {
-                KeyCode::Enter => {
-                    let input = std::mem::take(&mut app.input);
-                    if let Ok(page) = input.parse() {
-                        app.go_to_page(page);
+    match app.mode {
+        ViewMode::Normal => {}
+        ViewMode::SelectTable => {
+            if let Event::Key(key) = event {
+                match key.code {
+                    KeyCode::Enter => {
+                        app.select_table();
+                        app.mode = ViewMode::Normal;
                     }
-                    app.mode = ViewMode::Normal;
+                    KeyCode::Down => app.move_table_selection(true),
+                    KeyCode::Up => app.move_table_selection(false),
+                    KeyCode::Esc => app.mode = ViewMode::Normal,
+                    _ => {}
                 }
-                KeyCode::Char(c) => {
-                    app.input.push(c);
-                }
-                KeyCode::Backspace => {
-                    app.input.pop();
-                }
-                KeyCode::Esc => app.mode = ViewMode::Normal,
-                _ => {}
             }
+
+            return Ok(false)
         }
+        ViewMode::GoToPage | ViewMode::GoToKey | ViewMode::Filter => {
+            if let Event::Key(key) = event {
+                match key.code {
+                    KeyCode::Enter => {
+                        let input = std::mem::take(&mut app.input);
+                        match app.mode {
+                            ViewMode::GoToPage => {
+                                if let Ok(page) = input.parse() {
+                                    app.go_to_page(page);
+                                }
+                            }
+                            ViewMode::GoToKey => app.go_to_key(&input),
+                            ViewMode::Filter => app.filter(&input),
+                            _ => {}
+                        }
+                        app.mode = ViewMode::Normal;
+                    }
+                    KeyCode::Char(c) => {
+                        app.input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.input.pop();
+                    }
+                    KeyCode::Esc => {
+                        app.input.clear();
+                        app.mode = ViewMode::Normal;
+                    }
+                    _ => {}
+                }
+            }
 
-        return Ok(false)
+            return Ok(false)
+        }
     }
 
     match event {
@@ -252,6 +440,19 @@ where
             KeyCode::Char('G') => {
                 app.mode = ViewMode::GoToPage;
             }
+            KeyCode::Char('K') => {
+                app.mode = ViewMode::GoToKey;
+            }
+            KeyCode::Char('/') => {
+                app.input = app.query.filter.as_ref().map(ToString::to_string).unwrap_or_default();
+                app.mode = ViewMode::Filter;
+            }
+            KeyCode::Char('T') => {
+                let current = Tables::ALL.iter().position(|table| *table == app.query.table);
+                app.table_state.select(current);
+                app.mode = ViewMode::SelectTable;
+            }
+            KeyCode::Char('C') => app.clear(),
             _ => {}
         },
         Event::Mouse(e) => match e.kind {
@@ -280,9 +481,9 @@ where
 }
 
 /// Render the UI
-fn ui<B: Backend, F, T: Table>(f: &mut Frame<'_, B>, app: &mut DbListTUI<F, T>)
+fn ui<B: Backend, F>(f: &mut Frame<'_, B>, app: &mut DbListTUI<F>)
 where
-    F: FnMut(usize, usize) -> Vec<(T::Key, T::Value)>,
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
 {
     let outer_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -291,70 +492,209 @@ where
 
     // Columns
     {
+        let selected = app.list_state.selected().and_then(|selected| app.entries.get(selected));
+        let decoded = selected.and_then(|entry| decode_value(app.query.table, &entry.raw_value));
+
+        // The decoded value is shown next to the JSON value if the table has a decoded view.
+        let constraints = if decoded.is_some() {
+            vec![Constraint::Percentage(25); 4]
+        } else {
+            vec![Constraint::Percentage(34), Constraint::Percentage(33), Constraint::Percentage(33)]
+        };
         let inner_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(constraints)
             .split(outer_chunks[0]);
 
-        let key_length = format!("{}", app.skip + app.count - 1).len();
-
-        let entries: Vec<_> = app.entries.iter().map(|(k, _)| k).collect();
+        if app.mode == ViewMode::SelectTable {
+            let tables = Tables::ALL
+                .iter()
+                .map(|table| ListItem::new(table.name().to_string()))
+                .collect::<Vec<ListItem<'_>>>();
+            let table_list = List::new(tables)
+                .block(Block::default().borders(Borders::ALL).title("Tables"))
+                .style(Style::default().fg(Color::White))
+                .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC))
+                .highlight_symbol("➜ ")
+                .start_corner(Corner::TopLeft);
+            f.render_stateful_widget(table_list, inner_chunks[0], &mut app.table_state);
+        } else {
+            let key_length = format!("{}", app.query.skip + app.query.count - 1).len();
+
+            let formatted_keys = app
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    ListItem::new(format!(
+                        "[{:0>width$}]: {}",
+                        i + app.query.skip,
+                        entry.key,
+                        width = key_length
+                    ))
+                })
+                .collect::<Vec<ListItem<'_>>>();
+
+            let key_list = List::new(formatted_keys)
+                .block(Block::default().borders(Borders::ALL).title(keys_title(app)))
+                .style(Style::default().fg(Color::White))
+                .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC))
+                .highlight_symbol("➜ ")
+                .start_corner(Corner::TopLeft);
+            f.render_stateful_widget(key_list, inner_chunks[0], &mut app.list_state);
+        }
 
-        let formatted_keys = entries
-            .into_iter()
-            .enumerate()
-            .map(|(i, k)| {
-                ListItem::new(format!("[{:0>width$}]: {k:?}", i + app.skip, width = key_length))
-            })
-            .collect::<Vec<ListItem<'_>>>();
-
-        let key_list = List::new(formatted_keys)
-            .block(Block::default().borders(Borders::ALL).title(format!(
-                "Keys (Showing entries {}-{} out of {} entries)",
-                app.skip,
-                app.skip + app.entries.len() - 1,
-                app.total_entries
-            )))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC))
-            .highlight_symbol("➜ ")
-            .start_corner(Corner::TopLeft);
-        f.render_stateful_widget(key_list, inner_chunks[0], &mut app.list_state);
-
-        let values = app.entries.iter().map(|(_, v)| v).collect::<Vec<_>>();
+        let mut column = 1;
+        if let Some(decoded) = decoded {
+            let decoded_display = Paragraph::new(decoded)
+                .block(Block::default().borders(Borders::ALL).title("Value (decoded)"))
+                .wrap(Wrap { trim: false })
+                .alignment(Alignment::Left);
+            f.render_widget(decoded_display, inner_chunks[column]);
+            column += 1;
+        }
 
         let value_display = Paragraph::new(
-            app.list_state
-                .selected()
-                .and_then(|selected| values.get(selected))
+            selected.map(|entry| entry.value.clone()).unwrap_or("No value selected".to_string()),
+        )
+        .block(Block::default().borders(Borders::ALL).title("Value (JSON)"))
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Left);
+        f.render_widget(value_display, inner_chunks[column]);
+        column += 1;
+
+        let raw_display = Paragraph::new(
+            selected
                 .map(|entry| {
-                    serde_json::to_string_pretty(entry)
-                        .unwrap_or(String::from("Error serializing value"))
+                    format!(
+                        "Key:\n0x{}\n\nValue:\n0x{}",
+                        hex::encode(&entry.raw_key),
+                        hex::encode(&entry.raw_value)
+                    )
                 })
                 .unwrap_or("No value selected".to_string()),
         )
-        .block(Block::default().borders(Borders::ALL).title("Value (JSON)"))
-        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title("Raw"))
+        .wrap(Wrap { trim: true })
         .alignment(Alignment::Left);
-        f.render_widget(value_display, inner_chunks[1]);
+        f.render_widget(raw_display, inner_chunks[column]);
     }
 
     // Footer
     let footer = match app.mode {
-        ViewMode::Normal => Paragraph::new(
-            CMDS.iter().map(|(k, v)| format!("[{k}] {v}")).collect::<Vec<_>>().join(" | "),
-        ),
-        ViewMode::GoToPage => Paragraph::new(format!(
-            "Go to page (max {}): {}",
-            app.total_entries / app.count,
-            app.input
-        )),
+        ViewMode::Normal => Paragraph::new(app.status.clone().unwrap_or_else(|| {
+            CMDS.iter().map(|(k, v)| format!("[{k}] {v}")).collect::<Vec<_>>().join(" | ")
+        })),
+        ViewMode::GoToPage => Paragraph::new(match app.total_entries {
+            Some(total) => format!("Go to page (max {}): {}", total / app.query.count, app.input),
+            None => format!("Go to page: {}", app.input),
+        }),
+        ViewMode::GoToKey => {
+            Paragraph::new(format!("Jump to key or range (`key`, `start..end`): {}", app.input))
+        }
+        ViewMode::Filter => Paragraph::new(format!("Filter values by regex: {}", app.input)),
+        ViewMode::SelectTable => Paragraph::new("[↑/↓] Select table | [Enter] Open | [Esc] Back"),
     }
     .block(Block::default().borders(Borders::ALL))
     .alignment(match app.mode {
-        ViewMode::Normal => Alignment::Center,
-        ViewMode::GoToPage => Alignment::Left,
+        ViewMode::Normal | ViewMode::SelectTable => Alignment::Center,
+        ViewMode::GoToPage | ViewMode::GoToKey | ViewMode::Filter => Alignment::Left,
     })
     .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
     f.render_widget(footer, outer_chunks[1]);
 }
+
+/// Title of the key list, describing the shown entries and active key range and filter.
+fn keys_title<F>(app: &DbListTUI<F>) -> String
+where
+    F: FnMut(&PageQuery) -> eyre::Result<Page>,
+{
+    let mut title = format!("{} keys (", app.query.table);
+    if app.entries.is_empty() {
+        title.push_str("No entries");
+    } else {
+        let _ = write!(
+            title,
+            "Showing entries {}-{}",
+            app.query.skip,
+            app.query.skip + app.entries.len() - 1
+        );
+    }
+    if let Some(total) = app.total_entries {
+        let _ = write!(title, " out of {total} entries");
+    }
+    if app.query.start_key.is_some() || app.query.end_key.is_some() {
+        let _ = write!(
+            title,
+            ", range {}..{}",
+            app.query.start_key.as_deref().unwrap_or_default(),
+            app.query.end_key.as_deref().unwrap_or_default()
+        );
+    }
+    if let Some(filter) = &app.query.filter {
+        let _ = write!(title, ", filter /{filter}/");
+    }
+    title.push(')');
+    title
+}
+
+/// Decodes values of tables whose JSON representation is not very readable.
+///
+/// Returns `None` for all other tables.
+fn decode_value(table: Tables, raw_value: &[u8]) -> Option<String> {
+    match table {
+        Tables::AccountsTrie => BranchNodeCompact::decompress(raw_value).ok().map(|node| {
+            let mut out = String::new();
+            format_branch_node(&mut out, &node);
+            out
+        }),
+        Tables::StoragesTrie => StorageTrieEntry::decompress(raw_value).ok().map(|entry| {
+            let mut out = format!("Nibbles: 0x{}\n\n", hex::encode(&entry.nibbles.inner));
+            format_branch_node(&mut out, &entry.node);
+            out
+        }),
+        Tables::AccountHistory | Tables::StorageHistory => {
+            BlockNumberList::decompress(raw_value).ok().map(|list| {
+                let blocks = list.iter(0).collect::<Vec<_>>();
+                let mut out = format!("Blocks in shard: {}\n", blocks.len());
+                if let (Some(first), Some(last)) = (blocks.first(), blocks.last()) {
+                    let _ = writeln!(out, "Range: {first}..={last}");
+                }
+                out.push('\n');
+                for block in blocks {
+                    let _ = writeln!(out, "{block}");
+                }
+                out
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Writes the masks and children of a branch node, one child per line.
+fn format_branch_node(out: &mut String, node: &BranchNodeCompact) {
+    let _ = writeln!(out, "State mask: {:016b}", *node.state_mask);
+    let _ = writeln!(out, "Tree mask:  {:016b}", *node.tree_mask);
+    let _ = writeln!(out, "Hash mask:  {:016b}", *node.hash_mask);
+    match node.root_hash {
+        Some(root_hash) => {
+            let _ = writeln!(out, "Root hash: {root_hash:?}");
+        }
+        None => out.push_str("Root hash: none\n"),
+    }
+
+    out.push_str("\nChildren:\n");
+    for nibble in 0..16u8 {
+        if !node.state_mask.is_bit_set(nibble) {
+            continue
+        }
+        let _ = write!(out, "{nibble:x}:");
+        if node.tree_mask.is_bit_set(nibble) {
+            out.push_str(" [tree]");
+        }
+        if node.hash_mask.is_bit_set(nibble) {
+            let _ = write!(out, " {:?}", node.hash_for_nibble(nibble));
+        }
+        out.push('\n');
+    }
+}
@@ -0,0 +1,146 @@
+//! Command for checking that stage checkpoints agree with the table contents.
+use crate::{
+    args::{utils::genesis_value_parser, DatabaseArgs, StageEnum},
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_config::Config;
+use reth_db::open_db_read_only;
+use reth_primitives::{stage::StageId, ChainSpec};
+use reth_provider::{ConsistencyFix, ProviderFactory, CONSISTENCY_CHECKED_STAGES};
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// `reth debug consistency` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the configuration file to use.
+    ///
+    /// The pruning configuration is taken into account when checking the pruned tables.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    config: Option<PathBuf>,
+
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The stages to check. Checks all stages if empty.
+    #[arg(long, value_delimiter = ',')]
+    stages: Vec<StageEnum>,
+}
+
+impl Command {
+    /// Execute `debug consistency` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let config_path = self.config.clone().unwrap_or(data_dir.config_path());
+        let config: Config = confy::load_path(config_path).unwrap_or_default();
+        let tx_lookup_prune_mode = config.prune.and_then(|prune| prune.parts.transaction_lookup);
+
+        let db_path = data_dir.db_path();
+        if !db_path.exists() {
+            eyre::bail!("Database {db_path:?} does not exist.")
+        }
+
+        let db = open_db_read_only(&db_path, self.db.log_level)?;
+        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let provider = factory.provider()?;
+
+        let stages = if self.stages.is_empty() {
+            CONSISTENCY_CHECKED_STAGES.to_vec()
+        } else {
+            // Keep pipeline order regardless of the order the stages were passed in.
+            let requested = self.stages.iter().flat_map(stage_ids).collect::<Vec<_>>();
+            CONSISTENCY_CHECKED_STAGES.into_iter().filter(|id| requested.contains(id)).collect()
+        };
+
+        info!(target: "reth::cli", ?stages, "Checking consistency");
+        let report = provider.check_stages_consistency(stages, tx_lookup_prune_mode)?;
+
+        if report.is_consistent() {
+            println!("No inconsistencies found.");
+            return Ok(())
+        }
+
+        println!("Found {} inconsistencies:", report.inconsistencies.len());
+        for inconsistency in &report.inconsistencies {
+            println!("  {inconsistency}");
+        }
+
+        println!("Suggested fixes:");
+        for fix in report.fixes() {
+            match fix {
+                ConsistencyFix::Unwind { to } => println!("  reth stage unwind to-block {to}"),
+                ConsistencyFix::Drop(stage) => match drop_argument(stage) {
+                    Some(argument) => println!("  reth stage drop {argument}"),
+                    None => println!("  reset the {stage} stage checkpoint"),
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the stage ids that are covered by the given [StageEnum].
+fn stage_ids(stage: &StageEnum) -> Vec<StageId> {
+    match stage {
+        StageEnum::Headers => vec![StageId::Headers],
+        StageEnum::Bodies => vec![StageId::Bodies],
+        StageEnum::Senders => vec![StageId::SenderRecovery],
+        StageEnum::Execution => vec![StageId::Execution],
+        StageEnum::AccountHashing => vec![StageId::AccountHashing],
+        StageEnum::StorageHashing => vec![StageId::StorageHashing],
+        StageEnum::Hashing => vec![StageId::AccountHashing, StageId::StorageHashing],
+        StageEnum::Merkle => vec![StageId::MerkleExecute],
+        StageEnum::TxLookup => vec![StageId::TransactionLookup],
+        StageEnum::History => vec![StageId::IndexAccountHistory, StageId::IndexStorageHistory],
+        StageEnum::AccountHistory => vec![StageId::IndexAccountHistory],
+        StageEnum::StorageHistory => vec![StageId::IndexStorageHistory],
+        StageEnum::TotalDifficulty => vec![StageId::TotalDifficulty],
+    }
+}
+
+/// Returns the `reth stage drop` argument that resets the given stage.
+fn drop_argument(stage: StageId) -> Option<&'static str> {
+    let argument = match stage {
+        StageId::TotalDifficulty => "total-difficulty",
+        StageId::Bodies => "bodies",
+        StageId::SenderRecovery => "senders",
+        StageId::Execution => "execution",
+        StageId::AccountHashing => "account-hashing",
+        StageId::StorageHashing => "storage-hashing",
+        StageId::MerkleExecute | StageId::MerkleUnwind => "merkle",
+        StageId::TransactionLookup => "tx-lookup",
+        StageId::IndexAccountHistory | StageId::IndexStorageHistory => "history",
+        _ => return None,
+    };
+    Some(argument)
+}
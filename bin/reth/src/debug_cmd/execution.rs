@@ -141,7 +141,11 @@ impl Command {
                 })
                 .set(ExecutionStage::new(
                     factory,
-                    ExecutionStageThresholds { max_blocks: None, max_changes: None },
+                    ExecutionStageThresholds {
+                        max_blocks: None,
+                        max_changes: None,
+                        max_memory: None,
+                    },
                 )),
             )
             .build(db, self.chain.clone());
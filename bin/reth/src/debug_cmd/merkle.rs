@@ -95,7 +95,7 @@ impl Command {
         let factory = reth_revm::Factory::new(self.chain.clone());
         let mut execution_stage = ExecutionStage::new(
             factory,
-            ExecutionStageThresholds { max_blocks: Some(1), max_changes: None },
+            ExecutionStageThresholds { max_blocks: Some(1), max_changes: None, max_memory: None },
         );
 
         let mut account_hashing_stage = AccountHashingStage::default();
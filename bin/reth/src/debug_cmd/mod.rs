@@ -3,8 +3,10 @@ use clap::{Parser, Subcommand};
 
 use crate::runner::CliContext;
 
+mod consistency;
 mod execution;
 mod merkle;
+mod replay;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
@@ -20,6 +22,10 @@ pub enum Subcommands {
     Execution(execution::Command),
     /// Debug the clean & incremental state root calculations.
     Merkle(merkle::Command),
+    /// Verify that stage checkpoints agree with the contents of the tables they write.
+    Consistency(consistency::Command),
+    /// Re-execute a range of blocks and compare the results with the database.
+    Replay(replay::Command),
 }
 
 impl Command {
@@ -28,6 +34,8 @@ impl Command {
         match self.command {
             Subcommands::Execution(command) => command.execute(ctx).await,
             Subcommands::Merkle(command) => command.execute().await,
+            Subcommands::Consistency(command) => command.execute().await,
+            Subcommands::Replay(command) => command.execute().await,
         }
     }
 }
@@ -0,0 +1,521 @@
+//! Command for replaying historical blocks and comparing the results with the database.
+use crate::{
+    args::{utils::genesis_value_parser, DatabaseArgs},
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::BlockNumberAddress, open_db_read_only, tables,
+    transaction::DbTx,
+};
+use reth_primitives::{
+    Account, Address, Block, BlockNumber, BlockWithSenders, Bloom, ChainSpec, Hardfork, Receipt,
+    ReceiptWithBloom, H256, U256,
+};
+use reth_provider::{
+    BlockReader, ExecutorFactory, HeaderProvider, PostState, ProviderFactory, ReceiptProvider,
+    StateProvider,
+};
+use reth_revm::{revm::primitives::ResultAndState, Factory};
+use std::{collections::BTreeSet, sync::Arc};
+use tracing::info;
+
+/// `reth debug replay` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The first block to replay.
+    #[arg(long)]
+    from: BlockNumber,
+
+    /// The last block to replay (inclusive).
+    #[arg(long)]
+    to: BlockNumber,
+
+    /// Execute the transactions of every block optimistically in parallel.
+    ///
+    /// Checks that the parallel execution yields the same results as the sequential execution
+    /// that produced the database.
+    #[arg(long)]
+    parallel: bool,
+}
+
+impl Command {
+    /// Execute `debug replay` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from == 0 {
+            eyre::bail!("The genesis block can't be replayed.")
+        }
+        if self.from > self.to {
+            eyre::bail!("The first block {} is above the last block {}.", self.from, self.to)
+        }
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        if !db_path.exists() {
+            eyre::bail!("Database {db_path:?} does not exist.")
+        }
+
+        let db = open_db_read_only(&db_path, self.db.log_level)?;
+        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let mut executor_factory = Factory::new(self.chain.clone());
+        if self.parallel {
+            executor_factory = executor_factory.with_parallel_execution();
+        }
+
+        for number in self.from..=self.to {
+            let diff = BlockDiff::new(&factory, &executor_factory, number)?;
+            if !diff.is_empty() {
+                diff.print(number);
+                eyre::bail!("Block {number} diverged from the database.")
+            }
+            info!(target: "reth::cli", number, "Replayed block");
+        }
+
+        println!("Blocks {}..={} match the database.", self.from, self.to);
+        Ok(())
+    }
+}
+
+/// The results of a replayed block that differ from the database.
+#[derive(Debug, Default)]
+struct BlockDiff {
+    /// The first transaction with a divergent receipt.
+    transaction: Option<TransactionDiff>,
+    /// The replayed and the expected gas used of the block.
+    gas_used: Option<(u64, u64)>,
+    /// The replayed and the expected logs bloom of the block.
+    logs_bloom: Option<(Bloom, Bloom)>,
+    /// The divergent accounts and storage slots.
+    state: StateDiff,
+}
+
+impl BlockDiff {
+    /// Re-executes the block on top of the state of its parent and compares the results with the
+    /// database.
+    fn new<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        executor_factory: &Factory,
+        number: BlockNumber,
+    ) -> eyre::Result<Self> {
+        let provider = factory.provider()?;
+        let BlockWithSenders { block, senders } = provider
+            .block_with_senders(number)?
+            .ok_or_else(|| eyre::eyre!("Block {number} not found."))?;
+        let total_difficulty = provider
+            .header_td_by_number(number)?
+            .ok_or_else(|| eyre::eyre!("Total difficulty of block {number} not found."))?;
+        let expected_receipts = provider
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| eyre::eyre!("Receipts of block {number} not found."))?;
+
+        // re-execute the block on top of the state of its parent
+        let mut executor = executor_factory.with_sp(factory.history_by_block_number(number - 1)?);
+        let (post_state, gas_used) =
+            executor.execute_transactions(&block, total_difficulty, Some(senders.clone()))?;
+        let post_state = executor.apply_post_block_changes(&block, total_difficulty, post_state)?;
+        let receipts = post_state.receipts(number);
+
+        let mut diff = Self::default();
+
+        if let Some(idx) = (0..receipts.len().max(expected_receipts.len()))
+            .find(|idx| receipts.get(*idx) != expected_receipts.get(*idx))
+        {
+            let mut transaction = TransactionDiff {
+                idx,
+                hash: block.body.get(idx).map(|transaction| transaction.hash()),
+                expected_receipt: expected_receipts.get(idx).cloned(),
+                got_receipt: receipts.get(idx).cloned(),
+                accounts: Vec::new(),
+                storage: Vec::new(),
+            };
+            if idx < block.body.len() {
+                let changes = TransactionDiff::changes(
+                    factory,
+                    executor_factory,
+                    &block,
+                    &senders,
+                    total_difficulty,
+                    idx,
+                )?;
+                transaction.collect_changes(number, &changes);
+            }
+            diff.transaction = Some(transaction);
+        }
+
+        if gas_used != block.gas_used {
+            diff.gas_used = Some((gas_used, block.gas_used));
+        }
+
+        let logs_bloom = receipts
+            .iter()
+            .map(|receipt| ReceiptWithBloom::from(receipt.clone()).bloom)
+            .fold(Bloom::zero(), |bloom, receipt_bloom| bloom | receipt_bloom);
+        if logs_bloom != block.logs_bloom {
+            diff.logs_bloom = Some((logs_bloom, block.logs_bloom));
+        }
+
+        diff.state = StateDiff::new(factory, number, &post_state)?;
+
+        Ok(diff)
+    }
+
+    /// Returns `true` if the replayed block matches the database.
+    fn is_empty(&self) -> bool {
+        self.transaction.is_none() &&
+            self.gas_used.is_none() &&
+            self.logs_bloom.is_none() &&
+            self.state.is_empty()
+    }
+
+    /// Prints the divergent results of the block.
+    fn print(&self, number: BlockNumber) {
+        if let Some(transaction) = &self.transaction {
+            transaction.print(number);
+        }
+        if let Some((got, expected)) = self.gas_used {
+            println!("Block {number}: gas used {got}, expected {expected}");
+        }
+        if let Some((got, expected)) = self.logs_bloom {
+            println!("Block {number}: logs bloom {got:?}, expected {expected:?}");
+        }
+        if !self.state.is_empty() {
+            self.state.print(number);
+        }
+    }
+}
+
+/// The first transaction of a replayed block with a divergent receipt, along with the state
+/// changes of its replayed execution.
+#[derive(Debug)]
+struct TransactionDiff {
+    /// The index of the transaction in the block.
+    idx: usize,
+    /// The hash of the transaction, if the block contains it.
+    hash: Option<H256>,
+    /// The receipt in the database.
+    expected_receipt: Option<Receipt>,
+    /// The replayed receipt.
+    got_receipt: Option<Receipt>,
+    /// The accounts changed by the transaction, with the replayed state before and after it.
+    accounts: Vec<(Address, Option<Account>, Option<Account>)>,
+    /// The storage slots changed by the transaction, with the replayed value before and after it.
+    storage: Vec<(Address, H256, U256, U256)>,
+}
+
+impl TransactionDiff {
+    /// Re-executes the transactions of the block one at a time up to the transaction at the given
+    /// index, and returns the state changes of that transaction.
+    fn changes<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        executor_factory: &Factory,
+        block: &Block,
+        senders: &[Address],
+        total_difficulty: U256,
+        idx: usize,
+    ) -> eyre::Result<PostState> {
+        let number = block.number;
+        let has_state_clear_eip =
+            executor_factory.chain_spec().fork(Hardfork::SpuriousDragon).active_at_block(number);
+
+        let mut executor = executor_factory.with_sp(factory.history_by_block_number(number - 1)?);
+        executor.init_env(&block.header, total_difficulty);
+
+        let mut post_state = PostState::default();
+        for (transaction, sender) in block.body.iter().zip(senders.iter().copied()).take(idx) {
+            let ResultAndState { state, .. } = executor.transact(transaction, sender)?;
+            executor.commit_changes(number, state, has_state_clear_eip, &mut post_state);
+        }
+
+        let ResultAndState { state, .. } = executor.transact(&block.body[idx], senders[idx])?;
+        let mut changes = PostState::default();
+        executor.commit_changes(number, state, has_state_clear_eip, &mut changes);
+
+        Ok(changes)
+    }
+
+    /// Collects the changed accounts and storage slots from the state changes of the transaction.
+    fn collect_changes(&mut self, number: BlockNumber, changes: &PostState) {
+        if let Some(accounts) = changes.account_changes().get(&number) {
+            for (address, previous) in accounts {
+                let got = changes.account(address).copied().flatten();
+                self.accounts.push((*address, *previous, got));
+            }
+        }
+        if let Some(storages) = changes.storage_changes().get(&number) {
+            for (address, transition) in storages {
+                let storage = changes.account_storage(address);
+                for (slot, previous) in &transition.storage {
+                    let got = storage
+                        .and_then(|storage| storage.storage.get(slot))
+                        .copied()
+                        .unwrap_or_default();
+                    self.storage.push((*address, H256(slot.to_be_bytes()), *previous, got));
+                }
+            }
+        }
+    }
+
+    /// Prints the divergent receipt and the state changes of the transaction.
+    fn print(&self, number: BlockNumber) {
+        let Self { idx, hash, expected_receipt, got_receipt, accounts, storage } = self;
+        println!("Block {number}: first divergent transaction {idx} ({hash:?})");
+        println!("  expected receipt: {expected_receipt:?}");
+        println!("  got receipt:      {got_receipt:?}");
+
+        println!("Transaction {idx}: {} changed accounts", accounts.len());
+        for (address, before, after) in accounts {
+            println!("  {address:?}");
+            println!("    before: {before:?}");
+            println!("    after:  {after:?}");
+        }
+
+        println!("Transaction {idx}: {} changed storage slots", storage.len());
+        for (address, slot, before, after) in storage {
+            println!("  {address:?} {slot:?}");
+            println!("    before: {before}");
+            println!("    after:  {after}");
+        }
+    }
+}
+
+/// The accounts and storage slots for which the replayed state differs from the state in the
+/// database.
+#[derive(Debug, Default)]
+struct StateDiff {
+    /// The divergent accounts with the state before the block, and the expected and replayed
+    /// state.
+    accounts: Vec<(Address, Option<Account>, Option<Account>, Option<Account>)>,
+    /// The divergent storage slots with the value before the block, and the expected and replayed
+    /// value.
+    storage: Vec<(Address, H256, U256, U256, U256)>,
+}
+
+impl StateDiff {
+    /// Compares the replayed state changes of the block with the changesets and the state after
+    /// the block in the database.
+    fn new<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        number: BlockNumber,
+        post_state: &PostState,
+    ) -> eyre::Result<Self> {
+        let provider = factory.provider()?;
+        let before = factory.history_by_block_number(number - 1)?;
+        let after = factory.history_by_block_number(number)?;
+
+        // the accounts and slots changed by either the replay or the original execution
+        let mut changed_accounts = provider
+            .tx_ref()
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(number..=number)?
+            .map(|entry| entry.map(|(_, account)| account.address))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        changed_accounts.extend(post_state.accounts().keys());
+
+        let mut changed_slots = provider
+            .tx_ref()
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(BlockNumberAddress::range(number..=number))?
+            .map(|entry| entry.map(|(key, slot)| (key.address(), slot.key)))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        for (address, storage) in post_state.storage() {
+            changed_slots
+                .extend(storage.storage.keys().map(|slot| (*address, H256(slot.to_be_bytes()))));
+        }
+
+        let mut diff = Self::default();
+        for address in changed_accounts {
+            let previous = before.basic_account(address)?;
+            let expected = after.basic_account(address)?;
+            let got = post_state.accounts().get(&address).copied().unwrap_or(previous);
+            if expected != got {
+                diff.accounts.push((address, previous, expected, got));
+            }
+        }
+        for (address, slot) in changed_slots {
+            let previous = before.storage(address, slot)?.unwrap_or_default();
+            let expected = after.storage(address, slot)?.unwrap_or_default();
+            let got = match post_state.storage().get(&address) {
+                Some(storage) => match storage.storage.get(&U256::from_be_bytes(slot.0)) {
+                    Some(value) => *value,
+                    None if storage.wiped() => U256::ZERO,
+                    None => previous,
+                },
+                None => previous,
+            };
+            if expected != got {
+                diff.storage.push((address, slot, previous, expected, got));
+            }
+        }
+
+        Ok(diff)
+    }
+
+    /// Returns `true` if the replayed state matches the database.
+    fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storage.is_empty()
+    }
+
+    /// Prints the divergent accounts and storage slots.
+    fn print(&self, number: BlockNumber) {
+        println!("Block {number}: {} divergent accounts", self.accounts.len());
+        for (address, previous, expected, got) in &self.accounts {
+            println!("  {address:?}");
+            println!("    before:   {previous:?}");
+            println!("    expected: {expected:?}");
+            println!("    got:      {got:?}");
+        }
+
+        println!("Block {number}: {} divergent storage slots", self.storage.len());
+        for (address, slot, previous, expected, got) in &self.storage {
+            println!("  {address:?} {slot:?}");
+            println!("    before:   {previous}");
+            println!("    expected: {expected}");
+            println!("    got:      {got}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{test_utils::create_test_rw_db, transaction::DbTxMut, DatabaseEnv};
+    use reth_primitives::{
+        hex_literal::hex, keccak256, Bytecode, ChainSpecBuilder, SealedBlock, H160,
+    };
+    use reth_provider::{BlockExecutor, BlockWriter, HistoryWriter};
+    use reth_rlp::Decodable;
+
+    /// Creates a database with the first block of the execution stage tests and the results of
+    /// its execution on top of the state of the genesis block.
+    fn create_test_db(chain_spec: Arc<ChainSpec>) -> Arc<DatabaseEnv> {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), chain_spec.clone());
+
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+
+        // the state of the genesis block, written as its changes
+        let code = hex!("5a465a905090036002900360015500");
+        let code_hash = keccak256(code);
+        let mut genesis_state = PostState::new();
+        genesis_state.create_account(
+            0,
+            H160(hex!("1000000000000000000000000000000000000000")),
+            Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+        );
+        genesis_state.create_account(
+            0,
+            H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b")),
+            Account {
+                nonce: 0,
+                balance: U256::from(0x3635c9adc5dea00000u128),
+                bytecode_hash: None,
+            },
+        );
+        genesis_state.add_bytecode(code_hash, Bytecode::new_raw(code.to_vec().into()));
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(genesis, None).unwrap();
+        provider.insert_block(block.clone(), None).unwrap();
+        genesis_state.write_to_db(provider.tx_ref()).unwrap();
+        provider.commit().unwrap();
+
+        let post_state = {
+            let total_difficulty =
+                factory.provider().unwrap().header_td_by_number(1).unwrap().unwrap();
+            let mut executor = Factory::new(chain_spec).with_sp(factory.latest().unwrap());
+            executor.execute(&block.unseal(), total_difficulty, None).unwrap()
+        };
+
+        let provider = factory.provider_rw().unwrap();
+        post_state.write_to_db(provider.tx_ref()).unwrap();
+        provider.calculate_history_indices(0..=1).unwrap();
+        provider.commit().unwrap();
+
+        db
+    }
+
+    #[test]
+    fn replayed_block_matches_database() {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+        let db = create_test_db(chain_spec.clone());
+        let factory = ProviderFactory::new(db.as_ref(), chain_spec.clone());
+
+        let diff = BlockDiff::new(&factory, &Factory::new(chain_spec), 1).unwrap();
+        assert!(diff.is_empty(), "{diff:?}");
+    }
+
+    #[test]
+    fn replayed_block_with_divergent_receipt() {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+        let db = create_test_db(chain_spec.clone());
+        let factory = ProviderFactory::new(db.as_ref(), chain_spec.clone());
+
+        // doctor the receipt of the transaction in the database
+        let receipt = {
+            let provider = factory.provider_rw().unwrap();
+            let receipt = provider.tx_ref().get::<tables::Receipts>(0).unwrap().unwrap();
+            let doctored = Receipt { success: !receipt.success, ..receipt.clone() };
+            provider.tx_ref().put::<tables::Receipts>(0, doctored).unwrap();
+            provider.commit().unwrap();
+            receipt
+        };
+
+        let diff = BlockDiff::new(&factory, &Factory::new(chain_spec), 1).unwrap();
+        assert!(diff.gas_used.is_none());
+        assert!(diff.logs_bloom.is_none());
+        assert!(diff.state.is_empty());
+
+        let transaction = diff.transaction.unwrap();
+        assert_eq!(transaction.idx, 0);
+        assert_eq!(transaction.got_receipt, Some(receipt.clone()));
+        assert_eq!(
+            transaction.expected_receipt,
+            Some(Receipt { success: !receipt.success, ..receipt })
+        );
+
+        // the transaction pays for its gas and writes to the storage of the called contract
+        let sender = H160(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"));
+        let contract = H160(hex!("1000000000000000000000000000000000000000"));
+        assert!(transaction.accounts.iter().any(|(address, before, after)| *address == sender &&
+            before.map(|account| account.nonce) == Some(0) &&
+            after.map(|account| account.nonce) == Some(1)));
+        assert_eq!(
+            transaction.storage,
+            vec![(contract, H256::from_low_u64_be(1), U256::ZERO, U256::from(2))]
+        );
+    }
+}
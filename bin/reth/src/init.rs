@@ -23,6 +23,16 @@ pub enum InitDatabaseError {
         database_hash: H256,
     },
 
+    /// The genesis block of the chainspec does not hash to the known genesis hash of the
+    /// chainspec.
+    #[error("Genesis block of the specified chainspec does not match its genesis hash: chainspec is {chainspec_hash}, genesis block is {genesis_hash}")]
+    GenesisBlockMismatch {
+        /// Expected genesis hash.
+        chainspec_hash: H256,
+        /// Hash of the genesis block built from the chainspec.
+        genesis_hash: H256,
+    },
+
     /// Low-level database error.
     #[error(transparent)]
     DBError(#[from] reth_db::DatabaseError),
@@ -56,6 +66,19 @@ pub fn init_genesis<DB: Database>(
     }
 
     drop(tx);
+
+    // The genesis hash of known chains is not derived from the genesis block, so make sure the
+    // genesis state that is about to be written matches it.
+    if chain.genesis_hash.is_some() {
+        let genesis_hash = chain.genesis_header().hash_slow();
+        if genesis_hash != hash {
+            return Err(InitDatabaseError::GenesisBlockMismatch {
+                chainspec_hash: hash,
+                genesis_hash,
+            })
+        }
+    }
+
     debug!("Writing genesis block.");
 
     // use transaction to insert genesis header
@@ -244,6 +267,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn fail_init_genesis_block_mismatch() {
+        let db = create_test_rw_db();
+        let chain_spec =
+            Arc::new(ChainSpec { genesis_hash: Some(H256::random()), ..(**MAINNET).clone() });
+        let err = init_genesis(db, chain_spec.clone()).unwrap_err();
+
+        assert_eq!(
+            err,
+            InitDatabaseError::GenesisBlockMismatch {
+                chainspec_hash: chain_spec.genesis_hash(),
+                genesis_hash: MAINNET_GENESIS
+            }
+        )
+    }
+
     #[test]
     fn init_genesis_history() {
         let address_with_balance = Address::from_low_u64_be(1);
@@ -268,7 +307,7 @@ mod tests {
                 ..Default::default()
             },
             hardforks: BTreeMap::default(),
-            fork_timestamps: ForkTimestamps { shanghai: None },
+            fork_timestamps: ForkTimestamps { shanghai: None, cancun: None },
             genesis_hash: None,
             paris_block_and_final_difficulty: None,
         });
@@ -59,11 +59,16 @@ use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{EthTransactionValidator, TransactionPool};
 use secp256k1::SecretKey;
 use std::{
+    io::Write,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver},
+    oneshot, watch,
 };
-use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tracing::*;
 
 use crate::{
@@ -72,12 +77,15 @@ use crate::{
         DatabaseArgs, PayloadBuilderArgs,
     },
     dirs::MaybePlatformPath,
-    node::cl_events::ConsensusLayerHealthEvents,
+    node::{cl_events::ConsensusLayerHealthEvents, sync_status::SyncStatus},
 };
 use reth_interfaces::p2p::headers::client::HeadersClient;
-use reth_payload_builder::PayloadBuilderService;
+use reth_metrics::metrics::{self, absolute_counter};
+use reth_payload_builder::{BuiltPayload, PayloadBuilderService};
+use reth_payload_relay::{BeaconChainConfig, BuilderSigner, RelayConfig, RelaySubmissionService};
 use reth_primitives::DisplayHardforks;
 use reth_provider::providers::BlockchainProvider;
+use reth_rpc_types::SyncProgress;
 use reth_stages::stages::{
     AccountHashingStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
     StorageHashingStage, TransactionLookupStage,
@@ -85,6 +93,16 @@ use reth_stages::stages::{
 
 pub mod cl_events;
 pub mod events;
+pub mod sync_status;
+
+/// How often the peer set is written to the persistent peers file.
+const PEERS_PERSIST_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How often the share of free pages in the database is checked.
+const DB_FREELIST_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Share of free pages in the database above which a warning to compact it is logged.
+const DB_FREELIST_WARN_THRESHOLD: f64 = 0.25;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -174,6 +192,9 @@ impl Command {
 
         self.start_metrics_endpoint(Arc::clone(&db)).await?;
 
+        debug!(target: "reth::cli", "Spawning database free-list monitor task");
+        ctx.task_executor.spawn(Box::pin(monitor_db_freelist(Arc::clone(&db))));
+
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
 
         let genesis_hash = init_genesis(db.clone(), self.chain.clone())?;
@@ -195,10 +216,15 @@ impl Command {
         ctx.task_executor.spawn_critical("metrics listener task", metrics_listener);
 
         // configure blockchain tree
+        let mut executor_factory =
+            Factory::new(self.chain.clone()).with_prefetch(config.stages.execution.prefetch_depth);
+        if config.stages.execution.parallel_execution {
+            executor_factory = executor_factory.with_parallel_execution();
+        }
         let tree_externals = TreeExternals::new(
             db.clone(),
             Arc::clone(&consensus),
-            Factory::new(self.chain.clone()),
+            executor_factory,
             Arc::clone(&self.chain),
         );
         let tree_config = BlockchainTreeConfig::default();
@@ -206,14 +232,31 @@ impl Command {
         // depth at least N blocks must be sent at once.
         let (canon_state_notification_sender, _receiver) =
             tokio::sync::broadcast::channel(tree_config.max_reorg_depth() as usize * 2);
-        let blockchain_tree = ShareableBlockchainTree::new(
-            BlockchainTree::new(
-                tree_externals,
-                canon_state_notification_sender.clone(),
-                tree_config,
-            )?
-            .with_sync_metrics_tx(metrics_tx.clone()),
-        );
+        let mut tree = BlockchainTree::new(
+            tree_externals,
+            canon_state_notification_sender.clone(),
+            tree_config,
+        )?
+        .with_sync_metrics_tx(metrics_tx.clone());
+        // restore the sidechains and buffered blocks of the previous run
+        if let Err(err) = tree.restore_pending_blocks() {
+            warn!(target: "reth::cli", ?err, "Failed to restore pending blocks");
+        }
+        let blockchain_tree = ShareableBlockchainTree::new(tree);
+
+        // persist the pending blocks of the tree on shutdown
+        {
+            let tree = Arc::clone(&blockchain_tree.tree);
+            ctx.task_executor.spawn_critical_with_signal(
+                "blockchain tree persistence",
+                |shutdown| async move {
+                    shutdown.await;
+                    if let Err(err) = tree.write().persist_pending_blocks() {
+                        warn!(target: "reth::cli", ?err, "Failed to persist pending blocks");
+                    }
+                },
+            );
+        }
 
         // setup the blockchain provider
         let factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain));
@@ -275,7 +318,7 @@ impl Command {
 
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
-        let payload_generator = BasicPayloadJobGenerator::new(
+        let mut payload_generator = BasicPayloadJobGenerator::new(
             blockchain_db.clone(),
             transaction_pool.clone(),
             ctx.task_executor.clone(),
@@ -287,6 +330,15 @@ impl Command {
                 .max_gas_limit(self.builder.max_gas_limit),
             Arc::clone(&self.chain),
         );
+        let (better_payload_tx, better_payload_rx) = unbounded_channel();
+        if let Some(relay_service) = self.relay_submission_service(better_payload_rx)? {
+            payload_generator = payload_generator
+                .with_proposer_preferences(Arc::new(relay_service.registrations()))
+                .with_better_payload_tx(better_payload_tx);
+
+            debug!(target: "reth::cli", relays = ?self.builder.relays, "Spawning relay submission service");
+            ctx.task_executor.spawn_critical("relay submission service", relay_service);
+        }
         let (payload_service, payload_builder) = PayloadBuilderService::new(payload_generator);
 
         debug!(target: "reth::cli", "Spawning payload builder service");
@@ -346,6 +398,7 @@ impl Command {
         };
 
         let pipeline_events = pipeline.events();
+        let sync_status_events = pipeline.events();
 
         let initial_target = if let Some(tip) = self.debug.tip {
             // Set the provided tip as the initial pipeline target.
@@ -395,6 +448,21 @@ impl Command {
             events::handle_events(Some(network.clone()), Some(head.number), events),
         );
 
+        // track the sync progress that's served by the `reth` rpc namespace
+        let stage_checkpoints = StageId::ALL
+            .into_iter()
+            .map(|id| Ok((id, blockchain_db.get_stage_checkpoint(id)?.unwrap_or_default())))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let (sync_status_tx, sync_status_rx) = watch::channel(SyncProgress::default());
+        ctx.task_executor.spawn_critical(
+            "sync status task",
+            sync_status::track_sync_status(
+                SyncStatus::new(stage_checkpoints),
+                sync_status_events,
+                sync_status_tx,
+            ),
+        );
+
         let engine_api = EngineApi::new(
             blockchain_db.clone(),
             self.chain.clone(),
@@ -416,6 +484,7 @@ impl Command {
                 network.clone(),
                 ctx.task_executor.clone(),
                 blockchain_tree,
+                sync_status_rx,
                 engine_api,
                 jwt_secret,
             )
@@ -568,6 +637,32 @@ impl Command {
         })
     }
 
+    /// Returns the service that submits the better payloads received on the given channel to the
+    /// configured relays, if any.
+    fn relay_submission_service(
+        &self,
+        payloads: UnboundedReceiver<Arc<BuiltPayload>>,
+    ) -> eyre::Result<Option<RelaySubmissionService>> {
+        if self.builder.relays.is_empty() {
+            return Ok(None)
+        }
+
+        let beacon = BeaconChainConfig::from_chain(self.chain.chain).ok_or_else(|| {
+            eyre::eyre!("relay submissions are not supported on chain {}", self.chain.chain)
+        })?;
+        let secret_key_path =
+            self.builder.relay_secret_key.as_ref().expect("required by --builder.relay");
+        let secret_key = std::fs::read_to_string(secret_key_path).wrap_err_with(|| {
+            format!("Could not read relay secret key {}", secret_key_path.display())
+        })?;
+        let secret_key = hex::decode(secret_key.trim().trim_start_matches("0x"))?;
+        let signer = BuilderSigner::new(&secret_key, beacon.genesis_fork_version)?;
+        info!(target: "reth::cli", builder_pubkey = ?signer.public_key(), "Submitting payloads to relays");
+
+        let config = RelayConfig::new(self.builder.relays.clone(), beacon);
+        Ok(Some(RelaySubmissionService::new(config, signer, payloads)))
+    }
+
     /// Attempt to look up the block number for the tip hash in the database.
     /// If it doesn't exist, download the header and return the block number.
     ///
@@ -675,7 +770,11 @@ impl Command {
 
         let (tip_tx, tip_rx) = watch::channel(H256::zero());
         use reth_revm_inspectors::stack::InspectorStackConfig;
-        let factory = reth_revm::Factory::new(self.chain.clone());
+        let mut factory = reth_revm::Factory::new(self.chain.clone())
+            .with_prefetch(stage_config.execution.prefetch_depth);
+        if stage_config.execution.parallel_execution {
+            factory = factory.with_parallel_execution();
+        }
 
         let stack_config = InspectorStackConfig {
             use_printer_tracer: self.debug.print_inspector,
@@ -697,6 +796,8 @@ impl Command {
         let pipeline = builder
             .with_tip_sender(tip_tx)
             .with_metrics_tx(metrics_tx)
+            .with_commit_interval(stage_config.commit_interval)
+            .with_max_dirty_size(stage_config.memory_budget)
             .add_stages(
                 DefaultStages::new(
                     header_mode,
@@ -717,18 +818,28 @@ impl Command {
                     ExecutionStageThresholds {
                         max_blocks: stage_config.execution.max_blocks,
                         max_changes: stage_config.execution.max_changes,
+                        max_memory: stage_config.execution_memory_budget(),
                     },
                 ))
-                .set(AccountHashingStage::new(
-                    stage_config.account_hashing.clean_threshold,
-                    stage_config.account_hashing.commit_threshold,
-                ))
-                .set(StorageHashingStage::new(
-                    stage_config.storage_hashing.clean_threshold,
-                    stage_config.storage_hashing.commit_threshold,
-                ))
+                .set(
+                    AccountHashingStage::new(
+                        stage_config.account_hashing.clean_threshold,
+                        stage_config.account_hashing.commit_threshold,
+                    )
+                    .with_etl_buffer_size(stage_config.etl_buffer_size()),
+                )
+                .set(
+                    StorageHashingStage::new(
+                        stage_config.storage_hashing.clean_threshold,
+                        stage_config.storage_hashing.commit_threshold,
+                    )
+                    .with_etl_buffer_size(stage_config.etl_buffer_size()),
+                )
                 .set(MerkleStage::new_execution(stage_config.merkle.clean_threshold))
-                .set(TransactionLookupStage::new(stage_config.transaction_lookup.commit_threshold))
+                .set(
+                    TransactionLookupStage::new(stage_config.transaction_lookup.commit_threshold)
+                        .with_etl_buffer_size(stage_config.etl_buffer_size()),
+                )
                 .set(IndexAccountHistoryStage::new(
                     stage_config.index_account_history.commit_threshold,
                 ))
@@ -743,7 +854,8 @@ impl Command {
 }
 
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
-/// received. If configured, this writes known peers to `persistent_peers_file` afterwards.
+/// received. If configured, this periodically writes known peers and their history to
+/// `persistent_peers_file`, and once more afterwards.
 async fn run_network_until_shutdown<C>(
     shutdown: reth_tasks::shutdown::Shutdown,
     network: NetworkManager<C>,
@@ -753,23 +865,104 @@ async fn run_network_until_shutdown<C>(
 {
     pin_mut!(network, shutdown);
 
-    tokio::select! {
-        _ = &mut network => {},
-        _ = shutdown => {},
+    let mut persist_interval = tokio::time::interval(PEERS_PERSIST_INTERVAL);
+    persist_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // the first tick completes immediately
+    persist_interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = &mut network => break,
+            _ = &mut shutdown => break,
+            _ = persist_interval.tick() => {
+                if let Some(file_path) = &persistent_peers_file {
+                    write_peers_to_file(&network, file_path);
+                }
+            }
+        }
     }
 
     if let Some(file_path) = persistent_peers_file {
-        let known_peers = network.all_peers().collect::<Vec<_>>();
-        if let Ok(known_peers) = serde_json::to_string_pretty(&known_peers) {
-            trace!(target : "reth::cli", peers_file =?file_path, num_peers=%known_peers.len(), "Saving current peers");
-            let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
-            match parent_dir.and_then(|_| std::fs::write(&file_path, known_peers)) {
-                Ok(_) => {
-                    info!(target: "reth::cli", peers_file=?file_path, "Wrote network peers to file");
-                }
-                Err(err) => {
-                    warn!(target: "reth::cli", ?err, peers_file=?file_path, "Failed to write network peers to file");
-                }
+        write_peers_to_file(&network, &file_path);
+    }
+}
+
+/// Writes the peers of the network and their history to the given file.
+fn write_peers_to_file<C>(network: &NetworkManager<C>, file_path: &Path)
+where
+    C: BlockReader,
+{
+    let known_peers = network.persisted_peers();
+    let num_peers = known_peers.len();
+    if let Ok(known_peers) = serde_json::to_string_pretty(&known_peers) {
+        trace!(target : "reth::cli", peers_file =?file_path, %num_peers, "Saving current peers");
+        match write_file_atomic(file_path, known_peers.as_bytes()) {
+            Ok(_) => {
+                info!(target: "reth::cli", peers_file=?file_path, "Wrote network peers to file");
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", ?err, peers_file=?file_path, "Failed to write network peers to file");
+            }
+        }
+    }
+}
+
+/// Writes the contents to a temporary file next to the given path and renames it over the path, so
+/// that a crash while writing never leaves a truncated file behind.
+fn write_file_atomic(file_path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut tmp_path = file_path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = std::fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    std::fs::rename(tmp_path, file_path)
+}
+
+/// Periodically checks the share of free pages in the database, records it in the free-list
+/// metrics and warns if the database should be compacted with `reth db compact`.
+///
+/// Walking the free-list is too expensive to do on every metrics scrape, so the metrics are only
+/// updated here.
+async fn monitor_db_freelist(db: Arc<DatabaseEnv>) {
+    let mut interval = tokio::time::interval(DB_FREELIST_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        // walking the free-list reads the whole free-list table, so keep it off the async workers
+        let usage = match tokio::task::spawn_blocking({
+            let db = Arc::clone(&db);
+            move || db.page_usage()
+        })
+        .await
+        {
+            Ok(usage) => usage,
+            Err(error) => {
+                debug!(target: "reth::cli", ?error, "Database page usage task failed");
+                continue
+            }
+        };
+        if let Ok(usage) = &usage {
+            absolute_counter!("db.freelist_pages", usage.free_pages as u64);
+            absolute_counter!("db.total_pages", usage.total_pages as u64);
+        }
+        match usage {
+            Ok(usage) if usage.free_share() > DB_FREELIST_WARN_THRESHOLD => {
+                warn!(
+                    target: "reth::cli",
+                    free_pages = usage.free_pages,
+                    total_pages = usage.total_pages,
+                    free_share = usage.free_share(),
+                    "Database has a large share of free pages, consider running `reth db compact` while the node is stopped"
+                );
+            }
+            Ok(_) => {}
+            Err(error) => {
+                debug!(target: "reth::cli", ?error, "Failed to read database page usage");
             }
         }
     }
@@ -848,4 +1041,15 @@ mod tests {
         let db_path = data_dir.db_path();
         assert_eq!(db_path, Path::new("my/custom/path/db"));
     }
+
+    #[test]
+    fn write_file_atomic_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers").join("known-peers.json");
+
+        write_file_atomic(&path, b"first").unwrap();
+        write_file_atomic(&path, b"second").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"second");
+        assert!(!dir.path().join("peers").join("known-peers.json.tmp").exists());
+    }
 }
@@ -0,0 +1,439 @@
+//! Tracks the progress of the sync pipeline and estimates when its stages will be done.
+
+use futures::{Stream, StreamExt};
+use reth_metrics::{
+    metrics::{self, Gauge},
+    Metrics,
+};
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_rpc_types::{StageProgress, SyncProgress};
+use reth_stages::{ExecOutput, PipelineEvent, UnwindOutput};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+/// Interval of refreshing the ETAs while the pipeline emits no events.
+const STATUS_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The progress of a single stage.
+#[derive(Debug)]
+struct StageStatus {
+    /// The stage.
+    id: StageId,
+    /// The last checkpoint of the stage.
+    checkpoint: StageCheckpoint,
+    /// The time the current run of the stage started, along with the entities processed at that
+    /// time.
+    run_start: Option<(Instant, u64)>,
+    /// The number of entities processed per second during the last run of the stage.
+    throughput: Option<f64>,
+    /// The last time the stage reported its checkpoint.
+    last_update: Option<Instant>,
+}
+
+impl StageStatus {
+    fn new(id: StageId, checkpoint: StageCheckpoint) -> Self {
+        Self { id, checkpoint, run_start: None, throughput: None, last_update: None }
+    }
+
+    /// The number of processed entities, or the block number if the stage doesn't report
+    /// entities.
+    fn processed(&self) -> u64 {
+        self.checkpoint
+            .entities()
+            .map_or(self.checkpoint.block_number, |entities| entities.processed)
+    }
+}
+
+/// Aggregates the events emitted by the pipeline into the progress of every stage, and estimates
+/// the time until each stage and the whole pipeline are done.
+///
+/// The ETA of a stage is based on the throughput of its last run. Stages that report no entities
+/// are measured in blocks, up to the block reached by the previous stage.
+#[derive(Debug)]
+pub struct SyncStatus {
+    /// The stages in the order of the pipeline.
+    stages: Vec<StageStatus>,
+    /// The stage currently being executed or unwound.
+    current_stage: Option<StageId>,
+}
+
+impl SyncStatus {
+    /// Creates a new instance given the checkpoints of the stages, in the order of the pipeline.
+    pub fn new(checkpoints: impl IntoIterator<Item = (StageId, StageCheckpoint)>) -> Self {
+        let stages = checkpoints
+            .into_iter()
+            .map(|(id, checkpoint)| StageStatus::new(id, checkpoint))
+            .collect();
+        Self { stages, current_stage: None }
+    }
+
+    /// Returns the status of the given stage, moving it to its 1-indexed position in the pipeline
+    /// if given.
+    fn stage_mut(&mut self, id: StageId, pipeline_position: Option<usize>) -> &mut StageStatus {
+        let idx = match self.stages.iter().position(|stage| stage.id == id) {
+            Some(idx) => idx,
+            None => {
+                self.stages.push(StageStatus::new(id, StageCheckpoint::default()));
+                self.stages.len() - 1
+            }
+        };
+
+        let target_idx = pipeline_position
+            .map_or(idx, |position| position.saturating_sub(1).min(self.stages.len() - 1));
+        if target_idx != idx {
+            let stage = self.stages.remove(idx);
+            self.stages.insert(target_idx, stage);
+        }
+
+        &mut self.stages[target_idx]
+    }
+
+    /// Processes an event emitted by the pipeline.
+    pub fn on_pipeline_event(&mut self, event: PipelineEvent, now: Instant) {
+        match event {
+            PipelineEvent::Running { pipeline_position, stage_id, checkpoint, .. } => {
+                self.current_stage = Some(stage_id);
+
+                let stage = self.stage_mut(stage_id, Some(pipeline_position));
+                if let Some(checkpoint) = checkpoint {
+                    stage.checkpoint = checkpoint;
+                }
+                if stage.run_start.is_none() {
+                    stage.run_start = Some((now, stage.processed()));
+                }
+            }
+            PipelineEvent::Ran {
+                pipeline_position,
+                stage_id,
+                result: ExecOutput { checkpoint, done },
+                ..
+            } => {
+                let stage = self.stage_mut(stage_id, Some(pipeline_position));
+                stage.checkpoint = checkpoint;
+                stage.last_update = Some(now);
+
+                if let Some((start, start_processed)) = stage.run_start {
+                    let elapsed = now.saturating_duration_since(start).as_secs_f64();
+                    let processed = stage.processed();
+                    if elapsed > 0.0 && processed > start_processed {
+                        stage.throughput = Some((processed - start_processed) as f64 / elapsed);
+                    }
+                }
+
+                if done {
+                    stage.run_start = None;
+                    self.current_stage = None;
+                }
+            }
+            PipelineEvent::Unwinding { stage_id, .. } => {
+                self.current_stage = Some(stage_id);
+            }
+            PipelineEvent::Unwound { stage_id, result: UnwindOutput { checkpoint } } => {
+                let stage = self.stage_mut(stage_id, None);
+                stage.checkpoint = checkpoint;
+                stage.run_start = None;
+                stage.last_update = Some(now);
+                self.current_stage = None;
+            }
+            PipelineEvent::Error { stage_id } => {
+                self.stage_mut(stage_id, None).run_start = None;
+                self.current_stage = None;
+            }
+            PipelineEvent::Skipped { .. } => {}
+        }
+    }
+
+    /// Returns the block number reached by the stage preceding the stage at the given index.
+    fn target(&self, idx: usize) -> Option<u64> {
+        idx.checked_sub(1).map(|prev| self.stages[prev].checkpoint.block_number)
+    }
+
+    /// Returns the processed and total entities of the stage at the given index.
+    fn entities(&self, idx: usize) -> (u64, Option<u64>) {
+        let stage = &self.stages[idx];
+        match stage.checkpoint.entities() {
+            Some(entities) => (entities.processed, Some(entities.total)),
+            None => (stage.checkpoint.block_number, self.target(idx)),
+        }
+    }
+
+    /// Returns the estimated time until the stage at the given index is done, if known.
+    fn stage_eta(&self, idx: usize, now: Instant) -> Option<Duration> {
+        let stage = &self.stages[idx];
+        let (processed, total) = self.entities(idx);
+        let remaining = total?.saturating_sub(processed);
+
+        if remaining == 0 {
+            // The entities of a stage that is behind the previous stage are from its last run.
+            let caught_up =
+                self.target(idx).map_or(true, |target| stage.checkpoint.block_number >= target);
+            return caught_up.then_some(Duration::ZERO)
+        }
+
+        let eta = Duration::from_secs_f64(remaining as f64 / stage.throughput?);
+        let since_last_update =
+            stage.last_update.map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        Some(eta.saturating_sub(since_last_update))
+    }
+
+    /// Returns the progress of the pipeline at the given time.
+    ///
+    /// The ETA of the pipeline is only known if the ETAs of all stages are known.
+    pub fn progress(&self, now: Instant) -> SyncProgress {
+        let etas = (0..self.stages.len()).map(|idx| self.stage_eta(idx, now)).collect::<Vec<_>>();
+
+        let stages = self
+            .stages
+            .iter()
+            .zip(&etas)
+            .enumerate()
+            .map(|(idx, (stage, eta))| {
+                let (processed, total) = self.entities(idx);
+                StageProgress {
+                    name: stage.id.to_string(),
+                    checkpoint: stage.checkpoint.block_number,
+                    processed,
+                    total,
+                    throughput: stage.throughput,
+                    eta: eta.map(|eta| eta.as_secs()),
+                }
+            })
+            .collect();
+
+        SyncProgress {
+            syncing: self.current_stage.is_some(),
+            current_stage: self.current_stage.map(|id| id.to_string()),
+            eta: etas.into_iter().sum::<Option<Duration>>().map(|eta| eta.as_secs()),
+            stages,
+        }
+    }
+}
+
+/// Tracks the progress of the pipeline given its events, and publishes it to the given sender and
+/// as metrics.
+///
+/// The progress is refreshed whenever the pipeline emits an event, and periodically to count down
+/// the ETAs.
+pub async fn track_sync_status<E>(
+    mut status: SyncStatus,
+    mut events: E,
+    sender: watch::Sender<SyncProgress>,
+) where
+    E: Stream<Item = PipelineEvent> + Unpin,
+{
+    let mut metrics = SyncStatusMetrics::default();
+
+    let mut update_interval = tokio::time::interval(STATUS_UPDATE_INTERVAL);
+    update_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                status.on_pipeline_event(event, Instant::now());
+            }
+            _ = update_interval.tick() => {}
+        }
+
+        let progress = status.progress(Instant::now());
+        metrics.record(&progress);
+        sender.send_if_modified(|current| {
+            let modified = *current != progress;
+            *current = progress;
+            modified
+        });
+    }
+}
+
+/// Records the ETAs of the pipeline as metrics.
+#[derive(Debug, Default)]
+struct SyncStatusMetrics {
+    pipeline: PipelineEtaMetrics,
+    stages: HashMap<String, StageEtaMetrics>,
+}
+
+impl SyncStatusMetrics {
+    fn record(&mut self, progress: &SyncProgress) {
+        self.pipeline.eta_seconds.set(eta_gauge_value(progress.eta));
+
+        for stage in &progress.stages {
+            let metrics = self.stages.entry(stage.name.clone()).or_insert_with(|| {
+                StageEtaMetrics::new_with_labels(&[("stage", stage.name.clone())])
+            });
+            metrics.stage_eta_seconds.set(eta_gauge_value(stage.eta));
+            metrics.stage_throughput.set(stage.throughput.unwrap_or_default());
+        }
+    }
+}
+
+/// Unknown ETAs are reported as `NaN`.
+fn eta_gauge_value(eta: Option<u64>) -> f64 {
+    eta.map_or(f64::NAN, |eta| eta as f64)
+}
+
+/// Sync pipeline ETA metrics.
+#[derive(Metrics)]
+#[metrics(scope = "sync")]
+struct PipelineEtaMetrics {
+    /// The estimated number of seconds until all stages are done, `NaN` if unknown.
+    eta_seconds: Gauge,
+}
+
+/// Sync stage ETA metrics.
+#[derive(Metrics)]
+#[metrics(scope = "sync")]
+struct StageEtaMetrics {
+    /// The estimated number of seconds until the stage is done, `NaN` if unknown.
+    stage_eta_seconds: Gauge,
+    /// The number of entities processed per second during the last run of the stage.
+    stage_throughput: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::stage::EntitiesCheckpoint;
+
+    fn entities_checkpoint(block_number: u64, processed: u64, total: u64) -> StageCheckpoint {
+        StageCheckpoint::new(block_number)
+            .with_entities_stage_checkpoint(EntitiesCheckpoint { processed, total })
+    }
+
+    #[test]
+    fn stage_eta_from_throughput() {
+        let start = Instant::now();
+        let mut status = SyncStatus::new([
+            (StageId::Headers, entities_checkpoint(100, 100, 100)),
+            (StageId::Execution, entities_checkpoint(0, 0, 1000)),
+            (StageId::Finish, StageCheckpoint::new(0)),
+        ]);
+
+        status.on_pipeline_event(
+            PipelineEvent::Running {
+                pipeline_position: 2,
+                pipeline_total: 3,
+                stage_id: StageId::Execution,
+                checkpoint: Some(entities_checkpoint(0, 0, 1000)),
+            },
+            start,
+        );
+        status.on_pipeline_event(
+            PipelineEvent::Ran {
+                pipeline_position: 2,
+                pipeline_total: 3,
+                stage_id: StageId::Execution,
+                result: ExecOutput { checkpoint: entities_checkpoint(10, 100, 1000), done: false },
+            },
+            start + Duration::from_secs(10),
+        );
+
+        let progress = status.progress(start + Duration::from_secs(10));
+        assert!(progress.syncing);
+        assert_eq!(progress.current_stage, Some(StageId::Execution.to_string()));
+        assert_eq!(progress.stages[0].eta, Some(0));
+        assert_eq!(progress.stages[1].throughput, Some(10.0));
+        assert_eq!(progress.stages[1].eta, Some(90));
+        // the finish stage has no throughput yet
+        assert_eq!(progress.stages[2].total, Some(10));
+        assert_eq!(progress.stages[2].eta, None);
+        assert_eq!(progress.eta, None);
+
+        // the ETA counts down between checkpoints
+        let progress = status.progress(start + Duration::from_secs(40));
+        assert_eq!(progress.stages[1].eta, Some(60));
+    }
+
+    #[test]
+    fn pipeline_eta() {
+        let start = Instant::now();
+        let mut status = SyncStatus::new([
+            (StageId::Headers, entities_checkpoint(100, 100, 100)),
+            (StageId::Bodies, StageCheckpoint::new(0)),
+        ]);
+
+        status.on_pipeline_event(
+            PipelineEvent::Running {
+                pipeline_position: 2,
+                pipeline_total: 2,
+                stage_id: StageId::Bodies,
+                checkpoint: Some(StageCheckpoint::new(0)),
+            },
+            start,
+        );
+        status.on_pipeline_event(
+            PipelineEvent::Ran {
+                pipeline_position: 2,
+                pipeline_total: 2,
+                stage_id: StageId::Bodies,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(20), done: false },
+            },
+            start + Duration::from_secs(10),
+        );
+
+        let progress = status.progress(start + Duration::from_secs(10));
+        assert_eq!(progress.stages[1].processed, 20);
+        assert_eq!(progress.stages[1].total, Some(100));
+        assert_eq!(progress.eta, Some(40));
+
+        status.on_pipeline_event(
+            PipelineEvent::Ran {
+                pipeline_position: 2,
+                pipeline_total: 2,
+                stage_id: StageId::Bodies,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+            },
+            start + Duration::from_secs(50),
+        );
+
+        let progress = status.progress(start + Duration::from_secs(50));
+        assert!(!progress.syncing);
+        assert_eq!(progress.eta, Some(0));
+    }
+
+    #[test]
+    fn stale_entities_have_no_eta() {
+        let status = SyncStatus::new([
+            (StageId::Headers, entities_checkpoint(100, 100, 100)),
+            (StageId::Execution, entities_checkpoint(50, 1000, 1000)),
+        ]);
+
+        let progress = status.progress(Instant::now());
+        assert_eq!(progress.stages[1].eta, None);
+        assert_eq!(progress.eta, None);
+    }
+
+    #[test]
+    fn stages_follow_pipeline_order() {
+        let mut status = SyncStatus::new([
+            (StageId::IndexStorageHistory, StageCheckpoint::new(0)),
+            (StageId::IndexAccountHistory, StageCheckpoint::new(0)),
+        ]);
+
+        status.on_pipeline_event(
+            PipelineEvent::Running {
+                pipeline_position: 1,
+                pipeline_total: 2,
+                stage_id: StageId::IndexAccountHistory,
+                checkpoint: None,
+            },
+            Instant::now(),
+        );
+
+        let names = status
+            .progress(Instant::now())
+            .stages
+            .into_iter()
+            .map(|stage| stage.name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                StageId::IndexAccountHistory.to_string(),
+                StageId::IndexStorageHistory.to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Crawls the discv4 network and publishes the live nodes as an EIP-1459 DNS tree.
+use crate::args::read_secret_key;
+use clap::{Parser, ValueEnum};
+use enr::Enr;
+use reth_db::open_db_read_only;
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config, EnrForkIdEntry, DEFAULT_DISCOVERY_PORT};
+use reth_dns_discovery::{publish::DnsTree, tree::LinkEntry};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::{
+    mainnet_nodes, stage::StageId, ChainSpec, ForkCondition, ForkId, Head, NodeRecord, PeerId,
+};
+use reth_provider::{HeaderProvider, ProviderFactory, StageCheckpointReader};
+use reth_rlp::Decodable;
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+/// The output format of the tree.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, ValueEnum)]
+pub enum TreeFormat {
+    /// A DNS zone file with one TXT record per entry.
+    #[default]
+    Zone,
+    /// A JSON object mapping the record names to the TXT record content.
+    Json,
+}
+
+/// `reth p2p dns-tree` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The domain the tree is published under.
+    #[arg(long, value_name = "DOMAIN")]
+    domain: String,
+
+    /// The file containing the key the root of the tree is signed with.
+    #[arg(long, value_name = "PATH")]
+    signing_key: PathBuf,
+
+    /// How long to crawl the network for nodes.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5m")]
+    duration: Duration,
+
+    /// The sequence number of the tree.
+    ///
+    /// Must be increased with every published update. Defaults to the current unix timestamp.
+    #[arg(long)]
+    seq: Option<u64>,
+
+    /// Links to other trees to include, e.g. `enrtree://<key>@nodes.example.org`.
+    #[arg(long, value_delimiter = ',')]
+    link: Vec<LinkEntry>,
+
+    /// The UDP port the crawler binds to.
+    #[arg(long = "discovery.port", name = "discovery.port", default_value_t = DEFAULT_DISCOVERY_PORT)]
+    port: u16,
+
+    /// The TTL of the records in the zone file.
+    #[arg(long, default_value_t = 1800)]
+    ttl: u32,
+
+    /// The output format.
+    #[arg(long, value_enum, default_value_t = TreeFormat::Zone)]
+    format: TreeFormat,
+
+    /// The file to write the tree to, printed to stdout if not set.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `p2p dns-tree` command
+    ///
+    /// The database at the given path, if any, provides the latest block of the chain.
+    pub async fn execute(
+        &self,
+        chain: Arc<ChainSpec>,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<()> {
+        let signing_key = read_secret_key(&self.signing_key)?;
+        let seq = match self.seq {
+            Some(seq) => seq,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+
+        let head = crawl_head(chain.clone(), db_path, log_level)?;
+        let nodes = self.crawl(&chain, head).await?;
+        info!(target: "reth::cli", nodes = nodes.len(), "Building DNS tree");
+
+        let mut tree = DnsTree::new(nodes, self.link.clone(), seq);
+        tree.sign(&signing_key).map_err(|err| eyre::eyre!("Failed to sign tree root: {err}"))?;
+
+        let out = match self.format {
+            TreeFormat::Zone => tree.to_zone_file(&self.domain, self.ttl),
+            TreeFormat::Json => serde_json::to_string_pretty(&tree.to_txt_records(&self.domain))?,
+        };
+
+        match &self.output {
+            Some(path) => {
+                std::fs::write(path, out)?;
+                println!("DNS tree for {} written to {path:?}", self.domain);
+            }
+            None => println!("{out}"),
+        }
+
+        Ok(())
+    }
+
+    /// Crawls the network for the configured duration and returns the signed records of all nodes
+    /// that advertise a fork id compatible with the chain.
+    async fn crawl(&self, chain: &ChainSpec, head: Head) -> eyre::Result<Vec<Enr<SecretKey>>> {
+        let secret_key = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let id =
+            PeerId::from_slice(&secret_key.public_key(SECP256K1).serialize_uncompressed()[1..]);
+        let addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, self.port));
+        let local =
+            NodeRecord { address: addr.ip(), tcp_port: addr.port(), udp_port: addr.port(), id };
+
+        let fork_filter = chain.fork_filter(head);
+
+        let config = Discv4Config::builder()
+            .enable_eip868(true)
+            .lookup_interval(Duration::from_secs(5))
+            .add_boot_nodes(chain.chain.bootnodes().unwrap_or_else(mainnet_nodes))
+            .build();
+        let (_discv4, mut service) = Discv4::bind(addr, local, secret_key, config).await?;
+        let mut updates = service.update_stream();
+        let _handle = service.spawn();
+
+        info!(target: "reth::cli", duration = ?self.duration, "Crawling the network");
+
+        let mut nodes = HashMap::new();
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => break,
+                update = updates.next() => {
+                    let Some(update) = update else { break };
+                    let DiscoveryUpdate::SignedEnr(record, enr) = update else { continue };
+                    let Some(fork_id) = enr_fork_id(&enr) else { continue };
+                    if let Err(err) = fork_filter.validate(fork_id) {
+                        debug!(target: "reth::cli", ?record, ?err, "Skipping incompatible node");
+                        continue
+                    }
+                    nodes.insert(record.id, enr);
+                }
+            }
+        }
+
+        Ok(nodes.into_values().collect())
+    }
+}
+
+/// Returns the head the fork ids of the crawled nodes are validated against.
+///
+/// Like the network of the node, the crawler uses the latest block of the local database. Without a
+/// database, all block based forks of the chain are assumed to be activated. Timestamp based forks
+/// are activated according to the current time.
+fn crawl_head(
+    chain: Arc<ChainSpec>,
+    db_path: &Path,
+    log_level: Option<LogLevel>,
+) -> eyre::Result<Head> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    // only the number and the timestamp of the head are relevant for the fork filter
+    if db_path.exists() {
+        let db = open_db_read_only(db_path, log_level)?;
+        let provider = ProviderFactory::new(&db, chain).provider()?;
+        let number =
+            provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        let header = provider
+            .header_by_number(number)?
+            .ok_or_else(|| eyre::eyre!("Header of the latest block {number} not found."))?;
+        return Ok(Head { number, timestamp: header.timestamp.max(now), ..Default::default() })
+    }
+
+    let number = chain
+        .forks_iter()
+        .filter_map(|(_, condition)| match condition {
+            ForkCondition::Block(block) | ForkCondition::TTD { fork_block: Some(block), .. } => {
+                Some(block)
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or_default();
+    Ok(Head { number, timestamp: now, ..Default::default() })
+}
+
+/// Returns the [`ForkId`] of the `eth` entry of the record.
+fn enr_fork_id(enr: &Enr<SecretKey>) -> Option<ForkId> {
+    let mut eth = enr.get_raw_rlp(b"eth")?;
+    EnrForkIdEntry::decode(&mut eth).ok().map(|entry| entry.fork_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{ForkHash, MAINNET};
+
+    #[derive(Parser)]
+    struct CommandParser<T: clap::Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[tokio::test]
+    async fn missing_signing_key_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let signing_key = dir.path().join("key");
+        let cmd = CommandParser::<Command>::parse_from([
+            "reth",
+            "--domain",
+            "nodes.example.org",
+            "--signing-key",
+            signing_key.to_str().unwrap(),
+        ])
+        .args;
+        assert!(cmd.execute(MAINNET.clone(), dir.path(), None).await.is_err());
+        assert!(!signing_key.exists());
+    }
+
+    #[test]
+    fn parse_dns_tree_args() {
+        let cmd = CommandParser::<Command>::parse_from([
+            "reth",
+            "--domain",
+            "nodes.example.org",
+            "--signing-key",
+            "key",
+            "--duration",
+            "10s",
+            "--format",
+            "json",
+        ])
+        .args;
+        assert_eq!(cmd.duration, Duration::from_secs(10));
+        assert_eq!(cmd.format, TreeFormat::Json);
+        assert!(cmd.link.is_empty());
+    }
+
+    #[test]
+    fn crawl_head_without_database() {
+        let head = crawl_head(MAINNET.clone(), Path::new("/nonexistent/db"), None).unwrap();
+        assert_eq!(
+            MAINNET.fork_filter(head).current(),
+            ForkId { hash: ForkHash([0xdc, 0xe9, 0x6c, 0x2d]), next: 0 }
+        );
+    }
+}
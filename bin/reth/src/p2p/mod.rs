@@ -5,7 +5,7 @@ use crate::{
         utils::{chain_spec_value_parser, hash_or_num_value_parser},
         DatabaseArgs, DiscoveryArgs,
     },
-    dirs::{DataDirPath, MaybePlatformPath},
+    dirs::{ChainPath, DataDirPath, MaybePlatformPath},
     utils::get_single_header,
 };
 use backon::{ConstantBuilder, Retryable};
@@ -14,10 +14,13 @@ use reth_config::Config;
 use reth_db::open_db;
 use reth_discv4::NatResolver;
 use reth_interfaces::p2p::bodies::client::BodiesClient;
+use reth_network::FetchClient;
 use reth_primitives::{BlockHashOrNumber, ChainSpec, NodeRecord};
 use reth_provider::ProviderFactory;
 use std::{path::PathBuf, sync::Arc};
 
+mod dns_tree;
+
 /// `reth p2p` command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -99,49 +102,21 @@ pub enum Subcommands {
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
     },
+    /// Crawl the network and build a signed EIP-1459 DNS tree of the discovered nodes
+    DnsTree(dns_tree::Command),
 }
 impl Command {
     /// Execute `p2p` command
     pub async fn execute(&self) -> eyre::Result<()> {
-        let tempdir = tempfile::TempDir::new()?;
-        let noop_db = Arc::new(open_db(&tempdir.into_path(), self.db.log_level)?);
-
         // add network name to data dir
         let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
-        let config_path = self.config.clone().unwrap_or(data_dir.config_path());
-
-        let mut config: Config = confy::load_path(&config_path).unwrap_or_default();
-
-        if let Some(peer) = self.trusted_peer {
-            config.peers.trusted_nodes.insert(peer);
-        }
-
-        if config.peers.trusted_nodes.is_empty() && self.trusted_only {
-            eyre::bail!("No trusted nodes. Set trusted peer with `--trusted-peer <enode record>` or set `--trusted-only` to `false`")
-        }
-
-        config.peers.connect_trusted_nodes_only = self.trusted_only;
 
-        let default_secret_key_path = data_dir.p2p_secret_path();
-        let secret_key_path = self.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
-        let p2p_secret_key = get_secret_key(&secret_key_path)?;
-
-        let mut network_config_builder =
-            config.network_config(self.nat, None, p2p_secret_key).chain_spec(self.chain.clone());
-
-        network_config_builder = self.discovery.apply_to_builder(network_config_builder);
-
-        let network = network_config_builder
-            .build(Arc::new(ProviderFactory::new(noop_db, self.chain.clone())))
-            .start_network()
-            .await?;
-
-        let fetch_client = network.fetch_client().await?;
         let retries = self.retries.max(1);
         let backoff = ConstantBuilder::default().with_max_times(retries);
 
         match self.command {
             Subcommands::Header { id } => {
+                let fetch_client = self.fetch_client(&data_dir).await?;
                 let header = (move || get_single_header(fetch_client.clone(), id))
                     .retry(&backoff)
                     .notify(|err, _| println!("Error requesting header: {err}. Retrying..."))
@@ -149,6 +124,7 @@ impl Command {
                 println!("Successfully downloaded header: {header:?}");
             }
             Subcommands::Body { id } => {
+                let fetch_client = self.fetch_client(&data_dir).await?;
                 let hash = match id {
                     BlockHashOrNumber::Hash(hash) => hash,
                     BlockHashOrNumber::Number(number) => {
@@ -180,8 +156,48 @@ impl Command {
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
+            // the crawler runs its own discovery service and doesn't need the network
+            Subcommands::DnsTree(ref command) => {
+                command.execute(self.chain.clone(), &data_dir.db_path(), self.db.log_level).await?
+            }
         }
 
         Ok(())
     }
+
+    /// Starts the network and returns a client to fetch data from its peers.
+    async fn fetch_client(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<FetchClient> {
+        let tempdir = tempfile::TempDir::new()?;
+        let noop_db = Arc::new(open_db(&tempdir.into_path(), self.db.log_level)?);
+
+        let config_path = self.config.clone().unwrap_or(data_dir.config_path());
+
+        let mut config: Config = confy::load_path(&config_path).unwrap_or_default();
+
+        if let Some(peer) = self.trusted_peer {
+            config.peers.trusted_nodes.insert(peer);
+        }
+
+        if config.peers.trusted_nodes.is_empty() && self.trusted_only {
+            eyre::bail!("No trusted nodes. Set trusted peer with `--trusted-peer <enode record>` or set `--trusted-only` to `false`")
+        }
+
+        config.peers.connect_trusted_nodes_only = self.trusted_only;
+
+        let default_secret_key_path = data_dir.p2p_secret_path();
+        let secret_key_path = self.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
+        let p2p_secret_key = get_secret_key(&secret_key_path)?;
+
+        let mut network_config_builder =
+            config.network_config(self.nat, None, p2p_secret_key).chain_spec(self.chain.clone());
+
+        network_config_builder = self.discovery.apply_to_builder(network_config_builder);
+
+        let network = network_config_builder
+            .build(Arc::new(ProviderFactory::new(noop_db, self.chain.clone())))
+            .start_network()
+            .await?;
+
+        Ok(network.fetch_client().await?)
+    }
 }
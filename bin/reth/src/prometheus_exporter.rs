@@ -115,6 +115,8 @@ pub(crate) async fn initialize(
     // registered
     describe_counter!("db.table_size", Unit::Bytes, "The size of a database table (in bytes)");
     describe_counter!("db.table_pages", "The number of database pages for a table");
+    describe_counter!("db.freelist_pages", "The number of free database pages that can be reused");
+    describe_counter!("db.total_pages", "The number of pages allocated in the database file");
     process.describe();
     describe_memory_stats();
 
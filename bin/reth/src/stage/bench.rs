@@ -0,0 +1,450 @@
+//! `reth stage bench` command
+//!
+//! Benchmarks a stage on a database created by `reth stage dump`.
+use crate::args::{utils::chain_spec_value_parser, DatabaseArgs};
+use clap::{Parser, ValueEnum};
+use eyre::WrapErr;
+use reth_db::{
+    init_db,
+    mdbx::{tx::Tx, TransactionKind, WriteMap},
+    DatabaseEnv, Tables,
+};
+use reth_primitives::{stage::StageCheckpoint, BlockNumber, ChainSpec};
+use reth_provider::{HeaderProvider, ProviderFactory};
+use reth_revm::Factory;
+use reth_stages::{
+    stages::{AccountHashingStage, ExecutionStage, MerkleStage, StorageHashingStage},
+    ExecInput, ExecOutput, Stage, UnwindInput,
+};
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// `reth stage bench` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the database created by `reth stage dump`.
+    #[arg(long, value_name = "DUMP_PATH", verbatim_doc_comment)]
+    dump: PathBuf,
+
+    /// The chain the dump was created from.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = chain_spec_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The stage to benchmark.
+    #[arg(value_enum)]
+    stage: BenchStage,
+
+    /// The first block of the dump.
+    #[arg(long, short)]
+    from: BlockNumber,
+
+    /// The last block of the dump.
+    #[arg(long, short)]
+    to: BlockNumber,
+
+    /// How many times the stage is run.
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+
+    /// The path to write the JSON report to. The report is printed to stdout if not set.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Stages that can be benchmarked from a dump.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum BenchStage {
+    /// Execution stage.
+    Execution,
+    /// AccountHashing stage.
+    AccountHashing,
+    /// StorageHashing stage.
+    StorageHashing,
+    /// Merkle stage.
+    Merkle,
+}
+
+impl BenchStage {
+    /// Returns the stage configured the same way as the dry-run of `reth stage dump`.
+    fn stage(&self, chain: Arc<ChainSpec>) -> Box<dyn Stage<DatabaseEnv>> {
+        match self {
+            BenchStage::Execution => {
+                Box::new(ExecutionStage::new_with_factory(Factory::new(chain)))
+            }
+            BenchStage::AccountHashing => {
+                let mut stage = AccountHashingStage::default();
+                stage.clean_threshold = 1; // Forces hashing from scratch
+                Box::new(stage)
+            }
+            BenchStage::StorageHashing => {
+                let mut stage = StorageHashingStage::default();
+                stage.clean_threshold = 1; // Forces hashing from scratch
+                Box::new(stage)
+            }
+            // Forces updating the root instead of calculating from scratch
+            BenchStage::Merkle => Box::new(MerkleStage::Execution { clean_threshold: u64::MAX }),
+        }
+    }
+
+    /// Returns `true` if the stage can be unwound on the dump.
+    ///
+    /// Unwinding the merkle stage requires the hashed state as of the first block, while the dump
+    /// only contains the hashed state as of the last block.
+    fn is_unwindable(&self) -> bool {
+        !matches!(self, BenchStage::Merkle)
+    }
+}
+
+impl Command {
+    /// Execute `stage bench` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from >= self.to {
+            eyre::bail!("The first block {} must be below the last block {}.", self.from, self.to)
+        }
+        if !self.dump.exists() {
+            eyre::bail!("Database {:?} does not exist.", self.dump)
+        }
+
+        info!(target: "reth::cli", path = ?self.dump, "Opening database");
+        let db = init_db(&self.dump, self.db.log_level)?;
+        let factory = ProviderFactory::new(&db, self.chain.clone());
+
+        // the dumps of the hashing stages don't contain headers
+        let headers = factory.provider()?.headers_range(self.from + 1..=self.to)?;
+        let gas = (headers.len() as u64 == self.to - self.from)
+            .then(|| headers.iter().map(|header| header.gas_used).sum::<u64>());
+
+        let mut runs = Vec::with_capacity(self.runs as usize);
+        for run in 1..=self.runs {
+            info!(target: "reth::cli", stage = ?self.stage, run, runs = self.runs, "Running stage");
+            runs.push(self.run(&db, gas).await?);
+        }
+
+        let report = BenchReport {
+            stage: format!("{:?}", self.stage),
+            from: self.from,
+            to: self.to,
+            gas,
+            summary: BenchSummary::new(&runs),
+            runs,
+        };
+        let json = serde_json::to_string_pretty(&report)?;
+        match &self.output {
+            Some(path) => std::fs::write(path, json)
+                .wrap_err_with(|| format!("Could not write report to {path:?}"))?,
+            None => println!("{json}"),
+        }
+
+        Ok(())
+    }
+
+    /// Runs the stage once from the first to the last block of the dump, commits it and unwinds
+    /// it back.
+    ///
+    /// The run is made on a scratch copy of the dump that is removed afterwards, so every run
+    /// starts from the same checkpoint and tables.
+    async fn run(&self, dump: &DatabaseEnv, gas: Option<u64>) -> eyre::Result<RunReport> {
+        // the copy is made next to the dump, since it may not fit into the temporary directory
+        let parent = self.dump.parent().filter(|parent| !parent.as_os_str().is_empty());
+        let scratch_dir = tempfile::tempdir_in(parent.unwrap_or(Path::new(".")))
+            .wrap_err("Could not create scratch directory")?;
+        dump.copy_compacted(scratch_dir.path())?;
+        let db = init_db(scratch_dir.path(), self.db.log_level)?;
+        let factory = ProviderFactory::new(&db, self.chain.clone());
+
+        let provider = factory.provider_rw()?;
+        let mut stage = self.stage.stage(self.chain.clone());
+
+        let tables_before = table_stats(provider.tx_ref())?;
+        let allocations_before = allocation_stats();
+
+        let mut input =
+            ExecInput { target: Some(self.to), checkpoint: Some(StageCheckpoint::new(self.from)) };
+        let start = Instant::now();
+        let checkpoint = loop {
+            let ExecOutput { checkpoint, done } = stage.execute(&provider, input).await?;
+            if done {
+                break checkpoint
+            }
+            input.checkpoint = Some(checkpoint);
+        };
+        let execute_duration = start.elapsed();
+        let allocations_after = allocation_stats();
+
+        let start = Instant::now();
+        provider.commit()?;
+        let commit_duration = start.elapsed();
+
+        let tables = table_stats(factory.provider()?.tx_ref())?;
+        let net_entries_changed = tables
+            .iter()
+            .map(|table| {
+                let before = tables_before
+                    .iter()
+                    .find(|before| before.table == table.table)
+                    .map_or(0, |before| before.entries);
+                table.entries.abs_diff(before) as u64
+            })
+            .sum::<u64>();
+
+        let unwind_duration = if self.stage.is_unwindable() {
+            let provider = factory.provider_rw()?;
+            let start = Instant::now();
+            let mut input = UnwindInput { checkpoint, unwind_to: self.from, bad_block: None };
+            while input.checkpoint.block_number > self.from {
+                input.checkpoint = stage.unwind(&provider, input).await?.checkpoint;
+            }
+            Some(start.elapsed())
+        } else {
+            None
+        };
+
+        // the scratch copy must be closed before it's removed
+        drop(db);
+        scratch_dir.close().wrap_err("Could not remove scratch directory")?;
+
+        let execute_secs = execute_duration.as_secs_f64();
+        Ok(RunReport {
+            execute_secs,
+            commit_secs: commit_duration.as_secs_f64(),
+            unwind_secs: unwind_duration.as_ref().map(Duration::as_secs_f64),
+            blocks_per_sec: (self.to - self.from) as f64 / execute_secs,
+            gas_per_sec: gas.map(|gas| gas as f64 / execute_secs),
+            net_entries_changed,
+            entries_per_sec: net_entries_changed as f64 / execute_secs,
+            allocations: allocations_before
+                .zip(allocations_after)
+                .map(|(before, after)| after.count.saturating_sub(before.count)),
+            allocated_bytes: allocations_after.map(|after| after.allocated_bytes),
+            tables: tables.into_iter().filter(|table| table.entries > 0).collect(),
+        })
+    }
+}
+
+/// The JSON report of `reth stage bench`.
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    /// The benchmarked stage.
+    stage: String,
+    /// The first block of the dump.
+    from: BlockNumber,
+    /// The last block of the dump.
+    to: BlockNumber,
+    /// The gas used by the blocks of the dump, if the dump contains the headers.
+    gas: Option<u64>,
+    /// Aggregates of all runs.
+    summary: BenchSummary,
+    /// The individual runs.
+    runs: Vec<RunReport>,
+}
+
+/// Aggregates of the runs of a stage.
+#[derive(Debug, Serialize)]
+struct BenchSummary {
+    /// The mean duration of the execution of the stage.
+    mean_execute_secs: f64,
+    /// The shortest duration of the execution of the stage.
+    min_execute_secs: f64,
+    /// The longest duration of the execution of the stage.
+    max_execute_secs: f64,
+    /// The mean gas executed per second, if the dump contains the headers.
+    mean_gas_per_sec: Option<f64>,
+    /// The mean number of table entries changed per second.
+    mean_entries_per_sec: f64,
+}
+
+impl BenchSummary {
+    fn new(runs: &[RunReport]) -> Self {
+        let mean = |values: Vec<f64>| values.iter().sum::<f64>() / values.len() as f64;
+        let execute_secs = runs.iter().map(|run| run.execute_secs).collect::<Vec<_>>();
+
+        Self {
+            mean_execute_secs: mean(execute_secs.clone()),
+            min_execute_secs: execute_secs.iter().copied().fold(f64::INFINITY, f64::min),
+            max_execute_secs: execute_secs.iter().copied().fold(0.0, f64::max),
+            mean_gas_per_sec: runs
+                .iter()
+                .map(|run| run.gas_per_sec)
+                .collect::<Option<Vec<_>>>()
+                .map(mean),
+            mean_entries_per_sec: mean(runs.iter().map(|run| run.entries_per_sec).collect()),
+        }
+    }
+}
+
+/// The report of a single run of a stage.
+#[derive(Debug, Serialize)]
+struct RunReport {
+    /// The duration of the execution of the stage.
+    execute_secs: f64,
+    /// The duration of the commit of the execution.
+    commit_secs: f64,
+    /// The duration of the unwind of the stage, if the stage can be unwound on the dump.
+    unwind_secs: Option<f64>,
+    /// The number of blocks executed per second.
+    blocks_per_sec: f64,
+    /// The gas executed per second, if the dump contains the headers.
+    gas_per_sec: Option<f64>,
+    /// The net number of table entries added or removed by the execution.
+    net_entries_changed: u64,
+    /// The net number of table entries changed per second.
+    entries_per_sec: f64,
+    /// The number of allocations during the execution, if built with jemalloc.
+    allocations: Option<u64>,
+    /// The number of bytes allocated after the execution, if built with jemalloc.
+    allocated_bytes: Option<u64>,
+    /// The MDBX stats of all non-empty tables after the execution was committed.
+    tables: Vec<TableStats>,
+}
+
+/// The MDBX stats of a table.
+#[derive(Debug, Serialize)]
+struct TableStats {
+    table: &'static str,
+    entries: usize,
+    branch_pages: usize,
+    leaf_pages: usize,
+    overflow_pages: usize,
+    size_bytes: usize,
+}
+
+/// Returns the MDBX stats of all tables as seen by the given transaction.
+fn table_stats<K: TransactionKind>(tx: &Tx<'_, K, WriteMap>) -> eyre::Result<Vec<TableStats>> {
+    Tables::ALL
+        .iter()
+        .map(|table| {
+            let table = table.name();
+            let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
+            let stats =
+                tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {table}"))?;
+
+            let num_pages = stats.leaf_pages() + stats.branch_pages() + stats.overflow_pages();
+            Ok(TableStats {
+                table,
+                entries: stats.entries(),
+                branch_pages: stats.branch_pages(),
+                leaf_pages: stats.leaf_pages(),
+                overflow_pages: stats.overflow_pages(),
+                size_bytes: stats.page_size() as usize * num_pages,
+            })
+        })
+        .collect()
+}
+
+/// Allocation counters of the global allocator.
+#[derive(Debug, Clone, Copy)]
+struct AllocationStats {
+    /// The total number of allocations since the start of the process.
+    count: u64,
+    /// The number of bytes currently allocated.
+    allocated_bytes: u64,
+}
+
+/// Returns the allocation counters of jemalloc.
+///
+/// The counters are approximate, since allocations served by thread caches are only accounted
+/// when the caches are flushed.
+#[cfg(feature = "jemalloc")]
+fn allocation_stats() -> Option<AllocationStats> {
+    use jemalloc_ctl::{epoch, raw, stats};
+
+    epoch::advance().ok()?;
+    // `4096` is `MALLCTL_ARENAS_ALL`, i.e. the stats merged over all arenas.
+    // SAFETY: both names are valid, nul-terminated mallctl names of `uint64_t` statistics.
+    let (small, large) = unsafe {
+        (
+            raw::read::<u64>(b"stats.arenas.4096.small.nmalloc\0").ok()?,
+            raw::read::<u64>(b"stats.arenas.4096.large.nmalloc\0").ok()?,
+        )
+    };
+    Some(AllocationStats {
+        count: small + large,
+        allocated_bytes: stats::allocated::read().ok()? as u64,
+    })
+}
+
+/// Allocations are only counted when built with jemalloc.
+#[cfg(not(feature = "jemalloc"))]
+fn allocation_stats() -> Option<AllocationStats> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bench() {
+        let cmd = Command::parse_from([
+            "reth",
+            "--dump",
+            "dump",
+            "--from",
+            "100",
+            "--to",
+            "200",
+            "execution",
+        ]);
+        assert_eq!(cmd.stage, BenchStage::Execution);
+        assert_eq!((cmd.from, cmd.to, cmd.runs), (100, 200, 5));
+        assert_eq!(cmd.output, None);
+
+        let cmd = Command::parse_from([
+            "reth", "--dump", "dump", "-f", "100", "-t", "200", "--runs", "2", "merkle",
+        ]);
+        assert_eq!(cmd.stage, BenchStage::Merkle);
+        assert_eq!(cmd.runs, 2);
+
+        assert!(Command::try_parse_from([
+            "reth", "--dump", "dump", "-f", "100", "-t", "200", "--runs", "0", "merkle",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn summarize_runs() {
+        let run = |execute_secs: f64, gas_per_sec: Option<f64>| RunReport {
+            execute_secs,
+            commit_secs: 0.5,
+            unwind_secs: None,
+            blocks_per_sec: 100.0 / execute_secs,
+            gas_per_sec,
+            net_entries_changed: 10,
+            entries_per_sec: 10.0 / execute_secs,
+            allocations: None,
+            allocated_bytes: None,
+            tables: Vec::new(),
+        };
+
+        let summary = BenchSummary::new(&[run(1.0, Some(4.0)), run(4.0, Some(1.0))]);
+        assert_eq!(summary.mean_execute_secs, 2.5);
+        assert_eq!(summary.min_execute_secs, 1.0);
+        assert_eq!(summary.max_execute_secs, 4.0);
+        assert_eq!(summary.mean_gas_per_sec, Some(2.5));
+        assert_eq!(summary.mean_entries_per_sec, 6.25);
+
+        let summary = BenchSummary::new(&[run(1.0, Some(4.0)), run(4.0, None)]);
+        assert_eq!(summary.mean_gas_per_sec, None);
+    }
+}
@@ -135,6 +135,13 @@ impl Command {
                         None,
                     )?;
                 }
+                StageEnum::TxLookup => {
+                    tx.clear::<tables::TxHashNumber>()?;
+                    tx.put::<tables::SyncStage>(
+                        StageId::TransactionLookup.to_string(),
+                        Default::default(),
+                    )?;
+                }
                 StageEnum::History => {
                     tx.clear::<tables::AccountHistory>()?;
                     tx.clear::<tables::StorageHistory>()?;
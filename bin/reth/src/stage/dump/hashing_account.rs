@@ -70,10 +70,8 @@ async fn dry_run<DB: Database>(
 
     let factory = ProviderFactory::new(&output_db, chain);
     let provider = factory.provider_rw()?;
-    let mut exec_stage = AccountHashingStage {
-        clean_threshold: 1, // Forces hashing from scratch
-        ..Default::default()
-    };
+    let mut exec_stage = AccountHashingStage::default();
+    exec_stage.clean_threshold = 1; // Forces hashing from scratch
 
     let mut exec_output = false;
     while !exec_output {
@@ -69,7 +69,11 @@ async fn unwind_and_copy<DB: Database>(
     // Bring Plainstate to TO (hashing stage execution requires it)
     let mut exec_stage = ExecutionStage::new(
         reth_revm::Factory::new(db_tool.chain.clone()),
-        ExecutionStageThresholds { max_blocks: Some(u64::MAX), max_changes: None },
+        ExecutionStageThresholds {
+            max_blocks: Some(u64::MAX),
+            max_changes: None,
+            max_memory: None,
+        },
     );
 
     exec_stage
@@ -85,14 +89,8 @@ async fn unwind_and_copy<DB: Database>(
 
     // Bring hashes to TO
 
-    AccountHashingStage { clean_threshold: u64::MAX, commit_threshold: u64::MAX }
-        .execute(&provider, execute_input)
-        .await
-        .unwrap();
-    StorageHashingStage { clean_threshold: u64::MAX, commit_threshold: u64::MAX }
-        .execute(&provider, execute_input)
-        .await
-        .unwrap();
+    AccountHashingStage::new(u64::MAX, u64::MAX).execute(&provider, execute_input).await.unwrap();
+    StorageHashingStage::new(u64::MAX, u64::MAX).execute(&provider, execute_input).await.unwrap();
 
     let unwind_inner_tx = provider.into_tx();
 
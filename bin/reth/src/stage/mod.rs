@@ -1,6 +1,7 @@
 //! `reth stage` command
 use clap::{Parser, Subcommand};
 
+pub mod bench;
 pub mod drop;
 pub mod dump;
 pub mod run;
@@ -29,6 +30,11 @@ pub enum Subcommands {
     Dump(dump::Command),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command),
+    /// Benchmarks a stage on a database created by `reth stage dump`.
+    ///
+    /// The stage is run several times from the same state, and the throughput, MDBX table stats
+    /// and allocation counts of every run are reported as JSON.
+    Bench(bench::Command),
 }
 
 impl Command {
@@ -39,6 +45,7 @@ impl Command {
             Subcommands::Drop(command) => command.execute().await,
             Subcommands::Dump(command) => command.execute().await,
             Subcommands::Unwind(command) => command.execute().await,
+            Subcommands::Bench(command) => command.execute().await,
         }
     }
 }
@@ -201,6 +201,7 @@ impl Command {
                             ExecutionStageThresholds {
                                 max_blocks: Some(batch_size),
                                 max_changes: None,
+                                max_memory: None,
                             },
                         )),
                         None,
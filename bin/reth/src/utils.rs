@@ -76,13 +76,49 @@ impl<'a, DB: Database> DbTool<'a, DB> {
         len: usize,
         reverse: bool,
     ) -> Result<Vec<(T::Key, T::Value)>> {
+        self.list_filtered::<T>(None, None, skip, len, reverse, |_, _| true)
+    }
+
+    /// Grabs the contents of the table within the given inclusive key range, skipping the first
+    /// `skip` and taking `len` entries for which `filter` returns `true`.
+    pub fn list_filtered<T: Table>(
+        &self,
+        start_key: Option<T::Key>,
+        end_key: Option<T::Key>,
+        skip: usize,
+        len: usize,
+        reverse: bool,
+        mut filter: impl FnMut(&T::Key, &T::Value) -> bool,
+    ) -> Result<Vec<(T::Key, T::Value)>> {
+        let above_start = |key: &T::Key| start_key.as_ref().map_or(true, |start| key >= start);
+        let below_end = |key: &T::Key| end_key.as_ref().map_or(true, |end| key <= end);
+
         let data = self.db.view(|tx| {
             let mut cursor = tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
 
             if reverse {
-                cursor.walk_back(None)?.skip(skip).take(len).collect::<Result<_, _>>()
+                // The reverse walker starts at the first entry greater or equal to the end key,
+                // or at the last entry if there's none.
+                let start = match end_key.clone() {
+                    Some(end) if cursor.seek(end.clone())?.is_some() => Some(end),
+                    _ => None,
+                };
+                cursor
+                    .walk_back(start)?
+                    .skip_while(|entry| entry.as_ref().map_or(false, |(key, _)| !below_end(key)))
+                    .take_while(|entry| entry.as_ref().map_or(true, |(key, _)| above_start(key)))
+                    .filter(|entry| entry.as_ref().map_or(true, |(key, value)| filter(key, value)))
+                    .skip(skip)
+                    .take(len)
+                    .collect::<Result<_, _>>()
             } else {
-                cursor.walk(None)?.skip(skip).take(len).collect::<Result<_, _>>()
+                cursor
+                    .walk(start_key.clone())?
+                    .take_while(|entry| entry.as_ref().map_or(true, |(key, _)| below_end(key)))
+                    .filter(|entry| entry.as_ref().map_or(true, |(key, value)| filter(key, value)))
+                    .skip(skip)
+                    .take(len)
+                    .collect::<Result<_, _>>()
             }
         })?;
 
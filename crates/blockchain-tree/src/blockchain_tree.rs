@@ -23,12 +23,12 @@ use reth_provider::{
     chain::{ChainSplit, SplitAt},
     post_state::PostState,
     BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, Chain, DatabaseProvider,
-    DisplayBlocksChain, ExecutorFactory, HeaderProvider,
+    CanonStateNotificationSender, CanonStateNotifications, Chain, ChainStateReader,
+    ChainStateWriter, DatabaseProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 use tracing::{debug, error, info, instrument, trace, warn};
@@ -93,6 +93,8 @@ pub struct BlockchainTree<DB: Database, C: Consensus, EF: ExecutorFactory> {
     metrics: TreeMetrics,
     /// Metrics for sync stages.
     sync_metrics_tx: Option<MetricEventsSender>,
+    /// Hashes of the pending blocks that are currently persisted in the database.
+    persisted_pending_blocks: HashSet<BlockHash>,
 }
 
 /// A container that wraps chains and block indices to allow searching for block hashes across all
@@ -121,16 +123,27 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             .take((max_reorg_depth + config.num_of_additional_canonical_block_hashes()) as usize)
             .collect::<Result<Vec<(BlockNumber, BlockHash)>, _>>()?;
 
-        // TODO(rakita) save last finalized block inside database but for now just take
-        // tip-max_reorg_depth
-        // task: https://github.com/paradigmxyz/reth/issues/1712
-        let (last_finalized_block_number, _) =
-            if last_canonical_hashes.len() > max_reorg_depth as usize {
-                last_canonical_hashes[max_reorg_depth as usize]
-            } else {
-                // it is in reverse order from tip to N
-                last_canonical_hashes.last().cloned().unwrap_or_default()
+        // use the finalized block of the last forkchoice update if it was persisted, otherwise
+        // assume that everything below `tip - max_reorg_depth` is finalized
+        let (persisted_finalized_block, persisted_pending_blocks) = {
+            let factory = externals.database();
+            let provider = factory.provider()?;
+            let finalized = match provider.last_finalized_block_hash()? {
+                Some(hash) => provider.block_number(hash)?,
+                None => None,
             };
+            let pending = provider.pending_blocks()?.into_iter().map(|block| block.hash).collect();
+            (finalized, pending)
+        };
+
+        let last_finalized_block_number = match persisted_finalized_block {
+            Some(number) => number,
+            None if last_canonical_hashes.len() > max_reorg_depth as usize => {
+                last_canonical_hashes[max_reorg_depth as usize].0
+            }
+            // it is in reverse order from tip to N
+            None => last_canonical_hashes.last().map(|(number, _)| *number).unwrap_or_default(),
+        };
 
         Ok(Self {
             externals,
@@ -145,6 +158,7 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             canon_state_notification_sender,
             metrics: Default::default(),
             sync_metrics_tx: None,
+            persisted_pending_blocks,
         })
     }
 
@@ -378,8 +392,8 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         debug!(target: "blockchain_tree", head = ?block_num_hash.hash, ?parent, "Appending block to canonical chain");
         // create new chain that points to that block
         //return self.fork_canonical_chain(block.clone());
-        // TODO save pending block to database
-        // https://github.com/paradigmxyz/reth/issues/1713
+        // NOTE: pending blocks are persisted with the next canonical commit, see
+        // `write_pending_blocks`
 
         let (block_status, chain) = {
             let factory = self.externals.database();
@@ -626,6 +640,71 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         }
     }
 
+    /// Returns all blocks of the tree that are not canonical: the blocks of all sidechains and
+    /// the buffered blocks.
+    pub fn pending_blocks(&self) -> Vec<SealedBlock> {
+        let chain_blocks = self.chains.values().flat_map(|chain| chain.blocks().values());
+        let buffered_blocks =
+            self.buffered_blocks.blocks().values().flat_map(|blocks| blocks.values());
+        chain_blocks.chain(buffered_blocks).map(|block| block.block.clone()).collect()
+    }
+
+    /// Persists the [pending blocks](BlockchainTree::pending_blocks) in the database.
+    ///
+    /// The pending blocks are also persisted with every canonical commit, see
+    /// [BlockchainTree::make_canonical], so the blocks can be restored on startup with
+    /// [BlockchainTree::restore_pending_blocks].
+    pub fn persist_pending_blocks(&mut self) -> Result<(), Error> {
+        let factory = self.externals.database();
+        let provider = factory.provider_rw()?;
+        let persisted = self.write_pending_blocks(&*provider)?;
+        provider.commit()?;
+        self.persisted_pending_blocks = persisted;
+        Ok(())
+    }
+
+    /// Writes the pending blocks that are not persisted yet and removes the persisted blocks that
+    /// left the tree, using the given provider. Returns the hashes of the pending blocks, which are
+    /// persisted once the transaction of the provider is committed.
+    fn write_pending_blocks(
+        &self,
+        provider: &impl ChainStateWriter,
+    ) -> Result<HashSet<BlockHash>, Error> {
+        let blocks = self.pending_blocks();
+        let pending = blocks.iter().map(|block| block.hash).collect::<HashSet<_>>();
+        let inserted = blocks
+            .into_iter()
+            .filter(|block| !self.persisted_pending_blocks.contains(&block.hash))
+            .collect::<Vec<_>>();
+        let removed =
+            self.persisted_pending_blocks.difference(&pending).copied().collect::<Vec<_>>();
+
+        if !inserted.is_empty() || !removed.is_empty() {
+            debug!(target: "blockchain_tree", inserted = inserted.len(), removed = removed.len(), "Persisting pending blocks");
+            provider.update_pending_blocks(inserted, removed)?;
+        }
+        Ok(pending)
+    }
+
+    /// Inserts the blocks persisted by the previous run into the tree.
+    ///
+    /// Sidechain blocks are executed again and blocks with an unknown parent are buffered. Blocks
+    /// that became canonical or were finalized in the meantime are skipped.
+    pub fn restore_pending_blocks(&mut self) -> Result<(), Error> {
+        let mut blocks = self.externals.database().provider()?.pending_blocks()?;
+        // parents need to be inserted before their children
+        blocks.sort_unstable_by_key(|block| block.number);
+
+        let num_blocks = blocks.len();
+        for block in blocks {
+            if let Err(err) = self.insert_block_without_senders(block) {
+                debug!(target: "blockchain_tree", %err, "Skipping persisted pending block");
+            }
+        }
+        debug!(target: "blockchain_tree", num_blocks, "Restored pending blocks");
+        Ok(())
+    }
+
     /// Insert block for future execution.
     ///
     /// Returns an error if the block is invalid.
@@ -1020,7 +1099,9 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
     }
 
     /// Canonicalize the given chain and commit it to the database.
-    fn commit_canonical(&self, chain: Chain) -> Result<(), Error> {
+    ///
+    /// The pending blocks of the tree are persisted in the same transaction.
+    fn commit_canonical(&mut self, chain: Chain) -> Result<(), Error> {
         let provider = DatabaseProvider::new_rw(
             self.externals.db.tx_mut()?,
             self.externals.chain_spec.clone(),
@@ -1032,7 +1113,10 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             .append_blocks_with_post_state(blocks.into_blocks().collect(), state)
             .map_err(|e| BlockExecutionError::CanonicalCommit { inner: e.to_string() })?;
 
+        let persisted_pending_blocks = self.write_pending_blocks(&provider)?;
+
         provider.commit()?;
+        self.persisted_pending_blocks = persisted_pending_blocks;
 
         Ok(())
     }
@@ -1112,7 +1196,7 @@ mod tests {
         test_utils::{blocks::BlockChainTestData, TestExecutorFactory},
         BlockWriter, ProviderFactory,
     };
-    use std::{collections::HashSet, sync::Arc};
+    use std::sync::Arc;
 
     fn setup_externals(
         exec_res: Vec<PostState>,
@@ -1593,4 +1677,114 @@ mod tests {
             .with_buffered_blocks(BTreeMap::from([]))
             .assert(&tree);
     }
+
+    #[test]
+    fn persist_pending_blocks() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block2, exec2) = data.blocks[1].clone();
+
+        let externals = setup_externals(vec![exec2]);
+        setup_genesis(externals.db.clone(), data.genesis);
+        let db = externals.db.clone();
+        let chain_spec = externals.chain_spec.clone();
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let (sender, _) = tokio::sync::broadcast::channel(10);
+        let mut tree = BlockchainTree::new(externals, sender.clone(), config.clone())
+            .expect("failed to create tree");
+
+        // block 2 parent is not known, block2 is buffered.
+        assert_matches!(
+            tree.insert_block(block2.clone()),
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Disconnected { .. }))
+        );
+        assert_eq!(tree.pending_blocks(), vec![block2.block.clone()]);
+
+        // the node persists the pending blocks on shutdown
+        tree.persist_pending_blocks().unwrap();
+        drop(tree);
+
+        let factory = ProviderFactory::new(&db, MAINNET.clone());
+        assert_eq!(
+            factory.provider().unwrap().pending_blocks().unwrap(),
+            vec![block2.block.clone()]
+        );
+
+        // the tree of the next run finds block2 again
+        let externals = TreeExternals::new(
+            db.clone(),
+            Arc::new(TestConsensus::default()),
+            TestExecutorFactory::new(chain_spec.clone()),
+            chain_spec,
+        );
+        let mut tree =
+            BlockchainTree::new(externals, sender, config).expect("failed to create tree");
+        assert!(tree.pending_blocks().is_empty());
+
+        // block2 is buffered again, since its parent is still unknown
+        tree.restore_pending_blocks().unwrap();
+        assert_eq!(tree.pending_blocks(), vec![block2.block]);
+        TreeTester::default().with_chain_num(0).assert(&tree);
+    }
+
+    #[test]
+    fn restore_pending_blocks() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec2.clone(), exec1]);
+        setup_genesis(externals.db.clone(), data.genesis);
+        let db = externals.db.clone();
+        let chain_spec = externals.chain_spec.clone();
+        let factory = ProviderFactory::new(&db, MAINNET.clone());
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let (sender, _) = tokio::sync::broadcast::channel(10);
+        let mut tree = BlockchainTree::new(externals, sender.clone(), config.clone())
+            .expect("failed to create tree");
+
+        // block2 is buffered and connected once block1 is inserted.
+        assert_matches!(
+            tree.insert_block(block2.clone()),
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Disconnected { .. }))
+        );
+        assert_eq!(
+            tree.insert_block(block1.clone()).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid)
+        );
+
+        // committing block1 persists the sidechain block2 in the same transaction
+        assert!(tree.make_canonical(&block1.hash()).is_ok());
+        assert_eq!(
+            factory.provider().unwrap().pending_blocks().unwrap(),
+            vec![block2.block.clone()]
+        );
+        drop(tree);
+
+        // the tree of the next run restores block2 on top of the canonical block1
+        let executor_factory = TestExecutorFactory::new(chain_spec.clone());
+        executor_factory.extend(vec![exec2]);
+        let externals = TreeExternals::new(
+            db.clone(),
+            Arc::new(TestConsensus::default()),
+            executor_factory,
+            chain_spec,
+        );
+        let mut tree =
+            BlockchainTree::new(externals, sender, config).expect("failed to create tree");
+        assert!(tree.pending_blocks().is_empty());
+
+        tree.restore_pending_blocks().unwrap();
+        assert_eq!(tree.pending_blocks(), vec![block2.block.clone()]);
+        TreeTester::default()
+            .with_chain_num(1)
+            .with_block_to_chain(HashMap::from([(block2.hash, 0)]))
+            .assert(&tree);
+
+        // block2 is removed from the persisted blocks once it's canonical
+        assert!(tree.make_canonical(&block2.hash()).is_ok());
+        assert!(factory.provider().unwrap().pending_blocks().unwrap().is_empty());
+    }
 }
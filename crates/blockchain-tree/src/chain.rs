@@ -15,7 +15,6 @@ use reth_primitives::{
 };
 use reth_provider::{
     providers::PostStateProvider, BlockExecutor, Chain, ExecutorFactory, PostStateDataProvider,
-    StateRootProvider,
 };
 use std::{
     collections::BTreeMap,
@@ -201,15 +200,22 @@ impl AppendableChain {
         let canonical_fork = post_state_data_provider.canonical_fork();
         let state_provider = db.history_by_block_number(canonical_fork.number)?;
 
+        // the state of the chain on top of the database, needed to check the state root.
+        let chain_state =
+            block_kind.extends_canonical_head().then(|| post_state_data_provider.state().clone());
+
         let provider = PostStateProvider::new(state_provider, post_state_data_provider);
 
         let mut executor = externals.executor_factory.with_sp(&provider);
         let post_state = executor.execute_and_verify_receipt(&block, U256::MAX, Some(senders))?;
 
         // check state root if the block extends the canonical chain.
-        if block_kind.extends_canonical_head() {
-            // check state root
-            let state_root = provider.state_root(post_state.clone())?;
+        if let Some(mut state) = chain_state {
+            // check state root, the storage roots are computed in parallel.
+            state.extend(post_state.clone());
+            let state_root = state
+                .state_root_parallel(&externals.db)
+                .map_err(|err| Error::Database(err.into()))?;
             if block.state_root != state_root {
                 return Err(ConsensusError::BodyStateRootDiff {
                     got: state_root,
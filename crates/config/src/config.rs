@@ -6,9 +6,10 @@ use reth_downloaders::{
 };
 use reth_network::{NetworkConfigBuilder, PeersConfig, SessionsConfig};
 use reth_primitives::PruneMode;
+use reth_stages::etl::DEFAULT_ETL_BUFFER_SIZE;
 use secp256k1::SecretKey;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 /// Configuration for the reth node.
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
@@ -53,6 +54,24 @@ impl Config {
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct StageConfig {
+    /// The minimum time between two commits of the pipeline.
+    ///
+    /// The progress of a stage is committed once the interval has elapsed since the last commit,
+    /// and whenever the stage is done. If zero, the progress is committed after every step of a
+    /// stage.
+    ///
+    /// Default: 0s
+    #[serde(with = "humantime_serde")]
+    pub commit_interval: Duration,
+    /// The memory budget of a single step of a stage, in bytes.
+    ///
+    /// If set, the budget is split in half between the execution stage and the ETL collectors of
+    /// the hashing and transaction lookup stages. The execution stage ends its step and commits
+    /// once its estimated in-memory state changes exceed its half, regardless of the commit
+    /// interval, and the ETL collectors spill to disk once they exceed theirs. The pipeline also
+    /// commits once the pages dirtied by the uncommitted steps of any stage exceed the budget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_budget: Option<usize>,
     /// Header stage configuration.
     pub headers: HeadersConfig,
     /// Total Difficulty stage configuration
@@ -77,6 +96,20 @@ pub struct StageConfig {
     pub index_storage_history: IndexHistoryConfig,
 }
 
+impl StageConfig {
+    /// Returns the memory budget of the state changes of the execution stage, which is half of
+    /// [StageConfig::memory_budget].
+    pub fn execution_memory_budget(&self) -> Option<usize> {
+        self.memory_budget.map(|budget| budget / 2)
+    }
+
+    /// Returns the memory budget of the ETL collectors, which is half of
+    /// [StageConfig::memory_budget] and defaults to [DEFAULT_ETL_BUFFER_SIZE].
+    pub fn etl_buffer_size(&self) -> usize {
+        self.memory_budget.map(|budget| budget / 2).unwrap_or(DEFAULT_ETL_BUFFER_SIZE)
+    }
+}
+
 /// Header stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
@@ -210,11 +243,24 @@ pub struct ExecutionConfig {
     pub max_blocks: Option<u64>,
     /// The maximum amount of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The number of transactions to prefetch the state of ahead of the executed transaction.
+    ///
+    /// Prefetching is disabled if set to `0`.
+    pub prefetch_depth: usize,
+    /// Whether to execute the transactions of a block optimistically in parallel.
+    ///
+    /// Takes precedence over prefetching.
+    pub parallel_execution: bool,
 }
 
 impl Default for ExecutionConfig {
     fn default() -> Self {
-        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000) }
+        Self {
+            max_blocks: Some(500_000),
+            max_changes: Some(5_000_000),
+            prefetch_depth: 0,
+            parallel_execution: false,
+        }
     }
 }
 
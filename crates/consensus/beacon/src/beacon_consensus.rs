@@ -1,5 +1,5 @@
 //! Consensus for ethereum network
-use reth_consensus_common::validation;
+use reth_consensus_common::{calc, ethash::Ethash, validation};
 use reth_interfaces::consensus::{Consensus, ConsensusError};
 use reth_primitives::{
     constants::MAXIMUM_EXTRA_DATA_SIZE, Chain, ChainSpec, Hardfork, Header, SealedBlock,
@@ -10,16 +10,37 @@ use std::sync::Arc;
 /// Ethereum beacon consensus
 ///
 /// This consensus engine does basic checks as outlined in the execution specs.
+///
+/// The proof-of-work of pre-merge headers is only verified if enabled with
+/// [BeaconConsensus::with_ethash_verification].
 #[derive(Debug)]
 pub struct BeaconConsensus {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Verifies the difficulty and seal of pre-merge headers, if set.
+    ethash: Option<Ethash>,
 }
 
 impl BeaconConsensus {
     /// Create a new instance of [BeaconConsensus]
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, ethash: None }
+    }
+
+    /// Enables full ethash verification of pre-merge headers: the difficulty is checked against
+    /// the parent and the `mix_hash` and `nonce` against the difficulty.
+    ///
+    /// This is expensive, since the ethash cache of every epoch needs to be generated.
+    pub fn with_ethash_verification(mut self, enabled: bool) -> Self {
+        self.ethash = enabled.then(Ethash::new);
+        self
+    }
+
+    /// Returns `true` if the chain is known to be secured by ethash before the merge.
+    ///
+    /// Other chains, e.g. goerli which used clique before the merge, are not verified.
+    pub fn is_ethash_chain(&self) -> bool {
+        [Chain::mainnet(), Chain::sepolia()].contains(&self.chain_spec.chain)
     }
 }
 
@@ -35,6 +56,16 @@ impl Consensus for BeaconConsensus {
         parent: &SealedHeader,
     ) -> Result<(), ConsensusError> {
         validation::validate_header_regarding_parent(parent, header, &self.chain_spec)?;
+
+        // the difficulty of post-merge headers is zero, which is checked with the total
+        // difficulty
+        if self.ethash.is_some() && self.is_ethash_chain() && header.difficulty != U256::ZERO {
+            let expected = calc::ethash_difficulty(&self.chain_spec, parent, header.timestamp);
+            if header.difficulty != expected {
+                return Err(ConsensusError::DifficultyDiff { expected, got: header.difficulty })
+            }
+        }
+
         Ok(())
     }
 
@@ -65,9 +96,11 @@ impl Consensus for BeaconConsensus {
             // mixHash is used instead of difficulty inside EVM
             // https://eips.ethereum.org/EIPS/eip-4399#using-mixhash-field-instead-of-difficulty
         } else {
-            // TODO Consensus checks for old blocks:
-            //  * difficulty, mix_hash & nonce aka PoW stuff
-            // low priority as syncing is done in reverse order
+            // Proof-of-work checks for old blocks, if enabled. The difficulty itself is checked
+            // against the parent.
+            if let Some(ethash) = self.ethash.as_ref().filter(|_| self.is_ethash_chain()) {
+                ethash.verify_seal(header)?;
+            }
 
             // Goerli exception:
             //  * If the network is goerli pre-merge, ignore the extradata check, since we do not
@@ -96,3 +129,19 @@ fn validate_header_extradata(header: &Header) -> Result<(), ConsensusError> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{ChainSpecBuilder, GOERLI, MAINNET, SEPOLIA};
+
+    #[test]
+    fn only_verifies_known_ethash_chains() {
+        assert!(BeaconConsensus::new(MAINNET.clone()).is_ethash_chain());
+        assert!(BeaconConsensus::new(SEPOLIA.clone()).is_ethash_chain());
+        assert!(!BeaconConsensus::new(GOERLI.clone()).is_ethash_chain());
+
+        let custom = ChainSpecBuilder::mainnet().chain(Chain::Id(1337)).build();
+        assert!(!BeaconConsensus::new(Arc::new(custom)).is_ethash_chain());
+    }
+}
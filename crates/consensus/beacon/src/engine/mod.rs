@@ -21,7 +21,8 @@ use reth_primitives::{
     SealedHeader, H256, U256,
 };
 use reth_provider::{
-    BlockReader, BlockSource, CanonChainTracker, ProviderError, StageCheckpointReader,
+    BlockReader, BlockSource, CanonChainTracker, ChainStateWriter, ProviderError,
+    StageCheckpointReader,
 };
 use reth_rpc_types::engine::{
     ExecutionPayload, ForkchoiceUpdated, PayloadAttributes, PayloadStatus, PayloadStatusEnum,
@@ -217,7 +218,11 @@ pub struct BeaconConsensusEngine<DB, BT, Client>
 where
     DB: Database,
     Client: HeadersClient + BodiesClient,
-    BT: BlockchainTreeEngine + BlockReader + CanonChainTracker + StageCheckpointReader,
+    BT: BlockchainTreeEngine
+        + BlockReader
+        + CanonChainTracker
+        + ChainStateWriter
+        + StageCheckpointReader,
 {
     /// Controls syncing triggered by engine updates.
     sync: EngineSyncController<DB, Client>,
@@ -252,12 +257,20 @@ where
     /// blocks using the pipeline. Otherwise, the engine, sync controller, and blockchain tree will
     /// be used to download and execute the missing blocks.
     pipeline_run_threshold: u64,
+    /// The finalized and safe block hashes that were last persisted, used to only write them to
+    /// the database when they change.
+    persisted_finalized_and_safe: (H256, H256),
 }
 
 impl<DB, BT, Client> BeaconConsensusEngine<DB, BT, Client>
 where
     DB: Database + Unpin + 'static,
-    BT: BlockchainTreeEngine + BlockReader + CanonChainTracker + StageCheckpointReader + 'static,
+    BT: BlockchainTreeEngine
+        + BlockReader
+        + CanonChainTracker
+        + ChainStateWriter
+        + StageCheckpointReader
+        + 'static,
     Client: HeadersClient + BodiesClient + Clone + Unpin + 'static,
 {
     /// Create a new instance of the [BeaconConsensusEngine].
@@ -338,6 +351,7 @@ where
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
             metrics: EngineMetrics::default(),
             pipeline_run_threshold,
+            persisted_finalized_and_safe: Default::default(),
         };
 
         let maybe_pipeline_target = match target {
@@ -620,11 +634,14 @@ where
     ///
     /// This expects the given head to be the new canonical head.
     ///
+    /// The finalized and safe block hashes of the update are persisted if they changed, so they
+    /// are known after a restart.
+    ///
     /// Additionally, updates the head used for p2p handshakes.
     ///
     /// This should be called before issuing a VALID forkchoice update.
     fn update_canon_chain(
-        &self,
+        &mut self,
         head: SealedHeader,
         update: &ForkchoiceState,
     ) -> Result<(), reth_interfaces::Error> {
@@ -660,6 +677,8 @@ where
             self.blockchain.set_safe(safe.header.seal(update.safe_block_hash));
         }
 
+        self.persist_finalized_and_safe(update);
+
         head_block.total_difficulty =
             self.blockchain.header_td_by_number(head_block.number)?.ok_or_else(|| {
                 Error::Provider(ProviderError::TotalDifficultyNotFound {
@@ -671,6 +690,32 @@ where
         Ok(())
     }
 
+    /// Persists the finalized and safe block hashes of the update if they differ from the last
+    /// persisted ones.
+    ///
+    /// Zero hashes keep the previously persisted value. Failing to persist them is not fatal for
+    /// the forkchoice update, the error is only logged.
+    fn persist_finalized_and_safe(&mut self, update: &ForkchoiceState) {
+        let (persisted_finalized, persisted_safe) = self.persisted_finalized_and_safe;
+        let finalized = Some(update.finalized_block_hash)
+            .filter(|hash| !hash.is_zero() && *hash != persisted_finalized);
+        let safe =
+            Some(update.safe_block_hash).filter(|hash| !hash.is_zero() && *hash != persisted_safe);
+        if finalized.is_none() && safe.is_none() {
+            return
+        }
+
+        match self.blockchain.save_finalized_and_safe_block_hashes(finalized, safe) {
+            Ok(()) => {
+                self.persisted_finalized_and_safe =
+                    (finalized.unwrap_or(persisted_finalized), safe.unwrap_or(persisted_safe));
+            }
+            Err(error) => {
+                warn!(target: "consensus::engine", ?error, "Failed to persist finalized and safe blocks");
+            }
+        }
+    }
+
     /// Handler for a failed a forkchoice update due to a canonicalization error.
     ///
     /// This will determine if the state's head is invalid, and if so, return immediately.
@@ -735,6 +780,12 @@ where
             lowest_unknown_hash
         };
 
+        // a previous pipeline run may have found an invalid ancestor of the target, in which case
+        // the head is invalid as well and we must not sync to it again
+        if let Some(status) = self.check_invalid_ancestor_with_head(target, state.head_block_hash) {
+            return status
+        }
+
         // if the threshold is zero, we should not download the block first, and just use the
         // pipeline. Otherwise we use the tree to insert the block first
         if self.pipeline_run_threshold == 0 {
@@ -1206,8 +1257,8 @@ where
                 error!(target: "consensus::engine", "Failed to receive spawned pipeline");
                 return Some(Err(BeaconConsensusEngineError::PipelineChannelClosed))
             }
-            EngineSyncEvent::PipelineFinished { result, reached_max_block } => {
-                trace!(target: "consensus::engine", ?result, ?reached_max_block, "Pipeline finished");
+            EngineSyncEvent::PipelineFinished { result, target, reached_max_block } => {
+                trace!(target: "consensus::engine", ?result, ?target, ?reached_max_block, "Pipeline finished");
                 match result {
                     Ok(ctrl) => {
                         if reached_max_block {
@@ -1219,6 +1270,16 @@ where
                         if let ControlFlow::Unwind { bad_block, .. } = ctrl {
                             trace!(target: "consensus::engine", hash=?bad_block.hash, "Bad block detected in unwind");
 
+                            // the target of the pipeline descends from the bad block, so it must
+                            // not be synced to again
+                            if let Some(target) = target.filter(|target| *target != bad_block.hash)
+                            {
+                                self.invalid_headers.insert_with_invalid_ancestor(
+                                    target,
+                                    Arc::new(bad_block.header.clone()),
+                                );
+                            }
+
                             // update the `invalid_headers` cache with the new invalid headers
                             self.invalid_headers.insert(bad_block);
                             return None
@@ -1328,6 +1389,7 @@ where
     BT: BlockchainTreeEngine
         + BlockReader
         + CanonChainTracker
+        + ChainStateWriter
         + StageCheckpointReader
         + Unpin
         + 'static,
@@ -39,6 +39,8 @@ where
     pipeline_state: PipelineState<DB>,
     /// Pending target block for the pipeline to sync
     pending_pipeline_target: Option<H256>,
+    /// Target block of the running pipeline
+    running_pipeline_target: Option<H256>,
     /// In requests in progress.
     inflight_full_block_requests: Vec<FetchFullBlockFuture<Client>>,
     /// Buffered events until the manager is polled and the pipeline is idle.
@@ -70,6 +72,7 @@ where
             pipeline_task_spawner,
             pipeline_state: PipelineState::Idle(Some(pipeline)),
             pending_pipeline_target: None,
+            running_pipeline_target: None,
             inflight_full_block_requests: Vec::new(),
             queued_events: VecDeque::new(),
             run_pipeline_continuously,
@@ -186,7 +189,8 @@ where
                 let reached_max_block =
                     self.has_reached_max_block(minimum_block_number.unwrap_or_default());
                 self.pipeline_state = PipelineState::Idle(Some(pipeline));
-                EngineSyncEvent::PipelineFinished { result, reached_max_block }
+                let target = self.running_pipeline_target.take();
+                EngineSyncEvent::PipelineFinished { result, target, reached_max_block }
             }
             Err(_) => {
                 // failed to receive the pipeline
@@ -219,6 +223,7 @@ where
                     }),
                 );
                 self.pipeline_state = PipelineState::Running(rx);
+                self.running_pipeline_target = target;
 
                 // we also clear any pending full block requests because we expect them to be
                 // outdated (included in the range the pipeline is syncing anyway)
@@ -286,6 +291,10 @@ pub(crate) enum EngineSyncEvent {
     PipelineFinished {
         /// Final result of the pipeline run.
         result: Result<ControlFlow, PipelineError>,
+        /// The target the pipeline synced to.
+        ///
+        /// This is none if the pipeline was triggered without a specific target.
+        target: Option<H256>,
         /// Whether the pipeline reached the configured `max_block`.
         ///
         /// Note: this is only relevant in debugging scenarios.
@@ -1,4 +1,6 @@
-use reth_primitives::{constants::ETH_TO_WEI, BlockNumber, Chain, ChainSpec, Hardfork, U256};
+use reth_primitives::{
+    constants::ETH_TO_WEI, BlockNumber, Chain, ChainSpec, Hardfork, Header, EMPTY_OMMER_ROOT, U256,
+};
 
 /// Calculates the base block reward.
 ///
@@ -102,6 +104,90 @@ pub fn ommer_reward(
     U256::from(((8 + ommer_block_number - block_number) as u128 * base_block_reward) >> 3)
 }
 
+/// The lower bound of the difficulty of a proof-of-work block.
+const MINIMUM_DIFFICULTY: u64 = 131_072;
+
+/// The number of blocks after which the exponential difficulty bomb doubles.
+const EXP_DIFF_PERIOD: u64 = 100_000;
+
+/// Calculates the expected ethash difficulty of the child of `parent` with the given timestamp.
+///
+/// The difficulty adjustment and the delay of the difficulty bomb depend on the hardfork that is
+/// active at the child block:
+///
+/// - Frontier: [EIP-2][eip2] adjustment is not yet active, the difficulty goes up if the block time
+///   is below 13 seconds, and down otherwise.
+/// - Homestead: [EIP-2][eip2]
+/// - Byzantium and later: [EIP-100][eip100], with the bomb delayed by [EIP-649][eip649]
+///   (Byzantium), [EIP-1234][eip1234] (Constantinople), [EIP-2384][eip2384] (Muir Glacier),
+///   [EIP-3554][eip3554] (London), [EIP-4345][eip4345] (Arrow Glacier) and [EIP-5133][eip5133]
+///   (Gray Glacier).
+///
+/// This is only meaningful for blocks before the merge.
+///
+/// # References
+///
+/// - Implementation: [Geth][geth]
+///
+/// [eip2]: https://eips.ethereum.org/EIPS/eip-2
+/// [eip100]: https://eips.ethereum.org/EIPS/eip-100
+/// [eip649]: https://eips.ethereum.org/EIPS/eip-649
+/// [eip1234]: https://eips.ethereum.org/EIPS/eip-1234
+/// [eip2384]: https://eips.ethereum.org/EIPS/eip-2384
+/// [eip3554]: https://eips.ethereum.org/EIPS/eip-3554
+/// [eip4345]: https://eips.ethereum.org/EIPS/eip-4345
+/// [eip5133]: https://eips.ethereum.org/EIPS/eip-5133
+/// [geth]: https://github.com/ethereum/go-ethereum/blob/v1.12.0/consensus/ethash/difficulty.go
+pub fn ethash_difficulty(chain_spec: &ChainSpec, parent: &Header, timestamp: u64) -> U256 {
+    let block_number = parent.number + 1;
+    let elapsed = timestamp.saturating_sub(parent.timestamp);
+    let adjustment = parent.difficulty / U256::from(2048);
+
+    let bomb_delay = [
+        (Hardfork::GrayGlacier, 11_400_000),
+        (Hardfork::ArrowGlacier, 10_700_000),
+        (Hardfork::London, 9_700_000),
+        (Hardfork::MuirGlacier, 9_000_000),
+        (Hardfork::Constantinople, 5_000_000),
+        (Hardfork::Byzantium, 3_000_000),
+    ]
+    .into_iter()
+    .find(|(fork, _)| chain_spec.fork(*fork).active_at_block(block_number))
+    .map(|(_, delay)| delay);
+
+    // the signed multiple of the adjustment the difficulty changes by
+    let factor = if bomb_delay.is_some() {
+        let uncles = if parent.ommers_hash == EMPTY_OMMER_ROOT { 1 } else { 2 };
+        (uncles - (elapsed / 9) as i64).max(-99)
+    } else if chain_spec.fork(Hardfork::Homestead).active_at_block(block_number) {
+        (1 - (elapsed / 10) as i64).max(-99)
+    } else if elapsed < 13 {
+        1
+    } else {
+        -1
+    };
+
+    let change = adjustment * U256::from(factor.unsigned_abs());
+    let difficulty = if factor >= 0 {
+        parent.difficulty.saturating_add(change)
+    } else {
+        parent.difficulty.saturating_sub(change)
+    };
+    let mut difficulty = difficulty.max(U256::from(MINIMUM_DIFFICULTY));
+
+    // the bomb is calculated from a fake block number that is lagging behind by the delay
+    let fake_block_number = match bomb_delay {
+        Some(delay) => parent.number.saturating_sub(delay - 1),
+        None => block_number,
+    };
+    let periods = fake_block_number / EXP_DIFF_PERIOD;
+    if periods > 1 {
+        difficulty = difficulty.saturating_add(U256::from(1) << (periods - 2) as usize);
+    }
+
+    difficulty
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +228,42 @@ mod tests {
             assert_eq!(block_reward(base_reward, num_ommers), U256::from(expected_reward));
         }
     }
+
+    #[test]
+    fn calc_ethash_difficulty() {
+        let genesis = MAINNET.genesis_header();
+
+        // mainnet block 1
+        assert_eq!(
+            ethash_difficulty(&MAINNET, &genesis, 1438269988),
+            U256::from(17_171_480_576_u64)
+        );
+        // fast blocks increase the difficulty
+        assert_eq!(
+            ethash_difficulty(&MAINNET, &genesis, 5),
+            genesis.difficulty + genesis.difficulty / U256::from(2048)
+        );
+
+        // the difficulty never drops below the minimum
+        let parent = Header {
+            number: 1_150_000,
+            difficulty: U256::from(MINIMUM_DIFFICULTY),
+            ..Default::default()
+        };
+        assert_eq!(
+            ethash_difficulty(&MAINNET, &parent, 10_000),
+            U256::from(MINIMUM_DIFFICULTY) + (U256::from(1) << 9)
+        );
+
+        // the bomb is delayed after byzantium
+        let parent = Header {
+            number: 4_370_000,
+            difficulty: U256::from(1_000_000_000_u64),
+            timestamp: 0,
+            ..Default::default()
+        };
+        let expected =
+            parent.difficulty + parent.difficulty / U256::from(2048) + U256::from(1 << 11);
+        assert_eq!(ethash_difficulty(&MAINNET, &parent, 8), expected);
+    }
 }
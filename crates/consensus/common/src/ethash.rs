@@ -0,0 +1,378 @@
+//! Ethash proof-of-work verification of pre-merge headers.
+//!
+//! Only the light verification is implemented: dataset items are computed on demand from the
+//! cache of the header's epoch instead of generating the full DAG.
+//!
+//! See also <https://ethereum.org/en/developers/docs/consensus-mechanisms/pow/mining-algorithms/ethash/>
+
+use parking_lot::Mutex;
+use reth_interfaces::consensus::ConsensusError;
+use reth_primitives::{keccak256, BlockNumber, Header, H256, U256};
+use reth_rlp::Encodable;
+use std::{collections::VecDeque, fmt, sync::Arc};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Number of blocks per epoch, after which the cache and dataset are regenerated.
+pub const EPOCH_LENGTH: u64 = 30_000;
+
+/// Bytes in a word.
+const WORD_BYTES: usize = 4;
+/// Bytes in a hash.
+const HASH_BYTES: usize = 64;
+/// Width of the mix.
+const MIX_BYTES: usize = 128;
+/// Words in a hash.
+const HASH_WORDS: usize = HASH_BYTES / WORD_BYTES;
+/// Words in the mix.
+const MIX_WORDS: usize = MIX_BYTES / WORD_BYTES;
+/// Number of dataset items that are accessed per mix.
+const MIX_HASHES: usize = MIX_BYTES / HASH_BYTES;
+/// Bytes in the cache at genesis.
+const CACHE_BYTES_INIT: u64 = 1 << 24;
+/// Cache growth per epoch.
+const CACHE_BYTES_GROWTH: u64 = 1 << 17;
+/// Bytes in the dataset at genesis.
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+/// Dataset growth per epoch.
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+/// Number of rounds in the cache production.
+const CACHE_ROUNDS: usize = 3;
+/// Number of parents of each dataset item.
+const DATASET_PARENTS: u32 = 256;
+/// Number of accesses in the hashimoto loop.
+const ACCESSES: u32 = 64;
+/// The prime used by the FNV hash.
+const FNV_PRIME: u32 = 0x01000193;
+
+/// Number of epoch caches that are kept in memory.
+///
+/// Headers are usually verified in order, so this only needs to cover the current epoch and its
+/// neighbours.
+const MAX_CACHED_EPOCHS: usize = 3;
+
+/// An item of the cache or dataset, as little endian words.
+type Node = [u32; HASH_WORDS];
+
+/// Ethash proof-of-work verifier that keeps the caches of the most recently used epochs.
+pub struct Ethash {
+    /// The caches of recently used epochs, the most recently generated last.
+    caches: Mutex<VecDeque<(u64, Arc<Vec<Node>>)>>,
+}
+
+impl Ethash {
+    /// Creates a new verifier without any cached epochs.
+    pub fn new() -> Self {
+        Self { caches: Mutex::new(VecDeque::with_capacity(MAX_CACHED_EPOCHS)) }
+    }
+
+    /// Verifies the proof-of-work seal of the header, i.e. that the header's `mix_hash` and
+    /// `nonce` satisfy its difficulty.
+    pub fn verify_seal(&self, header: &Header) -> Result<(), ConsensusError> {
+        if header.difficulty == U256::ZERO {
+            return Err(ConsensusError::InvalidProofOfWork { difficulty: header.difficulty })
+        }
+
+        let (mix_hash, result) =
+            self.hashimoto_light(header.number, seal_hash(header), header.nonce);
+        if mix_hash != header.mix_hash {
+            return Err(ConsensusError::MixHashDiff { expected: mix_hash, got: header.mix_hash })
+        }
+
+        // the result must not exceed `2^256 / difficulty`
+        let result = U256::from_be_bytes(result.0);
+        if result.checked_mul(header.difficulty).is_none() {
+            return Err(ConsensusError::InvalidProofOfWork { difficulty: header.difficulty })
+        }
+
+        Ok(())
+    }
+
+    /// Computes the mix digest and the proof-of-work result for the given seal hash and nonce at
+    /// the given block, using only the cache of the block's epoch.
+    pub fn hashimoto_light(
+        &self,
+        block_number: BlockNumber,
+        seal_hash: H256,
+        nonce: u64,
+    ) -> (H256, H256) {
+        let epoch = block_number / EPOCH_LENGTH;
+        let cache = self.cache(epoch);
+        hashimoto(dataset_size(epoch), seal_hash, nonce, |index| calc_dataset_item(&cache, index))
+    }
+
+    /// Returns the cache of the given epoch, generating it if it is not cached yet.
+    fn cache(&self, epoch: u64) -> Arc<Vec<Node>> {
+        // the lock is held during generation, so concurrent verifications of the same epoch wait
+        // for a single generation
+        let mut caches = self.caches.lock();
+        if let Some((_, cache)) = caches.iter().find(|(cached, _)| *cached == epoch) {
+            return Arc::clone(cache)
+        }
+
+        let cache = Arc::new(make_cache(cache_size(epoch), seed_hash(epoch)));
+        if caches.len() == MAX_CACHED_EPOCHS {
+            caches.pop_front();
+        }
+        caches.push_back((epoch, Arc::clone(&cache)));
+        cache
+    }
+}
+
+impl Default for Ethash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Ethash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let epochs = self.caches.lock().iter().map(|(epoch, _)| *epoch).collect::<Vec<_>>();
+        f.debug_struct("Ethash").field("cached_epochs", &epochs).finish()
+    }
+}
+
+/// Returns the hash of the header without the `mix_hash` and `nonce`, which is the input of the
+/// proof-of-work.
+pub fn seal_hash(header: &Header) -> H256 {
+    let mut payload = Vec::new();
+    header.parent_hash.encode(&mut payload);
+    header.ommers_hash.encode(&mut payload);
+    header.beneficiary.encode(&mut payload);
+    header.state_root.encode(&mut payload);
+    header.transactions_root.encode(&mut payload);
+    header.receipts_root.encode(&mut payload);
+    header.logs_bloom.encode(&mut payload);
+    header.difficulty.encode(&mut payload);
+    U256::from(header.number).encode(&mut payload);
+    U256::from(header.gas_limit).encode(&mut payload);
+    U256::from(header.gas_used).encode(&mut payload);
+    header.timestamp.encode(&mut payload);
+    header.extra_data.encode(&mut payload);
+    if let Some(base_fee) = header.base_fee_per_gas {
+        U256::from(base_fee).encode(&mut payload);
+    }
+
+    let mut out = Vec::with_capacity(payload.len() + 9);
+    reth_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut out);
+    out.extend_from_slice(&payload);
+    keccak256(out)
+}
+
+/// Returns the seed hash of the given epoch.
+pub fn seed_hash(epoch: u64) -> H256 {
+    (0..epoch).fold(H256::zero(), |seed, _| keccak256(seed))
+}
+
+/// Returns the size of the cache of the given epoch in bytes.
+pub fn cache_size(epoch: u64) -> u64 {
+    let mut size = CACHE_BYTES_INIT + CACHE_BYTES_GROWTH * epoch - HASH_BYTES as u64;
+    while !is_prime(size / HASH_BYTES as u64) {
+        size -= 2 * HASH_BYTES as u64;
+    }
+    size
+}
+
+/// Returns the size of the full dataset of the given epoch in bytes.
+pub fn dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_INIT + DATASET_BYTES_GROWTH * epoch - MIX_BYTES as u64;
+    while !is_prime(size / MIX_BYTES as u64) {
+        size -= 2 * MIX_BYTES as u64;
+    }
+    size
+}
+
+/// Generates the cache of the given size from the seed.
+fn make_cache(size: u64, seed: H256) -> Vec<Node> {
+    let n = (size / HASH_BYTES as u64) as usize;
+
+    let mut cache = Vec::with_capacity(n);
+    cache.push(keccak512(seed.as_bytes()));
+    for i in 1..n {
+        let item = keccak512(&cache[i - 1]);
+        cache.push(item);
+    }
+
+    // RandMemoHash
+    for _ in 0..CACHE_ROUNDS {
+        for i in 0..n {
+            let index = u32::from_le_bytes([cache[i][0], cache[i][1], cache[i][2], cache[i][3]]);
+            let other = &cache[index as usize % n];
+            let prev = &cache[(i + n - 1) % n];
+
+            let mut item = [0u8; HASH_BYTES];
+            for (byte, (a, b)) in item.iter_mut().zip(prev.iter().zip(other.iter())) {
+                *byte = a ^ b;
+            }
+            cache[i] = keccak512(&item);
+        }
+    }
+
+    cache.iter().map(to_words).collect()
+}
+
+/// Computes the dataset item at the given index from the cache.
+fn calc_dataset_item(cache: &[Node], index: u32) -> Node {
+    let n = cache.len();
+
+    let mut mix = cache[index as usize % n];
+    mix[0] ^= index;
+    let mut mix = to_words(&keccak512(&to_bytes(&mix)));
+
+    for j in 0..DATASET_PARENTS {
+        let parent = fnv(index ^ j, mix[j as usize % HASH_WORDS]) as usize % n;
+        for (word, parent_word) in mix.iter_mut().zip(cache[parent].iter()) {
+            *word = fnv(*word, *parent_word);
+        }
+    }
+
+    to_words(&keccak512(&to_bytes(&mix)))
+}
+
+/// The hashimoto loop, with `lookup` returning the dataset item at the given index.
+///
+/// Returns the mix digest and the result.
+fn hashimoto(
+    full_size: u64,
+    seal_hash: H256,
+    nonce: u64,
+    lookup: impl Fn(u32) -> Node,
+) -> (H256, H256) {
+    let rows = (full_size / MIX_BYTES as u64) as u32;
+
+    let mut seed_input = [0u8; 40];
+    seed_input[..32].copy_from_slice(seal_hash.as_bytes());
+    seed_input[32..].copy_from_slice(&nonce.to_le_bytes());
+    let seed = keccak512(&seed_input);
+    let seed_words = to_words(&seed);
+
+    let mut mix = [0u32; MIX_WORDS];
+    for (i, word) in mix.iter_mut().enumerate() {
+        *word = seed_words[i % HASH_WORDS];
+    }
+
+    for i in 0..ACCESSES {
+        let parent = fnv(i ^ seed_words[0], mix[i as usize % MIX_WORDS]) % rows;
+        for j in 0..MIX_HASHES {
+            let item = lookup(parent * MIX_HASHES as u32 + j as u32);
+            for (word, item_word) in mix[j * HASH_WORDS..(j + 1) * HASH_WORDS].iter_mut().zip(item)
+            {
+                *word = fnv(*word, item_word);
+            }
+        }
+    }
+
+    // compress the mix into the digest
+    let mut digest = [0u8; 32];
+    for (i, chunk) in mix.chunks_exact(4).enumerate() {
+        let word = fnv(fnv(fnv(chunk[0], chunk[1]), chunk[2]), chunk[3]);
+        digest[i * WORD_BYTES..(i + 1) * WORD_BYTES].copy_from_slice(&word.to_le_bytes());
+    }
+
+    let mut result_input = [0u8; HASH_BYTES + 32];
+    result_input[..HASH_BYTES].copy_from_slice(&seed);
+    result_input[HASH_BYTES..].copy_from_slice(&digest);
+
+    (H256(digest), keccak256(result_input))
+}
+
+#[inline]
+fn fnv(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(FNV_PRIME) ^ b
+}
+
+fn keccak512(data: &[u8]) -> [u8; HASH_BYTES] {
+    let mut out = [0u8; HASH_BYTES];
+    let mut hasher = Keccak::v512();
+    hasher.update(data);
+    hasher.finalize(&mut out);
+    out
+}
+
+fn to_words(bytes: &[u8; HASH_BYTES]) -> Node {
+    let mut words = [0u32; HASH_WORDS];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(WORD_BYTES)) {
+        *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    words
+}
+
+fn to_bytes(words: &Node) -> [u8; HASH_BYTES] {
+    let mut bytes = [0u8; HASH_BYTES];
+    for (chunk, word) in bytes.chunks_exact_mut(WORD_BYTES).zip(words.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false
+    }
+    let mut i = 2;
+    while i * i <= n {
+        if n % i == 0 {
+            return false
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{hex_literal::hex, proofs::EMPTY_ROOT, Bloom, Bytes, EMPTY_OMMER_ROOT};
+
+    /// Header of mainnet block 1.
+    fn mainnet_block_1() -> Header {
+        Header {
+            parent_hash: hex!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+                .into(),
+            ommers_hash: EMPTY_OMMER_ROOT,
+            beneficiary: hex!("05a56e2d52c817161883f50c441c3228cfe54d9f").into(),
+            state_root: hex!("d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3")
+                .into(),
+            transactions_root: EMPTY_ROOT,
+            receipts_root: EMPTY_ROOT,
+            logs_bloom: Bloom::zero(),
+            difficulty: U256::from(17_171_480_576_u64),
+            number: 1,
+            gas_limit: 5000,
+            gas_used: 0,
+            timestamp: 1438269988,
+            extra_data: Bytes::from(hex!("476574682f76312e302e302f6c696e75782f676f312e342e32")),
+            mix_hash: hex!("969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59")
+                .into(),
+            nonce: 0x539bd4979fef1ec4,
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+        }
+    }
+
+    #[test]
+    fn epoch_parameters() {
+        assert_eq!(cache_size(0), 16776896);
+        assert_eq!(dataset_size(0), 1073739904);
+        assert_eq!(seed_hash(0), H256::zero());
+        assert_eq!(
+            seed_hash(1),
+            H256(hex!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"))
+        );
+    }
+
+    #[test]
+    fn verify_mainnet_seal() {
+        let header = mainnet_block_1();
+        assert_eq!(
+            header.hash_slow(),
+            H256(hex!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"))
+        );
+
+        let ethash = Ethash::new();
+        ethash.verify_seal(&header).unwrap();
+
+        let mut invalid = header;
+        invalid.nonce += 1;
+        assert!(ethash.verify_seal(&invalid).is_err());
+    }
+}
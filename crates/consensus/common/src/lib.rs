@@ -18,3 +18,5 @@ pub mod validation;
 
 /// Various calculation methods (e.g. block rewards)
 pub mod calc;
+
+pub mod ethash;
@@ -262,8 +262,8 @@ pub fn validate_header_regarding_parent(
         })
     }
 
-    // TODO Check difficulty increment between parent and child
-    // Ace age did increment it by some formula that we need to follow.
+    // The difficulty increment of pre-merge blocks is checked by the consensus if ethash
+    // verification is enabled, see [`crate::calc::ethash_difficulty`].
 
     let mut parent_gas_limit = parent.gas_limit;
 
@@ -99,6 +99,12 @@ pub enum ConsensusError {
     TransactionSignerRecoveryError,
     #[error("Extra data {len} exceeds max length: ")]
     ExtraDataExceedsMax { len: usize },
+    #[error("Block difficulty ({got}) is different from expected: ({expected}).")]
+    DifficultyDiff { expected: U256, got: U256 },
+    #[error("Block mix hash ({got:?}) is different from expected: ({expected:?}).")]
+    MixHashDiff { expected: H256, got: H256 },
+    #[error("Block proof-of-work does not satisfy the difficulty {difficulty}.")]
+    InvalidProofOfWork { difficulty: U256 },
     #[error("Difficulty after merge is not zero")]
     TheMergeDifficultyIsNotZero,
     #[error("Nonce after merge is not zero")]
@@ -117,3 +123,27 @@ pub enum ConsensusError {
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidTransactionError),
 }
+
+impl ConsensusError {
+    /// Returns `true` if the error is caused by a block body that doesn't match its header.
+    ///
+    /// Such a body can be re-requested from another peer, while any other error means that the
+    /// block itself is invalid.
+    pub fn is_body_mismatch(&self) -> bool {
+        matches!(
+            self,
+            ConsensusError::BodyOmmersHashDiff { .. } |
+                ConsensusError::BodyTransactionRootDiff { .. } |
+                ConsensusError::BodyWithdrawalsRootDiff { .. } |
+                ConsensusError::BodyWithdrawalsMissing
+        )
+    }
+
+    /// Returns `true` if the error depends on the local clock, e.g. a header with a timestamp in
+    /// the future.
+    ///
+    /// Such a header may become valid later on, so it must not be treated as permanently invalid.
+    pub fn is_time_dependent(&self) -> bool {
+        matches!(self, ConsensusError::TimestampIsInFuture { .. })
+    }
+}
@@ -1,4 +1,4 @@
-use reth_primitives::{BlockHash, BlockNumHash, Bloom, H256};
+use reth_primitives::{BlockHash, BlockNumHash, BlockNumber, Bloom, Hardfork, H256};
 use thiserror::Error;
 
 /// Transaction validation errors
@@ -24,6 +24,8 @@ pub enum BlockValidationError {
     BlockPreMerge { hash: H256 },
     #[error("Missing total difficulty")]
     MissingTotalDifficulty { hash: H256 },
+    #[error("Block {number} is subject to {hardfork:?}, which is not supported yet")]
+    UnsupportedHardfork { number: BlockNumber, hardfork: Hardfork },
 }
 
 /// BlockExecutor Errors
@@ -8,6 +8,13 @@ pub trait DownloadClient: Send + Sync + Debug {
     /// that violates validation rules
     fn report_bad_message(&self, peer_id: PeerId);
 
+    /// Penalize the peer for responding with a block that is invalid
+    ///
+    /// Defaults to [DownloadClient::report_bad_message].
+    fn report_bad_block(&self, peer_id: PeerId) {
+        self.report_bad_message(peer_id)
+    }
+
     /// Returns how many peers the network is currently connected to.
     fn num_connected_peers(&self) -> usize;
 }
@@ -28,6 +28,12 @@ where
             EitherDownloader::Right(b) => b.report_bad_message(peer_id),
         }
     }
+    fn report_bad_block(&self, peer_id: reth_primitives::PeerId) {
+        match self {
+            EitherDownloader::Left(a) => a.report_bad_block(peer_id),
+            EitherDownloader::Right(b) => b.report_bad_block(peer_id),
+        }
+    }
     fn num_connected_peers(&self) -> usize {
         match self {
             EitherDownloader::Left(a) => a.num_connected_peers(),
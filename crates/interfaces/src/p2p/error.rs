@@ -1,7 +1,7 @@
 use super::headers::client::HeadersRequest;
 use crate::{consensus, db};
 use reth_network_api::ReputationChangeKind;
-use reth_primitives::{BlockHashOrNumber, BlockNumber, Header, WithPeerId, H256};
+use reth_primitives::{BlockHashOrNumber, BlockNumber, Header, SealedHeader, WithPeerId, H256};
 use std::ops::RangeInclusive;
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
@@ -169,6 +169,15 @@ pub enum DownloadError {
         #[source]
         error: consensus::ConsensusError,
     },
+    /// The downloaded block matches its header, but is invalid.
+    #[error("Downloaded block #{number} ({hash:?}) is invalid. Details: {error}.", number = header.number, hash = header.hash)]
+    BadBlock {
+        /// The header of the invalid block.
+        header: Box<SealedHeader>,
+        /// The details of validation failure
+        #[source]
+        error: consensus::ConsensusError,
+    },
     /// Received more bodies than requested.
     #[error("Received more bodies than requested. Expected: {expected}. Received: {received}")]
     TooManyBodies {
@@ -19,4 +19,14 @@ pub enum HeadersDownloaderError {
         /// The error that occurred when attempting to attach the header.
         error: Box<ConsensusError>,
     },
+    /// A downloaded header on the chain towards the sync target is invalid on its own.
+    ///
+    /// Since the sync target descends from it, none of the downloaded headers can be imported.
+    #[error("Downloaded header #{number} ({hash:?}) is invalid. Details: {error}.", number = header.number, hash = header.hash)]
+    BadBlock {
+        /// The invalid header.
+        header: SealedHeader,
+        /// The validation error.
+        error: Box<ConsensusError>,
+    },
 }
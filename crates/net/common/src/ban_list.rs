@@ -119,6 +119,13 @@ impl BanList {
         self.ban_ip_with(ip, Some(until));
     }
 
+    /// Returns the timestamp until which the peer is banned.
+    ///
+    /// Returns `None` if the peer is not banned or banned indefinitely.
+    pub fn peer_banned_until(&self, peer_id: &PeerId) -> Option<Instant> {
+        self.banned_peers.get(peer_id).copied().flatten()
+    }
+
     /// Bans the peer until the timestamp
     pub fn ban_peer_until(&mut self, node_id: PeerId, until: Instant) {
         self.ban_peer_with(node_id, Some(until));
@@ -1128,6 +1128,12 @@ impl Discv4Service {
                     (Some(new), None) => self.notify(DiscoveryUpdate::EnrForkId(record, new)),
                     _ => {}
                 }
+
+                // only forward records that are signed by the node itself
+                let enr = msg.enr.into_inner();
+                if enr.public_key().serialize_uncompressed()[1..] == id[..] {
+                    self.notify(DiscoveryUpdate::SignedEnr(record, enr))
+                }
             }
         }
     }
@@ -1973,6 +1979,8 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the signed [`Enr`] of the given [`NodeRecord`] via EIP-868.
+    SignedEnr(NodeRecord, Enr<SecretKey>),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
@@ -222,6 +222,10 @@ impl<K: EnrKey> EnrWrapper<K> {
     pub fn new(enr: Enr<K>) -> Self {
         EnrWrapper(enr)
     }
+
+    pub fn into_inner(self) -> Enr<K> {
+        self.0
+    }
 }
 
 impl<K> Encodable for EnrWrapper<K>
@@ -0,0 +1,120 @@
+//! Configuration for the discv5 service.
+
+use discv5::{Discv5ConfigBuilder as InnerConfigBuilder, Enr, ListenConfig};
+use reth_primitives::ForkId;
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::Duration,
+};
+
+/// The default UDP port for discv5.
+///
+/// Note: this differs from the port 9000 consensus layer clients use by default, so that both can
+/// run on the same host.
+pub const DEFAULT_DISCV5_PORT: u16 = 9200;
+
+/// Configuration for the [`Discv5Service`](crate::Discv5Service).
+#[derive(Debug, Clone)]
+pub struct Discv5Config {
+    /// The address the UDP socket binds to.
+    pub listen_addr: SocketAddr,
+    /// The TCP port of the RLPx listener that is advertised in the local ENR.
+    pub tcp_port: u16,
+    /// Nodes to bootstrap the routing table with.
+    pub bootstrap_nodes: Vec<Enr>,
+    /// The [`ForkId`] advertised in the `eth` entry of the local ENR.
+    pub fork_id: Option<ForkId>,
+    /// How often to look up a random target to discover new nodes.
+    pub lookup_interval: Duration,
+    /// The config of the underlying discv5 service.
+    pub discv5_config: discv5::Discv5Config,
+}
+
+impl Discv5Config {
+    /// Returns a new builder that listens on the given address.
+    pub fn builder(listen_addr: SocketAddr) -> Discv5ConfigBuilder {
+        Discv5ConfigBuilder::new(listen_addr)
+    }
+}
+
+impl Default for Discv5Config {
+    fn default() -> Self {
+        Discv5ConfigBuilder::default().build()
+    }
+}
+
+/// Builder for [`Discv5Config`].
+#[derive(Debug, Clone)]
+pub struct Discv5ConfigBuilder {
+    listen_addr: SocketAddr,
+    tcp_port: u16,
+    bootstrap_nodes: Vec<Enr>,
+    fork_id: Option<ForkId>,
+    lookup_interval: Duration,
+}
+
+impl Discv5ConfigBuilder {
+    /// Creates a new builder that listens on the given address.
+    pub fn new(listen_addr: SocketAddr) -> Self {
+        Self {
+            listen_addr,
+            tcp_port: reth_discv4::DEFAULT_DISCOVERY_PORT,
+            bootstrap_nodes: Vec::new(),
+            fork_id: None,
+            lookup_interval: Duration::from_secs(20),
+        }
+    }
+
+    /// Sets the TCP port of the RLPx listener that is advertised in the local ENR.
+    pub fn tcp_port(&mut self, port: u16) -> &mut Self {
+        self.tcp_port = port;
+        self
+    }
+
+    /// Adds a node to bootstrap the routing table with.
+    pub fn add_boot_node(&mut self, node: Enr) -> &mut Self {
+        self.bootstrap_nodes.push(node);
+        self
+    }
+
+    /// Adds multiple nodes to bootstrap the routing table with.
+    pub fn add_boot_nodes(&mut self, nodes: impl IntoIterator<Item = Enr>) -> &mut Self {
+        self.bootstrap_nodes.extend(nodes);
+        self
+    }
+
+    /// Sets the [`ForkId`] advertised in the `eth` entry of the local ENR.
+    pub fn fork_id(&mut self, fork_id: ForkId) -> &mut Self {
+        self.fork_id = Some(fork_id);
+        self
+    }
+
+    /// Sets how often a random target is looked up.
+    pub fn lookup_interval(&mut self, interval: Duration) -> &mut Self {
+        self.lookup_interval = interval;
+        self
+    }
+
+    /// Returns the configured [`Discv5Config`]
+    pub fn build(&self) -> Discv5Config {
+        let listen_config = match self.listen_addr {
+            SocketAddr::V4(addr) => ListenConfig::Ipv4 { ip: *addr.ip(), port: addr.port() },
+            SocketAddr::V6(addr) => ListenConfig::Ipv6 { ip: *addr.ip(), port: addr.port() },
+        };
+
+        Discv5Config {
+            listen_addr: self.listen_addr,
+            tcp_port: self.tcp_port,
+            bootstrap_nodes: self.bootstrap_nodes.clone(),
+            fork_id: self.fork_id,
+            lookup_interval: self.lookup_interval,
+            discv5_config: InnerConfigBuilder::new(listen_config).build(),
+        }
+    }
+}
+
+impl Default for Discv5ConfigBuilder {
+    fn default() -> Self {
+        Self::new(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, DEFAULT_DISCV5_PORT).into())
+    }
+}
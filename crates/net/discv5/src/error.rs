@@ -0,0 +1,18 @@
+//! Error types that can occur in this crate.
+
+/// Errors that can occur when starting the discv5 service.
+#[derive(Debug, thiserror::Error)]
+pub enum Discv5Error {
+    /// The secret key can't be used as a discv5 key.
+    #[error("Invalid secret key.")]
+    InvalidSecretKey,
+    /// Failed to build the local ENR.
+    #[error("Failed to build local ENR: {0}")]
+    Enr(#[from] discv5::enr::EnrError),
+    /// Failed to create the discv5 service.
+    #[error("Failed to create discv5 service: {0}")]
+    Init(&'static str),
+    /// Failed to start the discv5 service.
+    #[error("Failed to start discv5 service: {0:?}")]
+    Start(discv5::Discv5Error),
+}
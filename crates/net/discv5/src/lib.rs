@@ -0,0 +1,440 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxzy/reth/issues/"
+)]
+#![warn(missing_docs, unreachable_pub, unused_crate_dependencies)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! Discovery v5 support: <https://github.com/ethereum/devp2p/blob/master/discv5/discv5.md>
+//!
+//! The wire protocol, including the `WHOAREYOU` session handshake and the routing table, is
+//! provided by the [discv5] crate. This crate runs it alongside discv4: the [`Discv5Service`]
+//! periodically looks up random targets with topic-less `FINDNODE` queries and only forwards
+//! nodes that advertise an `eth` entry ([`EnrForkIdEntry`]) in their ENR, which filters out nodes
+//! that don't run the execution layer, e.g. consensus layer clients.
+//!
+//! Like discv4, this consists of a [`Discv5`] frontend and a [`Discv5Service`] that emits a
+//! [`Discv5NodeRecordUpdate`] for every discovered node.
+use discv5::{
+    enr::{CombinedKey, EnrBuilder, EnrPublicKey, NodeId},
+    Discv5Event, Enr, QueryError,
+};
+use futures::{future::BoxFuture, Future};
+use reth_discv4::EnrForkIdEntry;
+use reth_primitives::{keccak256, ForkId, NodeRecord, PeerId};
+use reth_rlp::{Decodable, Encodable};
+use secp256k1::SecretKey;
+use std::{
+    collections::VecDeque,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::{
+    sync::{mpsc, mpsc::error::TrySendError},
+    task::JoinHandle,
+    time::Interval,
+};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use tracing::{debug, trace};
+
+mod config;
+pub use config::{Discv5Config, Discv5ConfigBuilder, DEFAULT_DISCV5_PORT};
+
+pub mod error;
+use error::Discv5Error;
+
+// reexport the ENR type used by discv5
+pub use discv5::Enr as Discv5Enr;
+
+/// The ENR key of the entry that carries the [`ForkId`], see also [EIP-868](https://eips.ethereum.org/EIPS/eip-868).
+const ETH_ENR_KEY: &str = "eth";
+
+/// The discv5 frontend
+#[derive(Clone)]
+pub struct Discv5 {
+    /// The running discv5 protocol service.
+    discv5: Arc<discv5::Discv5>,
+    /// The key that signs the local ENR.
+    enr_key: Arc<CombinedKey>,
+}
+
+// === impl Discv5 ===
+
+impl Discv5 {
+    /// Binds the UDP socket, starts the protocol service and returns the frontend together with
+    /// the [`Discv5Service`] that has to be polled or spawned to receive discovered nodes.
+    pub async fn start(
+        secret_key: &SecretKey,
+        config: Discv5Config,
+    ) -> Result<(Self, Discv5Service), Discv5Error> {
+        let Discv5Config {
+            listen_addr,
+            tcp_port,
+            bootstrap_nodes,
+            fork_id,
+            lookup_interval,
+            discv5_config,
+        } = config;
+
+        let enr_key = combined_key(secret_key)?;
+        let local_enr = build_local_enr(&enr_key, listen_addr, tcp_port, fork_id)?;
+
+        let mut discv5 = discv5::Discv5::new(local_enr, combined_key(secret_key)?, discv5_config)
+            .map_err(Discv5Error::Init)?;
+        discv5.start().await.map_err(Discv5Error::Start)?;
+        trace!(target : "discv5", ?listen_addr, "started discv5 service");
+
+        for node in bootstrap_nodes {
+            if let Err(err) = discv5.add_enr(node.clone()) {
+                debug!(target : "discv5", %err, ?node, "failed to add boot node");
+            }
+        }
+
+        let events = discv5.event_stream().await.map_err(Discv5Error::Start)?;
+        let discv5 = Arc::new(discv5);
+
+        let mut lookup_interval = tokio::time::interval(lookup_interval);
+        lookup_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let service = Discv5Service {
+            discv5: Arc::clone(&discv5),
+            events: ReceiverStream::new(events),
+            lookup_interval,
+            lookup: None,
+            update_listeners: Vec::new(),
+            queued_updates: VecDeque::new(),
+        };
+
+        Ok((Self { discv5, enr_key: Arc::new(enr_key) }, service))
+    }
+
+    /// Returns the local ENR.
+    pub fn local_enr(&self) -> Enr {
+        self.discv5.local_enr()
+    }
+
+    /// Adds the node to the routing table.
+    pub fn add_enr(&self, enr: Enr) {
+        if let Err(err) = self.discv5.add_enr(enr) {
+            debug!(target : "discv5", %err, "failed to add node");
+        }
+    }
+
+    /// Sets the [`ForkId`] advertised in the `eth` entry of the local ENR.
+    pub fn set_fork_id(&self, fork_id: ForkId) {
+        let mut buf = Vec::new();
+        EnrForkIdEntry::from(fork_id).encode(&mut buf);
+        if let Err(err) = self.discv5.external_enr().write().insert_raw_rlp(
+            ETH_ENR_KEY,
+            buf.into(),
+            &self.enr_key,
+        ) {
+            debug!(target : "discv5", %err, "failed to update fork id");
+        }
+    }
+
+    /// Adds the peer and ip to the ban list.
+    pub fn ban(&self, peer_id: PeerId, ip: IpAddr) {
+        self.discv5.ban_node(&node_id(peer_id), None);
+        self.discv5.ban_ip(ip, None);
+    }
+
+    /// Adds the ip to the ban list.
+    pub fn ban_ip(&self, ip: IpAddr) {
+        self.discv5.ban_ip(ip, None);
+    }
+}
+
+impl fmt::Debug for Discv5 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discv5").field("local_enr", &self.local_enr()).finish()
+    }
+}
+
+/// Drives random lookups and turns discv5 events into [`Discv5NodeRecordUpdate`]s.
+#[must_use = "Service does nothing unless polled"]
+pub struct Discv5Service {
+    /// The running discv5 protocol service.
+    discv5: Arc<discv5::Discv5>,
+    /// Events emitted by the discv5 protocol service.
+    events: ReceiverStream<Discv5Event>,
+    /// Interval at which a random target is looked up.
+    lookup_interval: Interval,
+    /// The currently active lookup.
+    lookup: Option<BoxFuture<'static, Result<Vec<Enr>, QueryError>>>,
+    /// All subscribers for discovered nodes.
+    update_listeners: Vec<mpsc::Sender<Discv5NodeRecordUpdate>>,
+    /// Discovered nodes that haven't been returned yet.
+    queued_updates: VecDeque<Discv5NodeRecordUpdate>,
+}
+
+// === impl Discv5Service ===
+
+impl Discv5Service {
+    /// Spawns this service onto a new task.
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            while let Some(update) = self.next().await {
+                trace!(target : "discv5", ?update, "processed");
+            }
+        })
+    }
+
+    /// Returns a new stream that yields all discovered nodes.
+    pub fn update_stream(&mut self) -> ReceiverStream<Discv5NodeRecordUpdate> {
+        let (tx, rx) = mpsc::channel(512);
+        self.update_listeners.push(tx);
+        ReceiverStream::new(rx)
+    }
+
+    /// Sends the update to all listeners.
+    ///
+    /// Remove channels that got closed.
+    fn notify(&mut self, update: Discv5NodeRecordUpdate) {
+        self.update_listeners.retain_mut(|listener| match listener.try_send(update.clone()) {
+            Ok(()) => true,
+            Err(err) => match err {
+                TrySendError::Full(_) => true,
+                TrySendError::Closed(_) => false,
+            },
+        });
+    }
+
+    /// Starts a lookup for a random target, if no lookup is currently active.
+    fn lookup_random(&mut self) {
+        if self.lookup.is_none() {
+            trace!(target : "discv5", "starting random lookup");
+            self.lookup = Some(Box::pin(self.discv5.find_node(NodeId::random())));
+        }
+    }
+
+    fn on_event(&mut self, event: Discv5Event) {
+        match event {
+            Discv5Event::Discovered(enr) |
+            Discv5Event::EnrAdded { enr, .. } |
+            Discv5Event::SessionEstablished(enr, _) => self.on_discovered(enr),
+            _ => {}
+        }
+    }
+
+    /// Queues an update if the node serves the `eth` protocol.
+    fn on_discovered(&mut self, enr: Enr) {
+        let Some(fork_id) = enr_fork_id(&enr) else {
+            trace!(target : "discv5", node_id=?enr.node_id(), "ignoring node without eth entry");
+            return
+        };
+        if let Some(node_record) = enr_to_node_record(&enr) {
+            self.queued_updates.push_back(Discv5NodeRecordUpdate { node_record, fork_id });
+        }
+    }
+
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Discv5NodeRecordUpdate> {
+        loop {
+            // Drain all buffered updates first
+            if let Some(update) = self.queued_updates.pop_front() {
+                self.notify(update.clone());
+                return Poll::Ready(update)
+            }
+
+            while let Poll::Ready(Some(event)) = self.events.poll_next_unpin(cx) {
+                self.on_event(event);
+            }
+
+            if self.lookup_interval.poll_tick(cx).is_ready() {
+                self.lookup_random();
+            }
+
+            if let Some(mut lookup) = self.lookup.take() {
+                match lookup.as_mut().poll(cx) {
+                    Poll::Ready(Ok(nodes)) => {
+                        trace!(target : "discv5", found=nodes.len(), "finished random lookup");
+                        nodes.into_iter().for_each(|enr| self.on_discovered(enr));
+                    }
+                    Poll::Ready(Err(err)) => {
+                        debug!(target : "discv5", %err, "random lookup failed");
+                    }
+                    Poll::Pending => self.lookup = Some(lookup),
+                }
+            }
+
+            if self.queued_updates.is_empty() {
+                return Poll::Pending
+            }
+        }
+    }
+}
+
+impl Stream for Discv5Service {
+    type Item = Discv5NodeRecordUpdate;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll(cx).map(Some)
+    }
+}
+
+/// A node discovered via discv5 that serves the `eth` protocol.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Discv5NodeRecordUpdate {
+    /// Discovered node and its addresses
+    pub node_record: NodeRecord,
+    /// The forkid from the `eth` entry of the node's ENR
+    pub fork_id: ForkId,
+}
+
+/// Converts the ENR into a [`NodeRecord`].
+///
+/// Returns `None` if the ENR has no TCP socket or uses a non-secp256k1 identity, since the node
+/// can't be dialed via RLPx then.
+pub fn enr_to_node_record(enr: &Enr) -> Option<NodeRecord> {
+    let public_key = enr.public_key().encode_uncompressed();
+    if public_key.len() != 64 {
+        return None
+    }
+
+    let (address, tcp_port, udp_port) = match (enr.ip4(), enr.tcp4()) {
+        (Some(ip), Some(tcp)) => (IpAddr::from(ip), tcp, enr.udp4().unwrap_or(tcp)),
+        _ => {
+            let tcp = enr.tcp6()?;
+            (IpAddr::from(enr.ip6()?), tcp, enr.udp6().unwrap_or(tcp))
+        }
+    };
+
+    Some(
+        NodeRecord { address, tcp_port, udp_port, id: PeerId::from_slice(&public_key) }
+            .into_ipv4_mapped(),
+    )
+}
+
+/// Returns the [`ForkId`] of the `eth` entry in the ENR, if present.
+pub fn enr_fork_id(enr: &Enr) -> Option<ForkId> {
+    let mut raw = enr.get_raw_rlp(ETH_ENR_KEY)?;
+    EnrForkIdEntry::decode(&mut raw).ok().map(|entry| entry.fork_id)
+}
+
+/// Returns the discv5 [`NodeId`] of the peer.
+fn node_id(peer_id: PeerId) -> NodeId {
+    NodeId::new(&keccak256(peer_id).0)
+}
+
+/// Converts the secret key into the key type used by discv5.
+fn combined_key(secret_key: &SecretKey) -> Result<CombinedKey, Discv5Error> {
+    CombinedKey::secp256k1_from_bytes(&mut secret_key.secret_bytes())
+        .map_err(|_| Discv5Error::InvalidSecretKey)
+}
+
+/// Builds the local ENR, advertising the UDP and RLPx sockets and the fork id.
+fn build_local_enr(
+    key: &CombinedKey,
+    listen_addr: SocketAddr,
+    tcp_port: u16,
+    fork_id: Option<ForkId>,
+) -> Result<Enr, Discv5Error> {
+    let mut builder = EnrBuilder::new("v4");
+    match listen_addr {
+        SocketAddr::V4(addr) => {
+            // discv5 updates the ip once peers report our external address
+            if !addr.ip().is_unspecified() {
+                builder.ip4(*addr.ip());
+            }
+            builder.udp4(addr.port()).tcp4(tcp_port);
+        }
+        SocketAddr::V6(addr) => {
+            if !addr.ip().is_unspecified() {
+                builder.ip6(*addr.ip());
+            }
+            builder.udp6(addr.port()).tcp6(tcp_port);
+        }
+    }
+    if let Some(fork_id) = fork_id {
+        let mut buf = Vec::new();
+        EnrForkIdEntry::from(fork_id).encode(&mut buf);
+        builder.add_value_rlp(ETH_ENR_KEY, buf.into());
+    }
+    Ok(builder.build(key)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use reth_primitives::{Hardfork, MAINNET};
+    use secp256k1::SECP256K1;
+    use std::{
+        net::{Ipv4Addr, SocketAddrV4},
+        time::Duration,
+    };
+
+    /// Returns a config on a free local port, the port is advertised in the ENR so it can't be `0`.
+    fn local_config(fork_id: Option<ForkId>) -> Discv5Config {
+        let port = std::net::UdpSocket::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let mut builder =
+            Discv5Config::builder(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port).into());
+        builder.tcp_port(30303).lookup_interval(Duration::from_millis(100));
+        if let Some(fork_id) = fork_id {
+            builder.fork_id(fork_id);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn local_enr_roundtrip() {
+        let (secret_key, public_key) = SECP256K1.generate_keypair(&mut thread_rng());
+        let key = combined_key(&secret_key).unwrap();
+        let fork_id = Hardfork::Frontier.fork_id(&MAINNET).unwrap();
+        let listen_addr = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 9000).into();
+
+        let enr = build_local_enr(&key, listen_addr, 30303, Some(fork_id)).unwrap();
+        assert_eq!(enr_fork_id(&enr), Some(fork_id));
+
+        let record = enr_to_node_record(&enr).unwrap();
+        assert_eq!(record.id, PeerId::from_slice(&public_key.serialize_uncompressed()[1..]));
+        assert_eq!(record.address, IpAddr::from(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(record.tcp_port, 30303);
+        assert_eq!(record.udp_port, 9000);
+
+        // the discv5 node id is derived from the same public key
+        assert_eq!(node_id(record.id), enr.node_id());
+    }
+
+    #[test]
+    fn enr_without_eth_entry() {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let key = combined_key(&secret_key).unwrap();
+        let listen_addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, 9000).into();
+
+        let enr = build_local_enr(&key, listen_addr, 30303, None).unwrap();
+        assert_eq!(enr_fork_id(&enr), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn discovers_boot_node() {
+        reth_tracing::init_test_tracing();
+        let fork_id = Hardfork::Frontier.fork_id(&MAINNET).unwrap();
+
+        let boot_key = SecretKey::new(&mut thread_rng());
+        let (boot_node, boot_service) =
+            Discv5::start(&boot_key, local_config(Some(fork_id))).await.unwrap();
+        let _boot_service = boot_service.spawn();
+
+        let mut config = local_config(None);
+        config.bootstrap_nodes.push(boot_node.local_enr());
+        let (_discv5, mut service) =
+            Discv5::start(&SecretKey::new(&mut thread_rng()), config).await.unwrap();
+
+        let update = tokio::time::timeout(Duration::from_secs(10), service.next())
+            .await
+            .expect("boot node is discovered")
+            .unwrap();
+        assert_eq!(update.fork_id, fork_id);
+        assert_eq!(Some(update.node_record), enr_to_node_record(&boot_node.local_enr()));
+    }
+}
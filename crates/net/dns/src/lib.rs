@@ -54,6 +54,7 @@ use tracing::{debug, trace, warn};
 
 mod config;
 mod error;
+pub mod publish;
 mod query;
 pub mod resolver;
 mod sync;
@@ -0,0 +1,241 @@
+//! Support for building and publishing an [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) tree.
+//!
+//! Node records and links are arranged in two merkle trees of branch entries with at most
+//! [`MAX_CHILDREN`] children each. Every entry is published as a TXT record under the subdomain
+//! named after the hash of its content, the signed root entry is published under the domain
+//! itself.
+
+use crate::tree::{BranchEntry, LinkEntry, NodeEntry, TreeRootEntry};
+use data_encoding::BASE32_NOPAD;
+use enr::{Enr, EnrError, EnrKey, EnrKeyUnambiguous};
+use reth_primitives::keccak256;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Maximum number of children of a branch entry.
+///
+/// This is the number of base32 encoded hashes that fit into a single 255 byte TXT string:
+/// `enrtree-branch:` followed by 8 hashes of 26 characters separated by commas is 230 bytes long.
+pub const MAX_CHILDREN: usize = 8;
+
+/// Maximum length of a single character-string in a TXT record.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// An [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) tree of node records and links to other
+/// trees.
+#[derive(Debug, Clone)]
+pub struct DnsTree {
+    /// The root entry, only valid once signed.
+    root: TreeRootEntry,
+    /// All entries of the tree, keyed by the subdomain they're published under.
+    entries: BTreeMap<String, String>,
+}
+
+// === impl DnsTree ===
+
+impl DnsTree {
+    /// Builds the tree from the given node records and links.
+    ///
+    /// The root of the returned tree is not signed yet, see [`DnsTree::sign`].
+    pub fn new<K: EnrKeyUnambiguous, L: EnrKeyUnambiguous>(
+        nodes: impl IntoIterator<Item = Enr<K>>,
+        links: impl IntoIterator<Item = LinkEntry<L>>,
+        sequence_number: u64,
+    ) -> Self {
+        // sort the records by node id so the tree is deterministic
+        let mut nodes = nodes.into_iter().map(|enr| (enr.node_id().raw(), enr)).collect::<Vec<_>>();
+        nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        nodes.dedup_by(|(a, _), (b, _)| a == b);
+        let nodes = nodes.into_iter().map(|(_, enr)| NodeEntry { enr }.to_string()).collect();
+
+        let mut links = links.into_iter().map(|link| link.to_string()).collect::<Vec<_>>();
+        links.sort();
+        links.dedup();
+
+        let mut entries = BTreeMap::new();
+        let enr_root = build_subtree(&mut entries, nodes);
+        let link_root = build_subtree(&mut entries, links);
+
+        let root = TreeRootEntry {
+            enr_root: insert_entry(&mut entries, enr_root),
+            link_root: insert_entry(&mut entries, link_root),
+            sequence_number,
+            signature: Default::default(),
+        };
+
+        Self { root, entries }
+    }
+
+    /// Signs the root entry with the given key.
+    pub fn sign<K: EnrKey>(&mut self, key: &K) -> Result<(), EnrError> {
+        self.root.sign(key)
+    }
+
+    /// Returns the root entry.
+    pub fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns all entries of the tree, except the root, keyed by their subdomain.
+    pub fn entries(&self) -> &BTreeMap<String, String> {
+        &self.entries
+    }
+
+    /// Returns the TXT records to publish the tree under the given domain, keyed by their fully
+    /// qualified name without trailing dot.
+    pub fn to_txt_records(&self, domain: &str) -> BTreeMap<String, String> {
+        std::iter::once((domain.to_string(), self.root.to_string()))
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(subdomain, entry)| (format!("{subdomain}.{domain}"), entry.clone())),
+            )
+            .collect()
+    }
+
+    /// Returns the tree as a DNS zone file with records for the given domain.
+    ///
+    /// Entries longer than a single TXT character-string are split into multiple strings.
+    pub fn to_zone_file(&self, domain: &str, ttl: u32) -> String {
+        let mut zone = String::new();
+        for (name, entry) in self.to_txt_records(domain) {
+            let strings = entry
+                .as_bytes()
+                .chunks(MAX_TXT_STRING_LEN)
+                .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = writeln!(zone, "{name}. {ttl} IN TXT {strings}");
+        }
+        zone
+    }
+}
+
+/// Returns the subdomain under which the entry is published: the base32 encoded first 16 bytes of
+/// the keccak256 hash of the entry.
+pub fn subdomain(entry: &str) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.as_bytes()).0[..16])
+}
+
+/// Inserts the entry and returns its subdomain.
+fn insert_entry(entries: &mut BTreeMap<String, String>, entry: String) -> String {
+    let hash = subdomain(&entry);
+    entries.insert(hash.clone(), entry);
+    hash
+}
+
+/// Builds the subtree for the given leaves, inserts all entries below the subtree's root and
+/// returns the root.
+///
+/// A single leaf is its own root, more leaves are grouped into branches of at most
+/// [`MAX_CHILDREN`] children until a single branch remains.
+fn build_subtree(entries: &mut BTreeMap<String, String>, mut leaves: Vec<String>) -> String {
+    if leaves.len() == 1 {
+        return leaves.remove(0)
+    }
+    if leaves.len() <= MAX_CHILDREN {
+        let children = leaves.into_iter().map(|leaf| insert_entry(entries, leaf)).collect();
+        return BranchEntry { children }.to_string()
+    }
+
+    let mut subtrees = Vec::with_capacity(leaves.len() / MAX_CHILDREN + 1);
+    while !leaves.is_empty() {
+        let rest = leaves.split_off(leaves.len().min(MAX_CHILDREN));
+        subtrees.push(build_subtree(entries, leaves));
+        leaves = rest;
+    }
+    build_subtree(entries, subtrees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tree::DnsEntry, DnsDiscoveryConfig, DnsDiscoveryEvent, DnsDiscoveryService, MapResolver,
+    };
+    use enr::EnrBuilder;
+    use secp256k1::{rand::thread_rng, SecretKey};
+    use std::{collections::HashSet, net::Ipv4Addr, num::NonZeroUsize, sync::Arc};
+    use tokio_stream::StreamExt;
+
+    fn random_enr() -> Enr<SecretKey> {
+        let key = SecretKey::new(&mut thread_rng());
+        EnrBuilder::new("v4").ip4(Ipv4Addr::LOCALHOST).udp4(30303).tcp4(30303).build(&key).unwrap()
+    }
+
+    #[test]
+    fn branch_fan_out() {
+        let nodes = (0..40).map(|_| random_enr()).collect::<Vec<_>>();
+        let tree = DnsTree::new(nodes, Vec::<LinkEntry<SecretKey>>::new(), 1);
+
+        let mut leaves = 0;
+        for entry in tree.entries().values() {
+            match entry.parse::<DnsEntry<SecretKey>>().unwrap() {
+                DnsEntry::Branch(branch) => {
+                    assert!(branch.children.len() <= MAX_CHILDREN);
+                    for child in branch.children {
+                        assert!(tree.entries().contains_key(&child));
+                    }
+                }
+                DnsEntry::Node(_) => leaves += 1,
+                _ => {}
+            }
+        }
+        assert_eq!(leaves, 40);
+
+        // every TXT string fits into a record
+        for entry in tree.entries().values().filter(|entry| entry.starts_with("enrtree-branch:")) {
+            assert!(entry.len() <= MAX_TXT_STRING_LEN);
+        }
+    }
+
+    #[test]
+    fn deterministic_tree() {
+        let nodes = (0..20).map(|_| random_enr()).collect::<Vec<_>>();
+        let links = Vec::<LinkEntry<SecretKey>>::new();
+        let tree = DnsTree::new(nodes.clone(), links.clone(), 1);
+        let reversed = DnsTree::new(nodes.into_iter().rev(), links, 1);
+        assert_eq!(tree.root().enr_root, reversed.root().enr_root);
+        assert_eq!(tree.entries(), reversed.entries());
+    }
+
+    #[test]
+    fn zone_file_records() {
+        let tree = DnsTree::new(vec![random_enr()], Vec::<LinkEntry<SecretKey>>::new(), 1);
+        let zone = tree.to_zone_file("nodes.example.org", 300);
+        assert_eq!(zone.lines().count(), tree.entries().len() + 1);
+        assert!(zone.contains("nodes.example.org. 300 IN TXT \"enrtree-root:v1 e="));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_published_tree() {
+        reth_tracing::init_test_tracing();
+
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let nodes = (0..30).map(|_| random_enr()).collect::<Vec<_>>();
+        let mut tree = DnsTree::new(nodes.clone(), Vec::<LinkEntry<SecretKey>>::new(), 1);
+        tree.sign(&secret_key).unwrap();
+        assert!(tree.root().verify::<SecretKey>(&secret_key.public()));
+
+        let link =
+            LinkEntry { domain: "nodes.example.org".to_string(), pubkey: secret_key.public() };
+        let resolver = MapResolver::default();
+        for (name, entry) in tree.to_txt_records(&link.domain) {
+            resolver.insert(name, entry);
+        }
+
+        let config = DnsDiscoveryConfig {
+            max_requests_per_sec: NonZeroUsize::new(100).unwrap(),
+            ..Default::default()
+        };
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), config);
+        service.sync_tree_with_link(link);
+
+        let mut discovered = HashSet::new();
+        while discovered.len() < nodes.len() {
+            match service.next().await.unwrap() {
+                DnsDiscoveryEvent::Enr(enr) => discovered.insert(enr.node_id()),
+            };
+        }
+        assert_eq!(discovered, nodes.iter().map(|enr| enr.node_id()).collect());
+    }
+}
@@ -23,7 +23,8 @@ use std::{
 /// will issue another request until all bodies are collected.
 ///
 /// It then proceeds to verify the downloaded bodies. In case of an validation error,
-/// the future will start over.
+/// the future will start over. If a body matches its header but the block is invalid, the future
+/// resolves with a [DownloadError::BadBlock] error instead.
 ///
 /// The future will filter out any empty headers (see [reth_primitives::Header::is_empty]) from the
 /// request. If [BodiesRequestFuture] was initialized with all empty headers, no request will be
@@ -173,6 +174,14 @@ where
                 };
 
                 if let Err(error) = self.consensus.validate_block(&block) {
+                    // The body matches its header, so the block itself is invalid
+                    if !error.is_body_mismatch() {
+                        return Err(DownloadError::BadBlock {
+                            header: Box::new(block.header),
+                            error,
+                        })
+                    }
+
                     // Body is invalid, put the header back and return an error
                     let hash = block.hash();
                     self.pending_headers.push_front(block.header);
@@ -207,8 +216,16 @@ where
                 match ready!(fut.poll_unpin(cx)) {
                     Ok(response) => {
                         let peer_id = response.peer_id();
-                        if let Err(error) = this.on_block_response(response) {
-                            this.on_error(error, Some(peer_id));
+                        match this.on_block_response(response) {
+                            Ok(()) => {}
+                            Err(error @ DownloadError::BadBlock { .. }) => {
+                                // Requesting the block from other peers won't help
+                                this.metrics.increment_errors(&error);
+                                tracing::error!(target: "downloaders::bodies", ?peer_id, %error, "Downloaded invalid block");
+                                this.client.report_bad_block(peer_id);
+                                return Poll::Ready(Err(error))
+                            }
+                            Err(error) => this.on_error(error, Some(peer_id)),
                         }
                     }
                     Err(error) => {
@@ -237,6 +254,7 @@ mod tests {
         bodies::test_utils::zip_blocks,
         test_utils::{generate_bodies, TestBodiesClient},
     };
+    use assert_matches::assert_matches;
     use reth_interfaces::{
         p2p::bodies::response::BlockResponse,
         test_utils::{generators, generators::random_header_range, TestConsensus},
@@ -289,4 +307,24 @@ mod tests {
             (headers.into_iter().filter(|h| !h.is_empty()).count() as u64 + 1) / 2
         );
     }
+
+    /// Check that the request future resolves with an error if a block is invalid.
+    #[tokio::test]
+    async fn request_returns_bad_block() {
+        let (headers, bodies) = generate_bodies(0..=19);
+
+        let client = Arc::new(TestBodiesClient::default().with_bodies(bodies));
+        let consensus = Arc::new(TestConsensus::default());
+        consensus.set_fail_validation(true);
+        let fut =
+            BodiesRequestFuture::new(client.clone(), consensus, BodyDownloaderMetrics::default())
+                .with_headers(headers.clone());
+
+        let first_non_empty = headers.iter().find(|header| !header.is_empty()).unwrap();
+        assert_matches!(
+            fut.await,
+            Err(DownloadError::BadBlock { header, .. }) if *header == *first_non_empty
+        );
+        assert_eq!(client.times_requested(), 1);
+    }
 }
@@ -11,7 +11,7 @@ use reth_interfaces::{
         error::{DownloadError, DownloadResult, PeerRequestResult},
         headers::{
             client::{HeadersClient, HeadersRequest},
-            downloader::{validate_header_download, HeaderDownloader, SyncTarget},
+            downloader::{HeaderDownloader, SyncTarget},
             error::{HeadersDownloaderError, HeadersDownloaderResult},
         },
         priority::Priority,
@@ -258,6 +258,7 @@ where
                 self.validate_sync_target(&parent, request.clone(), peer_id)?;
             }
 
+            self.validate_chain_header(&parent, &request, peer_id)?;
             validated.push(parent);
         }
 
@@ -267,17 +268,6 @@ where
             .zip(self.local_head.as_ref())
             .filter(|(last, head)| last.number == head.number + 1)
         {
-            // Every header must be valid on its own
-            if let Err(error) = self.consensus.validate_header(last_header) {
-                trace!(target: "downloaders::headers", ?error, "Failed to validate header");
-                return Err(HeadersResponseError {
-                    request,
-                    peer_id: Some(peer_id),
-                    error: DownloadError::HeaderValidation { hash: head.hash(), error },
-                }
-                .into())
-            }
-
             // If the header is valid on its own, but not against its parent, we return it as
             // detached head error.
             if let Err(error) = self.consensus.validate_header_against_parent(last_header, head) {
@@ -410,6 +400,7 @@ where
                 }
 
                 trace!(target: "downloaders::headers", head=?self.local_block_number(), hash=?target.hash(), number=%target.number, "Received sync target");
+                self.validate_chain_header(&target, &request, peer_id)?;
 
                 // This is the next block we need to start issuing requests from
                 let parent_block_number = target.number.saturating_sub(1);
@@ -519,6 +510,13 @@ where
     }
 
     fn penalize_peer(&self, peer_id: Option<PeerId>, error: &DownloadError) {
+        // A header that is not valid yet according to the local clock is not the peer's fault
+        if let DownloadError::HeaderValidation { error, .. } = error {
+            if error.is_time_dependent() {
+                return
+            }
+        }
+
         // Penalize the peer for bad response
         if let Some(peer_id) = peer_id {
             trace!(target: "downloaders::headers", ?peer_id, ?error, "Penalizing peer");
@@ -593,8 +591,50 @@ where
     }
 
     /// Validate whether the header is valid in relation to it's parent
+    ///
+    /// Whether the parent is valid on its own is validated separately, see
+    /// [Self::validate_chain_header].
     fn validate(&self, header: &SealedHeader, parent: &SealedHeader) -> DownloadResult<()> {
-        validate_header_download(&self.consensus, header, parent)
+        self.consensus
+            .validate_header_against_parent(header, parent)
+            .map_err(|error| DownloadError::HeaderValidation { hash: parent.hash(), error })
+    }
+
+    /// Validates a header of the chain towards the sync target on its own.
+    ///
+    /// The sync target descends from the header, so if it's invalid, the whole chain is invalid and
+    /// there is no point in requesting it from another peer. The peer is penalized and a
+    /// [HeadersDownloaderError::BadBlock] error is returned instead.
+    ///
+    /// Errors that depend on the local clock, like a timestamp in the future, don't make the header
+    /// invalid for good, so the request is retried instead and the peer is not penalized.
+    #[allow(clippy::result_large_err)]
+    fn validate_chain_header(
+        &self,
+        header: &SealedHeader,
+        request: &HeadersRequest,
+        peer_id: PeerId,
+    ) -> Result<(), ReverseHeadersDownloaderError> {
+        if let Err(error) = self.consensus.validate_header(header) {
+            if error.is_time_dependent() {
+                trace!(target: "downloaders::headers", ?error, ?peer_id, number = header.number, hash = ?header.hash, "Header is not valid yet");
+                return Err(HeadersResponseError {
+                    request: request.clone(),
+                    peer_id: Some(peer_id),
+                    error: DownloadError::HeaderValidation { hash: header.hash(), error },
+                }
+                .into())
+            }
+
+            error!(target: "downloaders::headers", ?error, ?peer_id, number = header.number, hash = ?header.hash, "Downloaded invalid header");
+            self.client.report_bad_block(peer_id);
+            return Err(HeadersDownloaderError::BadBlock {
+                header: header.clone(),
+                error: Box::new(error),
+            }
+            .into())
+        }
+        Ok(())
     }
 
     /// Clears all requests/responses.
@@ -1212,8 +1252,87 @@ mod tests {
 
     use crate::headers::test_utils::child_header;
     use assert_matches::assert_matches;
-    use reth_interfaces::test_utils::{TestConsensus, TestHeadersClient};
-    use reth_primitives::SealedHeader;
+    use reth_interfaces::{
+        consensus::ConsensusError,
+        test_utils::{TestConsensus, TestHeadersClient},
+    };
+    use reth_primitives::{SealedBlock, SealedHeader, U256};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Consensus that only rejects the header with the given hash on its own.
+    #[derive(Debug)]
+    struct RejectHeaderConsensus(H256);
+
+    impl Consensus for RejectHeaderConsensus {
+        fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+            if header.hash() == self.0 {
+                Err(ConsensusError::BaseFeeMissing)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn validate_header_against_parent(
+            &self,
+            _header: &SealedHeader,
+            _parent: &SealedHeader,
+        ) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+
+        fn validate_header_with_total_difficulty(
+            &self,
+            _header: &Header,
+            _total_difficulty: U256,
+        ) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+
+        fn validate_block(&self, _block: &SealedBlock) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+    }
+
+    /// Consensus that rejects the header with the given hash once because its timestamp is in the
+    /// future, as if the local clock was behind.
+    #[derive(Debug)]
+    struct FutureHeaderConsensus {
+        hash: H256,
+        rejected: AtomicBool,
+    }
+
+    impl Consensus for FutureHeaderConsensus {
+        fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+            if header.hash() == self.hash && !self.rejected.swap(true, Ordering::SeqCst) {
+                Err(ConsensusError::TimestampIsInFuture {
+                    timestamp: header.timestamp,
+                    present_timestamp: header.timestamp.saturating_sub(1),
+                })
+            } else {
+                Ok(())
+            }
+        }
+
+        fn validate_header_against_parent(
+            &self,
+            _header: &SealedHeader,
+            _parent: &SealedHeader,
+        ) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+
+        fn validate_header_with_total_difficulty(
+            &self,
+            _header: &Header,
+            _total_difficulty: U256,
+        ) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+
+        fn validate_block(&self, _block: &SealedBlock) -> Result<(), ConsensusError> {
+            Ok(())
+        }
+    }
 
     /// Tests that `replace_number` works the same way as Option::replace
     #[test]
@@ -1506,4 +1625,72 @@ mod tests {
 
         assert!(downloader.next().await.is_none());
     }
+
+    #[tokio::test]
+    async fn download_invalid_header_in_chain() {
+        reth_tracing::init_test_tracing();
+
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        // every header is validated on its own, including the sync target and the header attached
+        // to the local head
+        for invalid in [p0.clone(), p1.clone(), p2.clone()] {
+            let client = Arc::new(TestHeadersClient::default());
+            let mut downloader = ReverseHeadersDownloaderBuilder::default()
+                .stream_batch_size(3)
+                .request_limit(3)
+                .build(Arc::clone(&client), Arc::new(RejectHeaderConsensus(invalid.hash())));
+            downloader.update_local_head(p3.clone());
+            downloader.update_sync_target(SyncTarget::Tip(p0.hash()));
+
+            client
+                .extend(vec![
+                    p0.as_ref().clone(),
+                    p1.as_ref().clone(),
+                    p2.as_ref().clone(),
+                    p3.as_ref().clone(),
+                ])
+                .await;
+
+            assert_matches!(
+                downloader.next().await,
+                Some(Err(HeadersDownloaderError::BadBlock { header, .. })) if header == invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn download_future_header_is_retried() {
+        reth_tracing::init_test_tracing();
+
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let client = Arc::new(TestHeadersClient::default());
+        let consensus = FutureHeaderConsensus { hash: p0.hash(), rejected: AtomicBool::new(false) };
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(3)
+            .request_limit(3)
+            .build(Arc::clone(&client), Arc::new(consensus));
+        downloader.update_local_head(p3.clone());
+        downloader.update_sync_target(SyncTarget::Tip(p0.hash()));
+
+        // the sync target is served twice, since the first response is rejected
+        client
+            .extend(vec![
+                p0.as_ref().clone(),
+                p0.as_ref().clone(),
+                p1.as_ref().clone(),
+                p2.as_ref().clone(),
+                p3.as_ref().clone(),
+            ])
+            .await;
+
+        assert_eq!(downloader.next().await, Some(Ok(vec![p0, p1, p2])));
+    }
 }
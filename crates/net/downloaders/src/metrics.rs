@@ -56,7 +56,9 @@ impl BodyDownloaderMetrics {
     pub fn increment_errors(&self, error: &DownloadError) {
         match error {
             DownloadError::Timeout => self.timeout_errors.increment(1),
-            DownloadError::BodyValidation { .. } => self.validation_errors.increment(1),
+            DownloadError::BodyValidation { .. } | DownloadError::BadBlock { .. } => {
+                self.validation_errors.increment(1)
+            }
             _error => self.unexpected_errors.increment(1),
         }
     }
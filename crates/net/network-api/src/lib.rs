@@ -19,10 +19,11 @@
 //!
 //! - `serde` (default): Enable serde support
 use async_trait::async_trait;
-use reth_eth_wire::DisconnectReason;
+use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthVersion, Status};
 use reth_primitives::{NodeRecord, PeerId};
 use reth_rpc_types::NetworkStatus;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc, time::Instant};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
@@ -93,6 +94,17 @@ pub trait Peers: PeersInfo {
 
     /// Get the reputation of a peer.
     async fn reputation_by_id(&self, peer_id: PeerId) -> Result<Option<Reputation>, NetworkError>;
+
+    /// Returns [`PeerInfo`] for all connected peers.
+    async fn get_peers(&self) -> Result<Vec<PeerInfo>, NetworkError>;
+
+    /// Returns [`PeerInfo`] for a given peer.
+    ///
+    /// Returns `None` if there's no active session to the peer.
+    async fn get_peer_by_id(&self, peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError>;
+
+    /// Returns a new stream of [`PeerEvent`]s.
+    fn peer_events(&self) -> UnboundedReceiverStream<PeerEvent>;
 }
 
 /// Represents the kind of peer
@@ -104,3 +116,89 @@ pub enum PeerKind {
     /// Trusted peer.
     Trusted,
 }
+
+impl PeerKind {
+    /// Returns `true` if the peer is trusted.
+    pub const fn is_trusted(&self) -> bool {
+        matches!(self, PeerKind::Trusted)
+    }
+}
+
+/// Info about an active peer session.
+#[derive(Debug, Clone)]
+pub struct PeerInfo {
+    /// Announced capabilities of the peer
+    pub capabilities: Arc<Capabilities>,
+    /// The identifier of the remote peer
+    pub remote_id: PeerId,
+    /// The client's name and version
+    pub client_version: Arc<String>,
+    /// The address we're connected to
+    pub remote_addr: SocketAddr,
+    /// The local address of the connection
+    pub local_addr: Option<SocketAddr>,
+    /// The direction of the session
+    pub direction: Direction,
+    /// The negotiated eth version of the session
+    pub eth_version: EthVersion,
+    /// The [`Status`] the peer sent during the handshake
+    pub status: Arc<Status>,
+    /// The timestamp when the session has been established.
+    pub session_established: Instant,
+    /// The kind of the peer
+    pub kind: PeerKind,
+    /// The current reputation of the peer
+    pub reputation: Reputation,
+}
+
+/// The direction of the connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// Incoming connection.
+    Incoming,
+    /// Outgoing connection to a specific node.
+    Outgoing(PeerId),
+}
+
+impl Direction {
+    /// Returns `true` if this an incoming connection.
+    pub fn is_incoming(&self) -> bool {
+        matches!(self, Direction::Incoming)
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Incoming => write!(f, "incoming"),
+            Direction::Outgoing(_) => write!(f, "outgoing"),
+        }
+    }
+}
+
+/// Events related to the sessions and the peer set of the network.
+#[derive(Debug, Clone)]
+pub enum PeerEvent {
+    /// Established a new session with the given peer.
+    SessionEstablished {
+        /// The identifier of the peer to which a session was established.
+        peer_id: PeerId,
+        /// The remote addr of the peer to which a session was established.
+        remote_addr: SocketAddr,
+        /// The client version of the peer to which a session was established.
+        client_version: Arc<String>,
+        /// Negotiated eth version of the session.
+        version: EthVersion,
+    },
+    /// Closed the peer session.
+    SessionClosed {
+        /// The identifier of the peer to which a session was closed.
+        peer_id: PeerId,
+        /// Why the disconnect was triggered
+        reason: Option<DisconnectReason>,
+    },
+    /// A new peer was added to the peer set.
+    PeerAdded(PeerId),
+    /// A peer was removed from the peer set.
+    PeerRemoved(PeerId),
+}
@@ -1,11 +1,13 @@
 use crate::{
-    NetworkError, NetworkInfo, PeerKind, Peers, PeersInfo, Reputation, ReputationChangeKind,
+    NetworkError, NetworkInfo, PeerEvent, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
+    ReputationChangeKind,
 };
 use async_trait::async_trait;
 use reth_eth_wire::{DisconnectReason, ProtocolVersion};
 use reth_primitives::{Chain, NodeRecord, PeerId};
 use reth_rpc_types::{EthProtocolInfo, NetworkStatus};
 use std::net::{IpAddr, SocketAddr};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// A type that implements all network trait that does nothing.
 ///
@@ -66,4 +68,17 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn get_peers(&self) -> Result<Vec<PeerInfo>, NetworkError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_peer_by_id(&self, _peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError> {
+        Ok(None)
+    }
+
+    fn peer_events(&self) -> UnboundedReceiverStream<PeerEvent> {
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        UnboundedReceiverStream::new(rx)
+    }
 }
@@ -8,6 +8,7 @@ use crate::{
     NetworkHandle, NetworkManager,
 };
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_PORT};
+use reth_discv5::Discv5Config;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_ecies::util::pk2id;
 use reth_eth_wire::{HelloMessage, Status};
@@ -40,6 +41,8 @@ pub struct NetworkConfig<C> {
     pub dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     pub discovery_v4_config: Option<Discv4Config>,
+    /// How to set up discovery v5.
+    pub discovery_v5_config: Option<Discv5Config>,
     /// Address to use for discovery
     pub discovery_addr: SocketAddr,
     /// Address to listen for incoming connections
@@ -88,6 +91,12 @@ impl<C> NetworkConfig<C> {
         self
     }
 
+    /// Sets the config to use for the discovery v5 protocol.
+    pub fn set_discovery_v5(mut self, discovery_config: Discv5Config) -> Self {
+        self.discovery_v5_config = Some(discovery_config);
+        self
+    }
+
     /// Sets the address for the incoming connection listener.
     pub fn set_listener_addr(mut self, listener_addr: SocketAddr) -> Self {
         self.listener_addr = listener_addr;
@@ -123,6 +132,9 @@ pub struct NetworkConfigBuilder {
     dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     discovery_v4_builder: Option<Discv4ConfigBuilder>,
+    /// How to set up discovery v5.
+    #[serde(skip)]
+    discovery_v5_config: Option<Discv5Config>,
     /// All boot nodes to start network discovery with.
     boot_nodes: HashSet<NodeRecord>,
     /// Address to use for discovery
@@ -155,6 +167,7 @@ impl NetworkConfigBuilder {
             secret_key,
             dns_discovery_config: Some(Default::default()),
             discovery_v4_builder: Some(Default::default()),
+            discovery_v5_config: None,
             boot_nodes: Default::default(),
             discovery_addr: None,
             listener_addr: None,
@@ -272,6 +285,14 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the discv5 config to use.
+    ///
+    /// Discv5 is disabled by default.
+    pub fn discovery_v5(mut self, config: Discv5Config) -> Self {
+        self.discovery_v5_config = Some(config);
+        self
+    }
+
     /// Sets the dns discovery config to use.
     pub fn dns_discovery(mut self, config: DnsDiscoveryConfig) -> Self {
         self.dns_discovery_config = Some(config);
@@ -292,7 +313,7 @@ impl NetworkConfigBuilder {
 
     /// Disables all discovery.
     pub fn disable_discovery(self) -> Self {
-        self.disable_discv4_discovery().disable_dns_discovery()
+        self.disable_discv4_discovery().disable_discv5_discovery().disable_dns_discovery()
     }
 
     /// Disables all discovery if the given condition is true.
@@ -310,6 +331,12 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Disable the Discv5 discovery.
+    pub fn disable_discv5_discovery(mut self) -> Self {
+        self.discovery_v5_config = None;
+        self
+    }
+
     /// Disable the DNS discovery if the given condition is true.
     pub fn disable_dns_discovery_if(self, disable: bool) -> Self {
         if disable {
@@ -336,6 +363,7 @@ impl NetworkConfigBuilder {
             secret_key,
             mut dns_discovery_config,
             discovery_v4_builder,
+            discovery_v5_config,
             boot_nodes,
             discovery_addr,
             listener_addr,
@@ -387,6 +415,7 @@ impl NetworkConfigBuilder {
             boot_nodes,
             dns_discovery_config,
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
+            discovery_v5_config,
             discovery_addr: discovery_addr.unwrap_or_else(|| {
                 SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, DEFAULT_DISCOVERY_PORT))
             }),
@@ -454,6 +483,21 @@ mod tests {
         assert_eq!(bootstrap_nodes.len(), 1);
     }
 
+    #[test]
+    fn test_network_discv5_opt_in() {
+        let config = builder().build(NoopProvider::default());
+        assert!(config.discovery_v5_config.is_none());
+
+        let config = builder().discovery_v5(Discv5Config::default()).build(NoopProvider::default());
+        assert!(config.discovery_v5_config.is_some());
+
+        let config = builder()
+            .discovery_v5(Discv5Config::default())
+            .disable_discovery()
+            .build(NoopProvider::default());
+        assert!(config.discovery_v5_config.is_none());
+    }
+
     #[test]
     fn test_network_fork_filter_default() {
         let mut chain_spec = Arc::clone(&MAINNET);
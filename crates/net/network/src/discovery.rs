@@ -3,6 +3,7 @@
 use crate::error::{NetworkError, ServiceKind};
 use futures::StreamExt;
 use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config, EnrForkIdEntry};
+use reth_discv5::{Discv5, Discv5Config, Discv5NodeRecordUpdate};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
@@ -33,6 +34,12 @@ pub struct Discovery {
     discv4_updates: Option<ReceiverStream<DiscoveryUpdate>>,
     /// The handle to the spawned discv4 service
     _discv4_service: Option<JoinHandle<()>>,
+    /// Handler to interact with the Discovery v5 service
+    discv5: Option<Discv5>,
+    /// All nodes serving `eth` discovered by the discv5 service.
+    discv5_updates: Option<ReceiverStream<Discv5NodeRecordUpdate>>,
+    /// The handle to the spawned discv5 service
+    _discv5_service: Option<JoinHandle<()>>,
     /// Handler to interact with the DNS discovery service
     _dns_discovery: Option<DnsDiscoveryHandle>,
     /// Updates from the DNS discovery service.
@@ -46,12 +53,13 @@ pub struct Discovery {
 impl Discovery {
     /// Spawns the discovery service.
     ///
-    /// This will spawn the [`reth_discv4::Discv4Service`] onto a new task and establish a listener
-    /// channel to receive all discovered nodes.
+    /// This will spawn the [`reth_discv4::Discv4Service`] and [`reth_discv5::Discv5Service`] onto
+    /// new tasks and establish listener channels to receive all discovered nodes.
     pub async fn new(
         discovery_addr: SocketAddr,
         sk: SecretKey,
         discv4_config: Option<Discv4Config>,
+        discv5_config: Option<Discv5Config>,
         dns_discovery_config: Option<DnsDiscoveryConfig>,
     ) -> Result<Self, NetworkError> {
         // setup discv4
@@ -69,6 +77,20 @@ impl Discovery {
             (None, None, None)
         };
 
+        // setup discv5
+        let (discv5, discv5_updates, _discv5_service) = if let Some(disc_config) = discv5_config {
+            let discv5_addr = disc_config.listen_addr;
+            let (discv5, mut discv5_service) = Discv5::start(&sk, disc_config)
+                .await
+                .map_err(|err| NetworkError::Discv5(discv5_addr, err))?;
+            let discv5_updates = discv5_service.update_stream();
+            // spawn the service
+            let _discv5_service = discv5_service.spawn();
+            (Some(discv5), Some(discv5_updates), Some(_discv5_service))
+        } else {
+            (None, None, None)
+        };
+
         // setup DNS discovery
         let (_dns_discovery, dns_discovery_updates, _dns_disc_service) =
             if let Some(dns_config) = dns_discovery_config {
@@ -88,6 +110,9 @@ impl Discovery {
             discv4,
             discv4_updates,
             _discv4_service,
+            discv5,
+            discv5_updates,
+            _discv5_service,
             discovered_nodes: Default::default(),
             queued_events: Default::default(),
             _dns_disc_service,
@@ -96,27 +121,36 @@ impl Discovery {
         })
     }
 
-    /// Updates the `eth:ForkId` field in discv4.
+    /// Updates the `eth:ForkId` field in discv4 and discv5.
     #[allow(unused)]
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp("eth".as_bytes().to_vec(), EnrForkIdEntry::from(fork_id))
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.set_fork_id(fork_id)
+        }
     }
 
-    /// Bans the [`IpAddr`] in the discovery service.
+    /// Bans the [`IpAddr`] in the discovery services.
     pub(crate) fn ban_ip(&self, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban_ip(ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban_ip(ip)
+        }
     }
 
-    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
+    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery services.
     pub(crate) fn ban(&self, peer_id: PeerId, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban(peer_id, ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban(peer_id, ip)
+        }
     }
 
     /// Returns the id with which the local identifies itself in the network
@@ -156,6 +190,7 @@ impl Discovery {
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
             }
+            DiscoveryUpdate::SignedEnr(_, _) => {}
             DiscoveryUpdate::Removed(node) => {
                 self.discovered_nodes.remove(&node);
             }
@@ -184,6 +219,12 @@ impl Discovery {
                 self.on_discv4_update(update)
             }
 
+            while let Some(Poll::Ready(Some(update))) =
+                self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
+            {
+                self.on_node_record_update(update.node_record, Some(update.fork_id));
+            }
+
             while let Some(Poll::Ready(Some(update))) =
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
@@ -216,6 +257,9 @@ impl Discovery {
             discv4_updates: Default::default(),
             queued_events: Default::default(),
             _discv4_service: Default::default(),
+            discv5: None,
+            discv5_updates: None,
+            _discv5_service: None,
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
@@ -243,9 +287,14 @@ mod tests {
         let mut rng = thread_rng();
         let (secret_key, _) = SECP256K1.generate_keypair(&mut rng);
         let discovery_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
-        let _discovery =
-            Discovery::new(discovery_addr, secret_key, Default::default(), Default::default())
-                .await
-                .unwrap();
+        let _discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            Default::default(),
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
     }
 }
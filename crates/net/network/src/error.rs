@@ -1,6 +1,7 @@
 //! Possible errors when interacting with the network.
 
 use crate::session::PendingSessionHandshakeError;
+use reth_discv5::error::Discv5Error;
 use reth_dns_discovery::resolver::ResolveError;
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
@@ -43,6 +44,9 @@ pub enum NetworkError {
     /// IO error when creating the discovery service
     #[error("Failed to launch discovery service: {0}")]
     Discovery(io::Error),
+    /// Error when starting the discv5 service on the given address
+    #[error("Failed to launch discv5 service on {0}: {1}")]
+    Discv5(SocketAddr, Discv5Error),
     /// Error when setting up the DNS resolver failed
     ///
     /// See also [DnsResolver](reth_dns_discovery::DnsResolver::from_system_conf)
@@ -74,6 +74,10 @@ impl DownloadClient for FetchClient {
         self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
     }
 
+    fn report_bad_block(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadBlock);
+    }
+
     fn num_connected_peers(&self) -> usize {
         self.num_active_peers.load(Ordering::Relaxed)
     }
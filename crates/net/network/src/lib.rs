@@ -150,7 +150,7 @@ pub use fetch::FetchClient;
 pub use manager::{NetworkEvent, NetworkManager};
 pub use message::PeerRequest;
 pub use network::NetworkHandle;
-pub use peers::PeersConfig;
+pub use peers::{PeersConfig, PersistedPeer};
 pub use session::{PeerInfo, SessionsConfig};
 
 pub use reth_eth_wire::{DisconnectReason, HelloBuilder, HelloMessage};
@@ -25,7 +25,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedPeer, DEFAULT_REPUTATION},
     session::SessionManager,
     state::NetworkState,
     swarm::{NetworkConnectionState, Swarm, SwarmEvent},
@@ -40,7 +40,7 @@ use reth_eth_wire::{
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_net_common::bandwidth_meter::BandwidthMeter;
-use reth_network_api::ReputationChangeKind;
+use reth_network_api::{PeerEvent, PeerInfo, PeerKind, ReputationChangeKind};
 use reth_primitives::{listener::EventListeners, NodeRecord, PeerId, H256};
 use reth_provider::BlockReader;
 use reth_rpc_types::{EthProtocolInfo, NetworkStatus};
@@ -97,6 +97,8 @@ pub struct NetworkManager<C> {
     block_import: Box<dyn BlockImport>,
     /// All listeners for high level network events.
     event_listeners: EventListeners<NetworkEvent>,
+    /// All listeners for [`PeerEvent`]s.
+    peer_event_listeners: EventListeners<PeerEvent>,
     /// Sender half to send events to the
     /// [`TransactionsManager`](crate::transactions::TransactionsManager) task, if configured.
     to_transactions_manager: Option<UnboundedMeteredSender<NetworkTransactionEvent>>,
@@ -167,6 +169,7 @@ where
             client,
             secret_key,
             mut discovery_v4_config,
+            mut discovery_v5_config,
             discovery_addr,
             listener_addr,
             peers_config,
@@ -198,9 +201,20 @@ where
             disc_config
         });
 
-        let discovery =
-            Discovery::new(discovery_addr, secret_key, discovery_v4_config, dns_discovery_config)
-                .await?;
+        if let Some(disc_config) = discovery_v5_config.as_mut() {
+            // advertise the RLPx listener and the fork id in the ENR
+            disc_config.tcp_port = incoming.local_address().port();
+            disc_config.fork_id = Some(status.forkid);
+        }
+
+        let discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            discovery_v4_config,
+            discovery_v5_config,
+            dns_discovery_config,
+        )
+        .await?;
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
 
@@ -246,6 +260,7 @@ where
             from_handle_rx: UnboundedReceiverStream::new(from_handle_rx),
             block_import,
             event_listeners: Default::default(),
+            peer_event_listeners: Default::default(),
             to_transactions_manager: None,
             to_eth_request_handler: None,
             num_active_peers,
@@ -319,6 +334,11 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns the state of all peers in the peer set that should be persisted across restarts.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.swarm.state().peers().persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -351,6 +371,34 @@ where
         }
     }
 
+    /// Returns [`PeerInfo`] for all connected peers.
+    fn get_peer_infos(&self) -> Vec<PeerInfo> {
+        self.swarm
+            .sessions()
+            .active_sessions()
+            .keys()
+            .filter_map(|peer_id| self.get_peer_info_by_id(*peer_id))
+            .collect()
+    }
+
+    /// Returns [`PeerInfo`] for the given peer, if there's an active session.
+    fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
+        let session = self.swarm.sessions().active_sessions().get(&peer_id)?;
+        let (kind, reputation) = self
+            .swarm
+            .state()
+            .peers()
+            .get_kind_and_reputation(&peer_id)
+            .unwrap_or((PeerKind::Basic, DEFAULT_REPUTATION));
+        Some(session.peer_info(kind, reputation))
+    }
+
+    /// Notifies all listeners about the event.
+    fn notify_event(&mut self, event: NetworkEvent) {
+        self.peer_event_listeners.notify(PeerEvent::from(&event));
+        self.event_listeners.notify(event);
+    }
+
     /// Event hook for an unexpected message from the peer.
     fn on_invalid_message(
         &mut self,
@@ -575,10 +623,13 @@ where
                 }
             }
             NetworkHandleMessage::GetPeerInfo(tx) => {
-                let _ = tx.send(self.swarm.sessions_mut().get_peer_info());
+                let _ = tx.send(self.get_peer_infos());
             }
             NetworkHandleMessage::GetPeerInfoById(peer_id, tx) => {
-                let _ = tx.send(self.swarm.sessions_mut().get_peer_info_by_id(peer_id));
+                let _ = tx.send(self.get_peer_info_by_id(peer_id));
+            }
+            NetworkHandleMessage::PeerEventListener(tx) => {
+                self.peer_event_listeners.push_listener(tx);
             }
         }
     }
@@ -689,7 +740,11 @@ where
                                     .peers_mut()
                                     .on_incoming_session_established(peer_id, remote_addr);
                             }
-                            this.event_listeners.notify(NetworkEvent::SessionEstablished {
+                            this.swarm
+                                .state_mut()
+                                .peers_mut()
+                                .on_active_session_established(peer_id, client_version.clone());
+                            this.notify_event(NetworkEvent::SessionEstablished {
                                 peer_id,
                                 remote_addr,
                                 client_version,
@@ -701,14 +756,14 @@ where
                         }
                         SwarmEvent::PeerAdded(peer_id) => {
                             trace!(target: "net", ?peer_id, "Peer added");
-                            this.event_listeners.notify(NetworkEvent::PeerAdded(peer_id));
+                            this.notify_event(NetworkEvent::PeerAdded(peer_id));
                             this.metrics
                                 .tracked_peers
                                 .set(this.swarm.state().peers().num_known_peers() as f64);
                         }
                         SwarmEvent::PeerRemoved(peer_id) => {
                             trace!(target: "net", ?peer_id, "Peer dropped");
-                            this.event_listeners.notify(NetworkEvent::PeerRemoved(peer_id));
+                            this.notify_event(NetworkEvent::PeerRemoved(peer_id));
                             this.metrics
                                 .tracked_peers
                                 .set(this.swarm.state().peers().num_known_peers() as f64);
@@ -758,8 +813,7 @@ where
                                 this.swarm.state().peers().num_backed_off_peers().saturating_sub(1)
                                     as f64,
                             );
-                            this.event_listeners
-                                .notify(NetworkEvent::SessionClosed { peer_id, reason });
+                            this.notify_event(NetworkEvent::SessionClosed { peer_id, reason });
                         }
                         SwarmEvent::IncomingPendingSessionClosed { remote_addr, error } => {
                             debug!(
@@ -919,3 +973,27 @@ pub enum NetworkEvent {
     /// Event emitted when a new peer is removed
     PeerRemoved(PeerId),
 }
+
+impl From<&NetworkEvent> for PeerEvent {
+    fn from(event: &NetworkEvent) -> Self {
+        match event {
+            NetworkEvent::SessionClosed { peer_id, reason } => {
+                PeerEvent::SessionClosed { peer_id: *peer_id, reason: *reason }
+            }
+            NetworkEvent::SessionEstablished {
+                peer_id,
+                remote_addr,
+                client_version,
+                version,
+                ..
+            } => PeerEvent::SessionEstablished {
+                peer_id: *peer_id,
+                remote_addr: *remote_addr,
+                client_version: client_version.clone(),
+                version: *version,
+            },
+            NetworkEvent::PeerAdded(peer_id) => PeerEvent::PeerAdded(*peer_id),
+            NetworkEvent::PeerRemoved(peer_id) => PeerEvent::PeerRemoved(*peer_id),
+        }
+    }
+}
@@ -2,7 +2,9 @@ use crate::{
     error::{BackoffKind, SessionError},
     peers::{
         reputation::{is_banned_reputation, DEFAULT_REPUTATION},
-        ReputationChangeWeights, DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND,
+        store::{instant_to_unix, remaining_until_unix, unix_now, PeersFileEntry},
+        PersistedPeer, ReputationChangeWeights, DEFAULT_MAX_PEERS_INBOUND,
+        DEFAULT_MAX_PEERS_OUTBOUND,
     },
     session::{Direction, PendingSessionHandshakeError},
 };
@@ -17,6 +19,7 @@ use std::{
     io::{self, ErrorKind},
     net::{IpAddr, SocketAddr},
     path::Path,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -125,6 +128,7 @@ impl PeersManager {
             trusted_nodes,
             connect_trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             ..
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -133,7 +137,10 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut ban_list = ban_list;
+        let mut backed_off_peers = HashMap::new();
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len() + persisted_peers.len());
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
             peers.entry(id).or_insert_with(|| Peer::trusted(SocketAddr::from((address, tcp_port))));
@@ -143,6 +150,32 @@ impl PeersManager {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
 
+        for persisted in persisted_peers {
+            let std_now = std::time::Instant::now();
+            let id = persisted.record.id;
+
+            // skip known bad peers until their ban expires
+            if let Some(remaining) = persisted.banned_until.and_then(remaining_until_unix) {
+                ban_list.ban_peer_until(id, std_now + remaining);
+                continue
+            }
+
+            let peer = peers.entry(id).or_insert_with(|| Peer::new(persisted.record.tcp_addr()));
+            // the ban of peers with a banned reputation has expired
+            if !is_banned_reputation(persisted.reputation) {
+                peer.reputation = persisted.reputation;
+            }
+            peer.fork_id = peer.fork_id.or(persisted.fork_id);
+            peer.client_version = persisted.client_version.map(Arc::new);
+            peer.severe_backoff_counter = persisted.severe_backoff_counter;
+            peer.successful_sessions = persisted.successful_sessions;
+            peer.last_seen = persisted.last_seen;
+            if let Some(remaining) = persisted.backed_off_until.and_then(remaining_until_unix) {
+                peer.backed_off = true;
+                backed_off_peers.insert(id, std_now + remaining);
+            }
+        }
+
         Self {
             peers,
             manager_tx,
@@ -156,7 +189,7 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info,
             ban_list,
-            backed_off_peers: Default::default(),
+            backed_off_peers,
             ban_duration,
             backoff_durations,
             connect_trusted_nodes_only,
@@ -180,6 +213,34 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns the state of all peers that should be persisted across restarts.
+    ///
+    /// Peers that only connected to us are skipped, since their listening port is unknown.
+    pub(crate) fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        let now = std::time::Instant::now();
+        self.peers
+            .iter()
+            .filter(|(_, peer)| !peer.remove_after_disconnect)
+            .map(|(peer_id, peer)| PersistedPeer {
+                record: NodeRecord::new(peer.addr, *peer_id),
+                fork_id: peer.fork_id,
+                client_version: peer.client_version.as_ref().map(|version| version.to_string()),
+                reputation: peer.reputation,
+                severe_backoff_counter: peer.severe_backoff_counter,
+                backed_off_until: self
+                    .backed_off_peers
+                    .get(peer_id)
+                    .map(|until| instant_to_unix(*until, now)),
+                banned_until: self
+                    .ban_list
+                    .peer_banned_until(peer_id)
+                    .map(|until| instant_to_unix(until, now)),
+                successful_sessions: peer.successful_sessions,
+                last_seen: peer.last_seen,
+            })
+            .collect()
+    }
+
     /// Returns the number of currently active inbound connections.
     #[inline]
     pub(crate) fn num_inbound_connections(&self) -> usize {
@@ -283,6 +344,21 @@ impl PeersManager {
         }
     }
 
+    /// Called when a new active session was established to the given peer, in either direction.
+    ///
+    /// Records the session in the peer's history.
+    pub(crate) fn on_active_session_established(
+        &mut self,
+        peer_id: PeerId,
+        client_version: Arc<String>,
+    ) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.client_version = Some(client_version);
+            peer.successful_sessions += 1;
+            peer.last_seen = Some(unix_now());
+        }
+    }
+
     /// Bans the peer temporarily with the configured ban timeout
     fn ban_peer(&mut self, peer_id: PeerId) {
         self.ban_list.ban_peer_until(peer_id, std::time::Instant::now() + self.ban_duration);
@@ -337,6 +413,11 @@ impl PeersManager {
         self.peers.get(peer_id).map(|peer| peer.reputation)
     }
 
+    /// Returns the kind and the reputation of the peer, if it's tracked.
+    pub(crate) fn get_kind_and_reputation(&self, peer_id: &PeerId) -> Option<(PeerKind, i32)> {
+        self.peers.get(peer_id).map(|peer| (peer.kind, peer.reputation))
+    }
+
     /// Apply the corresponding reputation change to the given peer
     pub(crate) fn apply_reputation_change(&mut self, peer_id: &PeerId, rep: ReputationChangeKind) {
         let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -648,8 +729,11 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer using the reputation and prefer peers we
+            // had more successful sessions with
+            if (maybe_better.1.reputation, maybe_better.1.successful_sessions) >
+                (best_peer.1.reputation, best_peer.1.successful_sessions)
+            {
                 best_peer = maybe_better;
             }
         }
@@ -846,6 +930,12 @@ pub struct Peer {
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [BackoffKind].
     severe_backoff_counter: u32,
+    /// The client version the peer announced in its last session.
+    client_version: Option<Arc<String>>,
+    /// Number of sessions that were successfully established with the peer.
+    successful_sessions: u64,
+    /// Unix timestamp in seconds of the last established session.
+    last_seen: Option<u64>,
 }
 
 // === impl Peer ===
@@ -869,6 +959,9 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            client_version: None,
+            successful_sessions: 0,
+            last_seen: None,
         }
     }
 
@@ -1055,6 +1148,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers and their history restored from a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: Vec<PersistedPeer>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1083,6 +1179,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             connect_trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
         }
     }
 }
@@ -1134,7 +1231,16 @@ impl PeersConfig {
         self
     }
 
+    /// Peers and their history restored from a previous run.
+    pub fn with_persisted_peers(mut self, peers: Vec<PersistedPeer>) -> Self {
+        self.persisted_peers = peers;
+        self
+    }
+
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file contains either plain [`NodeRecord`]s or [`PersistedPeer`]s written by a previous
+    /// run.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1146,8 +1252,8 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let entries: Vec<PeersFileEntry> = serde_json::from_reader(reader)?;
+        Ok(self.with_persisted_peers(entries.into_iter().map(Into::into).collect()))
     }
 }
 
@@ -1227,7 +1333,7 @@ mod test {
         error::BackoffKind,
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
-            reputation::DEFAULT_REPUTATION,
+            reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
             PeerAction,
         },
         session::PendingSessionHandshakeError,
@@ -2066,4 +2172,50 @@ mod test {
         assert_eq!(peer.state, PeerConnectionState::Idle);
         assert!(!peer.remove_after_disconnect);
     }
+
+    #[tokio::test]
+    async fn test_restore_persisted_peers() {
+        let good = PeerId::random();
+        let good_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let fresh = PeerId::random();
+        let fresh_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        let banned = PeerId::random();
+        let banned_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 4)), 8008);
+
+        let mut peers = PeersManager::default();
+        for (peer_id, addr) in [(good, good_addr), (fresh, fresh_addr), (banned, banned_addr)] {
+            peers.add_peer(peer_id, addr, None);
+        }
+        peers.on_active_session_established(good, std::sync::Arc::new("reth".to_string()));
+        peers.ban_peer(banned);
+        peers.peers.get_mut(&banned).unwrap().reputation = BANNED_REPUTATION;
+
+        let persisted = peers.persisted_peers();
+        assert_eq!(persisted.len(), 3);
+        let good_entry = persisted.iter().find(|peer| peer.record.id == good).unwrap();
+        assert_eq!(good_entry.successful_sessions, 1);
+        assert_eq!(good_entry.client_version.as_deref(), Some("reth"));
+        assert!(persisted
+            .iter()
+            .find(|peer| peer.record.id == banned)
+            .unwrap()
+            .banned_until
+            .is_some());
+
+        let config = PeersConfig::default().with_max_outbound(1).with_persisted_peers(persisted);
+        let mut peers = PeersManager::new(config);
+
+        // known bad peers are skipped
+        assert!(peers.peers.get(&banned).is_none());
+        assert!(peers.ban_list.is_banned_peer(&banned));
+
+        // historically good peers are dialed first
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, good);
+                assert_eq!(remote_addr, good_addr);
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -2,11 +2,14 @@
 
 mod manager;
 mod reputation;
+mod store;
 
 pub(crate) use manager::{InboundConnectionError, PeerAction, PeersManager};
 pub use manager::{Peer, PeersConfig, PeersHandle};
 pub use reputation::ReputationChangeWeights;
+pub(crate) use reputation::DEFAULT_REPUTATION;
 pub use reth_network_api::PeerKind;
+pub use store::PersistedPeer;
 
 /// Maximum number of available slots for outbound sessions.
 pub(crate) const DEFAULT_MAX_PEERS_OUTBOUND: usize = 100;
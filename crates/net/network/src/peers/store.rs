@@ -0,0 +1,130 @@
+//! Persistence of the peer set across restarts.
+
+use reth_primitives::{ForkId, NodeRecord};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The state of a single peer that is persisted across restarts, so that historically good peers
+/// can be prioritised and known bad peers skipped on startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PersistedPeer {
+    /// The record of the peer with its last seen address.
+    pub record: NodeRecord,
+    /// The [`ForkId`] the peer announced via discovery, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fork_id: Option<ForkId>,
+    /// The client version the peer announced in its last session, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub client_version: Option<String>,
+    /// The cumulative reputation of the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation: i32,
+    /// How often the peer was backed off in a row due to severe connection problems.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub severe_backoff_counter: u32,
+    /// Unix timestamp in seconds until which the peer is backed off, if it is.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub backed_off_until: Option<u64>,
+    /// Unix timestamp in seconds until which the peer is banned, if it is.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub banned_until: Option<u64>,
+    /// Number of sessions that were successfully established with the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub successful_sessions: u64,
+    /// Unix timestamp in seconds of the last established session, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_seen: Option<u64>,
+}
+
+impl PersistedPeer {
+    /// Creates an entry for the given record without any history.
+    pub fn new(record: NodeRecord) -> Self {
+        Self {
+            record,
+            fork_id: None,
+            client_version: None,
+            reputation: 0,
+            severe_backoff_counter: 0,
+            backed_off_until: None,
+            banned_until: None,
+            successful_sessions: 0,
+            last_seen: None,
+        }
+    }
+}
+
+/// An entry of a peers file.
+///
+/// Older peers files only contain a list of [`NodeRecord`]s, these are still accepted.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub(crate) enum PeersFileEntry {
+    /// A plain record without history.
+    Record(NodeRecord),
+    /// A peer with its history.
+    Peer(PersistedPeer),
+}
+
+impl From<PeersFileEntry> for PersistedPeer {
+    fn from(entry: PeersFileEntry) -> Self {
+        match entry {
+            PeersFileEntry::Record(record) => PersistedPeer::new(record),
+            PeersFileEntry::Peer(peer) => peer,
+        }
+    }
+}
+
+/// Converts the given instant into a unix timestamp in seconds, relative to `now`.
+pub(crate) fn instant_to_unix(instant: std::time::Instant, now: std::time::Instant) -> u64 {
+    let remaining = instant.saturating_duration_since(now);
+    (SystemTime::now() + remaining).duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Returns how long it is until the given unix timestamp in seconds, or `None` if it is in the
+/// past.
+pub(crate) fn remaining_until_unix(timestamp: u64) -> Option<Duration> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    Duration::from_secs(timestamp).checked_sub(now).filter(|remaining| !remaining.is_zero())
+}
+
+/// Returns the current unix timestamp in seconds.
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::PeerId;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn parse_legacy_and_persisted_entries() {
+        let record = NodeRecord::new(SocketAddr::from(([127, 0, 0, 1], 30303)), PeerId::random());
+        let mut peer = PersistedPeer::new(record);
+        peer.reputation = -1024;
+        peer.successful_sessions = 3;
+
+        let file = format!(
+            "[{}, {}]",
+            serde_json::to_string(&record).unwrap(),
+            serde_json::to_string(&peer).unwrap()
+        );
+        let entries: Vec<PeersFileEntry> = serde_json::from_str(&file).unwrap();
+        let peers = entries.into_iter().map(PersistedPeer::from).collect::<Vec<_>>();
+        assert_eq!(peers, vec![PersistedPeer::new(record), peer]);
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        assert!(remaining_until_unix(unix_now().saturating_sub(10)).is_none());
+        let until = instant_to_unix(
+            std::time::Instant::now() + Duration::from_secs(100),
+            std::time::Instant::now(),
+        );
+        let remaining = remaining_until_unix(until).unwrap();
+        assert!(remaining > Duration::from_secs(90) && remaining <= Duration::from_secs(100));
+    }
+}
@@ -10,6 +10,7 @@ use reth_eth_wire::{
     DisconnectReason, EthStream, EthVersion, P2PStream, Status,
 };
 use reth_net_common::bandwidth_meter::MeteredStream;
+use reth_network_api::{PeerInfo, PeerKind, Reputation};
 use reth_primitives::PeerId;
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::{
@@ -65,6 +66,10 @@ pub(crate) struct ActiveSessionHandle {
     pub(crate) client_version: Arc<String>,
     /// The address we're connected to
     pub(crate) remote_addr: SocketAddr,
+    /// The local address of the connection
+    pub(crate) local_addr: Option<SocketAddr>,
+    /// The [`Status`] the peer sent during the handshake
+    pub(crate) status: Arc<Status>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -75,21 +80,23 @@ impl ActiveSessionHandle {
         // Note: we clone the sender which ensures the channel has capacity to send the message
         let _ = self.commands_to_session.clone().try_send(SessionCommand::Disconnect { reason });
     }
-}
 
-/// Info about an active peer session.
-#[derive(Debug, Clone)]
-pub struct PeerInfo {
-    /// Announced capabilities of the peer
-    pub capabilities: Arc<Capabilities>,
-    /// The identifier of the remote peer
-    pub remote_id: PeerId,
-    /// The client's name and version
-    pub client_version: Arc<String>,
-    /// The address we're connected to
-    pub remote_addr: SocketAddr,
-    /// The direction of the session
-    pub direction: Direction,
+    /// Returns the [`PeerInfo`] of the session for a peer of the given kind and reputation.
+    pub(crate) fn peer_info(&self, kind: PeerKind, reputation: Reputation) -> PeerInfo {
+        PeerInfo {
+            remote_id: self.remote_id,
+            direction: self.direction,
+            remote_addr: self.remote_addr,
+            local_addr: self.local_addr,
+            capabilities: self.capabilities.clone(),
+            client_version: self.client_version.clone(),
+            eth_version: self.version,
+            status: self.status.clone(),
+            session_established: self.established,
+            kind,
+            reputation,
+        }
+    }
 }
 
 /// Events a pending session can produce.
@@ -103,6 +110,8 @@ pub(crate) enum PendingSessionEvent {
     Established {
         session_id: SessionId,
         remote_addr: SocketAddr,
+        /// The local address of the connection
+        local_addr: Option<SocketAddr>,
         /// The remote node's public key
         peer_id: PeerId,
         capabilities: Arc<Capabilities>,
@@ -178,6 +178,8 @@ where
                 .pool
                 .get_all(request.0)
                 .into_iter()
+                // private transactions are never shared with peers
+                .filter(|tx| tx.propagate)
                 .map(|tx| tx.transaction.to_recovered_transaction().into_signed())
                 .collect::<Vec<_>>();
 
@@ -212,6 +214,7 @@ where
             self.pool
                 .get_all(hashes)
                 .into_iter()
+                .filter(|tx| tx.propagate)
                 .map(|tx| {
                     let tx = Arc::new(tx.transaction.to_recovered_transaction().into_signed());
                     PropagateTransaction::new(tx)
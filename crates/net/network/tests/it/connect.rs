@@ -16,7 +16,7 @@ use reth_network::{
     },
     NetworkConfigBuilder, NetworkEvent, NetworkManager, PeersConfig,
 };
-use reth_network_api::{NetworkInfo, Peers, PeersInfo};
+use reth_network_api::{Direction, NetworkInfo, PeerEvent, PeerKind, Peers, PeersInfo};
 use reth_primitives::{mainnet_nodes, HeadersDirection, NodeRecord, PeerId};
 use reth_provider::test_utils::NoopProvider;
 use reth_transaction_pool::test_utils::testing_pool;
@@ -196,6 +196,55 @@ async fn test_get_peer_by_id() {
     assert!(peer.is_none());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_peer_info_and_events() {
+    reth_tracing::init_test_tracing();
+    let mut net = Testnet::default();
+
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let client = NoopProvider::default();
+    let p1 = PeerConfig::default();
+    let p2 = PeerConfig::with_secret_key(client, secret_key);
+
+    net.extend_peer_with_config(vec![p1, p2]).await.unwrap();
+
+    let mut handles = net.handles();
+    let handle0 = handles.next().unwrap();
+    let handle1 = handles.next().unwrap();
+
+    drop(handles);
+    let _handle = net.spawn();
+
+    let mut events = handle0.peer_events();
+
+    handle0.add_trusted_peer(*handle1.peer_id(), handle1.local_addr());
+
+    loop {
+        match events.next().await.unwrap() {
+            PeerEvent::SessionEstablished { peer_id, .. } => {
+                assert_eq!(peer_id, *handle1.peer_id());
+                break
+            }
+            PeerEvent::PeerAdded(peer_id) => assert_eq!(peer_id, *handle1.peer_id()),
+            event => panic!("unexpected event {event:?}"),
+        }
+    }
+
+    let peer = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(peer.remote_id, *handle1.peer_id());
+    assert_eq!(peer.direction, Direction::Outgoing(*handle1.peer_id()));
+    assert_eq!(peer.kind, PeerKind::Trusted);
+    // the testnet listens on the unspecified address, so only the port is comparable
+    assert_eq!(peer.remote_addr.port(), handle1.local_addr().port());
+    assert!(peer.local_addr.is_some());
+
+    handle0.disconnect_peer(*handle1.peer_id());
+    match events.next().await.unwrap() {
+        PeerEvent::SessionClosed { peer_id, .. } => assert_eq!(peer_id, *handle1.peer_id()),
+        event => panic!("unexpected event {event:?}"),
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore]
 async fn test_connect_with_boot_nodes() {
@@ -18,7 +18,7 @@ use futures_core::ready;
 use futures_util::FutureExt;
 use reth_payload_builder::{
     database::CachedReads, error::PayloadBuilderError, BuiltPayload, KeepPayloadJobAlive,
-    PayloadBuilderAttributes, PayloadJob, PayloadJobGenerator,
+    PayloadBuilderAttributes, PayloadJob, PayloadJobGenerator, ProposerPreferencesProvider,
 };
 use reth_primitives::{
     bytes::{Bytes, BytesMut},
@@ -26,7 +26,8 @@ use reth_primitives::{
         BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS, EMPTY_WITHDRAWALS,
         ETHEREUM_BLOCK_GAS_LIMIT, RETH_CLIENT_VERSION, SLOT_DURATION,
     },
-    proofs, Block, BlockNumberOrTag, ChainSpec, Header, IntoRecoveredTransaction, Receipt,
+    gas_limit::calculate_next_block_gas_limit,
+    proofs, Address, Block, BlockNumberOrTag, ChainSpec, Header, IntoRecoveredTransaction, Receipt,
     SealedBlock, Withdrawal, EMPTY_OMMER_ROOT, H256, U256,
 };
 use reth_provider::{BlockReaderIdExt, BlockSource, PostState, StateProviderFactory};
@@ -40,10 +41,13 @@ use reth_revm::{
 };
 use reth_rlp::Encodable;
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use revm::{
-    db::{CacheDB, DatabaseRef},
-    primitives::{BlockEnv, CfgEnv, EVMError, Env, InvalidTransaction, ResultAndState},
+    db::{CacheDB, DatabaseRef, DbAccount},
+    primitives::{
+        hash_map::HashMap, Account as RevmAccount, BlockEnv, CfgEnv, EVMError, Env,
+        InvalidTransaction, ResultAndState,
+    },
 };
 use std::{
     future::Future,
@@ -53,7 +57,7 @@ use std::{
     time::Duration,
 };
 use tokio::{
-    sync::{oneshot, Semaphore},
+    sync::{mpsc::UnboundedSender, oneshot, Semaphore},
     time::{Interval, Sleep},
 };
 use tracing::{debug, trace};
@@ -61,7 +65,10 @@ use tracing::{debug, trace};
 mod metrics;
 
 /// The [PayloadJobGenerator] that creates [BasicPayloadJob]s.
-pub struct BasicPayloadJobGenerator<Client, Pool, Tasks> {
+///
+/// The payloads are built by the configured [PayloadBuilder], which defaults to the
+/// [EthereumPayloadBuilder].
+pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder = EthereumPayloadBuilder> {
     /// The client that can interact with the chain.
     client: Client,
     /// txpool
@@ -74,18 +81,39 @@ pub struct BasicPayloadJobGenerator<Client, Pool, Tasks> {
     payload_task_guard: PayloadTaskGuard,
     /// The chain spec.
     chain_spec: Arc<ChainSpec>,
+    /// The type responsible for building payloads.
+    builder: Builder,
+    /// Provides the fee recipient and gas limit preferences of upcoming proposers, if any.
+    proposer_preferences: Option<Arc<dyn ProposerPreferencesProvider>>,
+    /// Notified about every better payload built by a job, if set.
+    better_payload_tx: Option<UnboundedSender<Arc<BuiltPayload>>>,
 }
 
 // === impl BasicPayloadJobGenerator ===
 
 impl<Client, Pool, Tasks> BasicPayloadJobGenerator<Client, Pool, Tasks> {
-    /// Creates a new [BasicPayloadJobGenerator] with the given config.
+    /// Creates a new [BasicPayloadJobGenerator] with the given config that builds payloads with
+    /// the [EthereumPayloadBuilder].
     pub fn new(
         client: Client,
         pool: Pool,
         executor: Tasks,
         config: BasicPayloadJobGeneratorConfig,
         chain_spec: Arc<ChainSpec>,
+    ) -> Self {
+        Self::with_builder(client, pool, executor, config, chain_spec, EthereumPayloadBuilder)
+    }
+}
+
+impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
+    /// Creates a new [BasicPayloadJobGenerator] with the given config and [PayloadBuilder].
+    pub fn with_builder(
+        client: Client,
+        pool: Pool,
+        executor: Tasks,
+        config: BasicPayloadJobGeneratorConfig,
+        chain_spec: Arc<ChainSpec>,
+        builder: Builder,
     ) -> Self {
         Self {
             client,
@@ -94,25 +122,47 @@ impl<Client, Pool, Tasks> BasicPayloadJobGenerator<Client, Pool, Tasks> {
             payload_task_guard: PayloadTaskGuard::new(config.max_payload_tasks),
             config,
             chain_spec,
+            builder,
+            proposer_preferences: None,
+            better_payload_tx: None,
         }
     }
-}
 
-// === impl BasicPayloadJobGenerator ===
+    /// Sets the provider of proposer preferences.
+    ///
+    /// If the preferences of the proposer of a payload are known, the payload pays its fees to the
+    /// proposer's fee recipient and moves the gas limit towards the proposer's gas limit.
+    pub fn with_proposer_preferences(
+        mut self,
+        proposer_preferences: Arc<dyn ProposerPreferencesProvider>,
+    ) -> Self {
+        self.proposer_preferences = Some(proposer_preferences);
+        self
+    }
 
-impl<Client, Pool, Tasks> BasicPayloadJobGenerator<Client, Pool, Tasks> {}
+    /// Sets the channel that is notified about every better payload built by a job.
+    pub fn with_better_payload_tx(
+        mut self,
+        better_payload_tx: UnboundedSender<Arc<BuiltPayload>>,
+    ) -> Self {
+        self.better_payload_tx = Some(better_payload_tx);
+        self
+    }
+}
 
-impl<Client, Pool, Tasks> PayloadJobGenerator for BasicPayloadJobGenerator<Client, Pool, Tasks>
+impl<Client, Pool, Tasks, Builder> PayloadJobGenerator
+    for BasicPayloadJobGenerator<Client, Pool, Tasks, Builder>
 where
     Client: StateProviderFactory + BlockReaderIdExt + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     Tasks: TaskSpawner + Clone + Unpin + 'static,
+    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
 {
-    type Job = BasicPayloadJob<Client, Pool, Tasks>;
+    type Job = BasicPayloadJob<Client, Pool, Tasks, Builder>;
 
     fn new_payload_job(
         &self,
-        attributes: PayloadBuilderAttributes,
+        mut attributes: PayloadBuilderAttributes,
     ) -> Result<Self::Job, PayloadBuilderError> {
         let parent_block = if attributes.parent.is_zero() {
             // use latest block if parent is zero: genesis block
@@ -130,10 +180,25 @@ where
             block.seal(attributes.parent)
         };
 
+        let preferences = self
+            .proposer_preferences
+            .as_ref()
+            .and_then(|provider| provider.proposer_preferences(attributes.timestamp));
+        if let Some(preferences) = preferences {
+            attributes.suggested_fee_recipient = preferences.fee_recipient;
+        }
+
         // configure evm env based on parent block
-        let (initialized_cfg, initialized_block_env) =
+        let (initialized_cfg, mut initialized_block_env) =
             attributes.cfg_and_block_env(&self.chain_spec, &parent_block);
 
+        if let Some(preferences) = preferences {
+            initialized_block_env.gas_limit = U256::from(calculate_next_block_gas_limit(
+                parent_block.gas_limit,
+                preferences.gas_limit,
+            ));
+        }
+
         let config = PayloadConfig {
             initialized_block_env,
             initialized_cfg,
@@ -158,6 +223,8 @@ where
             cached_reads: None,
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
+            builder: self.builder.clone(),
+            better_payload_tx: self.better_payload_tx.clone(),
         })
     }
 }
@@ -248,7 +315,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
 }
 
 /// A basic payload job that continuously builds a payload with the best transactions from the pool.
-pub struct BasicPayloadJob<Client, Pool, Tasks> {
+pub struct BasicPayloadJob<Client, Pool, Tasks, Builder> {
     /// The configuration for how the payload will be created.
     config: PayloadConfig,
     /// The client that can interact with the chain.
@@ -274,13 +341,18 @@ pub struct BasicPayloadJob<Client, Pool, Tasks> {
     cached_reads: Option<CachedReads>,
     /// metrics for this type
     metrics: PayloadBuilderMetrics,
+    /// The type responsible for building payloads.
+    builder: Builder,
+    /// Notified about every better payload, if set.
+    better_payload_tx: Option<UnboundedSender<Arc<BuiltPayload>>>,
 }
 
-impl<Client, Pool, Tasks> Future for BasicPayloadJob<Client, Pool, Tasks>
+impl<Client, Pool, Tasks, Builder> Future for BasicPayloadJob<Client, Pool, Tasks, Builder>
 where
     Client: StateProviderFactory + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     Tasks: TaskSpawner + Clone + 'static,
+    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
 {
     type Output = Result<(), PayloadBuilderError>;
 
@@ -308,18 +380,19 @@ where
                 let best_payload = this.best_payload.clone();
                 this.metrics.inc_initiated_payload_builds();
                 let cached_reads = this.cached_reads.take().unwrap_or_default();
+                let builder = this.builder.clone();
                 this.executor.spawn_blocking(Box::pin(async move {
                     // acquire the permit for executing the task
                     let _permit = guard.0.acquire().await;
-                    build_payload(
+                    let args = BuildArguments {
                         client,
                         pool,
                         cached_reads,
-                        payload_config,
+                        config: payload_config,
                         cancel,
                         best_payload,
-                        tx,
-                    )
+                    };
+                    let _ = tx.send(builder.try_build(args));
                 }));
                 this.pending_block = Some(PendingPayload { _cancel, payload: rx });
             }
@@ -335,6 +408,9 @@ where
                             this.cached_reads = Some(cached_reads);
                             trace!("built better payload");
                             let payload = Arc::new(payload);
+                            if let Some(tx) = &this.better_payload_tx {
+                                let _ = tx.send(Arc::clone(&payload));
+                            }
                             this.best_payload = Some(payload);
                         }
                         BuildOutcome::Aborted { fees, cached_reads } => {
@@ -361,11 +437,12 @@ where
     }
 }
 
-impl<Client, Pool, Tasks> PayloadJob for BasicPayloadJob<Client, Pool, Tasks>
+impl<Client, Pool, Tasks, Builder> PayloadJob for BasicPayloadJob<Client, Pool, Tasks, Builder>
 where
     Client: StateProviderFactory + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     Tasks: TaskSpawner + Clone + 'static,
+    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
 {
     type ResolvePayloadFuture = ResolveBestPayload;
 
@@ -380,7 +457,7 @@ where
         // away and the first full block should have been built by the time CL is requesting the
         // payload.
         self.metrics.inc_requested_empty_payload();
-        build_empty_payload(&self.client, self.config.clone()).map(Arc::new)
+        self.builder.build_empty_payload(&self.client, self.config.clone()).map(Arc::new)
     }
 
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
@@ -395,8 +472,9 @@ where
             let (tx, rx) = oneshot::channel();
             let client = self.client.clone();
             let config = self.config.clone();
+            let builder = self.builder.clone();
             self.executor.spawn_blocking(Box::pin(async move {
-                let res = build_empty_payload(&client, config);
+                let res = builder.build_empty_payload(&client, config);
                 let _ = tx.send(res);
             }));
 
@@ -482,13 +560,13 @@ impl Future for PendingPayload {
 ///
 /// If dropped, it will set the `cancelled` flag to true.
 #[derive(Default, Clone, Debug)]
-struct Cancelled(Arc<AtomicBool>);
+pub struct Cancelled(Arc<AtomicBool>);
 
 // === impl Cancelled ===
 
 impl Cancelled {
     /// Returns true if the job was cancelled.
-    fn is_cancelled(&self) -> bool {
+    pub fn is_cancelled(&self) -> bool {
         self.0.load(std::sync::atomic::Ordering::Relaxed)
     }
 }
@@ -500,24 +578,25 @@ impl Drop for Cancelled {
 }
 
 /// Static config for how to build a payload.
-#[derive(Clone)]
-struct PayloadConfig {
+#[derive(Clone, Debug)]
+pub struct PayloadConfig {
     /// Pre-configured block environment.
-    initialized_block_env: BlockEnv,
+    pub initialized_block_env: BlockEnv,
     /// Configuration for the environment.
-    initialized_cfg: CfgEnv,
+    pub initialized_cfg: CfgEnv,
     /// The parent block.
-    parent_block: Arc<SealedBlock>,
+    pub parent_block: Arc<SealedBlock>,
     /// Block extra data.
-    extra_data: Bytes,
+    pub extra_data: Bytes,
     /// Requested attributes for the payload.
-    attributes: PayloadBuilderAttributes,
+    pub attributes: PayloadBuilderAttributes,
     /// The chain spec.
-    chain_spec: Arc<ChainSpec>,
+    pub chain_spec: Arc<ChainSpec>,
 }
 
+/// The possible outcomes of a payload building attempt.
 #[derive(Debug)]
-enum BuildOutcome {
+pub enum BuildOutcome {
     /// Successfully built a better block.
     Better {
         /// The new payload that was built.
@@ -527,6 +606,7 @@ enum BuildOutcome {
     },
     /// Aborted payload building because resulted in worse block wrt. fees.
     Aborted {
+        /// The total fees of the aborted payload.
         fees: U256,
         /// The cached reads that were used to build the payload.
         cached_reads: CachedReads,
@@ -535,77 +615,144 @@ enum BuildOutcome {
     Cancelled,
 }
 
-/// Builds the payload and sends the result to the given channel.
-fn build_payload<Pool, Client>(
-    client: Client,
-    pool: Pool,
-    cached_reads: CachedReads,
-    config: PayloadConfig,
-    cancel: Cancelled,
-    best_payload: Option<Arc<BuiltPayload>>,
-    to_job: oneshot::Sender<Result<BuildOutcome, PayloadBuilderError>>,
-) where
-    Client: StateProviderFactory,
-    Pool: TransactionPool,
-{
-    #[inline(always)]
-    fn try_build<Pool, Client>(
-        client: Client,
-        pool: Pool,
-        mut cached_reads: CachedReads,
+/// The arguments of a single payload building attempt, see [PayloadBuilder::try_build].
+#[derive(Debug)]
+pub struct BuildArguments<Pool, Client> {
+    /// How to interact with the chain.
+    pub client: Client,
+    /// The transaction pool.
+    pub pool: Pool,
+    /// Previously cached disk reads of the state the payload builds on.
+    pub cached_reads: CachedReads,
+    /// How to configure the payload.
+    pub config: PayloadConfig,
+    /// A marker that is set once the attempt is no longer needed.
+    pub cancel: Cancelled,
+    /// The best payload built by the job so far.
+    pub best_payload: Option<Arc<BuiltPayload>>,
+}
+
+/// A strategy for building payloads, used by the [BasicPayloadJob].
+///
+/// The job repeatedly invokes [PayloadBuilder::try_build] on a blocking task until the payload is
+/// resolved. Implementations are free to select, order and insert transactions as they see fit,
+/// for example to include bundles or to append a coinbase payment transaction.
+///
+/// If the payload is requested before any attempt finished, the job serves the payload of
+/// [PayloadBuilder::build_empty_payload] instead.
+pub trait PayloadBuilder<Pool, Client>: Send + Sync + Clone {
+    /// Tries to build a payload with the given arguments.
+    ///
+    /// Returns [BuildOutcome::Better] if the payload is better than the
+    /// [BuildArguments::best_payload], [BuildOutcome::Aborted] if it is not, and
+    /// [BuildOutcome::Cancelled] if the [BuildArguments::cancel] marker was set during the
+    /// attempt.
+    fn try_build(
+        &self,
+        args: BuildArguments<Pool, Client>,
+    ) -> Result<BuildOutcome, PayloadBuilderError>;
+
+    /// Builds a payload without any transactions of the pool.
+    ///
+    /// Defaults to [build_empty_payload], which only applies the withdrawals.
+    fn build_empty_payload(
+        &self,
+        client: &Client,
         config: PayloadConfig,
-        cancel: Cancelled,
-        best_payload: Option<Arc<BuiltPayload>>,
-    ) -> Result<BuildOutcome, PayloadBuilderError>
+    ) -> Result<BuiltPayload, PayloadBuilderError>
     where
         Client: StateProviderFactory,
-        Pool: TransactionPool,
     {
-        let PayloadConfig {
-            initialized_block_env,
-            initialized_cfg,
-            parent_block,
-            extra_data,
-            attributes,
-            chain_spec,
-        } = config;
+        build_empty_payload(client, config)
+    }
+}
 
-        debug!(parent_hash=?parent_block.hash, parent_number=parent_block.number, "building new payload");
+/// The default [PayloadBuilder]: greedily fills the block with the best transactions of the pool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EthereumPayloadBuilder;
 
-        let state = State::new(client.state_by_block_hash(parent_block.hash)?);
-        let mut db = CacheDB::new(cached_reads.as_db(&state));
-        let mut post_state = PostState::default();
+impl<Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder
+where
+    Client: StateProviderFactory,
+    Pool: TransactionPool,
+{
+    fn try_build(
+        &self,
+        args: BuildArguments<Pool, Client>,
+    ) -> Result<BuildOutcome, PayloadBuilderError> {
+        default_payload_builder(args)
+    }
+}
 
-        let mut cumulative_gas_used = 0;
-        let block_gas_limit: u64 = initialized_block_env.gas_limit.try_into().unwrap_or(u64::MAX);
+/// Builds a payload by including the pool's bundles that target this block, each atomically, and
+/// then executing the best transactions of the pool in order, until the block is full.
+///
+/// This is the building logic of the [EthereumPayloadBuilder], which custom [PayloadBuilder]s can
+/// fall back to.
+#[inline]
+pub fn default_payload_builder<Pool, Client>(
+    args: BuildArguments<Pool, Client>,
+) -> Result<BuildOutcome, PayloadBuilderError>
+where
+    Client: StateProviderFactory,
+    Pool: TransactionPool,
+{
+    let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
+    let PayloadConfig {
+        initialized_block_env,
+        initialized_cfg,
+        parent_block,
+        extra_data,
+        attributes,
+        chain_spec,
+    } = config;
 
-        let mut executed_txs = Vec::new();
-        let mut best_txs = pool.best_transactions();
+    debug!(parent_hash=?parent_block.hash, parent_number=parent_block.number, "building new payload");
 
-        let mut total_fees = U256::ZERO;
-        let base_fee = initialized_block_env.basefee.to::<u64>();
+    let state = State::new(client.state_by_block_hash(parent_block.hash)?);
+    let mut db = CacheDB::new(cached_reads.as_db(&state));
+    let mut post_state = PostState::default();
 
-        let block_number = initialized_block_env.number.to::<u64>();
+    let mut cumulative_gas_used = 0;
+    let block_gas_limit: u64 = initialized_block_env.gas_limit.try_into().unwrap_or(u64::MAX);
 
-        while let Some(pool_tx) = best_txs.next() {
-            // ensure we still have capacity for this transaction
-            if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
-                // we can't fit this transaction into the block, so we need to mark it as invalid
-                // which also removes all dependent transaction from the iterator before we can
-                // continue
-                best_txs.mark_invalid(&pool_tx);
-                continue
-            }
+    let mut executed_txs = Vec::new();
+    let mut best_txs = pool.best_transactions();
+
+    let mut total_fees = U256::ZERO;
+    let base_fee = initialized_block_env.basefee.to::<u64>();
+
+    let block_number = initialized_block_env.number.to::<u64>();
+
+    // bundles targeting this block are included atomically and in order at the top of the block
+    for bundle in pool.bundles(block_number) {
+        if !bundle.is_valid_at(attributes.timestamp) {
+            continue
+        }
+
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
 
-            // check if the job was cancelled, if so we can exit early
-            if cancel.is_cancelled() {
-                return Ok(BuildOutcome::Cancelled)
+        // the bundle is executed on top of the block's state, recording the cached state it
+        // overwrites so that it can be rolled back if it is not included
+        let mut journal = BundleJournal::default();
+        let mut bundle_post_state = PostState::default();
+        let mut bundle_receipts = Vec::with_capacity(bundle.transactions.len());
+        let mut bundle_gas_used = cumulative_gas_used;
+        let mut bundle_txs = Vec::with_capacity(bundle.transactions.len());
+        let mut bundle_fees = U256::ZERO;
+        let mut included = true;
+
+        for pool_tx in &bundle.transactions {
+            // the whole bundle must fit into the block
+            if bundle_gas_used + pool_tx.gas_limit() > block_gas_limit {
+                included = false;
+                break
             }
 
-            // convert tx to a signed transaction
             let tx = pool_tx.to_recovered_transaction();
-
-            // Configure the environment for the block.
             let env = Env {
                 cfg: initialized_cfg.clone(),
                 block: initialized_block_env.clone(),
@@ -617,119 +764,296 @@ fn build_payload<Pool, Client>(
 
             let ResultAndState { result, state } = match evm.transact() {
                 Ok(res) => res,
+                Err(EVMError::Transaction(err)) => {
+                    trace!(?err, ?tx, bundle=?bundle.hash, "skipping bundle with invalid transaction");
+                    included = false;
+                    break
+                }
                 Err(err) => {
-                    match err {
-                        EVMError::Transaction(err) => {
-                            if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
-                                // if the nonce is too low, we can skip this transaction
-                                trace!(?err, ?tx, "skipping nonce too low transaction");
-                            } else {
-                                // if the transaction is invalid, we can skip it and all of its
-                                // descendants
-                                trace!(
-                                    ?err,
-                                    ?tx,
-                                    "skipping invalid transaction and its descendants"
-                                );
-                                best_txs.mark_invalid(&pool_tx);
-                            }
-                            continue
-                        }
-                        err => {
-                            // this is an error that we should treat as fatal for this attempt
-                            return Err(PayloadBuilderError::EvmExecutionError(err))
-                        }
-                    }
+                    // this is an error that we should treat as fatal for this attempt
+                    return Err(PayloadBuilderError::EvmExecutionError(err))
                 }
             };
 
-            let gas_used = result.gas_used();
-
-            // commit changes
-            commit_state_changes(&mut db, &mut post_state, block_number, state, true);
-
-            // add gas used by the transaction to cumulative gas used, before creating the receipt
-            cumulative_gas_used += gas_used;
+            // a reverting transaction drops the bundle, unless it is allowed to revert
+            if !result.is_success() && !bundle.may_revert(pool_tx.hash()) {
+                trace!(?tx, bundle=?bundle.hash, "skipping bundle with reverting transaction");
+                included = false;
+                break
+            }
 
-            // Push transaction changeset and calculate header bloom filter for receipt.
-            post_state.add_receipt(
-                block_number,
-                Receipt {
-                    tx_type: tx.tx_type(),
-                    success: result.is_success(),
-                    cumulative_gas_used,
-                    logs: result.logs().into_iter().map(into_reth_log).collect(),
-                },
-            );
+            let gas_used = result.gas_used();
+            journal.record(&db, &state);
+            commit_state_changes(&mut db, &mut bundle_post_state, block_number, state, true);
+            bundle_gas_used += gas_used;
+            bundle_receipts.push(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used: bundle_gas_used,
+                logs: result.logs().into_iter().map(into_reth_log).collect(),
+            });
 
-            // update add to total fees
             let miner_fee = tx
                 .effective_tip_per_gas(base_fee)
                 .expect("fee is always valid; execution succeeded");
-            total_fees += U256::from(miner_fee) * U256::from(gas_used);
+            bundle_fees += U256::from(miner_fee) * U256::from(gas_used);
+            bundle_txs.push(tx.into_signed());
+        }
+
+        if included {
+            post_state.extend(bundle_post_state);
+            for receipt in bundle_receipts {
+                post_state.add_receipt(block_number, receipt);
+            }
+            cumulative_gas_used = bundle_gas_used;
+            total_fees += bundle_fees;
+            executed_txs.extend(bundle_txs);
+        } else {
+            journal.rollback(&mut db);
+        }
+    }
 
-            // append transaction to the list of executed transactions
-            executed_txs.push(tx.into_signed());
+    while let Some(pool_tx) = best_txs.next() {
+        // ensure we still have capacity for this transaction
+        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+            // we can't fit this transaction into the block, so we need to mark it as invalid
+            // which also removes all dependent transaction from the iterator before we can
+            // continue
+            best_txs.mark_invalid(&pool_tx);
+            continue
         }
 
-        // check if we have a better block
-        if !is_better_payload(best_payload.as_deref(), total_fees) {
-            // can skip building the block
-            return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
         }
 
-        let WithdrawalsOutcome { withdrawals_root, withdrawals } = commit_withdrawals(
-            &mut db,
-            &mut post_state,
-            &chain_spec,
+        // convert tx to a signed transaction
+        let tx = pool_tx.to_recovered_transaction();
+
+        // Configure the environment for the block.
+        let env = Env {
+            cfg: initialized_cfg.clone(),
+            block: initialized_block_env.clone(),
+            tx: tx_env_with_recovered(&tx),
+        };
+
+        let mut evm = revm::EVM::with_env(env);
+        evm.database(&mut db);
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(err) => {
+                match err {
+                    EVMError::Transaction(err) => {
+                        if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
+                            // if the nonce is too low, we can skip this transaction
+                            trace!(?err, ?tx, "skipping nonce too low transaction");
+                        } else {
+                            // if the transaction is invalid, we can skip it and all of its
+                            // descendants
+                            trace!(?err, ?tx, "skipping invalid transaction and its descendants");
+                            best_txs.mark_invalid(&pool_tx);
+                        }
+                        continue
+                    }
+                    err => {
+                        // this is an error that we should treat as fatal for this attempt
+                        return Err(PayloadBuilderError::EvmExecutionError(err))
+                    }
+                }
+            }
+        };
+
+        let gas_used = result.gas_used();
+
+        // commit changes
+        commit_state_changes(&mut db, &mut post_state, block_number, state, true);
+
+        // add gas used by the transaction to cumulative gas used, before creating the receipt
+        cumulative_gas_used += gas_used;
+
+        // Push transaction changeset and calculate header bloom filter for receipt.
+        post_state.add_receipt(
             block_number,
-            attributes.timestamp,
-            attributes.withdrawals,
-        )?;
+            Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used,
+                logs: result.logs().into_iter().map(into_reth_log).collect(),
+            },
+        );
+
+        // update add to total fees
+        let miner_fee =
+            tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
+        total_fees += U256::from(miner_fee) * U256::from(gas_used);
+
+        // append transaction to the list of executed transactions
+        executed_txs.push(tx.into_signed());
+    }
 
-        let receipts_root = post_state.receipts_root(block_number);
-        let logs_bloom = post_state.logs_bloom(block_number);
+    // check if we have a better block
+    if !is_better_payload(best_payload.as_deref(), total_fees) {
+        // can skip building the block
+        return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
+    }
 
-        // calculate the state root
-        let state_root = state.state().state_root(post_state)?;
+    let WithdrawalsOutcome { withdrawals_root, withdrawals } = commit_withdrawals(
+        &mut db,
+        &mut post_state,
+        &chain_spec,
+        block_number,
+        attributes.timestamp,
+        attributes.withdrawals,
+    )?;
 
-        // create the block header
-        let transactions_root = proofs::calculate_transaction_root(&executed_txs);
+    let receipts_root = post_state.receipts_root(block_number);
+    let logs_bloom = post_state.logs_bloom(block_number);
 
-        let header = Header {
-            parent_hash: parent_block.hash,
-            ommers_hash: EMPTY_OMMER_ROOT,
-            beneficiary: initialized_block_env.coinbase,
-            state_root,
-            transactions_root,
-            receipts_root,
-            withdrawals_root,
-            logs_bloom,
-            timestamp: attributes.timestamp,
-            mix_hash: attributes.prev_randao,
-            nonce: BEACON_NONCE,
-            base_fee_per_gas: Some(base_fee),
-            number: parent_block.number + 1,
-            gas_limit: block_gas_limit,
-            difficulty: U256::ZERO,
-            gas_used: cumulative_gas_used,
-            extra_data: extra_data.into(),
-        };
+    // calculate the state root
+    let state_root = state.state().state_root(post_state)?;
 
-        // seal the block
-        let block = Block { header, body: executed_txs, ommers: vec![], withdrawals };
+    // create the block header
+    let transactions_root = proofs::calculate_transaction_root(&executed_txs);
 
-        let sealed_block = block.seal_slow();
-        Ok(BuildOutcome::Better {
-            payload: BuiltPayload::new(attributes.id, sealed_block, total_fees),
-            cached_reads,
-        })
+    let header = Header {
+        parent_hash: parent_block.hash,
+        ommers_hash: EMPTY_OMMER_ROOT,
+        beneficiary: initialized_block_env.coinbase,
+        state_root,
+        transactions_root,
+        receipts_root,
+        withdrawals_root,
+        logs_bloom,
+        timestamp: attributes.timestamp,
+        mix_hash: attributes.prev_randao,
+        nonce: BEACON_NONCE,
+        base_fee_per_gas: Some(base_fee),
+        number: parent_block.number + 1,
+        gas_limit: block_gas_limit,
+        difficulty: U256::ZERO,
+        gas_used: cumulative_gas_used,
+        extra_data: extra_data.into(),
+    };
+
+    // seal the block
+    let block = Block { header, body: executed_txs, ommers: vec![], withdrawals };
+
+    let sealed_block = block.seal_slow();
+    Ok(BuildOutcome::Better {
+        payload: BuiltPayload::new(attributes.id, sealed_block, total_fees),
+        cached_reads,
+    })
+}
+
+/// Records the cached state that the transactions of a bundle overwrite in the runtime
+/// database, so that the bundle can be rolled back if it is not included.
+///
+/// Only the accounts and storage slots the bundle changes are recorded, the whole storage of an
+/// account is only recorded if the bundle clears it.
+#[derive(Debug, Default)]
+struct BundleJournal {
+    /// The cached accounts before the bundle changed them, `None` if they were not cached.
+    accounts: HashMap<Address, Option<AccountPreImage>>,
+    /// The bytecode the bundle added to the cache.
+    contracts: Vec<H256>,
+}
+
+/// The cached state of an account before a bundle changed it.
+#[derive(Debug)]
+struct AccountPreImage {
+    /// The cached account, without its storage.
+    account: DbAccount,
+    /// The cached values of the slots the bundle changed, `None` if they were not cached.
+    slots: HashMap<U256, Option<U256>>,
+    /// The whole cached storage, if the bundle cleared it.
+    storage: Option<HashMap<U256, U256>>,
+}
+
+impl BundleJournal {
+    /// Records the cached state in `db` that committing `changes` is about to overwrite.
+    ///
+    /// Must be called before the changes are committed.
+    fn record<DB>(&mut self, db: &CacheDB<DB>, changes: &HashMap<Address, RevmAccount>)
+    where
+        DB: DatabaseRef,
+    {
+        for (address, account) in changes {
+            if let Some(ref code) = account.info.code {
+                if !code.is_empty() && !db.contracts.contains_key(&account.info.code_hash) {
+                    self.contracts.push(account.info.code_hash);
+                }
+            }
+
+            let cached = db.accounts.get(address);
+            let pre_image = self.accounts.entry(*address).or_insert_with(|| {
+                cached.map(|cached| AccountPreImage {
+                    account: DbAccount {
+                        info: cached.info.clone(),
+                        account_state: cached.account_state.clone(),
+                        ..Default::default()
+                    },
+                    slots: Default::default(),
+                    storage: None,
+                })
+            });
+            let (Some(pre_image), Some(cached)) = (pre_image, cached) else { continue };
+
+            // once the whole storage is recorded, later changes of the bundle are covered by it
+            if pre_image.storage.is_some() {
+                continue
+            }
+            if account.is_destroyed || account.storage_cleared {
+                pre_image.storage = Some(cached.storage.clone());
+            } else {
+                for slot in account.storage.keys() {
+                    pre_image
+                        .slots
+                        .entry(*slot)
+                        .or_insert_with(|| cached.storage.get(slot).copied());
+                }
+            }
+        }
+    }
+
+    /// Restores the recorded state in `db`.
+    fn rollback<DB>(self, db: &mut CacheDB<DB>)
+    where
+        DB: DatabaseRef,
+    {
+        for (address, pre_image) in self.accounts {
+            let Some(AccountPreImage { account, slots, storage }) = pre_image else {
+                db.accounts.remove(&address);
+                continue
+            };
+
+            let cached = db.accounts.entry(address).or_default();
+            cached.info = account.info;
+            cached.account_state = account.account_state;
+            if let Some(storage) = storage {
+                cached.storage = storage;
+            }
+            // the slots were recorded before the storage was cleared, if at all
+            for (slot, value) in slots {
+                match value {
+                    Some(value) => cached.storage.insert(slot, value),
+                    None => cached.storage.remove(&slot),
+                };
+            }
+        }
+
+        for code_hash in self.contracts {
+            db.contracts.remove(&code_hash);
+        }
     }
-    let _ = to_job.send(try_build(client, pool, cached_reads, config, cancel, best_payload));
 }
 
 /// Builds an empty payload without any transactions.
-fn build_empty_payload<Client>(
+///
+/// This is the default of [PayloadBuilder::build_empty_payload], which custom [PayloadBuilder]s
+/// can fall back to.
+pub fn build_empty_payload<Client>(
     client: &Client,
     config: PayloadConfig,
 ) -> Result<BuiltPayload, PayloadBuilderError>
@@ -795,18 +1119,22 @@ where
 
 /// Represents the outcome of committing withdrawals to the runtime database and post state.
 /// Pre-shanghai these are `None` values.
-struct WithdrawalsOutcome {
-    withdrawals: Option<Vec<Withdrawal>>,
-    withdrawals_root: Option<H256>,
+#[derive(Debug)]
+pub struct WithdrawalsOutcome {
+    /// The withdrawals of the block.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The withdrawals root of the block.
+    pub withdrawals_root: Option<H256>,
 }
 
 impl WithdrawalsOutcome {
     /// No withdrawals pre shanghai
-    fn pre_shanghai() -> Self {
+    pub fn pre_shanghai() -> Self {
         Self { withdrawals: None, withdrawals_root: None }
     }
 
-    fn empty() -> Self {
+    /// No withdrawals post shanghai
+    pub fn empty() -> Self {
         Self { withdrawals: Some(vec![]), withdrawals_root: Some(EMPTY_WITHDRAWALS) }
     }
 }
@@ -817,7 +1145,7 @@ impl WithdrawalsOutcome {
 ///
 /// Returns `None` values pre shanghai
 #[allow(clippy::too_many_arguments)]
-fn commit_withdrawals<DB>(
+pub fn commit_withdrawals<DB>(
     db: &mut CacheDB<DB>,
     post_state: &mut PostState,
     chain_spec: &ChainSpec,
@@ -856,10 +1184,190 @@ where
 ///
 /// This compares the total fees of the blocks, higher is better.
 #[inline(always)]
-fn is_better_payload(best_payload: Option<&BuiltPayload>, new_fees: U256) -> bool {
+pub fn is_better_payload(best_payload: Option<&BuiltPayload>, new_fees: U256) -> bool {
     if let Some(best_payload) = best_payload {
         new_fees > best_payload.fees()
     } else {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_payload_builder::PayloadId;
+    use reth_primitives::MAINNET;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use revm::{db::EmptyDB, primitives::AccountInfo};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A [PayloadBuilder] that builds blocks without executing anything and counts its attempts.
+    #[derive(Debug, Clone, Default)]
+    struct StubBuilder {
+        attempts: Arc<AtomicUsize>,
+    }
+
+    impl StubBuilder {
+        /// The fees of every payload returned by [PayloadBuilder::try_build].
+        const FEES: u64 = 42;
+        /// The fees of every payload returned by [PayloadBuilder::build_empty_payload].
+        const EMPTY_FEES: u64 = 7;
+    }
+
+    impl<Pool, Client> PayloadBuilder<Pool, Client> for StubBuilder {
+        fn try_build(
+            &self,
+            args: BuildArguments<Pool, Client>,
+        ) -> Result<BuildOutcome, PayloadBuilderError> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            Ok(BuildOutcome::Better {
+                payload: stub_payload(&args.config, StubBuilder::FEES),
+                cached_reads: args.cached_reads,
+            })
+        }
+
+        fn build_empty_payload(
+            &self,
+            _client: &Client,
+            config: PayloadConfig,
+        ) -> Result<BuiltPayload, PayloadBuilderError>
+        where
+            Client: StateProviderFactory,
+        {
+            Ok(stub_payload(&config, StubBuilder::EMPTY_FEES))
+        }
+    }
+
+    fn stub_payload(config: &PayloadConfig, fees: u64) -> BuiltPayload {
+        let header = Header {
+            parent_hash: config.parent_block.hash,
+            number: config.parent_block.number + 1,
+            timestamp: config.attributes.timestamp,
+            ..Default::default()
+        };
+        let block = Block { header, ..Default::default() };
+        BuiltPayload::new(config.attributes.id, block.seal_slow(), U256::from(fees))
+    }
+
+    fn stub_job(
+        builder: StubBuilder,
+        config: BasicPayloadJobGeneratorConfig,
+    ) -> BasicPayloadJob<MockEthProvider, TestPool, TokioTaskExecutor, StubBuilder> {
+        let client = MockEthProvider::default();
+        let parent = Block {
+            header: Header {
+                number: 17_034_870,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                base_fee_per_gas: Some(7),
+                timestamp: 1_681_338_455,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let parent_hash = parent.header.hash_slow();
+        client.add_block(parent_hash, parent);
+
+        let generator = BasicPayloadJobGenerator::with_builder(
+            client,
+            testing_pool(),
+            TokioTaskExecutor::default(),
+            config,
+            MAINNET.clone(),
+            builder,
+        );
+        let attributes = PayloadBuilderAttributes {
+            id: PayloadId::new([1; 8]),
+            parent: parent_hash,
+            timestamp: 1_681_338_467,
+            suggested_fee_recipient: Default::default(),
+            prev_randao: Default::default(),
+            withdrawals: vec![],
+        };
+        generator.new_payload_job(attributes).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn job_builds_with_custom_builder() {
+        let builder = StubBuilder::default();
+        let config = BasicPayloadJobGeneratorConfig::default()
+            .interval(Duration::from_millis(10))
+            .deadline(Duration::from_millis(200));
+        let mut job = stub_job(builder.clone(), config);
+
+        (&mut job).await.unwrap();
+
+        assert!(builder.attempts.load(Ordering::SeqCst) > 0);
+        let payload = job.best_payload().unwrap();
+        assert_eq!(payload.fees(), U256::from(StubBuilder::FEES));
+        assert_eq!(payload.block().number, 17_034_871);
+
+        let (resolve, _) = job.resolve();
+        assert_eq!(resolve.await.unwrap().fees(), U256::from(StubBuilder::FEES));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn job_builds_empty_payload_with_custom_builder() {
+        let builder = StubBuilder::default();
+        let mut job = stub_job(builder.clone(), BasicPayloadJobGeneratorConfig::default());
+
+        // the job was never polled, so no attempt was made yet
+        assert_eq!(builder.attempts.load(Ordering::SeqCst), 0);
+        assert_eq!(job.best_payload().unwrap().fees(), U256::from(StubBuilder::EMPTY_FEES));
+
+        let (resolve, _) = job.resolve();
+        assert_eq!(resolve.await.unwrap().fees(), U256::from(StubBuilder::EMPTY_FEES));
+        assert_eq!(builder.attempts.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn bundle_journal_rolls_back_changes() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        let existing = Address::from_low_u64_be(1);
+        db.insert_account_info(
+            existing,
+            AccountInfo { balance: U256::from(1), ..Default::default() },
+        );
+        db.insert_account_storage(existing, U256::from(1), U256::from(2)).unwrap();
+        let created = Address::from_low_u64_be(2);
+
+        let touched = RevmAccount {
+            info: AccountInfo::default(),
+            storage: HashMap::default(),
+            is_destroyed: false,
+            is_touched: true,
+            storage_cleared: false,
+            is_not_existing: false,
+        };
+        let changes = HashMap::from([
+            (
+                existing,
+                RevmAccount {
+                    info: AccountInfo { balance: U256::from(2), ..Default::default() },
+                    storage_cleared: true,
+                    ..touched.clone()
+                },
+            ),
+            (
+                created,
+                RevmAccount {
+                    info: AccountInfo { balance: U256::from(3), ..Default::default() },
+                    ..touched
+                },
+            ),
+        ]);
+
+        let mut journal = BundleJournal::default();
+        journal.record(&db, &changes);
+        commit_state_changes(&mut db, &mut PostState::default(), 1, changes, true);
+        assert_eq!(db.accounts[&existing].info.balance, U256::from(2));
+        assert!(db.accounts[&existing].storage.is_empty());
+        assert_eq!(db.accounts[&created].info.balance, U256::from(3));
+
+        journal.rollback(&mut db);
+        assert_eq!(db.accounts[&existing].info.balance, U256::from(1));
+        assert_eq!(db.accounts[&existing].storage.get(&U256::from(1)), Some(&U256::from(2)));
+        assert!(!db.accounts.contains_key(&created));
+    }
+}
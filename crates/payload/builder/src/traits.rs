@@ -1,6 +1,7 @@
 //! Trait abstractions used by the payload crate.
 
 use crate::{error::PayloadBuilderError, BuiltPayload, PayloadBuilderAttributes};
+use reth_primitives::Address;
 use std::{future::Future, sync::Arc};
 
 /// A type that can build a payload.
@@ -73,3 +74,18 @@ pub trait PayloadJobGenerator: Send + Sync {
         attr: PayloadBuilderAttributes,
     ) -> Result<Self::Job, PayloadBuilderError>;
 }
+
+/// The preferences of the proposer a payload is built for, for example as registered with a relay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposerPreferences {
+    /// The address that should receive the fees of the payload.
+    pub fee_recipient: Address,
+    /// The gas limit the proposer wants the chain to converge to.
+    pub gas_limit: u64,
+}
+
+/// A type that knows the preferences of upcoming proposers.
+pub trait ProposerPreferencesProvider: Send + Sync {
+    /// Returns the preferences of the proposer of the payload with the given timestamp, if known.
+    fn proposer_preferences(&self, timestamp: u64) -> Option<ProposerPreferences>;
+}
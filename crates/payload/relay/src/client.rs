@@ -0,0 +1,69 @@
+//! HTTP client of the relay's builder API.
+
+use crate::RelayError;
+use reth_rpc_types::relay::{SignedBidSubmission, Validator};
+use std::time::Duration;
+use url::Url;
+
+/// The endpoint that returns the registrations of the proposers of the current and next epoch.
+const VALIDATORS_PATH: &str = "relay/v1/builder/validators";
+
+/// The endpoint that accepts block submissions.
+const BLOCKS_PATH: &str = "relay/v1/builder/blocks";
+
+/// A client of a relay's builder API.
+#[derive(Debug, Clone)]
+pub struct RelayClient {
+    /// The url of the relay.
+    url: Url,
+    /// The underlying http client.
+    client: reqwest::Client,
+}
+
+// === impl RelayClient ===
+
+impl RelayClient {
+    /// Creates a new client for the relay at the given url.
+    ///
+    /// Requests fail if the relay did not respond within the given timeout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TLS backend cannot be initialized, like [reqwest::Client::new].
+    pub fn new(url: Url, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed to initialize the http client");
+        Self { url, client }
+    }
+
+    /// Returns the url of the relay.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the registrations of the proposers of the current and next epoch.
+    pub async fn get_validators(&self) -> Result<Vec<Validator>, RelayError> {
+        let response = self.client.get(self.url.join(VALIDATORS_PATH)?).send().await?;
+        Ok(ensure_success(response).await?.json().await?)
+    }
+
+    /// Submits the given block to the relay.
+    pub async fn submit_block(&self, submission: &SignedBidSubmission) -> Result<(), RelayError> {
+        let response =
+            self.client.post(self.url.join(BLOCKS_PATH)?).json(submission).send().await?;
+        ensure_success(response).await?;
+        Ok(())
+    }
+}
+
+/// Returns an error if the relay did not respond with a success status.
+async fn ensure_success(response: reqwest::Response) -> Result<reqwest::Response, RelayError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response)
+    }
+    let message = response.text().await.unwrap_or_default();
+    Err(RelayError::UnexpectedStatus { status: status.as_u16(), message })
+}
@@ -0,0 +1,100 @@
+//! Configuration of the relay submission.
+
+use reth_primitives::Chain;
+use std::time::Duration;
+use url::Url;
+
+/// The parameters of the beacon chain that are required to sign bids for a relay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconChainConfig {
+    /// The timestamp of the beacon chain's genesis.
+    pub genesis_time: u64,
+    /// The fork version of the beacon chain's genesis.
+    pub genesis_fork_version: [u8; 4],
+    /// The duration of a slot, in seconds.
+    pub seconds_per_slot: u64,
+}
+
+// === impl BeaconChainConfig ===
+
+impl BeaconChainConfig {
+    /// The beacon chain of mainnet.
+    pub const fn mainnet() -> Self {
+        Self { genesis_time: 1606824023, genesis_fork_version: [0, 0, 0, 0], seconds_per_slot: 12 }
+    }
+
+    /// The beacon chain of goerli.
+    pub const fn goerli() -> Self {
+        Self {
+            genesis_time: 1616508000,
+            genesis_fork_version: [0x00, 0x00, 0x10, 0x20],
+            seconds_per_slot: 12,
+        }
+    }
+
+    /// The beacon chain of sepolia.
+    pub const fn sepolia() -> Self {
+        Self {
+            genesis_time: 1655733600,
+            genesis_fork_version: [0x90, 0x00, 0x00, 0x69],
+            seconds_per_slot: 12,
+        }
+    }
+
+    /// Returns the beacon chain of the given execution chain, if known.
+    pub fn from_chain(chain: Chain) -> Option<Self> {
+        match chain.id() {
+            1 => Some(Self::mainnet()),
+            5 => Some(Self::goerli()),
+            11155111 => Some(Self::sepolia()),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot of the block with the given timestamp.
+    ///
+    /// Returns `None` if the timestamp is before the genesis of the beacon chain.
+    pub fn slot_at(&self, timestamp: u64) -> Option<u64> {
+        Some(timestamp.checked_sub(self.genesis_time)? / self.seconds_per_slot)
+    }
+}
+
+/// Settings for the [RelaySubmissionService](crate::RelaySubmissionService).
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// The urls of the relays to submit payloads to.
+    pub relays: Vec<Url>,
+    /// The parameters of the beacon chain.
+    pub beacon: BeaconChainConfig,
+    /// The interval at which the registrations of upcoming proposers are fetched.
+    pub registrations_interval: Duration,
+    /// How long to wait for a relay to respond to a request.
+    pub request_timeout: Duration,
+}
+
+// === impl RelayConfig ===
+
+impl RelayConfig {
+    /// Creates a new config for the given relays.
+    pub fn new(relays: Vec<Url>, beacon: BeaconChainConfig) -> Self {
+        Self {
+            relays,
+            beacon,
+            registrations_interval: Duration::from_secs(beacon.seconds_per_slot),
+            // a bid is worthless once the slot it was built for started
+            request_timeout: Duration::from_secs(beacon.seconds_per_slot),
+        }
+    }
+
+    /// Sets the interval at which the registrations of upcoming proposers are fetched.
+    pub fn registrations_interval(mut self, interval: Duration) -> Self {
+        self.registrations_interval = interval;
+        self
+    }
+
+    /// Sets how long to wait for a relay to respond to a request.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+}
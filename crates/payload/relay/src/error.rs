@@ -0,0 +1,23 @@
+//! Error types emitted by the relay client.
+
+/// Errors that can occur when interacting with a relay.
+#[derive(Debug, thiserror::Error)]
+pub enum RelayError {
+    /// The request to the relay failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The url of the relay is invalid.
+    #[error("invalid relay url: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    /// The relay responded with an error.
+    #[error("relay responded with status {status}: {message}")]
+    UnexpectedStatus {
+        /// The status code of the response.
+        status: u16,
+        /// The body of the response.
+        message: String,
+    },
+    /// The builder secret key is invalid.
+    #[error("invalid builder secret key")]
+    InvalidSecretKey,
+}
@@ -0,0 +1,41 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxzy/reth/issues/"
+)]
+#![warn(missing_docs, unreachable_pub)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! Support for the MEV-Boost builder API.
+//!
+//! In builder mode, the node submits every better payload built by a payload job to the configured
+//! relays:
+//!   - The [RelaySubmissionService] fetches the registrations of upcoming proposers from the
+//!     relays, see [ProposerRegistrations].
+//!   - The registrations are used by the payload job generator to pay the fees of the payload to
+//!     the proposer's fee recipient and to target the proposer's gas limit.
+//!   - Every better payload of a registered proposer is signed with the builder's BLS key, see
+//!     [BuilderSigner], and submitted to all relays.
+
+mod client;
+mod config;
+mod error;
+mod metrics;
+mod registrations;
+mod service;
+mod signer;
+
+pub use client::RelayClient;
+pub use config::{BeaconChainConfig, RelayConfig};
+pub use error::RelayError;
+pub use registrations::ProposerRegistrations;
+pub use service::RelaySubmissionService;
+pub use signer::{
+    bid_trace_root, compute_builder_domain, compute_signing_root, verify_bid_signature,
+    BuilderSigner,
+};
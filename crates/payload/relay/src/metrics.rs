@@ -0,0 +1,22 @@
+//! Relay submission metrics.
+
+use reth_metrics::{
+    metrics::{self, Counter, Gauge},
+    Metrics,
+};
+
+/// Relay submission service metrics
+#[derive(Metrics)]
+#[metrics(scope = "payloads.relay")]
+pub(crate) struct RelaySubmissionMetrics {
+    /// Total number of payloads submitted to relays
+    pub(crate) submitted_payloads: Counter,
+    /// Total number of failed submissions
+    pub(crate) failed_submissions: Counter,
+    /// Total number of payloads that were not submitted because the proposer is not registered
+    pub(crate) skipped_payloads: Counter,
+    /// Total number of failed requests for the registrations of upcoming proposers
+    pub(crate) failed_registration_requests: Counter,
+    /// Number of known registrations of upcoming proposers
+    pub(crate) registrations: Gauge,
+}
@@ -0,0 +1,71 @@
+//! Registrations of upcoming proposers.
+
+use crate::BeaconChainConfig;
+use parking_lot::RwLock;
+use reth_payload_builder::{ProposerPreferences, ProposerPreferencesProvider};
+use reth_rpc_types::relay::{Validator, ValidatorRegistration};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The registrations of upcoming proposers, as reported by the relays.
+///
+/// This type is cheap to clone and shared between the
+/// [RelaySubmissionService](crate::RelaySubmissionService), which keeps it up to date, and the
+/// payload job generator, which uses it as [ProposerPreferencesProvider].
+#[derive(Debug, Clone)]
+pub struct ProposerRegistrations {
+    /// The parameters of the beacon chain, used to map timestamps to slots.
+    beacon: BeaconChainConfig,
+    /// The registrations by slot.
+    registrations: Arc<RwLock<BTreeMap<u64, ValidatorRegistration>>>,
+}
+
+// === impl ProposerRegistrations ===
+
+impl ProposerRegistrations {
+    /// Creates an empty set of registrations for the given beacon chain.
+    pub fn new(beacon: BeaconChainConfig) -> Self {
+        Self { beacon, registrations: Default::default() }
+    }
+
+    /// Returns the registration of the proposer of the given slot.
+    pub fn get(&self, slot: u64) -> Option<ValidatorRegistration> {
+        self.registrations.read().get(&slot).cloned()
+    }
+
+    /// Returns the registration of the proposer of the block with the given timestamp.
+    pub fn get_by_timestamp(&self, timestamp: u64) -> Option<ValidatorRegistration> {
+        self.get(self.beacon.slot_at(timestamp)?)
+    }
+
+    /// Returns the number of known registrations.
+    pub fn len(&self) -> usize {
+        self.registrations.read().len()
+    }
+
+    /// Returns `true` if there are no known registrations.
+    pub fn is_empty(&self) -> bool {
+        self.registrations.read().is_empty()
+    }
+
+    /// Inserts the given registrations, replacing existing registrations of the same slot.
+    pub fn extend(&self, validators: impl IntoIterator<Item = Validator>) {
+        self.registrations.write().extend(
+            validators.into_iter().map(|validator| (validator.slot, validator.entry.message)),
+        );
+    }
+
+    /// Removes all registrations of slots before the given slot.
+    pub fn prune_before(&self, slot: u64) {
+        let mut registrations = self.registrations.write();
+        *registrations = registrations.split_off(&slot);
+    }
+}
+
+impl ProposerPreferencesProvider for ProposerRegistrations {
+    fn proposer_preferences(&self, timestamp: u64) -> Option<ProposerPreferences> {
+        self.get_by_timestamp(timestamp).map(|registration| ProposerPreferences {
+            fee_recipient: registration.fee_recipient,
+            gas_limit: registration.gas_limit,
+        })
+    }
+}
@@ -0,0 +1,216 @@
+//! Submission of built payloads to the relays.
+
+use crate::{
+    metrics::RelaySubmissionMetrics, BeaconChainConfig, BuilderSigner, ProposerRegistrations,
+    RelayClient, RelayConfig, RelayError,
+};
+use futures_util::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use reth_payload_builder::BuiltPayload;
+use reth_rpc_types::relay::{BidTrace, SignedBidSubmission, Validator};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{sync::mpsc::UnboundedReceiver, time::Interval};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace, warn};
+use url::Url;
+
+/// A service that submits the better payloads of the payload jobs to the relays.
+///
+/// The service periodically fetches the registrations of upcoming proposers from all relays. Every
+/// payload it receives that pays a registered proposer is signed and submitted to all relays.
+///
+/// The service resolves once the channel of payloads is closed.
+#[must_use = "Future does nothing unless polled"]
+pub struct RelaySubmissionService {
+    /// The relays to submit payloads to.
+    relays: Vec<RelayClient>,
+    /// The parameters of the beacon chain.
+    beacon: BeaconChainConfig,
+    /// Signs the bids of the payloads.
+    signer: Arc<BuilderSigner>,
+    /// The known registrations of upcoming proposers.
+    registrations: ProposerRegistrations,
+    /// The better payloads of the payload jobs.
+    payloads: UnboundedReceiverStream<Arc<BuiltPayload>>,
+    /// Interval at which the registrations are fetched.
+    registrations_interval: Interval,
+    /// In-flight requests to the relays.
+    requests: FuturesUnordered<BoxFuture<'static, RelayResponse>>,
+    /// Metrics of the service.
+    metrics: RelaySubmissionMetrics,
+}
+
+// === impl RelaySubmissionService ===
+
+impl RelaySubmissionService {
+    /// Creates a new service that submits the payloads it receives on the given channel.
+    ///
+    /// Note: this must be called from within a tokio runtime.
+    pub fn new(
+        config: RelayConfig,
+        signer: BuilderSigner,
+        payloads: UnboundedReceiver<Arc<BuiltPayload>>,
+    ) -> Self {
+        let RelayConfig { relays, beacon, registrations_interval, request_timeout } = config;
+        Self {
+            relays: relays.into_iter().map(|url| RelayClient::new(url, request_timeout)).collect(),
+            beacon,
+            signer: Arc::new(signer),
+            registrations: ProposerRegistrations::new(beacon),
+            payloads: UnboundedReceiverStream::new(payloads),
+            registrations_interval: tokio::time::interval(registrations_interval),
+            requests: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the registrations of upcoming proposers, which are kept up to date by this service.
+    ///
+    /// These should be installed as the proposer preferences of the payload job generator.
+    pub fn registrations(&self) -> ProposerRegistrations {
+        self.registrations.clone()
+    }
+
+    /// Requests the registrations of upcoming proposers from all relays.
+    fn fetch_registrations(&mut self) {
+        for relay in self.relays.iter().cloned() {
+            self.requests.push(
+                async move { RelayResponse::Validators(relay.get_validators().await) }.boxed(),
+            );
+        }
+    }
+
+    /// Signs the given payload and submits it to all relays.
+    fn submit_payload(&mut self, payload: &BuiltPayload) {
+        let Some(submission) = self.bid_submission(payload) else {
+            trace!(target: "payload_builder::relay", id=%payload.id(), "skipping payload of unregistered proposer");
+            self.metrics.skipped_payloads.increment(1);
+            return
+        };
+
+        debug!(target: "payload_builder::relay", id=%payload.id(), slot=submission.message.slot, value=%submission.message.value, "submitting payload");
+        let submission = Arc::new(submission);
+        for relay in self.relays.iter().cloned() {
+            let submission = Arc::clone(&submission);
+            self.requests.push(
+                async move {
+                    let result = relay.submit_block(&submission).await;
+                    RelayResponse::Submission(relay.url().clone(), result)
+                }
+                .boxed(),
+            );
+        }
+    }
+
+    /// Returns the signed submission of the given payload.
+    ///
+    /// Returns `None` if the proposer of the payload's slot is not registered or if the payload
+    /// does not pay the registered fee recipient.
+    fn bid_submission(&self, payload: &BuiltPayload) -> Option<SignedBidSubmission> {
+        let block = payload.block();
+        let slot = self.beacon.slot_at(block.timestamp)?;
+        let registration = self.registrations.get(slot)?;
+        if block.beneficiary != registration.fee_recipient {
+            return None
+        }
+
+        let message = BidTrace {
+            slot,
+            parent_hash: block.parent_hash,
+            block_hash: block.hash(),
+            builder_pubkey: self.signer.public_key(),
+            proposer_pubkey: registration.pubkey,
+            proposer_fee_recipient: registration.fee_recipient,
+            gas_limit: block.gas_limit,
+            gas_used: block.gas_used,
+            value: payload.fees(),
+        };
+        let signature = self.signer.sign_bid(&message);
+        Some(SignedBidSubmission { message, execution_payload: block.clone().into(), signature })
+    }
+
+    /// Handles the response of a relay.
+    fn on_response(&mut self, response: RelayResponse) {
+        match response {
+            RelayResponse::Validators(Ok(validators)) => {
+                self.registrations.extend(validators);
+            }
+            RelayResponse::Validators(Err(err)) => {
+                warn!(target: "payload_builder::relay", ?err, "failed to fetch proposer registrations");
+                self.metrics.failed_registration_requests.increment(1);
+            }
+            RelayResponse::Submission(_, Ok(())) => {
+                self.metrics.submitted_payloads.increment(1);
+            }
+            RelayResponse::Submission(relay, Err(err)) => {
+                warn!(target: "payload_builder::relay", %relay, ?err, "failed to submit payload");
+                self.metrics.failed_submissions.increment(1);
+            }
+        }
+    }
+}
+
+impl Future for RelaySubmissionService {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            // refresh the registrations and drop those of past slots
+            while this.registrations_interval.poll_tick(cx).is_ready() {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+                if let Some(slot) = this.beacon.slot_at(now.as_secs()) {
+                    this.registrations.prune_before(slot);
+                }
+                this.fetch_registrations();
+            }
+
+            // submit all new payloads
+            while let Poll::Ready(payload) = this.payloads.poll_next_unpin(cx) {
+                let Some(payload) = payload else {
+                    // the payload job generator was dropped
+                    return Poll::Ready(())
+                };
+                this.submit_payload(&payload);
+            }
+
+            // advance the in-flight requests
+            let mut progressed = false;
+            while let Poll::Ready(Some(response)) = this.requests.poll_next_unpin(cx) {
+                this.on_response(response);
+                progressed = true;
+            }
+            this.metrics.registrations.set(this.registrations.len() as f64);
+
+            if !progressed {
+                return Poll::Pending
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RelaySubmissionService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelaySubmissionService")
+            .field("relays", &self.relays)
+            .field("beacon", &self.beacon)
+            .field("signer", &self.signer)
+            .field("registrations", &self.registrations)
+            .field("requests", &self.requests.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The response of a request to a relay.
+enum RelayResponse {
+    /// The registrations of upcoming proposers.
+    Validators(Result<Vec<Validator>, RelayError>),
+    /// The result of a block submission to the relay with the given url.
+    Submission(Url, Result<(), RelayError>),
+}
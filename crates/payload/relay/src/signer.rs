@@ -0,0 +1,184 @@
+//! Signing of bids with the builder's BLS key.
+
+use crate::RelayError;
+use blst::{
+    min_pk::{PublicKey, SecretKey, Signature},
+    BLST_ERROR,
+};
+use reth_primitives::H256;
+use reth_rpc_types::relay::{BidTrace, BlsPublicKey, BlsSignature};
+use sha2::{Digest, Sha256};
+
+/// The domain separation tag of the `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite used
+/// by the consensus layer.
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The domain type of the builder API, `DOMAIN_APPLICATION_BUILDER`.
+const DOMAIN_APPLICATION_BUILDER: [u8; 4] = [0, 0, 0, 1];
+
+/// Signs bids with the BLS key of the builder.
+pub struct BuilderSigner {
+    /// The secret key of the builder.
+    secret_key: SecretKey,
+    /// The public key of the builder.
+    public_key: BlsPublicKey,
+    /// The signing domain of the builder API.
+    domain: H256,
+}
+
+// === impl BuilderSigner ===
+
+impl BuilderSigner {
+    /// Creates a new signer from the 32 byte secret key of the builder for the beacon chain with
+    /// the given genesis fork version.
+    pub fn new(secret_key: &[u8], genesis_fork_version: [u8; 4]) -> Result<Self, RelayError> {
+        let secret_key =
+            SecretKey::from_bytes(secret_key).map_err(|_| RelayError::InvalidSecretKey)?;
+        let public_key = BlsPublicKey::from(secret_key.sk_to_pk().compress());
+        Ok(Self { secret_key, public_key, domain: compute_builder_domain(genesis_fork_version) })
+    }
+
+    /// Returns the public key of the builder.
+    pub fn public_key(&self) -> BlsPublicKey {
+        self.public_key
+    }
+
+    /// Returns the signing domain of the builder API.
+    pub fn domain(&self) -> H256 {
+        self.domain
+    }
+
+    /// Signs the given bid.
+    pub fn sign_bid(&self, bid: &BidTrace) -> BlsSignature {
+        let signing_root = compute_signing_root(bid_trace_root(bid), self.domain);
+        BlsSignature::from(self.secret_key.sign(signing_root.as_bytes(), DST, &[]).compress())
+    }
+}
+
+impl std::fmt::Debug for BuilderSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuilderSigner")
+            .field("public_key", &self.public_key)
+            .field("domain", &self.domain)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Computes the signing domain of the builder API for the given genesis fork version.
+///
+/// The builder domain is always computed with the genesis fork version and an empty genesis
+/// validators root.
+pub fn compute_builder_domain(genesis_fork_version: [u8; 4]) -> H256 {
+    // hash_tree_root(ForkData(current_version, genesis_validators_root))
+    let mut version = [0u8; 32];
+    version[..4].copy_from_slice(&genesis_fork_version);
+    let fork_data_root = hash_pair(&version, &[0u8; 32]);
+
+    let mut domain = [0u8; 32];
+    domain[..4].copy_from_slice(&DOMAIN_APPLICATION_BUILDER);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    H256(domain)
+}
+
+/// Computes the signing root of an object with the given SSZ hash tree root in the given domain.
+pub fn compute_signing_root(object_root: H256, domain: H256) -> H256 {
+    H256(hash_pair(object_root.as_fixed_bytes(), domain.as_fixed_bytes()))
+}
+
+/// Computes the SSZ hash tree root of the given bid.
+pub fn bid_trace_root(bid: &BidTrace) -> H256 {
+    let value = bid.value.to_le_bytes::<32>();
+
+    let mut fee_recipient = [0u8; 32];
+    fee_recipient[..20].copy_from_slice(bid.proposer_fee_recipient.as_bytes());
+
+    let leaves = [
+        uint64_chunk(bid.slot),
+        bid.parent_hash.0,
+        bid.block_hash.0,
+        pubkey_root(&bid.builder_pubkey),
+        pubkey_root(&bid.proposer_pubkey),
+        fee_recipient,
+        uint64_chunk(bid.gas_limit),
+        uint64_chunk(bid.gas_used),
+        value,
+    ];
+    H256(merkleize(&leaves))
+}
+
+/// Verifies the signature of the given bid against the public key of the builder.
+pub fn verify_bid_signature(bid: &BidTrace, signature: &BlsSignature, domain: H256) -> bool {
+    let Ok(public_key) = PublicKey::from_bytes(bid.builder_pubkey.as_bytes()) else { return false };
+    let Ok(signature) = Signature::from_bytes(signature.as_bytes()) else { return false };
+    let signing_root = compute_signing_root(bid_trace_root(bid), domain);
+    signature.verify(true, signing_root.as_bytes(), DST, &[], &public_key, true) ==
+        BLST_ERROR::BLST_SUCCESS
+}
+
+/// Returns the SSZ chunk of a `uint64`.
+fn uint64_chunk(value: u64) -> [u8; 32] {
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    chunk
+}
+
+/// Returns the SSZ hash tree root of a BLS public key, which spans two chunks.
+fn pubkey_root(pubkey: &BlsPublicKey) -> [u8; 32] {
+    let mut chunks = [0u8; 64];
+    chunks[..48].copy_from_slice(pubkey.as_bytes());
+    hash_pair(&chunks[..32], &chunks[32..])
+}
+
+/// Merkleizes the given chunks, padding them with zero chunks to the next power of two.
+fn merkleize(chunks: &[[u8; 32]]) -> [u8; 32] {
+    let mut layer = chunks.to_vec();
+    layer.resize(chunks.len().next_power_of_two(), [0u8; 32]);
+    while layer.len() > 1 {
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+    layer[0]
+}
+
+fn hash_pair(left: &[u8], right: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{hex_literal::hex, U256};
+
+    #[test]
+    fn mainnet_builder_domain() {
+        assert_eq!(
+            compute_builder_domain([0, 0, 0, 0]),
+            H256(hex!("00000001f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9"))
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_bid() {
+        let signer = BuilderSigner::new(&[1u8; 32], [0, 0, 0, 0]).unwrap();
+        let bid = BidTrace {
+            slot: 1,
+            builder_pubkey: signer.public_key(),
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            value: U256::from(1_000_000_000u64),
+            ..Default::default()
+        };
+        let signature = signer.sign_bid(&bid);
+        assert!(verify_bid_signature(&bid, &signature, signer.domain()));
+
+        let other = BidTrace { slot: 2, ..bid.clone() };
+        assert!(!verify_bid_signature(&other, &signature, signer.domain()));
+    }
+
+    #[test]
+    fn invalid_secret_key() {
+        assert!(BuilderSigner::new(&[0u8; 31], [0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Submits payloads to a mock relay.
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use reth_payload_builder::{BuiltPayload, PayloadId, ProposerPreferencesProvider};
+use reth_payload_relay::{
+    compute_builder_domain, verify_bid_signature, BeaconChainConfig, BuilderSigner, RelayClient,
+    RelayConfig, RelayError, RelaySubmissionService,
+};
+use reth_primitives::{Address, Header, SealedBlock, H384, H768, U256};
+use reth_rpc_types::relay::{
+    SignedBidSubmission, SignedValidatorRegistration, Validator, ValidatorRegistration,
+};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+
+const FEE_RECIPIENT: Address = Address::repeat_byte(0x11);
+
+fn beacon() -> BeaconChainConfig {
+    BeaconChainConfig { genesis_time: 0, genesis_fork_version: [0; 4], seconds_per_slot: 12 }
+}
+
+/// Spawns a relay that knows the registration of the proposer of the given slot and forwards all
+/// block submissions to the returned channel.
+async fn spawn_relay(slot: u64) -> (SocketAddr, mpsc::UnboundedReceiver<SignedBidSubmission>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let validators = vec![Validator {
+        slot,
+        validator_index: 1,
+        entry: SignedValidatorRegistration {
+            message: ValidatorRegistration {
+                fee_recipient: FEE_RECIPIENT,
+                gas_limit: 30_000_000,
+                timestamp: 0,
+                pubkey: H384::repeat_byte(0x22),
+            },
+            signature: H768::zero(),
+        },
+    }];
+    let validators = serde_json::to_string(&validators).unwrap();
+
+    let make_service = make_service_fn(move |_| {
+        let tx = tx.clone();
+        let validators = validators.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                let tx = tx.clone();
+                let validators = validators.clone();
+                async move {
+                    let response = match (req.method(), req.uri().path()) {
+                        (&Method::GET, "/relay/v1/builder/validators") => {
+                            Response::new(Body::from(validators))
+                        }
+                        (&Method::POST, "/relay/v1/builder/blocks") => {
+                            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                            tx.send(serde_json::from_slice(&body).unwrap()).unwrap();
+                            Response::new(Body::empty())
+                        }
+                        _ => Response::builder()
+                            .status(StatusCode::NOT_FOUND)
+                            .body(Body::empty())
+                            .unwrap(),
+                    };
+                    Ok::<_, Infallible>(response)
+                }
+            }))
+        }
+    });
+
+    let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+    (addr, rx)
+}
+
+fn payload(timestamp: u64, beneficiary: Address) -> BuiltPayload {
+    let header = Header {
+        timestamp,
+        beneficiary,
+        gas_limit: 30_000_000,
+        gas_used: 21_000,
+        base_fee_per_gas: Some(7),
+        ..Default::default()
+    };
+    let block =
+        SealedBlock { header: header.seal_slow(), withdrawals: Some(vec![]), ..Default::default() };
+    BuiltPayload::new(PayloadId::new([0; 8]), block, U256::from(1_000_000_000u64))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn submits_payloads_of_registered_proposers() {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let slot = beacon().slot_at(now).unwrap() + 2;
+    let timestamp = slot * beacon().seconds_per_slot;
+
+    let (addr, mut submissions) = spawn_relay(slot).await;
+    let config = RelayConfig::new(vec![format!("http://{addr}").parse().unwrap()], beacon())
+        .registrations_interval(Duration::from_secs(60));
+    let signer = BuilderSigner::new(&[1u8; 32], [0; 4]).unwrap();
+    let builder_pubkey = signer.public_key();
+
+    let (payloads_tx, payloads_rx) = mpsc::unbounded_channel();
+    let service = RelaySubmissionService::new(config, signer, payloads_rx);
+    let registrations = service.registrations();
+    tokio::spawn(service);
+
+    // wait for the registrations of upcoming proposers
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while registrations.is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let preferences = registrations.proposer_preferences(timestamp).unwrap();
+    assert_eq!(preferences.fee_recipient, FEE_RECIPIENT);
+    assert_eq!(preferences.gas_limit, 30_000_000);
+    assert!(registrations.proposer_preferences(timestamp + beacon().seconds_per_slot).is_none());
+
+    // payloads that don't pay the registered proposer are not submitted
+    payloads_tx.send(Arc::new(payload(timestamp, Address::repeat_byte(0x33)))).unwrap();
+
+    let payload = payload(timestamp, FEE_RECIPIENT);
+    payloads_tx.send(Arc::new(payload.clone())).unwrap();
+
+    let submission =
+        tokio::time::timeout(Duration::from_secs(10), submissions.recv()).await.unwrap().unwrap();
+    assert_eq!(submission.message.slot, slot);
+    assert_eq!(submission.message.block_hash, payload.block().hash());
+    assert_eq!(submission.message.proposer_fee_recipient, FEE_RECIPIENT);
+    assert_eq!(submission.message.proposer_pubkey, H384::repeat_byte(0x22));
+    assert_eq!(submission.message.builder_pubkey, builder_pubkey);
+    assert_eq!(submission.message.value, payload.fees());
+    assert_eq!(submission.execution_payload.block_hash, payload.block().hash());
+    assert!(verify_bid_signature(
+        &submission.message,
+        &submission.signature,
+        compute_builder_domain([0; 4])
+    ));
+    assert!(submissions.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn requests_time_out() {
+    // the relay accepts connections but never responds
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+    let relay = RelayClient::new(url, Duration::from_millis(100));
+
+    let err = tokio::time::timeout(Duration::from_secs(10), relay.get_validators())
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(err, RelayError::Http(err) if err.is_timeout()));
+}
@@ -71,4 +71,4 @@ macro_rules! impl_fixed_hash_type {
     };
 }
 
-impl_fixed_hash_type!((H64, 8), (H512, 64));
+impl_fixed_hash_type!((H64, 8), (H384, 48), (H512, 64), (H768, 96));
@@ -269,6 +269,15 @@ impl ChainSpec {
             .unwrap_or_else(|| self.is_fork_active_at_timestamp(Hardfork::Shanghai, timestamp))
     }
 
+    /// Convenience method to check if [Hardfork::Cancun] is active at a given timestamp.
+    #[inline]
+    pub fn is_cancun_activated_at_timestamp(&self, timestamp: u64) -> bool {
+        self.fork_timestamps
+            .cancun
+            .map(|cancun| timestamp >= cancun)
+            .unwrap_or_else(|| self.is_fork_active_at_timestamp(Hardfork::Cancun, timestamp))
+    }
+
     /// Creates a [`ForkFilter`](crate::ForkFilter) for the block described by [Head].
     pub fn fork_filter(&self, head: Head) -> ForkFilter {
         let forks = self.forks_iter().filter_map(|(_, condition)| {
@@ -330,6 +339,29 @@ impl ChainSpec {
         ForkId { hash: forkhash, next: 0 }
     }
 
+    /// Returns the [`ForkId`] of every fork once it is activated, in the order of activation.
+    ///
+    /// Forks that are activated by TTD without a known block are skipped, since they don't show
+    /// up in the fork id.
+    pub fn fork_ids(&self) -> Vec<(Hardfork, ForkId)> {
+        let mut head = Head::default();
+        self.forks_iter()
+            .filter_map(|(fork, condition)| {
+                match condition {
+                    ForkCondition::Block(block) |
+                    ForkCondition::TTD { fork_block: Some(block), .. } => {
+                        head.number = head.number.max(block)
+                    }
+                    ForkCondition::Timestamp(timestamp) => head.timestamp = timestamp,
+                    ForkCondition::TTD { fork_block: None, .. } | ForkCondition::Never => {
+                        return None
+                    }
+                }
+                Some((fork, self.fork_id(&head)))
+            })
+            .collect()
+    }
+
     /// Build a chainspec using [`ChainSpecBuilder`]
     pub fn builder() -> ChainSpecBuilder {
         ChainSpecBuilder::default()
@@ -340,6 +372,7 @@ impl From<Genesis> for ChainSpec {
     fn from(genesis: Genesis) -> Self {
         // Block-based hardforks
         let hardfork_opts = vec![
+            (Hardfork::Frontier, Some(0)),
             (Hardfork::Homestead, genesis.config.homestead_block),
             (Hardfork::Dao, genesis.config.dao_fork_block),
             (Hardfork::Tangerine, genesis.config.eip150_block),
@@ -360,22 +393,43 @@ impl From<Genesis> for ChainSpec {
             .collect::<BTreeMap<_, _>>();
 
         // Paris
-        if let Some(ttd) = genesis.config.terminal_total_difficulty {
+        //
+        // geth treats a network that has passed the TTD as post-merge, even if no TTD is set.
+        let ttd = genesis
+            .config
+            .terminal_total_difficulty
+            .or_else(|| genesis.config.terminal_total_difficulty_passed.then_some(U256::ZERO));
+        let mut paris_block_and_final_difficulty = None;
+        if let Some(ttd) = ttd {
+            // If the genesis block already reaches the TTD, the network is merged at genesis and
+            // the total difficulty never changes afterwards.
+            let merged_at_genesis = ttd <= genesis.difficulty;
+            if merged_at_genesis {
+                paris_block_and_final_difficulty = Some((0, genesis.difficulty));
+            }
+
             hardforks.insert(
                 Hardfork::Paris,
                 ForkCondition::TTD {
                     total_difficulty: ttd,
-                    fork_block: genesis.config.merge_netsplit_block,
+                    fork_block: genesis
+                        .config
+                        .merge_netsplit_block
+                        .or(merged_at_genesis.then_some(0)),
                 },
             );
         }
 
         // Time-based hardforks
-        let time_hardforks = genesis
-            .config
-            .shanghai_time
-            .map(|time| (Hardfork::Shanghai, ForkCondition::Timestamp(time)))
-            .into_iter()
+        let time_hardfork_opts = vec![
+            (Hardfork::Shanghai, genesis.config.shanghai_time),
+            (Hardfork::Cancun, genesis.config.cancun_time),
+        ];
+        let time_hardforks = time_hardfork_opts
+            .iter()
+            .filter_map(|(hardfork, opt)| {
+                opt.map(|time| (*hardfork, ForkCondition::Timestamp(time)))
+            })
             .collect::<BTreeMap<_, _>>();
 
         hardforks.extend(time_hardforks);
@@ -386,7 +440,7 @@ impl From<Genesis> for ChainSpec {
             genesis_hash: None,
             fork_timestamps: ForkTimestamps::from_hardforks(&hardforks),
             hardforks,
-            paris_block_and_final_difficulty: None,
+            paris_block_and_final_difficulty,
         }
     }
 }
@@ -396,6 +450,8 @@ impl From<Genesis> for ChainSpec {
 pub struct ForkTimestamps {
     /// The timestamp of the shanghai fork
     pub shanghai: Option<u64>,
+    /// The timestamp of the cancun fork
+    pub cancun: Option<u64>,
 }
 
 impl ForkTimestamps {
@@ -405,6 +461,9 @@ impl ForkTimestamps {
         if let Some(shanghai) = forks.get(&Hardfork::Shanghai).and_then(|f| f.as_timestamp()) {
             timestamps = timestamps.shanghai(shanghai);
         }
+        if let Some(cancun) = forks.get(&Hardfork::Cancun).and_then(|f| f.as_timestamp()) {
+            timestamps = timestamps.cancun(cancun);
+        }
         timestamps
     }
 
@@ -413,6 +472,12 @@ impl ForkTimestamps {
         self.shanghai = Some(shanghai);
         self
     }
+
+    /// Sets the given cancun timestamp
+    pub fn cancun(mut self, cancun: u64) -> Self {
+        self.cancun = Some(cancun);
+        self
+    }
 }
 
 /// A helper type for compatibility with geth's config
@@ -887,8 +952,8 @@ where
 mod tests {
     use crate::{
         Address, AllGenesisFormats, Chain, ChainSpec, ChainSpecBuilder, DisplayHardforks,
-        ForkCondition, ForkHash, ForkId, Genesis, Hardfork, Head, GOERLI, H256, MAINNET, SEPOLIA,
-        U256,
+        ForkCondition, ForkHash, ForkId, Genesis, Hardfork, Head, GOERLI, H256, MAINNET,
+        MAINNET_GENESIS, SEPOLIA, U256,
     };
     use bytes::BytesMut;
     use ethers_core::types as EtherType;
@@ -973,8 +1038,12 @@ Post-merge hard forks (timestamp based):
             // no corresponding entry in the ForkTimestamp types, See also
             // [ForkTimestamps::from_hardforks]
 
-            // currently there are only 1 timestamps known: shanghai
-            let known_timestamp_based_forks = 1;
+            // currently there are 2 timestamps known: shanghai and cancun
+            let known_timestamp_based_forks =
+                [spec.fork_timestamps.shanghai, spec.fork_timestamps.cancun]
+                    .iter()
+                    .flatten()
+                    .count();
             let num_timestamp_based_forks =
                 spec.hardforks.values().copied().filter(ForkCondition::is_timestamp).count();
             assert_eq!(num_timestamp_based_forks, known_timestamp_based_forks);
@@ -1188,6 +1257,34 @@ Post-merge hard forks (timestamp based):
         );
     }
 
+    #[test]
+    fn fork_ids_schedule() {
+        let fork_ids = MAINNET.fork_ids();
+        // Paris is TTD based without a known block, so it has no fork id
+        assert_eq!(fork_ids.len(), MAINNET.hardforks().len() - 1);
+        assert_eq!(
+            fork_ids.first(),
+            Some(&(
+                Hardfork::Frontier,
+                ForkId { hash: ForkHash([0xfc, 0x64, 0xec, 0x04]), next: 1150000 }
+            ))
+        );
+        assert_eq!(
+            fork_ids.iter().find(|(fork, _)| *fork == Hardfork::GrayGlacier),
+            Some(&(
+                Hardfork::GrayGlacier,
+                ForkId { hash: ForkHash([0xf0, 0xaf, 0xd0, 0xe3]), next: 1681338455 }
+            ))
+        );
+        assert_eq!(
+            fork_ids.last(),
+            Some(&(
+                Hardfork::Shanghai,
+                ForkId { hash: ForkHash([0xdc, 0xe9, 0x6c, 0x2d]), next: 0 }
+            ))
+        );
+    }
+
     /// Checks that time-based forks work
     ///
     /// This is based off of the test vectors here: https://github.com/ethereum/go-ethereum/blob/5c8cc10d1e05c23ff1108022f4150749e73c0ca1/core/forkid/forkid_test.go#L155-L188
@@ -1483,6 +1580,74 @@ Post-merge hard forks (timestamp based):
         assert_eq!(hash, expected_hash);
     }
 
+    #[test]
+    fn geth_genesis_fork_mapping() {
+        // the mainnet genesis with the config of geth's mainnet genesis
+        let mut genesis: serde_json::Value =
+            serde_json::from_str(include_str!("../../res/genesis/mainnet.json")).unwrap();
+        genesis["config"] = serde_json::json!({
+            "chainId": 1,
+            "homesteadBlock": 1150000,
+            "daoForkBlock": 1920000,
+            "daoForkSupport": true,
+            "eip150Block": 2463000,
+            "eip155Block": 2675000,
+            "eip158Block": 2675000,
+            "byzantiumBlock": 4370000,
+            "constantinopleBlock": 7280000,
+            "petersburgBlock": 7280000,
+            "istanbulBlock": 9069000,
+            "muirGlacierBlock": 9200000,
+            "berlinBlock": 12244000,
+            "londonBlock": 12965000,
+            "arrowGlacierBlock": 13773000,
+            "grayGlacierBlock": 15050000,
+            "terminalTotalDifficulty": "58750000000000000000000",
+            "terminalTotalDifficultyPassed": true,
+            "shanghaiTime": 1681338455
+        });
+        let genesis: Genesis = serde_json::from_value(genesis).unwrap();
+        let chainspec = ChainSpec::from(genesis);
+
+        // the geth config resolves to the same fork schedule as the built-in mainnet spec
+        assert_eq!(chainspec.chain, Chain::mainnet());
+        assert_eq!(chainspec.hardforks, MAINNET.hardforks);
+        assert_eq!(chainspec.fork_timestamps, MAINNET.fork_timestamps);
+        assert_eq!(chainspec.fork_ids(), MAINNET.fork_ids());
+
+        // the genesis header is derived from the allocations of the genesis file
+        let header = chainspec.genesis_header();
+        assert_eq!(header, MAINNET.genesis_header());
+        assert_eq!(header.hash_slow(), MAINNET_GENESIS);
+    }
+
+    #[test]
+    fn geth_genesis_ttd_passed() {
+        // a network that passed the TTD is post-merge, even without a TTD
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":1337,"terminalTotalDifficultyPassed":true}}"#,
+        )
+        .unwrap();
+        let chainspec = ChainSpec::from(genesis);
+        assert_eq!(
+            chainspec.fork(Hardfork::Paris),
+            ForkCondition::TTD { fork_block: Some(0), total_difficulty: U256::ZERO }
+        );
+        assert_eq!(chainspec.fork(Hardfork::Frontier), ForkCondition::Block(0));
+
+        // a TTD above the genesis difficulty leaves the merge block unknown
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":1337,"terminalTotalDifficulty":100,"terminalTotalDifficultyPassed":true},"difficulty":"0x1"}"#,
+        )
+        .unwrap();
+        let chainspec = ChainSpec::from(genesis);
+        assert_eq!(
+            chainspec.fork(Hardfork::Paris),
+            ForkCondition::TTD { fork_block: None, total_difficulty: U256::from(100) }
+        );
+        assert_eq!(chainspec.paris_block_and_final_difficulty, None);
+    }
+
     #[test]
     fn hive_geth_json() {
         let hive_json = r#"
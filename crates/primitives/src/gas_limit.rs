@@ -0,0 +1,28 @@
+//! Helpers for working with the block gas limit
+
+use crate::constants::{GAS_LIMIT_BOUND_DIVISOR, MIN_GAS_LIMIT};
+
+/// Returns the gas limit of the next block, moving the parent's gas limit towards the desired gas
+/// limit by at most the maximum change allowed by the protocol: `parent_gas_limit / 1024 - 1`.
+pub fn calculate_next_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let max_delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let min_gas_limit = parent_gas_limit.saturating_sub(max_delta).max(MIN_GAS_LIMIT);
+    let max_gas_limit = parent_gas_limit.saturating_add(max_delta);
+    desired_gas_limit.clamp(min_gas_limit, max_gas_limit.max(min_gas_limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_block_gas_limit() {
+        let parent = 30_000_000;
+        let max_delta = parent / GAS_LIMIT_BOUND_DIVISOR - 1;
+        assert_eq!(calculate_next_block_gas_limit(parent, parent), parent);
+        assert_eq!(calculate_next_block_gas_limit(parent, parent + 1000), parent + 1000);
+        assert_eq!(calculate_next_block_gas_limit(parent, 36_000_000), parent + max_delta);
+        assert_eq!(calculate_next_block_gas_limit(parent, 0), parent - max_delta);
+        assert_eq!(calculate_next_block_gas_limit(MIN_GAS_LIMIT, 0), MIN_GAS_LIMIT);
+    }
+}
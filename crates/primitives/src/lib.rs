@@ -31,6 +31,7 @@ pub mod constants;
 pub mod contract;
 mod forkid;
 pub mod fs;
+pub mod gas_limit;
 mod genesis;
 mod hardfork;
 mod header;
@@ -52,7 +53,7 @@ mod withdrawal;
 pub mod proofs;
 
 pub use account::{Account, Bytecode};
-pub use bits::H512;
+pub use bits::{H384, H512, H768};
 pub use block::{
     Block, BlockBody, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag, BlockWithSenders,
     ForkBlock, SealedBlock, SealedBlockWithSenders,
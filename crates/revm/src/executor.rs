@@ -1,8 +1,10 @@
 use crate::{
-    database::SubState,
+    database::{State, SubState},
     env::{fill_cfg_and_block_env, fill_tx_env},
     eth_dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
     into_reth_log,
+    parallel::execute_speculatively,
+    prefetch::{PrefetchCache, PrefetchedState, Prefetcher},
     stack::{InspectorStack, InspectorStackConfig},
     to_reth_acc,
 };
@@ -19,10 +21,11 @@ use revm::{
         hash_map::{self, Entry},
         Account as RevmAccount, AccountInfo, ResultAndState,
     },
-    EVM,
+    Database, EVM,
 };
 use std::{
     collections::{BTreeMap, HashMap},
+    fmt::Debug,
     sync::Arc,
 };
 
@@ -35,6 +38,10 @@ where
     pub chain_spec: Arc<ChainSpec>,
     evm: EVM<SubState<DB>>,
     stack: InspectorStack,
+    /// Prefetches the state of the next transactions of a block, if set.
+    prefetcher: Option<Prefetcher>,
+    /// Whether the transactions of a block are executed optimistically in parallel.
+    parallel_execution: bool,
 }
 
 impl<DB> From<Arc<ChainSpec>> for Executor<DB>
@@ -45,7 +52,13 @@ where
     /// `with_db` to set the database before executing.
     fn from(chain_spec: Arc<ChainSpec>) -> Self {
         let evm = EVM::new();
-        Executor { chain_spec, evm, stack: InspectorStack::new(InspectorStackConfig::default()) }
+        Executor {
+            chain_spec,
+            evm,
+            stack: InspectorStack::new(InspectorStackConfig::default()),
+            prefetcher: None,
+            parallel_execution: false,
+        }
     }
 }
 
@@ -58,7 +71,13 @@ where
         let mut evm = EVM::new();
         evm.database(db);
 
-        Executor { chain_spec, evm, stack: InspectorStack::new(InspectorStackConfig::default()) }
+        Executor {
+            chain_spec,
+            evm,
+            stack: InspectorStack::new(InspectorStackConfig::default()),
+            prefetcher: None,
+            parallel_execution: false,
+        }
     }
 
     /// Configures the executor with the given inspectors.
@@ -67,6 +86,28 @@ where
         self
     }
 
+    /// Configures the executor to prefetch the state of the next transactions of a block on the
+    /// thread of the given [Prefetcher], while the current transaction is executed.
+    ///
+    /// The prefetcher reads the state through the state provider of the executor and shares what it
+    /// read with the executor for the duration of the block.
+    pub fn with_prefetch(mut self, prefetcher: Prefetcher) -> Self {
+        self.prefetcher = Some(prefetcher);
+        self
+    }
+
+    /// Configures the executor to execute the transactions of a block optimistically in parallel.
+    ///
+    /// The transactions are executed speculatively in parallel and committed in order, conflicting
+    /// transactions are re-executed. The results are identical to the sequential execution. Takes
+    /// precedence over prefetching.
+    ///
+    /// The speculative executions read the state through the state provider of the executor.
+    pub fn with_parallel_execution(mut self) -> Self {
+        self.parallel_execution = true;
+        self
+    }
+
     /// Gives a reference to the database
     pub fn db(&mut self) -> &mut SubState<DB> {
         self.evm.db().expect("db to not be moved")
@@ -93,7 +134,7 @@ where
     }
 
     /// Initializes the config and block env.
-    fn init_env(&mut self, header: &Header, total_difficulty: U256) {
+    pub fn init_env(&mut self, header: &Header, total_difficulty: U256) {
         fill_cfg_and_block_env(
             &mut self.evm.env.cfg,
             &mut self.evm.env.block,
@@ -105,7 +146,7 @@ where
 
     /// Commit change to the run-time database, and update the given [PostState] with the changes
     /// made in the transaction, which can be persisted to the database.
-    fn commit_changes(
+    pub fn commit_changes(
         &mut self,
         block_number: BlockNumber,
         changes: hash_map::HashMap<Address, RevmAccount>,
@@ -177,30 +218,13 @@ where
     /// Runs a single transaction in the configured environment and proceeds
     /// to return the result and state diff (without applying it).
     ///
-    /// Assumes the rest of the block environment has been filled via `init_block_env`.
+    /// Assumes the rest of the block environment has been filled via [Self::init_env].
     pub fn transact(
         &mut self,
         transaction: &TransactionSigned,
         sender: Address,
     ) -> Result<ResultAndState, BlockExecutionError> {
-        // Fill revm structure.
-        fill_tx_env(&mut self.evm.env.tx, transaction, sender);
-
-        let hash = transaction.hash();
-        let out = if self.stack.should_inspect(&self.evm.env, hash) {
-            // execution with inspector.
-            let output = self.evm.inspect(&mut self.stack);
-            tracing::trace!(
-                target: "evm",
-                ?hash, ?output, ?transaction, env = ?self.evm.env,
-                "Executed transaction"
-            );
-            output
-        } else {
-            // main execution.
-            self.evm.transact()
-        };
-        out.map_err(|e| BlockValidationError::EVM { hash, message: format!("{e:?}") }.into())
+        transact(&mut self.evm, &mut self.stack, transaction, sender)
     }
 
     /// Runs the provided transactions and commits their state to the run-time database.
@@ -219,6 +243,15 @@ where
         total_difficulty: U256,
         senders: Option<Vec<Address>>,
     ) -> Result<(PostState, u64), BlockExecutionError> {
+        // Cancun is not implemented yet
+        if self.chain_spec.is_cancun_activated_at_timestamp(block.timestamp) {
+            return Err(BlockValidationError::UnsupportedHardfork {
+                number: block.number,
+                hardfork: Hardfork::Cancun,
+            }
+            .into())
+        }
+
         // perf: do not execute empty blocks
         if block.body.is_empty() {
             return Ok((PostState::default(), 0))
@@ -227,49 +260,129 @@ where
 
         self.init_env(&block.header, total_difficulty);
 
-        let mut cumulative_gas_used = 0;
-        let mut post_state = PostState::with_tx_capacity(block.number, block.body.len());
-        for (transaction, sender) in block.body.iter().zip(senders) {
-            // The sum of the transaction’s gas limit, Tg, and the gas utilised in this block prior,
-            // must be no greater than the block’s gasLimit.
-            let block_available_gas = block.header.gas_limit - cumulative_gas_used;
-            if transaction.gas_limit() > block_available_gas {
-                return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
-                    transaction_gas_limit: transaction.gas_limit(),
-                    block_available_gas,
-                }
-                .into())
+        if self.parallel_execution {
+            return self.execute_transactions_in_parallel(block, &senders)
+        }
+        match self.prefetcher.clone() {
+            Some(prefetcher) => {
+                self.execute_transactions_with_prefetch(block, &senders, prefetcher)
             }
-            // Execute transaction.
-            let ResultAndState { result, state } = self.transact(transaction, sender)?;
+            None => execute_transactions(
+                &mut self.evm,
+                &mut self.stack,
+                &self.chain_spec,
+                block,
+                &senders,
+                |evm, stack, _, transaction, sender| transact(evm, stack, transaction, sender),
+            ),
+        }
+    }
 
-            // commit changes
-            self.commit_changes(
-                block.number,
-                state,
-                self.chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block.number),
-                &mut post_state,
-            );
+    /// Runs the provided transactions like [Self::execute_transactions], but executes them
+    /// speculatively in parallel first.
+    ///
+    /// The speculative executions read the state of the executor before the block. The
+    /// speculative results are committed in order if they are still valid on top of the changes of
+    /// the previous transactions, otherwise the transaction is re-executed.
+    fn execute_transactions_in_parallel(
+        &mut self,
+        block: &Block,
+        senders: &[Address],
+    ) -> Result<(PostState, u64), BlockExecutionError> {
+        let transactions = block.body.iter().zip(senders.iter().copied()).collect::<Vec<_>>();
+        let state = self.evm.db.as_ref().expect("db to not be moved");
+        let speculations = execute_speculatively(state, &self.evm.env, &transactions);
+
+        let beneficiary = self.evm.env.block.coinbase;
+        let mut speculations = speculations.into_iter();
+        let mut reexecuted = 0usize;
+        let result = execute_transactions(
+            &mut self.evm,
+            &mut self.stack,
+            &self.chain_spec,
+            block,
+            senders,
+            |evm, stack, _, transaction, sender| {
+                let speculation = speculations.next().expect("speculation for every transaction");
+                if stack.should_inspect(&evm.env, transaction.hash()) {
+                    return transact(evm, stack, transaction, sender)
+                }
 
-            // append gas used
-            cumulative_gas_used += result.gas_used();
+                let db = evm.db.as_mut().expect("db to not be moved");
+                let Some(result) = speculation
+                    .validate(db, beneficiary)
+                    .map_err(|_| BlockExecutionError::ProviderError)?
+                else {
+                    tracing::trace!(target: "evm::parallel", hash = ?transaction.hash(), "Re-executing invalid speculation");
+                    reexecuted += 1;
+                    return transact(evm, stack, transaction, sender)
+                };
+                // the committed changes expect all changed accounts to be loaded
+                for address in result.state.keys() {
+                    db.load_account(*address).map_err(|_| BlockExecutionError::ProviderError)?;
+                }
+                Ok(result)
+            },
+        );
+        tracing::debug!(
+            target: "evm::parallel",
+            number = block.number,
+            transactions = transactions.len(),
+            reexecuted,
+            "Executed block in parallel"
+        );
+        result
+    }
 
-            // Push transaction changeset and calculate header bloom filter for receipt.
-            post_state.add_receipt(
-                block.number,
-                Receipt {
-                    tx_type: transaction.tx_type(),
-                    // Success flag was added in `EIP-658: Embedding transaction status code in
-                    // receipts`.
-                    success: result.is_success(),
-                    cumulative_gas_used,
-                    // convert to reth log
-                    logs: result.into_logs().into_iter().map(into_reth_log).collect(),
-                },
-            );
-        }
+    /// Runs the provided transactions like [Self::execute_transactions], while the state of the
+    /// next transactions is prefetched by the given [Prefetcher].
+    ///
+    /// The state provider of the executor is shared with the prefetcher for the duration of the
+    /// block, the cached state of the executor is moved into an EVM on top of a reference to it
+    /// that reads the state prefetched for the block first.
+    fn execute_transactions_with_prefetch(
+        &mut self,
+        block: &Block,
+        senders: &[Address],
+        prefetcher: Prefetcher,
+    ) -> Result<(PostState, u64), BlockExecutionError> {
+        let transactions = block.body.iter().zip(senders.iter().copied()).collect::<Vec<_>>();
 
-        Ok((post_state, cumulative_gas_used))
+        let CacheDB { accounts, contracts, logs, block_hashes, db: state } =
+            self.evm.db.take().expect("db to not be moved");
+        let mut evm = EVM::new();
+        evm.env = std::mem::take(&mut self.evm.env);
+        let cache = PrefetchCache::default();
+        evm.database(CacheDB {
+            accounts,
+            contracts,
+            logs,
+            block_hashes,
+            db: PrefetchedState::new(State::new(&state.0), &cache),
+        });
+
+        let env = evm.env.clone();
+        let result = prefetcher.run(&state.0, &cache, env, &transactions, |progress| {
+            execute_transactions(
+                &mut evm,
+                &mut self.stack,
+                &self.chain_spec,
+                block,
+                senders,
+                |evm, stack, idx, transaction, sender| {
+                    let result = transact(evm, stack, transaction, sender);
+                    progress.set_executed(idx + 1);
+                    result
+                },
+            )
+        });
+
+        let CacheDB { accounts, contracts, logs, block_hashes, .. } =
+            evm.db.take().expect("db to not be moved");
+        self.evm.env = std::mem::take(&mut evm.env);
+        drop(evm);
+        self.evm.database(CacheDB { accounts, contracts, logs, block_hashes, db: state });
+        result
     }
 
     /// Applies the post-block changes, assuming the poststate is generated after executing
@@ -343,6 +456,110 @@ where
     }
 }
 
+/// Runs a single transaction in the environment of the given EVM and returns the result and state
+/// diff (without applying it).
+fn transact<DB>(
+    evm: &mut EVM<DB>,
+    stack: &mut InspectorStack,
+    transaction: &TransactionSigned,
+    sender: Address,
+) -> Result<ResultAndState, BlockExecutionError>
+where
+    DB: Database,
+    DB::Error: Debug,
+{
+    // Fill revm structure.
+    fill_tx_env(&mut evm.env.tx, transaction, sender);
+
+    let hash = transaction.hash();
+    let out = if stack.should_inspect(&evm.env, hash) {
+        // execution with inspector.
+        let output = evm.inspect(&mut *stack);
+        tracing::trace!(
+            target: "evm",
+            ?hash, ?output, ?transaction, env = ?evm.env,
+            "Executed transaction"
+        );
+        output
+    } else {
+        // main execution.
+        evm.transact()
+    };
+    out.map_err(|e| BlockValidationError::EVM { hash, message: format!("{e:?}") }.into())
+}
+
+/// Runs the transactions of the block on the given EVM and commits their state to its run-time
+/// database.
+///
+/// Each transaction is run with `run_transaction`, which is called with the index of the
+/// transaction and its sender.
+fn execute_transactions<DB, F>(
+    evm: &mut EVM<CacheDB<DB>>,
+    stack: &mut InspectorStack,
+    chain_spec: &ChainSpec,
+    block: &Block,
+    senders: &[Address],
+    mut run_transaction: F,
+) -> Result<(PostState, u64), BlockExecutionError>
+where
+    DB: DatabaseRef,
+    F: FnMut(
+        &mut EVM<CacheDB<DB>>,
+        &mut InspectorStack,
+        usize,
+        &TransactionSigned,
+        Address,
+    ) -> Result<ResultAndState, BlockExecutionError>,
+{
+    let mut cumulative_gas_used = 0;
+    let mut post_state = PostState::with_tx_capacity(block.number, block.body.len());
+    let has_state_clear_eip =
+        chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block.number);
+    for (idx, (transaction, sender)) in block.body.iter().zip(senders).enumerate() {
+        // The sum of the transaction’s gas limit, Tg, and the gas utilised in this block prior,
+        // must be no greater than the block’s gasLimit.
+        let block_available_gas = block.header.gas_limit - cumulative_gas_used;
+        if transaction.gas_limit() > block_available_gas {
+            return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
+                transaction_gas_limit: transaction.gas_limit(),
+                block_available_gas,
+            }
+            .into())
+        }
+        // Execute transaction.
+        let ResultAndState { result, state } =
+            run_transaction(evm, stack, idx, transaction, *sender)?;
+
+        // commit changes
+        commit_state_changes(
+            evm.db.as_mut().expect("db to not be moved"),
+            &mut post_state,
+            block.number,
+            state,
+            has_state_clear_eip,
+        );
+
+        // append gas used
+        cumulative_gas_used += result.gas_used();
+
+        // Push transaction changeset and calculate header bloom filter for receipt.
+        post_state.add_receipt(
+            block.number,
+            Receipt {
+                tx_type: transaction.tx_type(),
+                // Success flag was added in `EIP-658: Embedding transaction status code in
+                // receipts`.
+                success: result.is_success(),
+                cumulative_gas_used,
+                // convert to reth log
+                logs: result.into_logs().into_iter().map(into_reth_log).collect(),
+            },
+        );
+    }
+
+    Ok((post_state, cumulative_gas_used))
+}
+
 /// Increment the balance for the given account in the [PostState].
 ///
 /// Returns an error if the database encountered an error while loading the account.
@@ -645,12 +862,12 @@ pub fn insert_post_block_withdrawals_balance_increments(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::database::State;
     use once_cell::sync::Lazy;
     use reth_consensus_common::calc;
     use reth_primitives::{
         constants::ETH_TO_WEI, hex_literal::hex, keccak256, Account, Address, BlockNumber,
-        Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, H256, MAINNET, U256,
+        Bytecode, Bytes, ChainSpecBuilder, ForkCondition, Signature, StorageKey, Transaction,
+        TransactionKind, TxLegacy, H256, MAINNET, U256,
     };
     use reth_provider::{
         post_state::{AccountChanges, Storage, StorageTransition, StorageWipe},
@@ -798,12 +1015,21 @@ mod tests {
         // spec at berlin fork
         let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
 
+        let prefetch_db = SubState::new(State::new(db.clone()));
         let db = SubState::new(State::new(db));
 
         // execute chain and verify receipts
-        let mut executor = Executor::new(chain_spec, db);
+        let mut executor = Executor::new(chain_spec.clone(), db);
         let post_state = executor.execute_and_verify_receipt(&block, U256::ZERO, None).unwrap();
 
+        // prefetching the state does not change the outcome of the execution
+        let mut prefetch_executor =
+            Executor::new(chain_spec, prefetch_db).with_prefetch(Prefetcher::new(4));
+        assert_eq!(
+            prefetch_executor.execute_and_verify_receipt(&block, U256::ZERO, None).unwrap(),
+            post_state
+        );
+
         let base_block_reward = ETH_TO_WEI * 2;
         let block_reward = calc::block_reward(base_block_reward, 1);
 
@@ -1108,6 +1334,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cancun_unsupported() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .shanghai_activated()
+                .with_fork(Hardfork::Cancun, ForkCondition::Timestamp(1000))
+                .build(),
+        );
+        let db = SubState::new(State::new(StateProviderTest::default()));
+        let mut executor = Executor::new(chain_spec, db);
+
+        let header = Header { number: 1, timestamp: 999, ..Default::default() };
+        let block = Block { header, withdrawals: Some(vec![]), ..Default::default() };
+        executor.execute_transactions(&block, U256::ZERO, None).unwrap();
+
+        let header = Header { number: 2, timestamp: 1000, ..block.header };
+        let block = Block { header, ..block };
+        assert_eq!(
+            executor.execute_transactions(&block, U256::ZERO, None).unwrap_err(),
+            BlockValidationError::UnsupportedHardfork { number: 2, hardfork: Hardfork::Cancun }
+                .into()
+        );
+    }
+
     #[test]
     fn test_account_state_preserved() {
         let account = Address::from_str("c94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
@@ -1333,4 +1583,82 @@ mod tests {
         assert_eq!(post_state_after_state_clear.accounts(), &BTreeMap::default());
         assert_eq!(post_state_after_state_clear.account_changes(), &AccountChanges::default());
     }
+
+    #[test]
+    fn parallel_execution_matches_sequential() {
+        let sender1 = Address::from_low_u64_be(0xa1);
+        let sender2 = Address::from_low_u64_be(0xa2);
+        let counter = Address::from_low_u64_be(0xc0);
+        let beneficiary = Address::from_low_u64_be(0xbe);
+
+        let mut db = StateProviderTest::default();
+        for sender in [sender1, sender2] {
+            db.insert_account(
+                sender,
+                Account { balance: U256::from(ETH_TO_WEI), nonce: 0, bytecode_hash: None },
+                None,
+                HashMap::new(),
+            );
+        }
+        // increments the value of slot 0
+        db.insert_account(
+            counter,
+            Account::default(),
+            Some(hex!("600054600101600055").into()),
+            HashMap::new(),
+        );
+
+        let block = |number, transactions: &[(Address, u64, Address, u128)]| {
+            let (body, senders): (Vec<_>, Vec<_>) = transactions
+                .iter()
+                .map(|&(sender, nonce, to, value)| {
+                    let transaction = Transaction::Legacy(TxLegacy {
+                        nonce,
+                        gas_price: 1,
+                        gas_limit: 100_000,
+                        to: TransactionKind::Call(to),
+                        value,
+                        ..Default::default()
+                    });
+                    (
+                        TransactionSigned::from_transaction_and_signature(
+                            transaction,
+                            Signature::default(),
+                        ),
+                        sender,
+                    )
+                })
+                .unzip();
+            let header = Header { number, gas_limit: 1_000_000, beneficiary, ..Default::default() };
+            (Block { header, body, ..Default::default() }, senders)
+        };
+        // every transaction after the first one conflicts with a previous one, and the second
+        // block depends on the uncommitted changes of the first one
+        let blocks = [
+            block(1, &[(sender1, 0, counter, 0), (sender2, 0, counter, 0)]),
+            block(
+                2,
+                &[
+                    (sender1, 1, sender2, 1000),
+                    (sender2, 1, counter, 0),
+                    (sender1, 2, beneficiary, 1),
+                ],
+            ),
+        ];
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+        let mut sequential =
+            Executor::new(chain_spec.clone(), SubState::new(State::new(db.clone())));
+        let mut parallel =
+            Executor::new(chain_spec, SubState::new(State::new(db))).with_parallel_execution();
+        for (block, senders) in blocks {
+            let expected =
+                sequential.execute_transactions(&block, U256::ZERO, Some(senders.clone())).unwrap();
+            assert!(expected.0.receipts(block.number).iter().all(|receipt| receipt.success));
+            assert_eq!(
+                parallel.execute_transactions(&block, U256::ZERO, Some(senders)).unwrap(),
+                expected
+            );
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
     database::{State, SubState},
+    prefetch::Prefetcher,
     stack::{InspectorStack, InspectorStackConfig},
 };
 use reth_primitives::ChainSpec;
@@ -13,12 +14,14 @@ use std::sync::Arc;
 pub struct Factory {
     chain_spec: Arc<ChainSpec>,
     stack: Option<InspectorStack>,
+    prefetcher: Option<Prefetcher>,
+    parallel_execution: bool,
 }
 
 impl Factory {
     /// Create new factory
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec, stack: None }
+        Self { chain_spec, stack: None, prefetcher: None, parallel_execution: false }
     }
 
     /// Sets the inspector stack for all generated executors.
@@ -32,6 +35,27 @@ impl Factory {
         self.stack = Some(InspectorStack::new(config));
         self
     }
+
+    /// Sets the number of transactions all generated executors prefetch the state of ahead of the
+    /// executed transaction.
+    ///
+    /// All executors share the thread of a single [Prefetcher]. A depth of `0` disables
+    /// prefetching, which is the default.
+    ///
+    /// See [Executor::with_prefetch].
+    pub fn with_prefetch(mut self, depth: usize) -> Self {
+        self.prefetcher = (depth > 0).then(|| Prefetcher::new(depth));
+        self
+    }
+
+    /// Configures all generated executors to execute the transactions of a block optimistically in
+    /// parallel.
+    ///
+    /// See [Executor::with_parallel_execution].
+    pub fn with_parallel_execution(mut self) -> Self {
+        self.parallel_execution = true;
+        self
+    }
 }
 
 impl ExecutorFactory for Factory {
@@ -45,6 +69,12 @@ impl ExecutorFactory for Factory {
         if let Some(ref stack) = self.stack {
             executor = executor.with_stack(stack.clone());
         }
+        if let Some(ref prefetcher) = self.prefetcher {
+            executor = executor.with_prefetch(prefetcher.clone());
+        }
+        if self.parallel_execution {
+            executor = executor.with_parallel_execution();
+        }
         executor
     }
 
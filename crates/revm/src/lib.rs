@@ -19,10 +19,15 @@ pub mod database;
 /// revm implementation of reth block and transaction executors.
 pub mod executor;
 mod factory;
+mod parallel;
+mod prefetch;
 
 /// revm executor factory.
 pub use factory::Factory;
 
+/// Prefetcher of the state of the transactions of a block.
+pub use prefetch::Prefetcher;
+
 /// reexport for convenience
 pub use reth_revm_inspectors::*;
 /// reexport for convenience
@@ -0,0 +1,290 @@
+//! Optimistic parallel execution of the transactions of a block.
+//!
+//! All transactions of a block are first executed speculatively in parallel, each on its own
+//! [CacheDB] overlay on top of the state of the executor before the block, i.e. the same database
+//! transaction, including its uncommitted changes, and the cached changes of the previous blocks.
+//! Every speculative execution records the state it read.
+//!
+//! The results are then committed in order. A result is only committed if all recorded reads still
+//! match the state after the previous transactions, i.e. the transaction would have read the same
+//! state if executed sequentially, which means its execution is identical. Otherwise the
+//! transaction conflicts with a previous transaction and is re-executed on top of the committed
+//! state.
+//!
+//! Every transaction pays its fee to the block beneficiary, so every transaction would conflict
+//! with all previous ones on the balance of the beneficiary. If the balance of the beneficiary was
+//! not observed by the transaction itself, the fee is instead applied on top of the committed
+//! balance.
+
+use crate::env::fill_tx_env;
+use rayon::prelude::*;
+use reth_primitives::{bytes::Bytes, Address, TransactionSigned, H160, H256, U256};
+use revm::{
+    db::{CacheDB, DatabaseRef},
+    interpreter::{opcode, CallInputs, CreateInputs, Gas, InstructionResult, Interpreter},
+    primitives::{AccountInfo, Bytecode, Env, ResultAndState},
+    Database, EVMData, Inspector, EVM,
+};
+use std::{cell::RefCell, collections::HashMap};
+use tracing::trace;
+
+/// Executes the given transactions speculatively in parallel on top of the given state.
+///
+/// Returns the speculative executions in the order of the transactions.
+pub(crate) fn execute_speculatively<DB>(
+    state: &DB,
+    env: &Env,
+    transactions: &[(&TransactionSigned, Address)],
+) -> Vec<Speculation>
+where
+    DB: DatabaseRef + Sync,
+    DB::Error: std::fmt::Debug,
+{
+    transactions
+        .par_iter()
+        .map(|(transaction, sender)| Speculation::execute(state, env, transaction, *sender))
+        .collect()
+}
+
+/// The result of a speculative execution of a transaction along with the state it read.
+#[derive(Debug)]
+pub(crate) struct Speculation {
+    /// The result of the execution, `None` if the execution failed.
+    result: Option<ResultAndState>,
+    /// The state read by the execution.
+    reads: ReadSet,
+    /// Whether the execution observed the balance of the beneficiary.
+    beneficiary_observed: bool,
+}
+
+impl Speculation {
+    /// Executes the transaction on a [CacheDB] overlay on top of the given state.
+    fn execute<DB: DatabaseRef>(
+        state: &DB,
+        env: &Env,
+        transaction: &TransactionSigned,
+        sender: Address,
+    ) -> Self
+    where
+        DB::Error: std::fmt::Debug,
+    {
+        let mut evm = EVM::new();
+        evm.env = env.clone();
+        fill_tx_env(&mut evm.env.tx, transaction, sender);
+        evm.database(CacheDB::new(RecordingState::new(state)));
+
+        let beneficiary = env.block.coinbase;
+        let mut inspector = BeneficiaryInspector::new(beneficiary);
+        let result = match evm.inspect(&mut inspector) {
+            Ok(result) => Some(result),
+            Err(err) => {
+                trace!(
+                    target: "evm::parallel",
+                    hash = ?transaction.hash(),
+                    ?err,
+                    "Speculative execution failed"
+                );
+                None
+            }
+        };
+
+        let reads = evm.db.take().expect("db to not be moved").db.reads.into_inner();
+        Self { result, reads, beneficiary_observed: inspector.observed || sender == beneficiary }
+    }
+
+    /// Returns the result of the speculative execution if it is identical to the result of an
+    /// execution on top of the given state.
+    ///
+    /// The given state must be the state before the transaction, i.e. with the changes of the
+    /// previous transactions committed. All recorded reads are checked against it, since any of
+    /// them may have been changed by the previous transactions.
+    pub(crate) fn validate<DB: Database>(
+        self,
+        db: &mut DB,
+        beneficiary: Address,
+    ) -> Result<Option<ResultAndState>, DB::Error> {
+        let Some(mut result) = self.result else { return Ok(None) };
+
+        for (number, hash) in self.reads.block_hashes {
+            if db.block_hash(number)? != hash {
+                return Ok(None)
+            }
+        }
+
+        for ((address, slot), value) in self.reads.storage {
+            if db.storage(address, slot)? != value {
+                return Ok(None)
+            }
+        }
+
+        let mut beneficiary_read = None;
+        for (address, account) in self.reads.accounts {
+            if address == beneficiary && !self.beneficiary_observed {
+                beneficiary_read = Some(account);
+                continue
+            }
+            if !is_same_account(db.basic(address)?.as_ref(), account.as_ref()) {
+                return Ok(None)
+            }
+        }
+
+        // apply the fee on top of the committed balance of the beneficiary
+        if let Some(read) = beneficiary_read {
+            let committed = db.basic(beneficiary)?;
+            if is_same_account(committed.as_ref(), read.as_ref()) {
+                return Ok(Some(result))
+            }
+
+            let (Some(read), Some(committed)) = (read, committed) else { return Ok(None) };
+            if read.nonce != committed.nonce || read.code_hash != committed.code_hash {
+                return Ok(None)
+            }
+            let Some(account) = result.state.get_mut(&beneficiary) else { return Ok(None) };
+            let Some(fee) = account.info.balance.checked_sub(read.balance) else { return Ok(None) };
+            // a touched empty account may be removed, which depends on the balance
+            if fee == U256::ZERO {
+                return Ok(None)
+            }
+            let Some(balance) = committed.balance.checked_add(fee) else { return Ok(None) };
+            account.info.balance = balance;
+        }
+
+        Ok(Some(result))
+    }
+}
+
+/// Returns `true` if both accounts are equal, ignoring the cached bytecode.
+fn is_same_account(a: Option<&AccountInfo>, b: Option<&AccountInfo>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.balance == b.balance && a.nonce == b.nonce && a.code_hash == b.code_hash
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// The state read by a speculative execution.
+///
+/// Bytecodes are not recorded, since they are addressed by their hash.
+#[derive(Debug, Default)]
+struct ReadSet {
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+    block_hashes: HashMap<U256, H256>,
+}
+
+/// Database that records all reads of the underlying state.
+struct RecordingState<'a, DB> {
+    state: &'a DB,
+    reads: RefCell<ReadSet>,
+}
+
+impl<'a, DB> RecordingState<'a, DB> {
+    fn new(state: &'a DB) -> Self {
+        Self { state, reads: Default::default() }
+    }
+}
+
+impl<'a, DB: DatabaseRef> DatabaseRef for RecordingState<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic(&self, address: H160) -> Result<Option<AccountInfo>, Self::Error> {
+        let account = self.state.basic(address)?;
+        self.reads.borrow_mut().accounts.insert(address, account.clone());
+        Ok(account)
+    }
+
+    fn code_by_hash(&self, code_hash: H256) -> Result<Bytecode, Self::Error> {
+        self.state.code_by_hash(code_hash)
+    }
+
+    fn storage(&self, address: H160, index: U256) -> Result<U256, Self::Error> {
+        let value = self.state.storage(address, index)?;
+        self.reads.borrow_mut().storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<H256, Self::Error> {
+        let hash = self.state.block_hash(number)?;
+        self.reads.borrow_mut().block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// An [Inspector] that detects whether a transaction observes the beneficiary account, other than
+/// by receiving the fee.
+#[derive(Debug)]
+struct BeneficiaryInspector {
+    beneficiary: Address,
+    observed: bool,
+}
+
+impl BeneficiaryInspector {
+    fn new(beneficiary: Address) -> Self {
+        Self { beneficiary, observed: false }
+    }
+}
+
+impl<DB> Inspector<DB> for BeneficiaryInspector
+where
+    DB: Database,
+{
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+    ) -> InstructionResult {
+        let pc = interpreter.program_counter();
+        let op = interpreter.contract.bytecode.bytecode()[pc];
+
+        let address = match op {
+            opcode::EXTCODECOPY |
+            opcode::EXTCODEHASH |
+            opcode::EXTCODESIZE |
+            opcode::BALANCE |
+            opcode::SELFDESTRUCT => interpreter.stack().peek(0).ok(),
+            opcode::DELEGATECALL | opcode::CALL | opcode::STATICCALL | opcode::CALLCODE => {
+                interpreter.stack().peek(1).ok()
+            }
+            _ => None,
+        };
+        if let Some(address) = address {
+            let address: Address = H256::from(address.to_be_bytes()).into();
+            self.observed |= address == self.beneficiary;
+        }
+
+        InstructionResult::Continue
+    }
+
+    fn call(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        _is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.observed |= [
+            inputs.contract,
+            inputs.context.address,
+            inputs.transfer.source,
+            inputs.transfer.target,
+        ]
+        .contains(&self.beneficiary);
+
+        (InstructionResult::Continue, Gas::new(inputs.gas_limit), Bytes::new())
+    }
+
+    fn create_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<Address>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        self.observed |= address == Some(self.beneficiary);
+        (ret, address, remaining_gas, out)
+    }
+}
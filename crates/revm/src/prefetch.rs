@@ -0,0 +1,301 @@
+//! Prefetching of the state accessed by the transactions of a block.
+//!
+//! Reading cold accounts and storage slots from the database dominates the execution of a block.
+//! While a transaction is executed, the prefetcher reads the state of the next transactions of the
+//! block on a separate thread, which warms the database pages for the executing thread:
+//!
+//! - the sender and the recipient of the transaction, including the bytecode of the recipient,
+//! - the accounts and storage slots of the access list,
+//! - everything read by a speculative run of the transaction.
+//!
+//! The prefetcher reads through the state provider of the executor, i.e. the same database
+//! transaction, including its uncommitted changes, and the same in-memory state of the blockchain
+//! tree. Everything it reads from the state provider is kept in a [PrefetchCache] that the executor
+//! reads from first for the duration of the block, so the executor only goes to the database for
+//! the state that was not prefetched yet. Only the values of the state provider are shared, the
+//! speculative changes of the previous transactions stay with the prefetcher, so prefetching can
+//! never change the outcome of the execution.
+
+use crate::{database::State, env::fill_tx_env};
+use parking_lot::{Condvar, Mutex, RwLock};
+use reth_primitives::{Address, TransactionSigned, H256, KECCAK_EMPTY, U256};
+use reth_provider::StateProvider;
+use revm::{
+    db::{CacheDB, DatabaseCommit, DatabaseRef},
+    primitives::{AccountInfo, Bytecode, Env, TransactTo},
+    EVM,
+};
+use std::{collections::HashMap, sync::Arc};
+use tracing::trace;
+
+/// The size of the stack of the prefetching thread.
+///
+/// The speculative runs can reach the max call depth just like the executor, see the stack size of
+/// the execution stage.
+const PREFETCH_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Prefetches the state of the transactions of a block on a long-lived worker thread.
+///
+/// Cloning the prefetcher shares the worker thread.
+#[derive(Debug, Clone)]
+pub struct Prefetcher {
+    /// The number of transactions to prefetch the state of ahead of the executed transaction.
+    depth: usize,
+    /// The pool of the single prefetching thread.
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl Prefetcher {
+    /// Spawns the worker thread of a prefetcher that stays at most `depth` transactions ahead of
+    /// the executed transaction.
+    pub fn new(depth: usize) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .stack_size(PREFETCH_STACK_SIZE)
+            .thread_name(|_| "prefetch".to_string())
+            .build()
+            .expect("failed to spawn the prefetch thread");
+        Self { depth, pool: Arc::new(pool) }
+    }
+
+    /// Runs `execute` on the current thread while the worker prefetches the state of the given
+    /// transactions from `state` into `cache`.
+    ///
+    /// `execute` must report its progress, the prefetcher is stopped once `execute` returns.
+    pub(crate) fn run<SP, R>(
+        &self,
+        state: &SP,
+        cache: &PrefetchCache,
+        env: Env,
+        transactions: &[(&TransactionSigned, Address)],
+        execute: impl FnOnce(&PrefetchProgress) -> R,
+    ) -> R
+    where
+        SP: StateProvider,
+    {
+        let progress = PrefetchProgress::default();
+        self.pool.in_place_scope(|scope| {
+            let (progress, depth) = (&progress, self.depth);
+            scope.spawn(move |_| {
+                prefetch_transactions(state, cache, env, transactions, depth, progress)
+            });
+
+            let result = execute(progress);
+            progress.finish();
+            result
+        })
+    }
+}
+
+/// The state read from the state provider while a block is executed, shared by the prefetcher and
+/// the executor.
+///
+/// The state provider does not change during the execution of a block, the changes of the executed
+/// transactions are kept in memory on top of it, so the cached values stay valid for the whole
+/// block.
+#[derive(Debug, Default)]
+pub(crate) struct PrefetchCache {
+    /// The accounts read, `None` if the account does not exist.
+    accounts: RwLock<HashMap<Address, Option<AccountInfo>>>,
+    /// The bytecode read, by code hash.
+    contracts: RwLock<HashMap<H256, Bytecode>>,
+    /// The storage slots read.
+    storage: RwLock<HashMap<(Address, U256), U256>>,
+}
+
+/// Reads the state from a [PrefetchCache] first and adds everything it reads from the underlying
+/// database to the cache.
+#[derive(Debug)]
+pub(crate) struct PrefetchedState<'a, DB> {
+    /// The underlying database.
+    db: DB,
+    /// The state read so far.
+    cache: &'a PrefetchCache,
+}
+
+impl<'a, DB> PrefetchedState<'a, DB> {
+    /// Creates a new view of `db` that shares the read state through `cache`.
+    pub(crate) fn new(db: DB, cache: &'a PrefetchCache) -> Self {
+        Self { db, cache }
+    }
+}
+
+impl<'a, DB: DatabaseRef> DatabaseRef for PrefetchedState<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account) = self.cache.accounts.read().get(&address) {
+            return Ok(account.clone())
+        }
+        let account = self.db.basic(address)?;
+        self.cache.accounts.write().insert(address, account.clone());
+        Ok(account)
+    }
+
+    fn code_by_hash(&self, code_hash: H256) -> Result<Bytecode, Self::Error> {
+        if let Some(bytecode) = self.cache.contracts.read().get(&code_hash) {
+            return Ok(bytecode.clone())
+        }
+        let bytecode = self.db.code_by_hash(code_hash)?;
+        self.cache.contracts.write().insert(code_hash, bytecode.clone());
+        Ok(bytecode)
+    }
+
+    fn storage(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.cache.storage.read().get(&(address, index)) {
+            return Ok(*value)
+        }
+        let value = self.db.storage(address, index)?;
+        self.cache.storage.write().insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<H256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+/// The execution progress of a block, shared with the prefetcher.
+#[derive(Debug, Default)]
+pub(crate) struct PrefetchProgress {
+    /// The number of executed transactions and whether the execution of the block is finished.
+    state: Mutex<(usize, bool)>,
+    /// Notifies the prefetcher about changes of the progress.
+    changed: Condvar,
+}
+
+impl PrefetchProgress {
+    /// Sets the number of executed transactions.
+    pub(crate) fn set_executed(&self, executed: usize) {
+        self.state.lock().0 = executed;
+        self.changed.notify_one();
+    }
+
+    /// Marks the execution of the block as finished.
+    fn finish(&self) {
+        self.state.lock().1 = true;
+        self.changed.notify_one();
+    }
+
+    /// Waits until the transaction with the given index is at most `depth` transactions ahead of
+    /// the executed transactions.
+    ///
+    /// Returns the number of executed transactions, or `None` if the execution is finished.
+    fn wait(&self, idx: usize, depth: usize) -> Option<usize> {
+        let mut state = self.state.lock();
+        loop {
+            let (executed, finished) = *state;
+            if finished {
+                return None
+            }
+            if idx <= executed + depth {
+                return Some(executed)
+            }
+            self.changed.wait(&mut state);
+        }
+    }
+}
+
+/// Prefetches the state of the given transactions, staying at most `depth` transactions ahead of
+/// the executed transactions.
+///
+/// Returns once all transactions are prefetched or the execution of the block is finished.
+fn prefetch_transactions<SP: StateProvider>(
+    state: &SP,
+    cache: &PrefetchCache,
+    env: Env,
+    transactions: &[(&TransactionSigned, Address)],
+    depth: usize,
+    progress: &PrefetchProgress,
+) {
+    let mut evm = EVM::new();
+    evm.env = env;
+    evm.database(CacheDB::new(PrefetchedState::new(State::new(state), cache)));
+
+    for (idx, (transaction, sender)) in transactions.iter().enumerate() {
+        let Some(executed) = progress.wait(idx, depth) else { return };
+
+        // the prefetcher fell behind, skip the transactions that are already executed
+        if idx < executed {
+            continue
+        }
+        prefetch_transaction(&mut evm, transaction, *sender);
+    }
+}
+
+/// Reads the state accessed by the transaction into the cache and applies its speculative changes.
+fn prefetch_transaction<SP: StateProvider>(
+    evm: &mut EVM<CacheDB<PrefetchedState<'_, State<&SP>>>>,
+    transaction: &TransactionSigned,
+    sender: Address,
+) {
+    fill_tx_env(&mut evm.env.tx, transaction, sender);
+    let hash = transaction.hash();
+
+    let state = &evm.db.as_ref().expect("db to not be moved").db;
+    if let Err(err) = state.basic(sender) {
+        trace!(target: "evm::prefetch", ?hash, ?sender, ?err, "Failed to prefetch sender");
+    }
+    if let TransactTo::Call(to) = evm.env.tx.transact_to {
+        match state.basic(to) {
+            Ok(Some(account)) if account.code_hash != KECCAK_EMPTY => {
+                if let Err(err) = state.code_by_hash(account.code_hash) {
+                    trace!(target: "evm::prefetch", ?hash, ?to, ?err, "Failed to prefetch code");
+                }
+            }
+            Ok(_) => {}
+            Err(err) => {
+                trace!(target: "evm::prefetch", ?hash, ?to, ?err, "Failed to prefetch recipient")
+            }
+        }
+    }
+    for (address, slots) in evm.env.tx.access_list.iter() {
+        if let Err(err) = state.basic(*address) {
+            trace!(target: "evm::prefetch", ?hash, ?address, ?err, "Failed to prefetch account");
+        }
+        for slot in slots {
+            if let Err(err) = state.storage(*address, *slot) {
+                trace!(
+                    target: "evm::prefetch",
+                    ?hash,
+                    ?address,
+                    ?slot,
+                    ?err,
+                    "Failed to prefetch slot"
+                );
+            }
+        }
+    }
+
+    // Errors are expected, e.g. if the speculative state diverges from the actual state.
+    match evm.transact() {
+        Ok(result) => evm.db.as_mut().expect("db to not be moved").commit(result.state),
+        Err(err) => trace!(target: "evm::prefetch", ?hash, ?err, "Speculative run failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+
+    #[test]
+    fn prefetched_state_is_shared() {
+        let address = Address::from_low_u64_be(1);
+        let account = AccountInfo { balance: U256::from(1), nonce: 1, ..Default::default() };
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(address, account.clone());
+        db.insert_account_storage(address, U256::from(1), U256::from(2)).unwrap();
+
+        let cache = PrefetchCache::default();
+        let prefetched = PrefetchedState::new(db, &cache);
+        assert_eq!(prefetched.basic(address).unwrap(), Some(account.clone()));
+        assert_eq!(prefetched.storage(address, U256::from(1)).unwrap(), U256::from(2));
+
+        // the executor reads the prefetched state from the cache instead of its database
+        let executed = PrefetchedState::new(EmptyDB::default(), &cache);
+        assert_eq!(executed.basic(address).unwrap(), Some(account));
+        assert_eq!(executed.storage(address, U256::from(1)).unwrap(), U256::from(2));
+        assert_eq!(executed.basic(Address::from_low_u64_be(2)).unwrap(), None);
+    }
+}
@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::NodeRecord;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_types::{ConnectedPeer, NodeInfo, PeerEvent};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -28,11 +28,16 @@ pub trait AdminApi {
     #[method(name = "removeTrustedPeer")]
     fn remove_trusted_peer(&self, record: NodeRecord) -> RpcResult<bool>;
 
-    /// Creates an RPC subscription which serves events received from the network.
+    /// Returns info about all peers the node has an active session with.
+    #[method(name = "peers")]
+    async fn peers(&self) -> RpcResult<Vec<ConnectedPeer>>;
+
+    /// Creates an RPC subscription which serves events when peers are added to or dropped from the
+    /// node's sessions.
     #[subscription(
     name = "peerEvents",
     unsubscribe = "peerEvents_unsubscribe",
-    item = String
+    item = PeerEvent
     )]
     async fn subscribe_peer_events(&self) -> jsonrpsee::core::SubscriptionResult;
 
@@ -0,0 +1,40 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Bytes, H256};
+use reth_rpc_types::{
+    CallBundleRequest, CallBundleResponse, PrivateTransactionPreferences, SendBundleRequest,
+    SendBundleResponse,
+};
+
+/// Eth bundle rpc interface.
+///
+/// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint>
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+#[async_trait]
+pub trait EthBundleApi {
+    /// Simulates a bundle of signed transactions on top of the given state block.
+    ///
+    /// Returns the gas used, the coinbase payment, the logs and the revert reason of every
+    /// transaction of the bundle.
+    #[method(name = "callBundle")]
+    async fn call_bundle(&self, request: CallBundleRequest) -> RpcResult<CallBundleResponse>;
+
+    /// Submits a bundle of signed transactions for inclusion by the local block builder.
+    ///
+    /// The transactions of the bundle are not propagated to peers. The bundle is included
+    /// atomically and in order at the top of the block with the bundle's block number, or not at
+    /// all.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse>;
+
+    /// Submits a signed transaction for inclusion by the local block builder.
+    ///
+    /// The transaction is not propagated to peers and is dropped once its max block number was
+    /// mined.
+    #[method(name = "sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(
+        &self,
+        bytes: Bytes,
+        preferences: Option<PrivateTransactionPreferences>,
+    ) -> RpcResult<H256>;
+}
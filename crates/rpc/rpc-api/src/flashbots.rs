@@ -0,0 +1,22 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::relay::BuilderBlockValidationRequest;
+
+/// Flashbots rpc interface.
+///
+/// See also <https://github.com/flashbots/builder/blob/main/eth/block-validation/api.go>
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "flashbots"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "flashbots"))]
+#[async_trait]
+pub trait FlashbotsApi {
+    /// Validates a block submitted to a relay by a builder.
+    ///
+    /// The block is fully executed on top of its parent's state, but not made canonical. Returns
+    /// an error with the reason if the block is invalid, does not match the bid, does not pay the
+    /// proposer the bid value, does not target the proposer's registered gas limit or touches a
+    /// blacklisted account.
+    #[method(name = "validateBuilderSubmissionV2")]
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()>;
+}
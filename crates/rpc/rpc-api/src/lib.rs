@@ -20,12 +20,15 @@
 //! - `client`: Enables JSON-RPC client support.
 
 mod admin;
+mod bundle;
 mod debug;
 mod engine;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
+mod flashbots;
 mod net;
+mod reth;
 mod rpc;
 mod trace;
 mod txpool;
@@ -38,12 +41,15 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        bundle::EthBundleApiServer,
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
+        flashbots::FlashbotsApiServer,
         net::NetApiServer,
+        reth::RethApiServer,
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -60,10 +66,13 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
+        bundle::EthBundleApiClient,
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
+        flashbots::FlashbotsApiClient,
         net::NetApiClient,
+        reth::RethApiClient,
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
@@ -0,0 +1,22 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::SyncProgress;
+
+/// Reth namespace rpc interface that gives access to reth specific methods.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+#[async_trait::async_trait]
+pub trait RethApi {
+    /// Returns the progress of the sync pipeline, including the estimated time until every stage
+    /// is done.
+    #[method(name = "syncStatus")]
+    fn sync_status(&self) -> RpcResult<SyncProgress>;
+
+    /// Creates an RPC subscription which serves the progress of the sync pipeline whenever it
+    /// changes.
+    #[subscription(
+    name = "subscribeSyncStatus" => "subscription",
+    unsubscribe = "unsubscribeSyncStatus",
+    item = SyncProgress
+    )]
+    async fn subscribe_sync_status(&self) -> jsonrpsee::core::SubscriptionResult;
+}
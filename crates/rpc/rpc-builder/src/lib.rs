@@ -121,10 +121,12 @@ use reth_rpc::{
         cache::{cache_new_blocks_task, EthStateCache},
         gas_oracle::GasPriceOracle,
     },
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, RPCApi, TraceApi, TracingCallGuard, TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthBundle, EthFilter, EthPubSub,
+    EthSubscriptionIdProvider, FlashbotsApi, FlashbotsConfig, NetApi, RPCApi, RethApi, TraceApi,
+    TracingCallGuard, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
+use reth_rpc_types::SyncProgress;
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use serde::{Deserialize, Serialize, Serializer};
@@ -135,6 +137,7 @@ use std::{
     str::FromStr,
 };
 use strum::{AsRefStr, EnumString, EnumVariantNames, ParseError, VariantNames};
+use tokio::sync::watch;
 use tower::layer::util::{Identity, Stack};
 use tower_http::cors::CorsLayer;
 use tracing::{instrument, trace};
@@ -205,6 +208,8 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events> {
     executor: Tasks,
     /// Provides access to chain events, such as new blocks, required by pubsub.
     events: Events,
+    /// Provides the progress of the sync pipeline, required by the `reth` namespace.
+    sync_status: watch::Receiver<SyncProgress>,
 }
 
 // === impl RpcBuilder ===
@@ -220,7 +225,8 @@ impl<Provider, Pool, Network, Tasks, Events>
         executor: Tasks,
         events: Events,
     ) -> Self {
-        Self { provider, pool, network, executor, events }
+        let (_, sync_status) = watch::channel(SyncProgress::default());
+        Self { provider, pool, network, executor, events, sync_status }
     }
 
     /// Configure the provider instance.
@@ -228,8 +234,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, executor, events, sync_status, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, sync_status }
     }
 
     /// Configure the transaction pool instance.
@@ -237,8 +243,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, network, executor, events, sync_status, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, sync_status }
     }
 
     /// Configure the network instance.
@@ -246,8 +252,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, events, sync_status, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, sync_status }
     }
 
     /// Configure the task executor to use for additional tasks.
@@ -258,8 +264,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, provider, events, sync_status, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, sync_status }
     }
 
     /// Configure the event subscriber instance
@@ -267,8 +273,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, network, sync_status, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, sync_status }
+    }
+
+    /// Configure the receiver of the sync pipeline progress.
+    ///
+    /// If this is not configured, the `reth` namespace always reports the default progress.
+    pub fn with_sync_status(mut self, sync_status: watch::Receiver<SyncProgress>) -> Self {
+        self.sync_status = sync_status;
+        self
     }
 }
 
@@ -302,7 +316,7 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, sync_status } = self;
 
         let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
 
@@ -313,7 +327,8 @@ where
             executor,
             events,
             config.unwrap_or_default(),
-        );
+        )
+        .with_sync_status(sync_status);
 
         modules.config = module_config;
         modules.http = registry.maybe_module(http.as_ref());
@@ -332,7 +347,7 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, sync_status } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -344,7 +359,8 @@ where
                 executor,
                 events,
                 config.unwrap_or_default(),
-            );
+            )
+            .with_sync_status(sync_status);
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -367,6 +383,8 @@ impl Default for RpcModuleBuilder<(), (), (), (), ()> {
 pub struct RpcModuleConfig {
     /// `eth` namespace settings
     eth: EthConfig,
+    /// `flashbots` namespace settings
+    flashbots: FlashbotsConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -378,7 +396,7 @@ impl RpcModuleConfig {
     }
     /// Returns a new RPC module config given the eth namespace config
     pub fn new(eth: EthConfig) -> Self {
-        Self { eth }
+        Self { eth, flashbots: Default::default() }
     }
 }
 
@@ -386,6 +404,7 @@ impl RpcModuleConfig {
 #[derive(Default)]
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
+    flashbots: Option<FlashbotsConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -397,10 +416,16 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures a custom flashbots namespace config
+    pub fn flashbots(mut self, flashbots: FlashbotsConfig) -> Self {
+        self.flashbots = Some(flashbots);
+        self
+    }
+
     /// Consumes the type and creates the [RpcModuleConfig]
     pub fn build(self) -> RpcModuleConfig {
-        let RpcModuleConfigBuilder { eth } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default() }
+        let RpcModuleConfigBuilder { eth, flashbots } = self;
+        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
     }
 }
 
@@ -592,8 +617,12 @@ pub enum RethRpcModule {
     Debug,
     /// `eth_` module
     Eth,
+    /// `flashbots_` module
+    Flashbots,
     /// `net_` module
     Net,
+    /// `reth_` module
+    Reth,
     /// `trace_` module
     Trace,
     /// `txpool_` module
@@ -635,6 +664,8 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
     network: Network,
     executor: Tasks,
     events: Events,
+    /// Provides the progress of the sync pipeline.
+    sync_status: watch::Receiver<SyncProgress>,
     /// Additional settings for handlers.
     config: RpcModuleConfig,
     /// Holds a clone of all the eth namespace handlers
@@ -659,6 +690,7 @@ impl<Provider, Pool, Network, Tasks, Events>
         events: Events,
         config: RpcModuleConfig,
     ) -> Self {
+        let (_, sync_status) = watch::channel(SyncProgress::default());
         Self {
             provider,
             pool,
@@ -669,9 +701,17 @@ impl<Provider, Pool, Network, Tasks, Events>
             tracing_call_guard: TracingCallGuard::new(config.eth.max_tracing_requests),
             config,
             events,
+            sync_status,
         }
     }
 
+    /// Configures the receiver of the sync pipeline progress that's served by the `reth`
+    /// namespace.
+    pub fn with_sync_status(mut self, sync_status: watch::Receiver<SyncProgress>) -> Self {
+        self.sync_status = sync_status;
+        self
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -691,11 +731,19 @@ impl<Provider, Pool, Network, Tasks, Events>
     RethModuleRegistry<Provider, Pool, Network, Tasks, Events>
 where
     Network: NetworkInfo + Peers + Clone + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
 {
     /// Register Admin Namespace
     pub fn register_admin(&mut self) -> &mut Self {
-        self.modules
-            .insert(RethRpcModule::Admin, AdminApi::new(self.network.clone()).into_rpc().into());
+        let admin = AdminApi::with_spawner(self.network.clone(), Box::new(self.executor.clone()));
+        self.modules.insert(RethRpcModule::Admin, admin.into_rpc().into());
+        self
+    }
+
+    /// Register Reth Namespace
+    pub fn register_reth(&mut self) -> &mut Self {
+        let reth = RethApi::with_spawner(self.sync_status.clone(), Box::new(self.executor.clone()));
+        self.modules.insert(RethRpcModule::Reth, reth.into_rpc().into());
         self
     }
 
@@ -725,7 +773,14 @@ where
     /// Register Eth Namespace
     pub fn register_eth(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        let mut module = eth_api.clone().into_rpc();
+        let eth_bundle = EthBundle::new(
+            eth_api,
+            Box::new(self.executor.clone()),
+            self.tracing_call_guard.clone(),
+        );
+        module.merge(eth_bundle.into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Eth, module.into());
         self
     }
 
@@ -785,6 +840,21 @@ where
         AuthRpcModule { inner: module }
     }
 
+    /// Register Flashbots Namespace
+    pub fn register_flashbots(&mut self) -> &mut Self {
+        self.modules.insert(
+            RethRpcModule::Flashbots,
+            FlashbotsApi::new(
+                self.provider.clone(),
+                self.config.flashbots.clone(),
+                Box::new(self.executor.clone()),
+            )
+            .into_rpc()
+            .into(),
+        );
+        self
+    }
+
     /// Register Net Namespace
     pub fn register_net(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
@@ -834,9 +904,12 @@ where
                 self.modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Admin => AdminApi::with_spawner(
+                            self.network.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
@@ -850,12 +923,31 @@ where
                             let mut module = eth_api.clone().into_rpc();
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+                            let eth_bundle = EthBundle::new(
+                                eth_api.clone(),
+                                Box::new(self.executor.clone()),
+                                self.tracing_call_guard.clone(),
+                            );
+                            module.merge(eth_bundle.into_rpc()).expect("No conflicts");
 
                             module.into()
                         }
+                        RethRpcModule::Flashbots => FlashbotsApi::new(
+                            self.provider.clone(),
+                            self.config.flashbots.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
+                        RethRpcModule::Reth => RethApi::with_spawner(
+                            self.sync_status.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Trace => TraceApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
@@ -1699,7 +1791,9 @@ mod tests {
                 "admin" =>  RethRpcModule::Admin,
                 "debug" =>  RethRpcModule::Debug,
                 "eth" =>  RethRpcModule::Eth,
+                "flashbots" =>  RethRpcModule::Flashbots,
                 "net" =>  RethRpcModule::Net,
+                "reth" =>  RethRpcModule::Reth,
                 "trace" =>  RethRpcModule::Trace,
                 "web3" =>  RethRpcModule::Web3,
                 "rpc" => RethRpcModule::Rpc,
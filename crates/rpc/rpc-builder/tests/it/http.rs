@@ -14,10 +14,19 @@ use reth_primitives::{
 };
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, NetApiClient, TraceApiClient, Web3ApiClient,
+    DebugApiClient, EthBundleApiClient, FlashbotsApiClient, NetApiClient, RethApiClient,
+    TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_builder::RethRpcModule;
-use reth_rpc_types::{trace::filter::TraceFilter, CallRequest, Index, TransactionRequest};
+use reth_rpc_types::{
+    relay::{
+        BidTrace, BlsSignature, BuilderBlockValidationRequest, ExecutionPayload,
+        SignedBidSubmission,
+    },
+    trace::filter::TraceFilter,
+    Bundle, CallBundleRequest, CallRequest, Index, SendBundleRequest, SimulatePayload,
+    SyncProgress, TransactionRequest,
+};
 use std::collections::HashSet;
 
 fn is_unimplemented(err: Error) -> bool {
@@ -42,6 +51,7 @@ where
     AdminApiClient::add_trusted_peer(client, node).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    assert!(AdminApiClient::peers(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -93,6 +103,34 @@ where
     EthApiClient::call(client, call_request.clone(), Some(block_number.into()), None, None)
         .await
         .unwrap();
+    EthApiClient::call_many(
+        client,
+        vec![Bundle { transactions: vec![call_request.clone()], block_override: None }],
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    EthBundleApiClient::call_bundle(
+        client,
+        CallBundleRequest {
+            txs: vec![],
+            block_number: 1u64.into(),
+            state_block_number: BlockNumberOrTag::Latest,
+            timestamp: None,
+            coinbase: None,
+            gas_limit: None,
+            difficulty: None,
+            base_fee: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    EthBundleApiClient::send_bundle(client, SendBundleRequest::default()).await.unwrap_err();
+    EthBundleApiClient::send_private_raw_transaction(client, Bytes::default(), None)
+        .await
+        .unwrap_err();
+    EthApiClient::simulate_v1(client, SimulatePayload::default(), None).await.unwrap_err();
     EthApiClient::syncing(client).await.unwrap();
     EthApiClient::send_transaction(client, transaction_request).await.unwrap_err();
     EthApiClient::hashrate(client).await.unwrap();
@@ -172,6 +210,35 @@ where
     ));
 }
 
+async fn test_basic_flashbots_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let request = BuilderBlockValidationRequest {
+        request: SignedBidSubmission {
+            message: BidTrace::default(),
+            execution_payload: ExecutionPayload::default(),
+            signature: BlsSignature::default(),
+        },
+        registered_gas_limit: 30_000_000,
+        withdrawals_root: H256::default(),
+    };
+
+    // a payload without base fee is rejected with the reason
+    match FlashbotsApiClient::validate_builder_submission_v2(client, request).await {
+        Err(Error::Call(err)) => assert_eq!(err.code(), ErrorCode::InvalidParams.code()),
+        res => panic!("expected invalid params error, got {res:?}"),
+    }
+}
+
+async fn test_basic_reth_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let progress = RethApiClient::sync_status(client).await.unwrap();
+    assert_eq!(progress, SyncProgress::default());
+}
+
 async fn test_basic_web3_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -315,6 +382,24 @@ async fn test_call_trace_functions_http_and_ws() {
     test_basic_trace_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_flashbots_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Flashbots]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_flashbots_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_reth_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Reth]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_reth_calls(&client).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_web3_functions_http() {
     reth_tracing::init_test_tracing();
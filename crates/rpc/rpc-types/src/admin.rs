@@ -88,6 +88,90 @@ pub struct EthProtocolInfo {
     pub genesis: H256,
 }
 
+/// Represents a single entry of the `admin_peers` response: a peer the local node has an active
+/// session with.
+///
+/// Note: this follows Geth's format with additional reth specific fields, see:
+/// <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-admin#admin-peers>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectedPeer {
+    /// Enode of the peer in URL format.
+    pub enode: NodeRecord,
+    /// ID of the peer.
+    pub id: PeerId,
+    /// The client version the peer announced.
+    pub name: String,
+    /// Capabilities the peer announced, e.g. `eth/68`.
+    pub caps: Vec<String>,
+    /// Info about the connection to the peer.
+    pub network: ConnectedPeerNetwork,
+    /// Info about the protocols negotiated with the peer.
+    pub protocols: ConnectedPeerProtocols,
+}
+
+/// Info about the connection to a peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectedPeerNetwork {
+    /// Local endpoint of the connection.
+    pub local_address: Option<SocketAddr>,
+    /// Remote endpoint of the connection.
+    pub remote_address: SocketAddr,
+    /// Whether the connection was initiated by the peer.
+    pub inbound: bool,
+    /// Whether the peer is trusted.
+    pub trusted: bool,
+    /// The current reputation of the peer.
+    pub reputation: i32,
+}
+
+/// Protocols negotiated with a peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectedPeerProtocols {
+    /// Info about the `eth` sub-protocol.
+    pub eth: ConnectedPeerEthInfo,
+}
+
+/// Info about the `eth` sub-protocol negotiated with a peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectedPeerEthInfo {
+    /// The negotiated eth version.
+    pub version: u8,
+    /// The total difficulty the peer announced in its status message.
+    pub difficulty: U256,
+    /// The head hash the peer announced in its status message.
+    pub head: H256,
+}
+
+/// An event emitted by the `admin_peerEvents` subscription.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerEvent {
+    /// The kind of the event.
+    #[serde(rename = "type")]
+    pub kind: PeerEventType,
+    /// ID of the peer.
+    pub peer: PeerId,
+    /// The reason the session was closed, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Remote endpoint of the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<SocketAddr>,
+    /// The client version the peer announced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// The kind of a [`PeerEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerEventType {
+    /// A session with the peer was established.
+    Add,
+    /// The session with the peer was closed.
+    Drop,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +185,20 @@ mod tests {
         let de_serialized: NodeInfo = serde_json::from_str(&serialized).unwrap();
         assert_eq!(info, de_serialized)
     }
+
+    #[test]
+    fn serialize_peer_event() {
+        let event = PeerEvent {
+            kind: PeerEventType::Drop,
+            peer: PeerId::zero(),
+            error: Some("too many peers".to_string()),
+            remote: None,
+            name: None,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "drop");
+        assert_eq!(json["error"], "too many peers");
+        assert!(json.get("remote").is_none());
+        assert_eq!(serde_json::from_value::<PeerEvent>(json).unwrap(), event);
+    }
 }
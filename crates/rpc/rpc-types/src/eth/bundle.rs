@@ -0,0 +1,330 @@
+//! Types for the Flashbots compatible bundle endpoints.
+
+use crate::Log;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, H256, U256, U64};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+/// Request for `eth_callBundle`
+///
+/// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint#eth_callbundle>
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleRequest {
+    /// Signed raw transactions of the bundle, in execution order
+    pub txs: Vec<Bytes>,
+    /// Number of the block the bundle is valid for
+    pub block_number: U64,
+    /// Either a hex encoded block number or a block tag for which state to base this simulation
+    /// on.
+    pub state_block_number: BlockNumberOrTag,
+    /// The timestamp to use for the simulation, defaults to the timestamp of the state block + 12
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// The coinbase to use for the simulation, defaults to the coinbase of the state block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// The gas limit to use for the simulation, defaults to the gas limit of the state block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// The difficulty to use for the simulation, defaults to the difficulty of the state block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    /// The base fee to use for the simulation, defaults to the base fee of the block following the
+    /// state block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
+}
+
+impl CallBundleRequest {
+    /// Returns the [BlockId] of the state the bundle is simulated on.
+    pub fn state_block_id(&self) -> BlockId {
+        self.state_block_number.into()
+    }
+}
+
+/// Response for `eth_callBundle`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResponse {
+    /// Hash of the bundle: keccak256 of the concatenated transaction hashes
+    pub bundle_hash: H256,
+    /// Effective gas price of the bundle: `coinbase_diff / total_gas_used`
+    pub bundle_gas_price: SignedU256,
+    /// Balance change of the coinbase over the whole bundle
+    pub coinbase_diff: SignedU256,
+    /// Ether sent to the coinbase directly, excluding gas fees: `coinbase_diff - gas_fees`
+    pub eth_sent_to_coinbase: SignedU256,
+    /// Priority fees paid by the transactions of the bundle
+    #[serde(with = "u256_numeric_string")]
+    pub gas_fees: U256,
+    /// Results of the individual transactions
+    pub results: Vec<CallBundleTransactionResult>,
+    /// Number of the block the bundle was simulated on
+    pub state_block_number: u64,
+    /// Total gas used by the bundle
+    pub total_gas_used: u64,
+}
+
+/// Result of a single transaction of a bundle executed by `eth_callBundle`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTransactionResult {
+    /// Balance change of the coinbase caused by this transaction
+    pub coinbase_diff: SignedU256,
+    /// Ether sent to the coinbase directly, excluding gas fees: `coinbase_diff - gas_fees`
+    pub eth_sent_to_coinbase: SignedU256,
+    /// Sender of the transaction
+    pub from_address: Address,
+    /// Priority fees paid by the transaction
+    #[serde(with = "u256_numeric_string")]
+    pub gas_fees: U256,
+    /// Effective priority fee per gas of the transaction
+    #[serde(with = "u256_numeric_string")]
+    pub gas_price: U256,
+    /// Gas used by the transaction
+    pub gas_used: u64,
+    /// Recipient of the transaction, `None` for contract creations
+    pub to_address: Option<Address>,
+    /// Hash of the transaction
+    pub tx_hash: H256,
+    /// Output of the transaction if it succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Error message if the transaction reverted or halted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Decoded revert reason if the transaction reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+    /// Logs emitted by the transaction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<Log>,
+}
+
+/// Request for `eth_sendBundle`
+///
+/// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint#eth_sendbundle>
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleRequest {
+    /// Signed raw transactions of the bundle, in execution order
+    pub txs: Vec<Bytes>,
+    /// Number of the block the bundle is valid for
+    pub block_number: U64,
+    /// The minimum timestamp for which the bundle is valid, in seconds since the unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp for which the bundle is valid, in seconds since the unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<u64>,
+    /// Hashes of the transactions of the bundle that are allowed to revert
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reverting_tx_hashes: Vec<H256>,
+}
+
+/// Response for `eth_sendBundle`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleResponse {
+    /// Hash of the bundle: keccak256 of the concatenated transaction hashes
+    pub bundle_hash: H256,
+}
+
+/// Preferences for `eth_sendPrivateRawTransaction`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateTransactionPreferences {
+    /// Number of the last block the transaction is valid for, defaults to 25 blocks after the
+    /// current block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block_number: Option<U64>,
+}
+
+/// A signed 256 bit integer, e.g. the balance change of an account.
+///
+/// Serialized as a decimal string with a leading `-` if negative, as used by the Flashbots API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignedU256 {
+    /// Whether the value is below zero, never set for zero.
+    negative: bool,
+    /// The absolute value.
+    abs: U256,
+}
+
+impl SignedU256 {
+    /// Creates a non-negative value.
+    pub fn positive(abs: U256) -> Self {
+        Self { negative: false, abs }
+    }
+
+    /// Returns `lhs - rhs`.
+    pub fn diff(lhs: U256, rhs: U256) -> Self {
+        if lhs >= rhs {
+            Self::positive(lhs - rhs)
+        } else {
+            Self { negative: true, abs: rhs - lhs }
+        }
+    }
+
+    /// Returns `true` if the value is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the absolute value.
+    pub fn abs(&self) -> U256 {
+        self.abs
+    }
+
+    /// Returns `self - rhs`, saturating at the numeric bounds.
+    pub fn saturating_sub(self, rhs: U256) -> Self {
+        if self.negative {
+            Self { negative: true, abs: self.abs.saturating_add(rhs) }
+        } else {
+            Self::diff(self.abs, rhs)
+        }
+    }
+
+    /// Returns `self / rhs` rounded down like the Euclidean division of the Flashbots API, or zero
+    /// if `rhs` is zero.
+    pub fn div_euclid(self, rhs: U256) -> Self {
+        if rhs == U256::ZERO {
+            return Self::default()
+        }
+        let quotient = self.abs / rhs;
+        if self.negative {
+            // a negative value with a remainder is rounded down to the next lower integer
+            let rounding = if self.abs % rhs == U256::ZERO { 0 } else { 1 };
+            Self::diff(U256::ZERO, quotient + U256::from(rounding))
+        } else {
+            Self::positive(quotient)
+        }
+    }
+}
+
+impl From<U256> for SignedU256 {
+    fn from(abs: U256) -> Self {
+        Self::positive(abs)
+    }
+}
+
+impl fmt::Display for SignedU256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.abs)
+    }
+}
+
+impl FromStr for SignedU256 {
+    type Err = <U256 as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(abs) => Ok(Self::diff(U256::ZERO, U256::from_str(abs)?)),
+            None => Ok(Self::positive(U256::from_str(s)?)),
+        }
+    }
+}
+
+impl Serialize for SignedU256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SignedU256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let val = String::deserialize(deserializer)?;
+        val.parse().map_err(de::Error::custom)
+    }
+}
+
+/// (De)serializes a [U256] as a decimal string, as used by the Flashbots API.
+mod u256_numeric_string {
+    use reth_primitives::U256;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub(crate) fn serialize<S: Serializer>(value: &U256, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&value.to_string())
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let val = String::deserialize(deserializer)?;
+        U256::from_str(&val).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_call_bundle_request() {
+        let s = r#"{"txs":["0x02f86b0180843b9aca00852ecc889a0082520894c7d613f6f91f6b7e3d89ed2bf1c5d34bad3e9fa7808080c001a0b5d4d5c4c0e7f8b2c1b6a1e8c8f1b1c2d5e6f7a8b9c0d1e2f3a4b5c6d7e8f90a01c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f7a8b9c0d1e2f3a4"],"blockNumber":"0x1","stateBlockNumber":"latest"}"#;
+        let req = serde_json::from_str::<CallBundleRequest>(s).unwrap();
+        assert_eq!(req.txs.len(), 1);
+        assert_eq!(req.block_number, U64::from(1));
+        assert_eq!(req.state_block_id(), BlockId::Number(BlockNumberOrTag::Latest));
+        assert_eq!(req.timestamp, None);
+    }
+
+    #[test]
+    fn serde_send_bundle_request() {
+        let s = r#"{"txs":["0x01","0x02"],"blockNumber":"0x10","minTimestamp":1,"revertingTxHashes":["0x0000000000000000000000000000000000000000000000000000000000000001"]}"#;
+        let req = serde_json::from_str::<SendBundleRequest>(s).unwrap();
+        assert_eq!(req.txs.len(), 2);
+        assert_eq!(req.block_number, U64::from(16));
+        assert_eq!(req.min_timestamp, Some(1));
+        assert_eq!(req.max_timestamp, None);
+        assert_eq!(req.reverting_tx_hashes, vec![H256::from_low_u64_be(1)]);
+        assert_eq!(
+            serde_json::from_str::<SendBundleRequest>(&serde_json::to_string(&req).unwrap())
+                .unwrap(),
+            req
+        );
+    }
+
+    #[test]
+    fn serde_call_bundle_response() {
+        let response = CallBundleResponse {
+            bundle_gas_price: U256::from(476190476193u64).into(),
+            coinbase_diff: U256::from(20000000000126000u64).into(),
+            eth_sent_to_coinbase: SignedU256::diff(U256::ZERO, U256::from(42000)),
+            total_gas_used: 42000,
+            ..Default::default()
+        };
+        let s = serde_json::to_string(&response).unwrap();
+        assert!(s.contains(r#""bundleGasPrice":"476190476193""#));
+        assert!(s.contains(r#""coinbaseDiff":"20000000000126000""#));
+        assert!(s.contains(r#""ethSentToCoinbase":"-42000""#));
+        assert!(s.contains(r#""totalGasUsed":42000"#));
+        assert_eq!(serde_json::from_str::<CallBundleResponse>(&s).unwrap(), response);
+    }
+
+    #[test]
+    fn signed_u256_arithmetic() {
+        let diff = SignedU256::diff(U256::from(10), U256::from(25));
+        assert!(diff.is_negative());
+        assert_eq!(diff.abs(), U256::from(15));
+        assert_eq!(diff.to_string(), "-15");
+        assert_eq!("-15".parse::<SignedU256>().unwrap(), diff);
+        assert_eq!("-0".parse::<SignedU256>().unwrap(), SignedU256::default());
+
+        assert_eq!(
+            SignedU256::positive(U256::from(5)).saturating_sub(U256::from(5)).to_string(),
+            "0"
+        );
+        assert_eq!(diff.saturating_sub(U256::from(5)).to_string(), "-20");
+        assert_eq!(diff.div_euclid(U256::from(4)).to_string(), "-4");
+        assert_eq!(diff.div_euclid(U256::from(5)).to_string(), "-3");
+        assert_eq!(SignedU256::positive(U256::from(15)).div_euclid(U256::from(4)).to_string(), "3");
+        assert_eq!(diff.div_euclid(U256::ZERO), SignedU256::default());
+    }
+}
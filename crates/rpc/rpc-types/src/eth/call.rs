@@ -1,5 +1,91 @@
-use reth_primitives::{AccessList, Address, Bytes, U256, U64, U8};
-use serde::{Deserialize, Serialize};
+use crate::BlockOverrides;
+use reth_primitives::{AccessList, Address, BlockId, Bytes, U256, U64, U8};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Bundle of transactions
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Bundle {
+    /// Transactions
+    pub transactions: Vec<CallRequest>,
+    /// Block overrides
+    pub block_override: Option<BlockOverrides>,
+}
+
+/// State context for callMany
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StateContext {
+    /// Block Number
+    pub block_number: Option<BlockId>,
+    /// Inclusive number of tx to replay in block. -1 means replay all
+    pub transaction_index: Option<TransactionIndex>,
+}
+
+/// CallResponse for eth_callMany
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EthCallResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// eth_call output (if no error)
+    pub value: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// eth_call output (if error)
+    pub error: Option<String>,
+}
+
+/// Represents a transaction index where -1 means all transactions
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TransactionIndex {
+    /// -1 means all transactions
+    #[default]
+    All,
+    /// Transaction index
+    Index(usize),
+}
+
+impl TransactionIndex {
+    /// Returns true if this is the all variant
+    pub fn is_all(&self) -> bool {
+        matches!(self, TransactionIndex::All)
+    }
+
+    /// Returns the index if this is the index variant
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            TransactionIndex::All => None,
+            TransactionIndex::Index(idx) => Some(*idx),
+        }
+    }
+}
+
+impl Serialize for TransactionIndex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            TransactionIndex::All => serializer.serialize_i8(-1),
+            TransactionIndex::Index(idx) => idx.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionIndex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match isize::deserialize(deserializer)? {
+            -1 => Ok(TransactionIndex::All),
+            idx if idx < -1 => Err(serde::de::Error::custom(format!(
+                "Invalid transaction index, expected -1 or positive integer, got {}",
+                idx
+            ))),
+            idx => Ok(TransactionIndex::Index(idx as usize)),
+        }
+    }
+}
 
 /// Call request
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -47,10 +133,37 @@ impl CallRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_primitives::BlockNumberOrTag;
 
     #[test]
     fn serde_call_request() {
         let s = r#"{"accessList":[],"data":"0x0902f1ac","to":"0xa478c2975ab1ea89e8196811f51a7b7ade33eb11","type":"0x02"}"#;
         let _req = serde_json::from_str::<CallRequest>(s).unwrap();
     }
+
+    #[test]
+    fn serde_transaction_index() {
+        let s = "-1";
+        let idx = serde_json::from_str::<TransactionIndex>(s).unwrap();
+        assert_eq!(idx, TransactionIndex::All);
+
+        let s = "5";
+        let idx = serde_json::from_str::<TransactionIndex>(s).unwrap();
+        assert_eq!(idx, TransactionIndex::Index(5));
+
+        let s = "-2";
+        let res = serde_json::from_str::<TransactionIndex>(s);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn serde_state_context() {
+        let s = r#"{"blockNumber":"pending"}"#;
+        let state_context = serde_json::from_str::<StateContext>(s).unwrap();
+        assert_eq!(state_context.block_number, Some(BlockNumberOrTag::Pending.into()));
+        let s = r#"{"blockNumber":"0x1","transactionIndex":-1}"#;
+        let state_context = serde_json::from_str::<StateContext>(s).unwrap();
+        assert_eq!(state_context.block_number, Some(1u64.into()));
+        assert_eq!(state_context.transaction_index, Some(TransactionIndex::All));
+    }
 }
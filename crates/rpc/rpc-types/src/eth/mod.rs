@@ -2,6 +2,7 @@
 
 mod account;
 mod block;
+mod bundle;
 mod call;
 pub mod engine;
 pub mod error;
@@ -10,6 +11,7 @@ mod filter;
 mod index;
 mod log;
 pub mod pubsub;
+mod simulate;
 pub mod state;
 mod syncing;
 pub mod trace;
@@ -19,11 +21,13 @@ mod work;
 
 pub use account::*;
 pub use block::*;
-pub use call::CallRequest;
+pub use bundle::*;
+pub use call::{Bundle, CallRequest, EthCallResponse, StateContext, TransactionIndex};
 pub use fee::{FeeHistory, TxGasAndReward};
 pub use filter::*;
 pub use index::Index;
 pub use log::Log;
+pub use simulate::*;
 pub use syncing::*;
 pub use transaction::*;
 pub use work::Work;
@@ -0,0 +1,105 @@
+//! Types for the `eth_simulateV1` endpoint.
+
+use crate::{state::StateOverride, Block, BlockOverrides, CallRequest, Log};
+use reth_primitives::{Bytes, U64};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be simulated in a single `eth_simulateV1` request.
+pub const MAX_SIMULATE_BLOCKS: usize = 256;
+
+/// Options for `eth_simulateV1`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order. Every block is built on top of the previous one.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to validate the calls like real transactions: nonces, balances and the base fee
+    /// are checked and the block gas limit is enforced.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return full transaction objects instead of the transaction hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// A block to simulate as part of `eth_simulateV1`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the block's header fields.
+    ///
+    /// Fields that are not overridden are derived from the parent block: the number and the
+    /// timestamp are incremented, the base fee is calculated from the parent's gas usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides that are applied before the first call of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls of the block, executed in order.
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// A simulated block, returned by `eth_simulateV1`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the block's calls, in order.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// The result of a simulated call.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The output of the call, or the revert data if it reverted.
+    pub return_data: Bytes,
+    /// The logs emitted by the call.
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    pub gas_used: U64,
+    /// `1` if the call succeeded, `0` otherwise.
+    pub status: U64,
+    /// The reason the call failed, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// The error of a failed simulated call.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The JSON-RPC error code.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_simulate_payload() {
+        let s = r#"{"blockStateCalls":[{"blockOverrides":{"baseFee":"0x9"},"stateOverrides":{"0xc000000000000000000000000000000000000000":{"balance":"0x4a817c800"}},"calls":[{"from":"0xc000000000000000000000000000000000000000","to":"0xc000000000000000000000000000000000000001","maxFeePerGas":"0xf","value":"0x1"}]}],"validation":true,"returnFullTransactions":true}"#;
+        let payload = serde_json::from_str::<SimulatePayload>(s).unwrap();
+        assert_eq!(payload.block_state_calls.len(), 1);
+        assert!(payload.validation);
+        assert!(payload.return_full_transactions);
+
+        let block = &payload.block_state_calls[0];
+        assert_eq!(block.calls.len(), 1);
+        assert!(block.state_overrides.is_some());
+        assert_eq!(block.block_overrides.as_ref().unwrap().base_fee, Some(9u64.into()));
+    }
+
+    #[test]
+    fn serde_simulate_payload_defaults() {
+        let s = r#"{"blockStateCalls":[{}]}"#;
+        let payload = serde_json::from_str::<SimulatePayload>(s).unwrap();
+        assert!(!payload.validation);
+        assert_eq!(payload.block_state_calls, vec![SimBlock::default()]);
+    }
+}
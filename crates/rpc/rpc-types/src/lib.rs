@@ -17,8 +17,11 @@
 
 mod admin;
 mod eth;
+pub mod relay;
+mod reth;
 mod rpc;
 
 pub use admin::*;
 pub use eth::*;
+pub use reth::*;
 pub use rpc::*;
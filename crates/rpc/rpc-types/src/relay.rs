@@ -0,0 +1,327 @@
+//! Relay API types.
+//!
+//! See also the [builder specs](https://github.com/ethereum/builder-specs) and the
+//! [relay specs](https://flashbots.github.io/relay-specs/).
+
+use crate::engine;
+use reth_primitives::{
+    Address, Bloom, Bytes, SealedBlock, Withdrawal as PrimitiveWithdrawal, H256, H384, H768, U256,
+};
+use serde::{Deserialize, Serialize};
+
+/// The public key of a validator or a builder: a compressed BLS12-381 G1 point.
+pub type BlsPublicKey = H384;
+
+/// A compressed BLS12-381 signature.
+pub type BlsSignature = H768;
+
+/// A validator registration of an upcoming proposer, as returned by the relay's
+/// `/relay/v1/builder/validators` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validator {
+    /// The slot the validator proposes in.
+    #[serde(with = "quoted")]
+    pub slot: u64,
+    /// The index of the validator.
+    #[serde(with = "quoted")]
+    pub validator_index: u64,
+    /// The signed registration of the validator.
+    pub entry: SignedValidatorRegistration,
+}
+
+/// A signed [ValidatorRegistration].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedValidatorRegistration {
+    /// The registration.
+    pub message: ValidatorRegistration,
+    /// The signature of the validator over the registration.
+    pub signature: BlsSignature,
+}
+
+/// The preferences a validator registered with the relay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorRegistration {
+    /// The address that should receive the payment for the block.
+    pub fee_recipient: Address,
+    /// The gas limit the validator targets.
+    #[serde(with = "quoted")]
+    pub gas_limit: u64,
+    /// The timestamp of the registration.
+    #[serde(with = "quoted")]
+    pub timestamp: u64,
+    /// The public key of the validator.
+    pub pubkey: BlsPublicKey,
+}
+
+/// The bid of a builder for a slot, signed as part of a [SignedBidSubmission].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BidTrace {
+    /// The slot the block is built for.
+    #[serde(with = "quoted")]
+    pub slot: u64,
+    /// The hash of the parent block.
+    pub parent_hash: H256,
+    /// The hash of the block.
+    pub block_hash: H256,
+    /// The public key of the builder.
+    pub builder_pubkey: BlsPublicKey,
+    /// The public key of the proposer of the slot.
+    pub proposer_pubkey: BlsPublicKey,
+    /// The fee recipient of the proposer of the slot.
+    pub proposer_fee_recipient: Address,
+    /// The gas limit of the block.
+    #[serde(with = "quoted")]
+    pub gas_limit: u64,
+    /// The gas used by the block.
+    #[serde(with = "quoted")]
+    pub gas_used: u64,
+    /// The value paid to the proposer, in wei.
+    #[serde(with = "quoted")]
+    pub value: U256,
+}
+
+/// A block submission of a builder, sent to the relay's `/relay/v1/builder/blocks` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedBidSubmission {
+    /// The bid.
+    pub message: BidTrace,
+    /// The block.
+    pub execution_payload: ExecutionPayload,
+    /// The signature of the builder over the bid.
+    pub signature: BlsSignature,
+}
+
+/// A request to validate a [SignedBidSubmission], see `flashbots_validateBuilderSubmissionV2`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuilderBlockValidationRequest {
+    /// The block submission of the builder.
+    #[serde(flatten)]
+    pub request: SignedBidSubmission,
+    /// The gas limit the proposer of the slot registered with the relay.
+    #[serde(with = "quoted")]
+    pub registered_gas_limit: u64,
+    /// The withdrawals root of the block, as expected by the relay.
+    pub withdrawals_root: H256,
+}
+
+/// A capella execution payload, in the format of the beacon API.
+///
+/// This is the same as [engine::ExecutionPayload], but with quoted decimal numbers and snake case
+/// field names.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayload {
+    /// The hash of the parent block.
+    pub parent_hash: H256,
+    /// The beneficiary of the block.
+    pub fee_recipient: Address,
+    /// The state root of the block.
+    pub state_root: H256,
+    /// The receipts root of the block.
+    pub receipts_root: H256,
+    /// The logs bloom of the block.
+    pub logs_bloom: Bloom,
+    /// The randomness of the block.
+    pub prev_randao: H256,
+    /// The number of the block.
+    #[serde(with = "quoted")]
+    pub block_number: u64,
+    /// The gas limit of the block.
+    #[serde(with = "quoted")]
+    pub gas_limit: u64,
+    /// The gas used by the block.
+    #[serde(with = "quoted")]
+    pub gas_used: u64,
+    /// The timestamp of the block.
+    #[serde(with = "quoted")]
+    pub timestamp: u64,
+    /// The extra data of the block.
+    pub extra_data: Bytes,
+    /// The base fee of the block.
+    #[serde(with = "quoted")]
+    pub base_fee_per_gas: U256,
+    /// The hash of the block.
+    pub block_hash: H256,
+    /// The EIP-2718 encoded transactions of the block.
+    pub transactions: Vec<Bytes>,
+    /// The withdrawals of the block.
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+impl From<SealedBlock> for ExecutionPayload {
+    fn from(block: SealedBlock) -> Self {
+        engine::ExecutionPayload::from(block).into()
+    }
+}
+
+impl From<engine::ExecutionPayload> for ExecutionPayload {
+    fn from(payload: engine::ExecutionPayload) -> Self {
+        Self {
+            parent_hash: payload.parent_hash,
+            fee_recipient: payload.fee_recipient,
+            state_root: payload.state_root,
+            receipts_root: payload.receipts_root,
+            logs_bloom: payload.logs_bloom,
+            prev_randao: payload.prev_randao,
+            block_number: payload.block_number.as_u64(),
+            gas_limit: payload.gas_limit.as_u64(),
+            gas_used: payload.gas_used.as_u64(),
+            timestamp: payload.timestamp.as_u64(),
+            extra_data: payload.extra_data,
+            base_fee_per_gas: payload.base_fee_per_gas,
+            block_hash: payload.block_hash,
+            transactions: payload.transactions,
+            withdrawals: payload
+                .withdrawals
+                .unwrap_or_default()
+                .into_iter()
+                .map(Withdrawal::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<ExecutionPayload> for engine::ExecutionPayload {
+    fn from(payload: ExecutionPayload) -> Self {
+        Self {
+            parent_hash: payload.parent_hash,
+            fee_recipient: payload.fee_recipient,
+            state_root: payload.state_root,
+            receipts_root: payload.receipts_root,
+            logs_bloom: payload.logs_bloom,
+            prev_randao: payload.prev_randao,
+            block_number: payload.block_number.into(),
+            gas_limit: payload.gas_limit.into(),
+            gas_used: payload.gas_used.into(),
+            timestamp: payload.timestamp.into(),
+            extra_data: payload.extra_data,
+            base_fee_per_gas: payload.base_fee_per_gas,
+            block_hash: payload.block_hash,
+            transactions: payload.transactions,
+            withdrawals: Some(
+                payload.withdrawals.into_iter().map(PrimitiveWithdrawal::from).collect(),
+            ),
+        }
+    }
+}
+
+/// A withdrawal, in the format of the beacon API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Withdrawal {
+    /// Monotonically increasing identifier issued by consensus layer.
+    #[serde(with = "quoted")]
+    pub index: u64,
+    /// Index of validator associated with withdrawal.
+    #[serde(with = "quoted")]
+    pub validator_index: u64,
+    /// Target address for withdrawn ether.
+    pub address: Address,
+    /// Value of the withdrawal in gwei.
+    #[serde(with = "quoted")]
+    pub amount: u64,
+}
+
+impl From<PrimitiveWithdrawal> for Withdrawal {
+    fn from(withdrawal: PrimitiveWithdrawal) -> Self {
+        let PrimitiveWithdrawal { index, validator_index, address, amount } = withdrawal;
+        Self { index, validator_index, address, amount }
+    }
+}
+
+impl From<Withdrawal> for PrimitiveWithdrawal {
+    fn from(withdrawal: Withdrawal) -> Self {
+        let Withdrawal { index, validator_index, address, amount } = withdrawal;
+        Self { index, validator_index, address, amount }
+    }
+}
+
+/// (De)serializes numbers as quoted decimal strings, as used by the beacon API.
+mod quoted {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub(crate) fn serialize<T, S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        s.collect_str(value)
+    }
+
+    pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_validators() {
+        let s = r#"[{"slot":"1","validator_index":"1","entry":{"message":{"fee_recipient":"0xabcf8e0d4e9587369b2301d0790347320302cc09","gas_limit":"30000000","timestamp":"1234567890","pubkey":"0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a"},"signature":"0x1b66ac1fb663c9bc59509846d6ec05345bd908eda73e670af888da41af171505cc411d61252fb6cb3fa0017b679f8bb2305b26a285fa2737f175668d0dff91cc1b66ac1fb663c9bc59509846d6ec05345bd908eda73e670af888da41af171505"}}]"#;
+        let validators = serde_json::from_str::<Vec<Validator>>(s).unwrap();
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators[0].slot, 1);
+        assert_eq!(validators[0].entry.message.gas_limit, 30_000_000);
+        assert_eq!(serde_json::to_string(&validators).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_bid_trace() {
+        let bid = BidTrace {
+            slot: 1,
+            gas_limit: 30_000_000,
+            value: U256::from(1_000_000_000_000_000_000u128),
+            ..Default::default()
+        };
+        let s = serde_json::to_string(&bid).unwrap();
+        assert!(s.contains(r#""slot":"1""#));
+        assert!(s.contains(r#""gas_limit":"30000000""#));
+        assert!(s.contains(r#""value":"1000000000000000000""#));
+        assert_eq!(serde_json::from_str::<BidTrace>(&s).unwrap(), bid);
+    }
+
+    #[test]
+    fn serde_builder_block_validation_request() {
+        let request = BuilderBlockValidationRequest {
+            request: SignedBidSubmission {
+                message: BidTrace { slot: 1, ..Default::default() },
+                execution_payload: ExecutionPayload::default(),
+                signature: BlsSignature::zero(),
+            },
+            registered_gas_limit: 30_000_000,
+            withdrawals_root: H256::zero(),
+        };
+        let s = serde_json::to_string(&request).unwrap();
+        assert!(s.starts_with(r#"{"message":{"slot":"1""#));
+        assert!(s.contains(r#""registered_gas_limit":"30000000""#));
+        assert_eq!(serde_json::from_str::<BuilderBlockValidationRequest>(&s).unwrap(), request);
+    }
+
+    #[test]
+    fn execution_payload_roundtrip() {
+        let payload = ExecutionPayload {
+            block_number: 2,
+            base_fee_per_gas: U256::from(7),
+            withdrawals: vec![Withdrawal {
+                index: 1,
+                validator_index: 2,
+                amount: 3,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let engine_payload = engine::ExecutionPayload::from(payload.clone());
+        assert_eq!(ExecutionPayload::from(engine_payload), payload);
+
+        let s = serde_json::to_string(&payload).unwrap();
+        assert!(s.contains(r#""block_number":"2""#));
+        assert!(s.contains(r#""base_fee_per_gas":"7""#));
+        assert_eq!(serde_json::from_str::<ExecutionPayload>(&s).unwrap(), payload);
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the `reth_syncStatus` response, which describes the progress of the sync pipeline.
+///
+/// Note: this format is specific to reth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    /// Whether the pipeline is currently running.
+    pub syncing: bool,
+    /// The name of the stage that is currently being executed, if any.
+    pub current_stage: Option<String>,
+    /// Estimated number of seconds until all stages are done, if all stages have an estimate.
+    pub eta: Option<u64>,
+    /// The progress of every stage, in the order of the pipeline.
+    pub stages: Vec<StageProgress>,
+}
+
+/// The progress of a single stage of the sync pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageProgress {
+    /// The name of the stage.
+    pub name: String,
+    /// The block number the stage has reached.
+    pub checkpoint: u64,
+    /// The number of entities the stage has processed.
+    ///
+    /// For stages that don't report entities, this is the block number the stage has reached.
+    pub processed: u64,
+    /// The total number of entities the stage has to process, if known.
+    ///
+    /// For stages that don't report entities, this is the block number reached by the previous
+    /// stage.
+    pub total: Option<u64>,
+    /// The number of entities processed per second during the last run of the stage.
+    pub throughput: Option<f64>,
+    /// Estimated number of seconds until the stage is done.
+    pub eta: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_sync_progress() {
+        let s = r#"{"syncing":true,"currentStage":"Execution","eta":null,"stages":[{"name":"Execution","checkpoint":10,"processed":100,"total":200,"throughput":12.5,"eta":8}]}"#;
+        let progress: SyncProgress = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            progress,
+            SyncProgress {
+                syncing: true,
+                current_stage: Some("Execution".to_string()),
+                eta: None,
+                stages: vec![StageProgress {
+                    name: "Execution".to_string(),
+                    checkpoint: 10,
+                    processed: 100,
+                    total: Some(200),
+                    throughput: Some(12.5),
+                    eta: Some(8),
+                }],
+            }
+        );
+        assert_eq!(serde_json::to_string(&progress).unwrap(), s);
+    }
+}
@@ -1,10 +1,15 @@
 use crate::result::ToRpcResult;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_network_api::{NetworkInfo, PeerKind, Peers};
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, server::SubscriptionMessage, PendingSubscriptionSink};
+use reth_network_api::{NetworkInfo, PeerEvent, PeerInfo, PeerKind, Peers};
 use reth_primitives::NodeRecord;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_types::{
+    ConnectedPeer, ConnectedPeerEthInfo, ConnectedPeerNetwork, ConnectedPeerProtocols, NodeInfo,
+    PeerEventType,
+};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 
 /// `admin` API implementation.
 ///
@@ -12,12 +17,21 @@ use reth_rpc_types::NodeInfo;
 pub struct AdminApi<N> {
     /// An interface to interact with the network
     network: N,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<N> AdminApi<N> {
     /// Creates a new instance of `AdminApi`.
+    ///
+    /// Subscription tasks are spawned via [tokio::task::spawn]
     pub fn new(network: N) -> Self {
-        AdminApi { network }
+        Self::with_spawner(network, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `AdminApi` that spawns subscription tasks with the given spawner.
+    pub fn with_spawner(network: N, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        AdminApi { network, subscription_task_spawner }
     }
 }
 
@@ -50,12 +64,56 @@ where
         Ok(true)
     }
 
+    /// Handler for `admin_peers`
+    async fn peers(&self) -> RpcResult<Vec<ConnectedPeer>> {
+        let peers = self.network.get_peers().await.to_rpc_result()?;
+        Ok(peers.into_iter().map(connected_peer).collect())
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
-        _pending: jsonrpsee::PendingSubscriptionSink,
+        pending: PendingSubscriptionSink,
     ) -> jsonrpsee::core::SubscriptionResult {
-        Err("admin_peerEvents is not implemented yet".into())
+        let sink = pending.accept().await?;
+        let mut events = self.network.peer_events().filter_map(|event| async move {
+            match event {
+                PeerEvent::SessionEstablished { peer_id, remote_addr, client_version, .. } => {
+                    Some(reth_rpc_types::PeerEvent {
+                        kind: PeerEventType::Add,
+                        peer: peer_id,
+                        error: None,
+                        remote: Some(remote_addr),
+                        name: Some(client_version.to_string()),
+                    })
+                }
+                PeerEvent::SessionClosed { peer_id, reason } => Some(reth_rpc_types::PeerEvent {
+                    kind: PeerEventType::Drop,
+                    peer: peer_id,
+                    error: reason.map(|reason| reason.to_string()),
+                    remote: None,
+                    name: None,
+                }),
+                PeerEvent::PeerAdded(_) | PeerEvent::PeerRemoved(_) => None,
+            }
+        });
+
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    event = events.next() => {
+                        let Some(event) = event else { break };
+                        let Ok(msg) = SubscriptionMessage::from_json(&event) else { break };
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
     }
 
     /// Handler for `admin_nodeInfo`
@@ -67,6 +125,35 @@ where
     }
 }
 
+/// Converts the [`PeerInfo`] of a session into the `admin_peers` representation.
+fn connected_peer(peer: PeerInfo) -> ConnectedPeer {
+    ConnectedPeer {
+        enode: NodeRecord::new(peer.remote_addr, peer.remote_id),
+        id: peer.remote_id,
+        name: peer.client_version.to_string(),
+        caps: peer
+            .capabilities
+            .capabilities()
+            .iter()
+            .map(|cap| format!("{}/{}", cap.name, cap.version))
+            .collect(),
+        network: ConnectedPeerNetwork {
+            local_address: peer.local_addr,
+            remote_address: peer.remote_addr,
+            inbound: peer.direction.is_incoming(),
+            trusted: peer.kind.is_trusted(),
+            reputation: peer.reputation,
+        },
+        protocols: ConnectedPeerProtocols {
+            eth: ConnectedPeerEthInfo {
+                version: peer.eth_version as u8,
+                difficulty: peer.status.total_difficulty,
+                head: peer.status.blockhash,
+            },
+        },
+    }
+}
+
 impl<N> std::fmt::Debug for AdminApi<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AdminApi").finish_non_exhaustive()
@@ -5,7 +5,8 @@ use crate::{
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
         revm_utils::{
             build_call_evm_env, caller_gas_allowance, cap_tx_gas_limit_with_caller_allowance,
-            get_precompiles, inspect, transact, EvmOverrides,
+            get_precompiles, inspect, prepare_call_env, replay_transactions_until, transact,
+            EvmOverrides,
         },
         EthTransactions,
     },
@@ -19,12 +20,15 @@ use reth_revm::{
     access_list::AccessListInspector,
     database::{State, SubState},
 };
-use reth_rpc_types::CallRequest;
+use reth_rpc_types::{
+    state::StateOverride, Bundle, CallRequest, EthCallResponse, StateContext, TransactionIndex,
+};
 use reth_transaction_pool::TransactionPool;
 use revm::{
     db::{CacheDB, DatabaseRef},
-    primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, Halt, TransactTo},
+    primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, Halt, ResultAndState, TransactTo},
 };
+use revm_primitives::db::DatabaseCommit;
 use tracing::trace;
 
 // Gas per transaction not creating a contract.
@@ -66,6 +70,78 @@ where
         ensure_success(res.result)
     }
 
+    /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
+    /// optionality of state overrides (`eth_callMany`)
+    ///
+    /// All bundles are executed in order on top of the same state: every call sees the changes of
+    /// all previous calls.
+    pub(crate) async fn call_many(
+        &self,
+        bundles: Vec<Bundle>,
+        state_context: Option<StateContext>,
+        mut state_override: Option<StateOverride>,
+    ) -> EthResult<Vec<Vec<EthCallResponse>>> {
+        let StateContext { block_number, transaction_index } = state_context.unwrap_or_default();
+        let transaction_index = transaction_index.unwrap_or_default();
+        let target_block = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+
+        let (cfg, block_env, at) = self.evm_env_at(target_block).await?;
+
+        // if only a prefix of the block's transactions should be applied, the calls are executed
+        // on top of the parent state with all transactions up to the target index replayed
+        let mut replay = None;
+        if let TransactionIndex::Index(index) = transaction_index {
+            let block = self.block_by_id(at).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+            if let Some(target_tx) = block.body.get(index) {
+                let target_tx_hash = target_tx.hash;
+                replay = Some((block.parent_hash, block.body, target_tx_hash));
+            }
+        }
+        let state_at = replay.as_ref().map(|(parent, _, _)| BlockId::from(*parent)).unwrap_or(at);
+
+        self.with_state_at_block(state_at, |state| {
+            let mut db = SubState::new(State::new(state));
+
+            if let Some((_, transactions, target_tx_hash)) = replay {
+                replay_transactions_until(
+                    &mut db,
+                    cfg.clone(),
+                    block_env.clone(),
+                    transactions,
+                    target_tx_hash,
+                )?;
+            }
+
+            let mut results = Vec::with_capacity(bundles.len());
+            for Bundle { transactions, block_override } in bundles {
+                let block_overrides = block_override.map(Box::new);
+                let mut bundle_results = Vec::with_capacity(transactions.len());
+
+                for tx in transactions {
+                    // the state overrides are applied once, before the first call
+                    let overrides =
+                        EvmOverrides::new(state_override.take(), block_overrides.clone());
+                    let env =
+                        prepare_call_env(cfg.clone(), block_env.clone(), tx, &mut db, overrides)?;
+                    let (ResultAndState { result, state }, _) = transact(&mut db, env)?;
+
+                    let response = match ensure_success(result) {
+                        Ok(output) => EthCallResponse { value: Some(output), error: None },
+                        Err(err) => EthCallResponse { value: None, error: Some(err.to_string()) },
+                    };
+                    bundle_results.push(response);
+
+                    // the following calls are executed on top of this call's state changes
+                    db.commit(state);
+                }
+
+                results.push(bundle_results);
+            }
+
+            Ok(results)
+        })
+    }
+
     /// Estimates the gas usage of the `request` with the state.
     ///
     /// This will execute the [CallRequest] and find the best gas limit via binary search
@@ -323,3 +399,126 @@ where
         ExecutionResult::Halt { reason, .. } => RpcInvalidTransactionError::EvmHalt(reason).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle};
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{
+        proofs, sign_message, Address, Block, Header, Transaction, TransactionKind,
+        TransactionSigned, TxEip1559, H256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_types::CallRequest;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// Increments the counter in slot zero and returns its new value.
+    const COUNTER_CODE: [u8; 18] = [
+        0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52, 0x60, 0x20,
+        0x60, 0x00, 0xf3,
+    ];
+
+    /// Returns an api on top of a block with the given transactions and a counter contract. The
+    /// parent of the block has the same state.
+    fn call_api(
+        counter: Address,
+        body: Vec<TransactionSigned>,
+    ) -> (EthApi<MockEthProvider, TestPool, NoopNetwork>, H256) {
+        let provider = MockEthProvider::default();
+        let header = Header {
+            parent_hash: H256::random(),
+            number: 17_034_870,
+            timestamp: 1_681_338_455,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(proofs::EMPTY_ROOT),
+            ..Default::default()
+        };
+        provider.add_historical_state(header.parent_hash);
+        let block = Block { header, body, ommers: vec![], withdrawals: Some(vec![]) };
+        let block_hash = block.header.hash_slow();
+        provider.add_block(block_hash, block);
+        provider.add_account(
+            counter,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from(COUNTER_CODE.to_vec())),
+        );
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork,
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+        );
+        (eth_api, block_hash)
+    }
+
+    fn counter_call(counter: Address) -> CallRequest {
+        CallRequest { to: Some(counter), ..Default::default() }
+    }
+
+    /// Returns the response of a counter call that returns the given value.
+    fn counter_value(value: u64) -> EthCallResponse {
+        EthCallResponse {
+            value: Some(Bytes::from(H256::from_low_u64_be(value).as_bytes().to_vec())),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn call_many_carries_state_across_calls_and_bundles() {
+        let counter = Address::random();
+        let (eth_api, _) = call_api(counter, vec![]);
+
+        let bundle = Bundle {
+            transactions: vec![counter_call(counter), counter_call(counter)],
+            block_override: None,
+        };
+        let results = eth_api.call_many(vec![bundle.clone(), bundle], None, None).await.unwrap();
+        assert_eq!(
+            results,
+            vec![
+                vec![counter_value(1), counter_value(2)],
+                vec![counter_value(3), counter_value(4)]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn call_many_replays_transactions_before_index() {
+        let counter = Address::random();
+        let secret = H256::from_low_u64_be(1);
+        let body = (0..3)
+            .map(|nonce| {
+                let transaction = Transaction::Eip1559(TxEip1559 {
+                    chain_id: 1,
+                    nonce,
+                    gas_limit: 100_000,
+                    max_fee_per_gas: 10,
+                    max_priority_fee_per_gas: 1,
+                    to: TransactionKind::Call(counter),
+                    ..Default::default()
+                });
+                let signature = sign_message(secret, transaction.signature_hash()).unwrap();
+                TransactionSigned::from_transaction_and_signature(transaction, signature)
+            })
+            .collect::<Vec<_>>();
+        let signer = body[0].recover_signer().unwrap();
+
+        let (eth_api, block_hash) = call_api(counter, body);
+        eth_api.provider().add_account(signer, ExtendedAccount::new(0, U256::from(u64::MAX)));
+
+        // the calls are executed on top of the parent state and all transactions before the index
+        for (index, expected) in [(0, 1), (1, 2), (2, 3)] {
+            let state_context = StateContext {
+                block_number: Some(block_hash.into()),
+                transaction_index: Some(TransactionIndex::Index(index)),
+            };
+            let bundle = Bundle { transactions: vec![counter_call(counter)], block_override: None };
+            let results = eth_api.call_many(vec![bundle], Some(state_context), None).await.unwrap();
+            assert_eq!(results, vec![vec![counter_value(expected)]]);
+        }
+    }
+}
@@ -25,6 +25,7 @@ mod call;
 mod fees;
 mod server;
 mod sign;
+mod simulate;
 mod state;
 mod transactions;
 
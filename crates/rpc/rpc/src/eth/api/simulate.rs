@@ -0,0 +1,648 @@
+//! Contains the implementation of the `eth_simulateV1` handler.
+
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        revm_utils::{
+            apply_state_overrides, build_call_evm_env, cap_tx_gas_limit_with_caller_allowance,
+            transact,
+        },
+        EthTransactions,
+    },
+    EthApi,
+};
+use reth_interfaces::{provider::ProviderError, Error as InterfacesError};
+use reth_network_api::NetworkInfo;
+use reth_primitives::{
+    bloom::logs_bloom, constants::EMPTY_OMMER_ROOT, proofs, AccessList, AccessListItem, BlockId,
+    BlockNumberOrTag, Bytecode, Bytes, Header, Receipt, SealedHeader, Signature, Transaction,
+    TransactionKind, TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930,
+    TxLegacy, H256, U256,
+};
+use reth_provider::{
+    post_state::{PostState, StorageChangeset},
+    BlockReaderIdExt, EvmEnvProvider, HeaderProvider, StateProvider, StateProviderFactory,
+};
+use reth_revm::{
+    database::{State, SubState},
+    env::fill_block_env_with_coinbase,
+    executor::commit_state_changes,
+    into_reth_log, to_reth_acc,
+};
+use reth_rpc_types::{
+    error::EthRpcErrorCode, Block, BlockOverrides, BlockTransactions, Log, SimBlock, SimCallResult,
+    SimulateError, SimulatePayload, SimulatedBlock, StateOverride, Transaction as RpcTransaction,
+    MAX_SIMULATE_BLOCKS,
+};
+use reth_transaction_pool::TransactionPool;
+use revm::{
+    db::CacheDB,
+    primitives::{ExecutionResult, ResultAndState, SpecId, TransactTo, TxEnv},
+};
+use revm_primitives::db::DatabaseRef;
+
+/// The error code of a simulated call that failed with an EVM error other than a revert.
+const SIMULATE_VM_ERROR_CODE: i32 = -32015;
+
+impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider: BlockReaderIdExt + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+{
+    /// Simulates the given blocks on top of the given block (`eth_simulateV1`).
+    ///
+    /// Every block is built on top of the previous one, so later blocks see the state changes of
+    /// earlier ones. Header fields that are not overridden are derived from the parent block.
+    pub(crate) async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> EthResult<Vec<SimulatedBlock>> {
+        let SimulatePayload { block_state_calls, validation, return_full_transactions } = payload;
+        if block_state_calls.len() > MAX_SIMULATE_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many blocks, at most {MAX_SIMULATE_BLOCKS} blocks can be simulated"
+            )))
+        }
+
+        let base_block = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let (mut cfg, block_env, at) = self.evm_env_at(base_block).await?;
+        let base_block = self.block_by_id(at).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let base_total_difficulty =
+            self.provider().header_td(&base_block.hash)?.unwrap_or_default();
+
+        if !validation {
+            // without validation the calls are executed like `eth_call`
+            cfg.disable_base_fee = true;
+            cfg.disable_block_gas_limit = true;
+            cfg.disable_eip3607 = true;
+        }
+        let chain_id: u64 = cfg.chain_id.try_into().unwrap_or_default();
+        let after_merge = cfg.spec_id >= SpecId::MERGE;
+        let has_state_clear_eip = cfg.spec_id >= SpecId::SPURIOUS_DRAGON;
+
+        self.with_state_at_block(at, |state| {
+            let mut db = SubState::new(State::new(state));
+            // the accumulated changes of all simulated blocks, used to compute their state roots
+            let mut post_state = PostState::default();
+
+            let mut parent = base_block.header;
+            let mut total_difficulty = base_total_difficulty;
+            let mut blocks = Vec::with_capacity(block_state_calls.len());
+
+            for SimBlock { block_overrides, state_overrides, calls } in block_state_calls {
+                let mut header = Header {
+                    parent_hash: parent.hash,
+                    ommers_hash: EMPTY_OMMER_ROOT,
+                    beneficiary: parent.beneficiary,
+                    difficulty: parent.difficulty,
+                    number: parent.number + 1,
+                    gas_limit: parent.gas_limit,
+                    timestamp: parent.timestamp + 12,
+                    mix_hash: parent.mix_hash,
+                    base_fee_per_gas: parent.next_block_base_fee(),
+                    withdrawals_root: parent.withdrawals_root.map(|_| proofs::EMPTY_ROOT),
+                    ..Default::default()
+                };
+                if let Some(block_overrides) = block_overrides {
+                    apply_block_overrides(block_overrides, &mut header);
+                }
+                if header.number <= parent.number {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block number {} is not greater than the parent block number {}",
+                        header.number, parent.number
+                    )))
+                }
+                if header.timestamp <= parent.timestamp {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block timestamp {} is not greater than the parent block timestamp {}",
+                        header.timestamp, parent.timestamp
+                    )))
+                }
+
+                let mut block_env = block_env.clone();
+                fill_block_env_with_coinbase(
+                    &mut block_env,
+                    &header,
+                    after_merge,
+                    header.beneficiary,
+                );
+
+                if let Some(state_overrides) = state_overrides {
+                    apply_simulated_state_overrides(
+                        state_overrides,
+                        &mut db,
+                        &mut post_state,
+                        header.number,
+                    )?;
+                }
+
+                let mut cumulative_gas_used = 0u64;
+                let mut transactions = Vec::with_capacity(calls.len());
+                let mut receipts = Vec::with_capacity(calls.len());
+                let mut call_results = Vec::with_capacity(calls.len());
+
+                for call in calls {
+                    let request_gas = call.gas;
+                    let mut env = build_call_evm_env(cfg.clone(), block_env.clone(), call)?;
+                    if request_gas.is_none() {
+                        // the calls can use the remaining gas of the block
+                        env.tx.gas_limit = header.gas_limit.saturating_sub(cumulative_gas_used);
+                        if !validation && env.tx.gas_price > U256::ZERO {
+                            cap_tx_gas_limit_with_caller_allowance(&mut db, &mut env.tx)?;
+                        }
+                    }
+
+                    let nonce = match env.tx.nonce {
+                        Some(nonce) => nonce,
+                        None => db.basic(env.tx.caller)?.unwrap_or_default().nonce,
+                    };
+                    if validation {
+                        // calls without a nonce use the nonce of the account, which is then
+                        // enforced like the nonce of a transaction
+                        env.tx.nonce = Some(nonce);
+                    }
+                    let transaction = simulated_transaction(&env.tx, nonce, chain_id);
+
+                    let (ResultAndState { result, state }, _) = transact(&mut db, env)?;
+                    commit_state_changes(
+                        &mut db,
+                        &mut post_state,
+                        header.number,
+                        state,
+                        has_state_clear_eip,
+                    );
+
+                    let gas_used = result.gas_used();
+                    cumulative_gas_used += gas_used;
+
+                    let logs: Vec<_> = result.logs().into_iter().map(into_reth_log).collect();
+                    let (return_data, error) = match result {
+                        ExecutionResult::Success { output, .. } => {
+                            (output.into_data().into(), None)
+                        }
+                        ExecutionResult::Revert { output, .. } => {
+                            let error = SimulateError {
+                                code: EthRpcErrorCode::ExecutionError.code(),
+                                message: RevertError::new(output.clone()).to_string(),
+                            };
+                            (output.into(), Some(error))
+                        }
+                        ExecutionResult::Halt { reason, .. } => {
+                            let error = SimulateError {
+                                code: SIMULATE_VM_ERROR_CODE,
+                                message: RpcInvalidTransactionError::halt(
+                                    reason,
+                                    transaction.gas_limit(),
+                                )
+                                .to_string(),
+                            };
+                            (Bytes::default(), Some(error))
+                        }
+                    };
+
+                    receipts.push(
+                        Receipt {
+                            tx_type: transaction.tx_type(),
+                            success: error.is_none(),
+                            cumulative_gas_used,
+                            logs,
+                        }
+                        .with_bloom(),
+                    );
+                    call_results.push(SimCallResult {
+                        return_data,
+                        logs: Vec::new(),
+                        gas_used: gas_used.into(),
+                        status: u64::from(error.is_none()).into(),
+                        error,
+                    });
+                    transactions.push(transaction);
+                }
+
+                header.gas_used = cumulative_gas_used;
+                header.transactions_root = proofs::calculate_transaction_root(&transactions);
+                header.receipts_root = proofs::calculate_receipt_root(&receipts);
+                header.logs_bloom =
+                    logs_bloom(receipts.iter().flat_map(|receipt| receipt.receipt.logs.iter()));
+                header.state_root = match db.db.state().state_root(post_state.clone()) {
+                    Ok(state_root) => state_root,
+                    // the state root can only be computed on top of the latest state, blocks
+                    // simulated on top of a historical block report a zero state root
+                    Err(InterfacesError::Provider(
+                        ProviderError::StateRootNotAvailableForHistoricalBlock,
+                    )) => H256::zero(),
+                    Err(err) => return Err(err.into()),
+                };
+
+                let header = header.seal_slow();
+                total_difficulty += header.difficulty;
+
+                // populate the logs of the calls with the block context
+                let mut log_index = 0usize;
+                for (tx_index, (call_result, receipt)) in
+                    call_results.iter_mut().zip(receipts).enumerate()
+                {
+                    for log in receipt.receipt.logs {
+                        call_result.logs.push(Log {
+                            block_hash: Some(header.hash),
+                            block_number: Some(U256::from(header.number)),
+                            transaction_hash: Some(transactions[tx_index].hash),
+                            transaction_index: Some(U256::from(tx_index)),
+                            log_index: Some(U256::from(log_index)),
+                            ..Log::from_primitive(log)
+                        });
+                        log_index += 1;
+                    }
+                }
+
+                blocks.push(SimulatedBlock {
+                    inner: simulated_block(
+                        header.clone(),
+                        transactions,
+                        total_difficulty,
+                        return_full_transactions,
+                    ),
+                    calls: call_results,
+                });
+                parent = header;
+            }
+
+            Ok(blocks)
+        })
+    }
+}
+
+/// Applies the state overrides of a simulated block and records them in the post state, so they
+/// are part of the state root of the block.
+fn apply_simulated_state_overrides<DB>(
+    overrides: StateOverride,
+    db: &mut CacheDB<DB>,
+    post_state: &mut PostState,
+    block_number: u64,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    for (address, account_override) in overrides {
+        let replaces_storage = account_override.state.is_some();
+        let slots = account_override
+            .state
+            .iter()
+            .chain(account_override.state_diff.iter())
+            .flat_map(|slots| slots.keys())
+            .map(|slot| U256::from_be_bytes(slot.0))
+            .collect::<Vec<_>>();
+        let old_account = db.basic(address)?;
+        let old_slots = slots
+            .into_iter()
+            .map(|slot| Ok((slot, db.storage(address, slot)?)))
+            .collect::<EthResult<Vec<_>>>()?;
+
+        apply_state_overrides(StateOverride::from([(address, account_override)]), db)?;
+
+        let new_account = db.basic(address)?.unwrap_or_default();
+        if let Some(code) = new_account.code.clone() {
+            post_state.add_bytecode(new_account.code_hash, Bytecode(code));
+        }
+        let new_account = to_reth_acc(&new_account);
+        match old_account {
+            Some(old_account) if replaces_storage => {
+                // the overridden storage replaces the storage of the account
+                post_state.destroy_account(block_number, address, to_reth_acc(&old_account));
+                post_state.create_account(block_number, address, new_account);
+            }
+            Some(old_account) => {
+                post_state.change_account(
+                    block_number,
+                    address,
+                    to_reth_acc(&old_account),
+                    new_account,
+                );
+            }
+            None => post_state.create_account(block_number, address, new_account),
+        }
+
+        let changeset = old_slots
+            .into_iter()
+            .map(|(slot, old)| Ok((slot, (old, db.storage(address, slot)?))))
+            .collect::<EthResult<StorageChangeset>>()?;
+        if !changeset.is_empty() {
+            post_state.change_storage(block_number, address, changeset);
+        }
+    }
+    Ok(())
+}
+
+/// Applies the given block overrides to the header of a simulated block.
+fn apply_block_overrides(overrides: BlockOverrides, header: &mut Header) {
+    let BlockOverrides { number, difficulty, time, gas_limit, coinbase, random, base_fee } =
+        overrides;
+
+    if let Some(number) = number {
+        header.number = number.try_into().unwrap_or(u64::MAX);
+    }
+    if let Some(difficulty) = difficulty {
+        header.difficulty = difficulty;
+    }
+    if let Some(time) = time {
+        header.timestamp = time.as_u64();
+    }
+    if let Some(gas_limit) = gas_limit {
+        header.gas_limit = gas_limit.as_u64();
+    }
+    if let Some(coinbase) = coinbase {
+        header.beneficiary = coinbase;
+    }
+    if let Some(random) = random {
+        header.mix_hash = random;
+    }
+    if let Some(base_fee) = base_fee {
+        header.base_fee_per_gas = Some(base_fee.try_into().unwrap_or(u64::MAX));
+    }
+}
+
+/// Creates the transaction of a simulated call from its [TxEnv].
+///
+/// The transaction is not signed, it carries an empty signature and the caller as its signer.
+fn simulated_transaction(tx: &TxEnv, nonce: u64, chain_id: u64) -> TransactionSignedEcRecovered {
+    let to = match tx.transact_to {
+        TransactTo::Call(to) => TransactionKind::Call(to),
+        TransactTo::Create(_) => TransactionKind::Create,
+    };
+    let gas_price = tx.gas_price.try_into().unwrap_or(u128::MAX);
+    let value = tx.value.try_into().unwrap_or(u128::MAX);
+    let input = Bytes(tx.data.clone());
+    let access_list = AccessList(
+        tx.access_list
+            .iter()
+            .map(|(address, storage_keys)| AccessListItem {
+                address: *address,
+                storage_keys: storage_keys
+                    .iter()
+                    .map(|key| H256(key.to_be_bytes::<32>()))
+                    .collect(),
+            })
+            .collect(),
+    );
+
+    let transaction = if let Some(max_priority_fee_per_gas) = tx.gas_priority_fee {
+        Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.try_into().unwrap_or(u128::MAX),
+            to,
+            value,
+            access_list,
+            input,
+        })
+    } else if !access_list.0.is_empty() {
+        Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value,
+            access_list,
+            input,
+        })
+    } else {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value,
+            input,
+        })
+    };
+
+    let signed =
+        TransactionSigned::from_transaction_and_signature(transaction, Signature::default());
+    TransactionSignedEcRecovered::from_signed_transaction(signed, tx.caller)
+}
+
+/// Creates the rpc [Block] of a simulated block.
+fn simulated_block(
+    header: SealedHeader,
+    transactions: Vec<TransactionSignedEcRecovered>,
+    total_difficulty: U256,
+    full_transactions: bool,
+) -> Block {
+    let transactions = if full_transactions {
+        BlockTransactions::Full(
+            transactions
+                .into_iter()
+                .enumerate()
+                .map(|(idx, tx)| {
+                    RpcTransaction::from_recovered_with_block_context(
+                        tx,
+                        header.hash,
+                        header.number,
+                        header.base_fee_per_gas,
+                        U256::from(idx),
+                    )
+                })
+                .collect(),
+        )
+    } else {
+        BlockTransactions::Hashes(transactions.iter().map(|tx| tx.hash).collect())
+    };
+    let withdrawals = header.withdrawals_root.map(|_| Vec::new());
+
+    Block {
+        header: reth_rpc_types::Header::from_primitive_with_hash(header),
+        total_difficulty: Some(total_difficulty),
+        uncles: Vec::new(),
+        transactions,
+        size: None,
+        withdrawals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle};
+    use assert_matches::assert_matches;
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{Address, Block};
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        StateRootProvider,
+    };
+    use reth_rpc_types::{state::AccountOverride, CallRequest};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    const SENDER_BALANCE: u64 = 1_000_000_000_000_000_000;
+
+    /// Returns an api on top of a shanghai block with a funded sender.
+    fn simulate_api(sender: Address) -> EthApi<MockEthProvider, TestPool, NoopNetwork> {
+        let provider = MockEthProvider::default();
+        let header = Header {
+            number: 17_034_870,
+            timestamp: 1_681_338_455,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(proofs::EMPTY_ROOT),
+            ..Default::default()
+        };
+        let block = Block { header, body: vec![], ommers: vec![], withdrawals: Some(vec![]) };
+        provider.add_block(block.header.hash_slow(), block);
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(SENDER_BALANCE)));
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork,
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+        )
+    }
+
+    fn transfer(from: Address, to: Address, value: u64) -> CallRequest {
+        CallRequest {
+            from: Some(from),
+            to: Some(to),
+            value: Some(U256::from(value)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn simulate_transfers_across_blocks() {
+        let sender = Address::random();
+        let recipient = Address::random();
+        let eth_api = simulate_api(sender);
+
+        let block =
+            SimBlock { calls: vec![transfer(sender, recipient, 1000)], ..Default::default() };
+        let payload = SimulatePayload {
+            block_state_calls: vec![block.clone(), block],
+            validation: false,
+            return_full_transactions: false,
+        };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].inner.header.number, Some(U256::from(17_034_871)));
+        assert_eq!(blocks[1].inner.header.number, Some(U256::from(17_034_872)));
+        assert_eq!(blocks[1].inner.header.parent_hash, blocks[0].inner.header.hash.unwrap());
+        assert!(blocks.iter().all(|block| block.calls[0].error.is_none()));
+
+        // the second block is executed on top of the state of the first one
+        let expected = MockEthProvider::default();
+        expected.add_account(sender, ExtendedAccount::new(2, U256::from(SENDER_BALANCE - 2000)));
+        expected.add_account(recipient, ExtendedAccount::new(0, U256::from(2000)));
+        assert_eq!(
+            blocks[1].inner.header.state_root,
+            expected.state_root(PostState::default()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_state_overrides_in_state_root() {
+        let sender = Address::random();
+        let overridden = Address::random();
+        let eth_api = simulate_api(sender);
+
+        let account_override = AccountOverride {
+            balance: Some(U256::from(1000)),
+            code: Some(Bytes::from(vec![0x00])),
+            state_diff: Some([(H256::from_low_u64_be(1), H256::from_low_u64_be(2))].into()),
+            ..Default::default()
+        };
+        let block = SimBlock {
+            state_overrides: Some([(overridden, account_override)].into()),
+            ..Default::default()
+        };
+        let payload = SimulatePayload { block_state_calls: vec![block], ..Default::default() };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+
+        let expected = MockEthProvider::default();
+        expected.add_account(sender, ExtendedAccount::new(0, U256::from(SENDER_BALANCE)));
+        expected.add_account(
+            overridden,
+            ExtendedAccount::new(0, U256::from(1000))
+                .with_bytecode(Bytes::from(vec![0x00]))
+                .extend_storage([(H256::from_low_u64_be(1), U256::from(2))]),
+        );
+        assert_eq!(
+            blocks[0].inner.header.state_root,
+            expected.state_root(PostState::default()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_validation_checks_nonces() {
+        let sender = Address::random();
+        let eth_api = simulate_api(sender);
+        let call = CallRequest {
+            max_fee_per_gas: Some(U256::from(1_000_000_000)),
+            ..transfer(sender, Address::random(), 1000)
+        };
+
+        // calls without a nonce use the nonce of the sender
+        let block = SimBlock { calls: vec![call.clone(), call.clone()], ..Default::default() };
+        let payload = SimulatePayload {
+            block_state_calls: vec![block],
+            validation: true,
+            return_full_transactions: true,
+        };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+        let BlockTransactions::Full(transactions) = &blocks[0].inner.transactions else {
+            panic!("expected full transactions")
+        };
+        let nonces = transactions.iter().map(|tx| tx.nonce).collect::<Vec<_>>();
+        assert_eq!(nonces, vec![U256::ZERO, U256::from(1)]);
+
+        // a nonce that does not match the sender is rejected
+        let block = SimBlock {
+            calls: vec![CallRequest { nonce: Some(U256::from(5)), ..call }],
+            ..Default::default()
+        };
+        let payload = SimulatePayload {
+            block_state_calls: vec![block],
+            validation: true,
+            return_full_transactions: false,
+        };
+        assert!(eth_api.simulate_v1(payload, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn simulate_rejects_non_increasing_blocks() {
+        let eth_api = simulate_api(Address::random());
+
+        let block = SimBlock {
+            block_overrides: Some(BlockOverrides {
+                number: Some(U256::from(17_034_870)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let payload = SimulatePayload { block_state_calls: vec![block], ..Default::default() };
+        assert_matches!(
+            eth_api.simulate_v1(payload, None).await,
+            Err(EthApiError::InvalidParams(_))
+        );
+
+        let block = SimBlock {
+            block_overrides: Some(BlockOverrides {
+                time: Some(1_681_338_455u64.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let payload = SimulatePayload { block_state_calls: vec![block], ..Default::default() };
+        assert_matches!(
+            eth_api.simulate_v1(payload, None).await,
+            Err(EthApiError::InvalidParams(_))
+        );
+    }
+}
@@ -25,10 +25,10 @@ use reth_revm::{
     tracing::{TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
-    CallRequest, Index, Log, Transaction, TransactionInfo, TransactionReceipt, TransactionRequest,
-    TypedTransactionRequest,
+    CallRequest, Index, Log, SendBundleRequest, Transaction, TransactionInfo, TransactionReceipt,
+    TransactionRequest, TypedTransactionRequest,
 };
-use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{PoolBundle, TransactionOrigin, TransactionPool};
 use revm::{
     db::CacheDB,
     primitives::{BlockEnv, CfgEnv},
@@ -112,6 +112,23 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<H256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool as a private transaction
+    /// that is not propagated to peers and is valid until the block `max_block_number`.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: u64,
+    ) -> EthResult<H256>;
+
+    /// Decodes and recovers the transactions of the bundle and submits the bundle to the pool.
+    ///
+    /// Either the whole bundle is added to the pool or none of it.
+    ///
+    /// Returns the hash of the bundle.
+    async fn send_bundle(&self, bundle: SendBundleRequest) -> EthResult<H256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<H256>;
@@ -411,6 +428,43 @@ where
         Ok(hash)
     }
 
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: u64,
+    ) -> EthResult<H256> {
+        let recovered = recover_raw_transaction(tx)?;
+        let pool_transaction = <Pool::Transaction>::from_recovered_transaction(recovered);
+
+        // submit the transaction to the pool with a `Private` origin
+        let hash = self.pool().add_private_transaction(pool_transaction, max_block_number).await?;
+
+        Ok(hash)
+    }
+
+    async fn send_bundle(&self, bundle: SendBundleRequest) -> EthResult<H256> {
+        let SendBundleRequest {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+        } = bundle;
+        let transactions = txs
+            .into_iter()
+            .map(|tx| {
+                recover_raw_transaction(tx).map(<Pool::Transaction>::from_recovered_transaction)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bundle = PoolBundle::new(transactions, block_number.as_u64())
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes);
+        let hash = self.pool().add_bundle(bundle).await?;
+
+        Ok(hash)
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<H256> {
         let from = match request.from {
             Some(from) => from,
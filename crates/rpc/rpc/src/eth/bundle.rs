@@ -0,0 +1,493 @@
+//! `Eth` bundle implementation and helpers.
+
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult, RpcInvalidTransactionError},
+        revm_utils::transact,
+        utils::recover_raw_transaction,
+        EthTransactions,
+    },
+    EthApiSpec, TracingCallGuard,
+};
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{abi::decode_revert_reason, keccak256, BlockId, Bytes, H256, U256};
+use reth_revm::{
+    database::{State, SubState},
+    env::tx_env_with_recovered,
+    into_reth_log,
+};
+use reth_rpc_api::EthBundleApiServer;
+use reth_rpc_types::{
+    CallBundleRequest, CallBundleResponse, CallBundleTransactionResult, Log,
+    PrivateTransactionPreferences, SendBundleRequest, SendBundleResponse, SignedU256,
+};
+use reth_tasks::TaskSpawner;
+use revm::{
+    primitives::{Env, ExecutionResult, ResultAndState},
+    Database,
+};
+use revm_primitives::db::DatabaseCommit;
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The number of blocks a private transaction is valid for if no max block number is provided.
+pub const DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS: u64 = 25;
+
+/// `Eth` bundle implementation.
+///
+/// Simulates bundles of signed transactions on top of a given state, see `eth_callBundle`, and
+/// submits bundles and private transactions to the pool, see `eth_sendBundle` and
+/// `eth_sendPrivateRawTransaction`.
+pub struct EthBundle<Eth> {
+    /// All nested fields bundled together.
+    inner: Arc<EthBundleInner<Eth>>,
+}
+
+impl<Eth> EthBundle<Eth> {
+    /// Create a new `EthBundle` instance.
+    pub fn new(
+        eth_api: Eth,
+        task_spawner: Box<dyn TaskSpawner>,
+        blocking_task_guard: TracingCallGuard,
+    ) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, task_spawner, blocking_task_guard }) }
+    }
+}
+
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Simulates a bundle of transactions at the top of a given block number with the state of
+    /// another (or the same) block. This can be used to simulate future blocks with the current
+    /// state, or it can be used to simulate a past block. The sender is responsible for signing
+    /// the transactions and using the correct nonce and ensuring validity.
+    pub async fn call_bundle(&self, bundle: CallBundleRequest) -> EthResult<CallBundleResponse> {
+        let CallBundleRequest {
+            txs,
+            block_number,
+            state_block_number,
+            timestamp,
+            coinbase,
+            gas_limit,
+            difficulty,
+            base_fee,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        if block_number.as_u64() == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+
+        let transactions =
+            txs.into_iter().map(recover_raw_transaction).collect::<Result<Vec<_>, _>>()?;
+
+        let block_id: BlockId = state_block_number.into();
+        let (cfg, mut block_env, at) = self.inner.eth_api.evm_env_at(block_id).await?;
+        let state_block =
+            self.inner.eth_api.block_by_id(at).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        // the bundle is simulated as part of the requested block on top of the state block, so
+        // the block env is adjusted accordingly
+        block_env.number = U256::from(block_number.as_u64());
+        block_env.timestamp = U256::from(timestamp.unwrap_or(state_block.timestamp + 12));
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(difficulty) = difficulty {
+            block_env.difficulty = difficulty;
+        }
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = base_fee;
+        } else if let Some(next_base_fee) = state_block.next_block_base_fee() {
+            block_env.basefee = U256::from(next_base_fee);
+        }
+
+        let state_block_number = state_block.number;
+        let coinbase = block_env.coinbase;
+        let basefee: u64 = block_env.basefee.try_into().unwrap_or(u64::MAX);
+
+        self.on_blocking_task(|this| async move {
+            // limit the number of concurrent bundle simulations
+            let _permit = this
+                .inner
+                .blocking_task_guard
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|_| EthApiError::InternalEthError)?;
+
+            this.inner.eth_api.with_state_at_block(at, |state| {
+                let mut db = SubState::new(State::new(state));
+
+                let coinbase_balance_before = db.basic(coinbase)?.unwrap_or_default().balance;
+                let mut coinbase_balance_before_tx = coinbase_balance_before;
+                let mut total_gas_used = 0u64;
+                let mut total_gas_fees = U256::ZERO;
+                let mut hash_bytes = Vec::with_capacity(32 * transactions.len());
+                let mut results = Vec::with_capacity(transactions.len());
+
+                for tx in transactions {
+                    hash_bytes.extend_from_slice(tx.hash.as_bytes());
+
+                    let gas_price = tx
+                        .effective_tip_per_gas(basefee)
+                        .ok_or(RpcInvalidTransactionError::FeeCapTooLow)?;
+                    let env = Env {
+                        cfg: cfg.clone(),
+                        block: block_env.clone(),
+                        tx: tx_env_with_recovered(&tx),
+                    };
+                    let (ResultAndState { result, state }, _) = transact(&mut db, env)?;
+
+                    let gas_used = result.gas_used();
+                    total_gas_used += gas_used;
+
+                    let gas_fees = U256::from(gas_used) * U256::from(gas_price);
+                    total_gas_fees += gas_fees;
+
+                    // coinbase is always present in the result state
+                    let coinbase_balance_after_tx = state
+                        .get(&coinbase)
+                        .map(|acc| acc.info.balance)
+                        .unwrap_or(coinbase_balance_before_tx);
+                    // the coinbase may pay for the transaction itself, so the diff can be negative
+                    let coinbase_diff =
+                        SignedU256::diff(coinbase_balance_after_tx, coinbase_balance_before_tx);
+                    let eth_sent_to_coinbase = coinbase_diff.saturating_sub(gas_fees);
+
+                    // update the coinbase balance for the next transaction
+                    coinbase_balance_before_tx = coinbase_balance_after_tx;
+
+                    let mut tx_res = CallBundleTransactionResult {
+                        coinbase_diff,
+                        eth_sent_to_coinbase,
+                        from_address: tx.signer(),
+                        gas_fees,
+                        gas_price: U256::from(gas_price),
+                        gas_used,
+                        to_address: tx.to(),
+                        tx_hash: tx.hash,
+                        ..Default::default()
+                    };
+                    match result {
+                        ExecutionResult::Success { output, logs, .. } => {
+                            tx_res.value = Some(output.into_data().into());
+                            tx_res.logs = logs
+                                .into_iter()
+                                .map(|log| Log::from_primitive(into_reth_log(log)))
+                                .collect();
+                        }
+                        ExecutionResult::Revert { output, .. } => {
+                            tx_res.error = Some("execution reverted".to_string());
+                            tx_res.revert = decode_revert_reason(&output);
+                        }
+                        ExecutionResult::Halt { reason, .. } => {
+                            tx_res.error = Some(
+                                RpcInvalidTransactionError::halt(reason, tx.gas_limit())
+                                    .to_string(),
+                            );
+                        }
+                    }
+                    results.push(tx_res);
+
+                    // the next transaction is executed on top of this one
+                    db.commit(state)
+                }
+
+                // populate the response
+                let coinbase_balance_after = db.basic(coinbase)?.unwrap_or_default().balance;
+                let coinbase_diff =
+                    SignedU256::diff(coinbase_balance_after, coinbase_balance_before);
+                let eth_sent_to_coinbase = coinbase_diff.saturating_sub(total_gas_fees);
+                let bundle_gas_price = coinbase_diff.div_euclid(U256::from(total_gas_used));
+
+                Ok(CallBundleResponse {
+                    bundle_hash: keccak256(&hash_bytes),
+                    bundle_gas_price,
+                    coinbase_diff,
+                    eth_sent_to_coinbase,
+                    gas_fees: total_gas_fees,
+                    results,
+                    state_block_number,
+                    total_gas_used,
+                })
+            })
+        })
+        .await
+    }
+}
+
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + EthApiSpec + 'static,
+{
+    /// Submits a bundle of signed transactions to the pool.
+    ///
+    /// The transactions are not propagated to peers. The local payload builder includes the
+    /// bundle atomically and in order at the top of the block with the bundle's block number, if
+    /// its timestamp bounds are met and none of its transactions reverts unless allowed to.
+    pub async fn send_bundle(&self, bundle: SendBundleRequest) -> EthResult<SendBundleResponse> {
+        if bundle.txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        let block_number = bundle.block_number.as_u64();
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+        self.ensure_not_expired(block_number)?;
+
+        let bundle_hash = self.inner.eth_api.send_bundle(bundle).await?;
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Submits a signed transaction to the pool without propagating it to peers.
+    ///
+    /// The transaction is included by the local payload builder until its max block number was
+    /// mined, which defaults to [DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS] after the current block.
+    pub async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        preferences: PrivateTransactionPreferences,
+    ) -> EthResult<H256> {
+        let max_block_number = match preferences.max_block_number {
+            Some(max_block_number) => {
+                let max_block_number = max_block_number.as_u64();
+                self.ensure_not_expired(max_block_number)?;
+                max_block_number
+            }
+            None => {
+                self.inner.eth_api.chain_info()?.best_number +
+                    DEFAULT_PRIVATE_TRANSACTION_MAX_BLOCKS
+            }
+        };
+
+        self.inner.eth_api.send_private_raw_transaction(tx, max_block_number).await
+    }
+
+    /// Returns an error if the block with the given number was already mined.
+    fn ensure_not_expired(&self, block_number: u64) -> EthResult<()> {
+        if block_number <= self.inner.eth_api.chain_info()?.best_number {
+            return Err(EthApiError::InvalidParams(EthBundleError::BlockNumberInPast.to_string()))
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + EthApiSpec + 'static,
+{
+    /// Handler for `eth_callBundle`
+    async fn call_bundle(&self, request: CallBundleRequest) -> RpcResult<CallBundleResponse> {
+        Ok(EthBundle::call_bundle(self, request).await?)
+    }
+
+    /// Handler for `eth_sendBundle`
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Ok(EthBundle::send_bundle(self, request).await?)
+    }
+
+    /// Handler for `eth_sendPrivateRawTransaction`
+    async fn send_private_raw_transaction(
+        &self,
+        bytes: Bytes,
+        preferences: Option<PrivateTransactionPreferences>,
+    ) -> RpcResult<H256> {
+        Ok(EthBundle::send_private_raw_transaction(self, bytes, preferences.unwrap_or_default())
+            .await?)
+    }
+}
+
+/// Container type for `EthBundle` internals
+struct EthBundleInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Restricts the number of concurrent bundle simulations.
+    blocking_task_guard: TracingCallGuard,
+}
+
+impl<Eth> std::fmt::Debug for EthBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthBundle").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for EthBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// [EthBundle] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum EthBundleError {
+    /// Thrown if the bundle does not contain any transactions.
+    #[error("bundle missing txs")]
+    EmptyBundleTransactions,
+    /// Thrown if the bundle does not contain a block number, or block number is 0.
+    #[error("bundle missing blockNumber")]
+    BundleMissingBlockNumber,
+    /// Thrown if the block number a bundle or private transaction is valid for was already mined.
+    #[error("block number is in the past")]
+    BlockNumberInPast,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi,
+    };
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{
+        proofs, sign_message, Address, Block, BlockNumberOrTag, Header, Transaction,
+        TransactionKind, TransactionSigned, TxEip1559, U64,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// The secret key of the sender of all transactions.
+    fn secret() -> H256 {
+        H256::from_low_u64_be(1)
+    }
+
+    /// Returns a bundle api on top of a block, with the sender of all transactions funded.
+    fn bundle_api() -> EthBundle<EthApi<MockEthProvider, TestPool, NoopNetwork>> {
+        let provider = MockEthProvider::default();
+        let header = Header {
+            number: 17_034_870,
+            timestamp: 1_681_338_455,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(proofs::EMPTY_ROOT),
+            ..Default::default()
+        };
+        let block = Block { header, body: vec![], ommers: vec![], withdrawals: Some(vec![]) };
+        provider.add_block(block.header.hash_slow(), block);
+        provider.add_account(
+            transfer(Address::zero(), 0, 0).recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(u64::MAX)),
+        );
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork,
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+        );
+        EthBundle::new(eth_api, Box::new(TokioTaskExecutor::default()), TracingCallGuard::new(1))
+    }
+
+    /// Returns a transfer that pays a priority fee of 1 wei per gas at a
+    /// base fee of 7 wei.
+    fn transfer(to: Address, value: u128, nonce: u64) -> TransactionSigned {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TransactionKind::Call(to),
+            value,
+            ..Default::default()
+        });
+        let signature = sign_message(secret(), transaction.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(transaction, signature)
+    }
+
+    fn call_bundle_request(txs: &[TransactionSigned], coinbase: Address) -> CallBundleRequest {
+        CallBundleRequest {
+            txs: txs.iter().map(|tx| tx.envelope_encoded().into()).collect(),
+            block_number: U64::from(17_034_871),
+            state_block_number: BlockNumberOrTag::Latest,
+            timestamp: None,
+            coinbase: Some(coinbase),
+            gas_limit: None,
+            difficulty: None,
+            base_fee: Some(U256::from(7)),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_bundle_reports_coinbase_payment() {
+        let eth_bundle = bundle_api();
+        let coinbase = Address::random();
+
+        // the second transaction is only valid on top of the first one
+        let txs = [transfer(coinbase, 1000, 0), transfer(Address::random(), 0, 1)];
+        let response = eth_bundle.call_bundle(call_bundle_request(&txs, coinbase)).await.unwrap();
+
+        let results = response
+            .results
+            .iter()
+            .map(|res| {
+                assert_eq!(res.error, None);
+                (res.coinbase_diff.to_string(), res.eth_sent_to_coinbase.to_string(), res.gas_fees)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                ("22000".to_string(), "1000".to_string(), U256::from(21_000)),
+                ("21000".to_string(), "0".to_string(), U256::from(21_000)),
+            ]
+        );
+        assert_eq!(response.coinbase_diff.to_string(), "43000");
+        assert_eq!(response.eth_sent_to_coinbase.to_string(), "1000");
+        assert_eq!(response.gas_fees, U256::from(42_000));
+        assert_eq!(response.bundle_gas_price.to_string(), "1");
+        assert_eq!(response.total_gas_used, 42_000);
+    }
+
+    #[tokio::test]
+    async fn call_bundle_reports_negative_coinbase_diff() {
+        let eth_bundle = bundle_api();
+        let tx = transfer(Address::random(), 1000, 0);
+        let coinbase = tx.recover_signer().unwrap();
+
+        // the coinbase pays the value and the base fee, and only receives the priority fee back
+        let response = eth_bundle.call_bundle(call_bundle_request(&[tx], coinbase)).await.unwrap();
+        assert_eq!(response.coinbase_diff.to_string(), "-148000");
+        assert_eq!(response.eth_sent_to_coinbase.to_string(), "-169000");
+        assert_eq!(response.gas_fees, U256::from(21_000));
+        assert_eq!(response.bundle_gas_price.to_string(), "-8");
+        assert_eq!(response.results[0].coinbase_diff, response.coinbase_diff);
+    }
+}
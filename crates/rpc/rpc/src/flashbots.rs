@@ -0,0 +1,621 @@
+//! `flashbots` namespace implementation.
+
+use crate::result::{internal_rpc_err, invalid_params_rpc_err};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_consensus_common::validation::{
+    validate_block_standalone, validate_header_regarding_parent,
+};
+use reth_interfaces::{
+    consensus::ConsensusError, executor::BlockExecutionError, provider::ProviderError,
+};
+use reth_primitives::{
+    gas_limit::calculate_next_block_gas_limit, Address, Header, SealedBlock, H256, U256,
+};
+use reth_provider::{
+    BlockExecutor, BlockReaderIdExt, BlockSource, ChainSpecProvider, StateProvider,
+    StateProviderFactory, StateRootProvider,
+};
+use reth_revm::{
+    database::{State, SubState},
+    executor::Executor,
+};
+use reth_rpc_api::FlashbotsApiServer;
+use reth_rpc_types::{
+    engine::{ExecutionPayload, PayloadError},
+    relay::{BuilderBlockValidationRequest, SignedBidSubmission},
+};
+use reth_tasks::TaskSpawner;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::oneshot;
+use tracing::trace;
+
+/// Settings for the `flashbots` namespace.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FlashbotsConfig {
+    /// Accounts that must not be touched by validated blocks, e.g. sanctioned addresses.
+    pub blacklist: HashSet<Address>,
+}
+
+/// `flashbots` API implementation.
+///
+/// This type provides the functionality relays need from an execution client to validate the
+/// blocks submitted by builders.
+pub struct FlashbotsApi<Provider> {
+    inner: Arc<FlashbotsApiInner<Provider>>,
+}
+
+// === impl FlashbotsApi ===
+
+impl<Provider> FlashbotsApi<Provider> {
+    /// Create a new instance of the [FlashbotsApi]
+    pub fn new(
+        provider: Provider,
+        config: FlashbotsConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(FlashbotsApiInner { provider, config, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider> FlashbotsApi<Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + 'static,
+{
+    /// Validates the block of the given builder submission on a new blocking task.
+    ///
+    /// See [Self::validate_builder_submission_v2_blocking].
+    pub async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), FlashbotsApiError> {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = this.validate_builder_submission_v2_blocking(request);
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| {
+            FlashbotsApiError::Internal(reth_interfaces::Error::Custom(
+                "block validation task dropped".to_string(),
+            ))
+        })?
+    }
+
+    /// Validates the block of the given builder submission.
+    ///
+    /// The block is checked against the bid and executed on top of the state of its parent,
+    /// without being inserted. The parent can be any block of the database or a pending block of
+    /// the blockchain tree, but the state root can only be computed on top of the latest block or
+    /// a pending block extending it. The block is valid if:
+    ///  - it matches the bid trace and the expected withdrawals root
+    ///  - it is valid in regards to its parent
+    ///  - its gas limit moves towards the gas limit registered by the proposer
+    ///  - it executes and results in the header's receipts root and state root
+    ///  - it pays at least the bid value to the proposer's fee recipient
+    ///  - it does not touch any blacklisted account
+    pub fn validate_builder_submission_v2_blocking(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), FlashbotsApiError> {
+        let BuilderBlockValidationRequest {
+            request: SignedBidSubmission { message, execution_payload, .. },
+            registered_gas_limit,
+            withdrawals_root,
+        } = request;
+
+        let block = SealedBlock::try_from(ExecutionPayload::from(execution_payload))?;
+        trace!(target: "rpc::flashbots", hash=?block.hash(), number=block.number, "validating builder submission");
+
+        // the block must match the bid
+        if message.block_hash != block.hash() {
+            return Err(FlashbotsApiError::BlockHashMismatch {
+                bid: message.block_hash,
+                block: block.hash(),
+            })
+        }
+        if message.parent_hash != block.parent_hash {
+            return Err(FlashbotsApiError::ParentHashMismatch {
+                bid: message.parent_hash,
+                block: block.parent_hash,
+            })
+        }
+        if message.gas_limit != block.gas_limit {
+            return Err(FlashbotsApiError::GasLimitMismatch {
+                bid: message.gas_limit,
+                block: block.gas_limit,
+            })
+        }
+        if message.gas_used != block.gas_used {
+            return Err(FlashbotsApiError::GasUsedMismatch {
+                bid: message.gas_used,
+                block: block.gas_used,
+            })
+        }
+        if block.withdrawals_root != Some(withdrawals_root) {
+            return Err(FlashbotsApiError::WithdrawalsRootMismatch {
+                expected: withdrawals_root,
+                got: block.withdrawals_root,
+            })
+        }
+
+        let senders = block.senders().ok_or(FlashbotsApiError::InvalidTransactionSignature)?;
+        let blacklist = &self.inner.config.blacklist;
+        if let Some(account) = [block.beneficiary, message.proposer_fee_recipient]
+            .into_iter()
+            .chain(senders.iter().copied())
+            .chain(block.body.iter().filter_map(|tx| tx.to()))
+            .find(|account| blacklist.contains(account))
+        {
+            return Err(FlashbotsApiError::Blacklisted(account))
+        }
+
+        // the block must be valid in regards to its parent
+        let provider = &self.inner.provider;
+        let chain_spec = provider.chain_spec();
+        let parent = self.header(block.parent_hash)?.seal(block.parent_hash);
+        validate_block_standalone(&block, &chain_spec)?;
+        validate_header_regarding_parent(&parent, &block.header, &chain_spec)?;
+
+        let expected_gas_limit =
+            calculate_next_block_gas_limit(parent.gas_limit, registered_gas_limit);
+        if block.gas_limit != expected_gas_limit {
+            return Err(FlashbotsApiError::IncorrectGasLimit {
+                expected: expected_gas_limit,
+                got: block.gas_limit,
+            })
+        }
+
+        // execute the block on top of the parent's state
+        let total_difficulty = self.total_difficulty(block.parent_hash)? + block.difficulty;
+
+        let state = provider.state_by_block_hash(block.parent_hash)?;
+        let balance_before =
+            state.account_balance(message.proposer_fee_recipient)?.unwrap_or_default();
+
+        let mut executor = Executor::new(Arc::clone(&chain_spec), SubState::new(State::new(state)));
+        let post_state = executor.execute_and_verify_receipt(
+            &block.clone().unseal(),
+            total_difficulty,
+            Some(senders),
+        )?;
+
+        if let Some(account) =
+            post_state.accounts().keys().find(|account| blacklist.contains(*account))
+        {
+            return Err(FlashbotsApiError::Blacklisted(*account))
+        }
+
+        let balance_after = match post_state.account(&message.proposer_fee_recipient) {
+            Some(account) => account.map(|account| account.balance).unwrap_or_default(),
+            None => balance_before,
+        };
+        let payment = balance_after.saturating_sub(balance_before);
+        if payment < message.value {
+            return Err(FlashbotsApiError::InsufficientProposerPayment {
+                expected: message.value,
+                got: payment,
+            })
+        }
+
+        let state_root = provider
+            .state_by_block_hash(block.parent_hash)?
+            .state_root(post_state)
+            .map_err(|err| match err {
+                reth_interfaces::Error::Provider(
+                    ProviderError::StateRootNotAvailableForHistoricalBlock,
+                ) => FlashbotsApiError::StateRootUnavailable(block.parent_hash),
+                err => err.into(),
+            })?;
+        if state_root != block.state_root {
+            return Err(FlashbotsApiError::StateRootMismatch {
+                expected: block.state_root,
+                got: state_root,
+            })
+        }
+
+        Ok(())
+    }
+
+    /// Returns the header of the given block of the database or pending block of the tree.
+    fn header(&self, hash: H256) -> Result<Header, FlashbotsApiError> {
+        let provider = &self.inner.provider;
+        if let Some(header) = provider.header(&hash)? {
+            return Ok(header)
+        }
+        provider
+            .find_block_by_hash(hash, BlockSource::Pending)?
+            .map(|block| block.header)
+            .ok_or(FlashbotsApiError::UnknownParent(hash))
+    }
+
+    /// Returns the total difficulty of the given block.
+    ///
+    /// The total difficulty of pending blocks of the tree is derived from their closest ancestor
+    /// in the database.
+    fn total_difficulty(&self, mut hash: H256) -> Result<U256, FlashbotsApiError> {
+        let provider = &self.inner.provider;
+        let mut difficulty = U256::ZERO;
+        loop {
+            if let Some(td) = provider.header_td(&hash)? {
+                return Ok(td + difficulty)
+            }
+            let header = provider
+                .find_block_by_hash(hash, BlockSource::Pending)?
+                .map(|block| block.header)
+                .ok_or(FlashbotsApiError::UnknownParent(hash))?;
+            difficulty += header.difficulty;
+            hash = header.parent_hash;
+        }
+    }
+}
+
+#[async_trait]
+impl<Provider> FlashbotsApiServer for FlashbotsApi<Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + 'static,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        Ok(FlashbotsApi::validate_builder_submission_v2(self, request).await?)
+    }
+}
+
+impl<Provider> std::fmt::Debug for FlashbotsApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlashbotsApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for FlashbotsApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct FlashbotsApiInner<Provider> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Settings of the namespace.
+    config: FlashbotsConfig,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Reasons why a builder submission is invalid.
+#[derive(Debug, thiserror::Error)]
+pub enum FlashbotsApiError {
+    /// The execution payload could not be converted into a block.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// The hash of the block does not match the bid.
+    #[error("block hash mismatch: bid {bid:?}, block {block:?}")]
+    BlockHashMismatch {
+        /// The block hash of the bid.
+        bid: H256,
+        /// The hash of the block.
+        block: H256,
+    },
+    /// The parent hash of the block does not match the bid.
+    #[error("parent hash mismatch: bid {bid:?}, block {block:?}")]
+    ParentHashMismatch {
+        /// The parent hash of the bid.
+        bid: H256,
+        /// The parent hash of the block.
+        block: H256,
+    },
+    /// The gas limit of the block does not match the bid.
+    #[error("gas limit mismatch: bid {bid}, block {block}")]
+    GasLimitMismatch {
+        /// The gas limit of the bid.
+        bid: u64,
+        /// The gas limit of the block.
+        block: u64,
+    },
+    /// The gas used by the block does not match the bid.
+    #[error("gas used mismatch: bid {bid}, block {block}")]
+    GasUsedMismatch {
+        /// The gas used of the bid.
+        bid: u64,
+        /// The gas used of the block.
+        block: u64,
+    },
+    /// The withdrawals root of the block is not the expected one.
+    #[error("withdrawals root mismatch: expected {expected:?}, got {got:?}")]
+    WithdrawalsRootMismatch {
+        /// The withdrawals root expected by the relay.
+        expected: H256,
+        /// The withdrawals root of the block.
+        got: Option<H256>,
+    },
+    /// A transaction of the block has an invalid signature.
+    #[error("invalid transaction signature")]
+    InvalidTransactionSignature,
+    /// The block touches a blacklisted account.
+    #[error("block touches blacklisted account {0:?}")]
+    Blacklisted(Address),
+    /// The parent of the block is unknown.
+    #[error("unknown parent block {0:?}")]
+    UnknownParent(H256),
+    /// The block is invalid in regards to its parent.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// The gas limit of the block does not move towards the proposer's registered gas limit.
+    #[error("incorrect gas limit: expected {expected}, got {got}")]
+    IncorrectGasLimit {
+        /// The gas limit derived from the parent and the registered gas limit.
+        expected: u64,
+        /// The gas limit of the block.
+        got: u64,
+    },
+    /// The block failed to execute.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// The block pays the proposer less than the bid value.
+    #[error("insufficient proposer payment: expected {expected}, got {got}")]
+    InsufficientProposerPayment {
+        /// The value of the bid.
+        expected: U256,
+        /// The balance increase of the proposer's fee recipient.
+        got: U256,
+    },
+    /// The state root can not be computed on top of the parent's state, because the parent is
+    /// neither the latest block nor a pending block extending it.
+    #[error("state root not available on top of parent block {0:?}")]
+    StateRootUnavailable(H256),
+    /// The state root of the block does not match the state root after execution.
+    #[error("state root mismatch: expected {expected:?}, got {got:?}")]
+    StateRootMismatch {
+        /// The state root of the block.
+        expected: H256,
+        /// The state root after executing the block.
+        got: H256,
+    },
+    /// An internal error occurred while validating the block.
+    #[error(transparent)]
+    Internal(#[from] reth_interfaces::Error),
+}
+
+impl From<FlashbotsApiError> for ErrorObject<'static> {
+    fn from(error: FlashbotsApiError) -> Self {
+        match error {
+            FlashbotsApiError::Internal(_) => internal_rpc_err(error.to_string()),
+            _ => invalid_params_rpc_err(error.to_string()),
+        }
+    }
+}
+
+impl From<FlashbotsApiError> for jsonrpsee::core::Error {
+    fn from(error: FlashbotsApiError) -> Self {
+        jsonrpsee::core::Error::Call(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::generators::sign_tx_with_key_pair;
+    use reth_primitives::{
+        proofs, Receipt, SealedHeader, Transaction, TransactionKind, TxEip1559, TxType,
+        EMPTY_OMMER_ROOT,
+    };
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        PostState,
+    };
+    use reth_rpc_types::relay::{BidTrace, BlsSignature};
+    use reth_tasks::TokioTaskExecutor;
+    use secp256k1::{KeyPair, Secp256k1, SecretKey};
+
+    /// Returns an api with a known parent and an empty child block on top of it.
+    fn api_with_child(config: FlashbotsConfig) -> (FlashbotsApi<MockEthProvider>, SealedBlock) {
+        let parent = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            timestamp: 1_700_000_000,
+            // the parent reaches the terminal total difficulty of mainnet
+            difficulty: U256::from(58_750_000_000_000_000_000_000_u128),
+            ..Default::default()
+        }
+        .seal_slow();
+        let provider = MockEthProvider::default();
+        provider.add_header(parent.hash(), parent.header.clone());
+
+        let child = Header {
+            parent_hash: parent.hash(),
+            ommers_hash: EMPTY_OMMER_ROOT,
+            transactions_root: proofs::calculate_transaction_root(&[]),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            number: 2,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: parent.next_block_base_fee(),
+            timestamp: parent.timestamp + 12,
+            ..Default::default()
+        }
+        .seal_slow();
+        let block = SealedBlock { header: child, withdrawals: Some(vec![]), ..Default::default() };
+
+        let api = FlashbotsApi::new(provider, config, Box::<TokioTaskExecutor>::default());
+        (api, block)
+    }
+
+    fn request(block: &SealedBlock, registered_gas_limit: u64) -> BuilderBlockValidationRequest {
+        let SealedHeader { header, hash } = &block.header;
+        BuilderBlockValidationRequest {
+            request: SignedBidSubmission {
+                message: BidTrace {
+                    slot: 1,
+                    parent_hash: header.parent_hash,
+                    block_hash: *hash,
+                    gas_limit: header.gas_limit,
+                    gas_used: header.gas_used,
+                    ..Default::default()
+                },
+                execution_payload: block.clone().into(),
+                signature: BlsSignature::zero(),
+            },
+            registered_gas_limit,
+            withdrawals_root: header.withdrawals_root.unwrap(),
+        }
+    }
+
+    /// Sets the receipts root and state root of the given empty block to the ones after executing
+    /// it on top of the state of the provider.
+    fn executed_empty_block(block: SealedBlock, provider: &MockEthProvider) -> SealedBlock {
+        let header = Header {
+            receipts_root: proofs::calculate_receipt_root(&[]),
+            state_root: provider.state_root(PostState::default()).unwrap(),
+            ..block.header.header
+        }
+        .seal_slow();
+        SealedBlock { header, ..block }
+    }
+
+    #[test]
+    fn rejects_bid_mismatch() {
+        let (api, block) = api_with_child(FlashbotsConfig::default());
+        let mut request = request(&block, 30_000_000);
+        request.request.message.block_hash = H256::random();
+        assert!(matches!(
+            api.validate_builder_submission_v2_blocking(request),
+            Err(FlashbotsApiError::BlockHashMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_blacklisted_fee_recipient() {
+        let blacklisted = Address::random();
+        let config = FlashbotsConfig { blacklist: HashSet::from([blacklisted]) };
+        let (api, block) = api_with_child(config);
+        let mut request = request(&block, 30_000_000);
+        request.request.message.proposer_fee_recipient = blacklisted;
+        assert!(matches!(
+            api.validate_builder_submission_v2_blocking(request),
+            Err(FlashbotsApiError::Blacklisted(account)) if account == blacklisted
+        ));
+    }
+
+    #[test]
+    fn rejects_unregistered_gas_limit() {
+        let (api, block) = api_with_child(FlashbotsConfig::default());
+        let request = request(&block, 36_000_000);
+        assert!(matches!(
+            api.validate_builder_submission_v2_blocking(request),
+            Err(FlashbotsApiError::IncorrectGasLimit { expected, got: 30_000_000 })
+                if expected == calculate_next_block_gas_limit(30_000_000, 36_000_000)
+        ));
+    }
+
+    #[test]
+    fn validates_block_on_top_of_older_parent() {
+        let (api, block) = api_with_child(FlashbotsConfig::default());
+        let provider = &api.inner.provider;
+        let latest = Header { number: 2, ..Default::default() }.seal_slow();
+        provider.add_header(latest.hash(), latest.header.clone());
+        provider.add_historical_state(block.parent_hash);
+        let block = executed_empty_block(block, provider);
+
+        api.validate_builder_submission_v2_blocking(request(&block, 30_000_000)).unwrap();
+    }
+
+    #[test]
+    fn validates_block_on_top_of_pending_parent() {
+        let (api, parent) = api_with_child(FlashbotsConfig::default());
+        let provider = &api.inner.provider;
+        // the parent is only known to the tree, so it has no total difficulty
+        provider.blocks.lock().insert(parent.hash(), parent.clone().unseal());
+        provider.add_historical_state(parent.hash());
+
+        let child = Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            base_fee_per_gas: parent.next_block_base_fee(),
+            timestamp: parent.timestamp + 12,
+            ..parent.header.header.clone()
+        }
+        .seal_slow();
+        let block = executed_empty_block(SealedBlock { header: child, ..parent }, provider);
+
+        api.validate_builder_submission_v2_blocking(request(&block, 30_000_000)).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_parent() {
+        let (api, mut block) = api_with_child(FlashbotsConfig::default());
+        block.header = Header { parent_hash: H256::random(), ..block.header.header }.seal_slow();
+        assert!(matches!(
+            api.validate_builder_submission_v2_blocking(request(&block, 30_000_000)),
+            Err(FlashbotsApiError::UnknownParent(hash)) if hash == block.parent_hash
+        ));
+    }
+
+    #[test]
+    fn validates_block_paying_proposer() {
+        let (api, block) = api_with_child(FlashbotsConfig::default());
+        let provider = &api.inner.provider;
+
+        let secp = Secp256k1::new();
+        let key_pair = KeyPair::from_secret_key(&secp, &SecretKey::from_slice(&[1; 32]).unwrap());
+        let fee_recipient = Address::random();
+        let transaction = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce: 0,
+                gas_limit: 21_000,
+                max_fee_per_gas: 1_000_000_000,
+                max_priority_fee_per_gas: 0,
+                to: TransactionKind::Call(fee_recipient),
+                value: 1000,
+                ..Default::default()
+            }),
+        );
+        let sender = transaction.recover_signer().unwrap();
+        let balance = U256::from(1_000_000_000_000_000_000_u64);
+        provider.add_account(sender, ExtendedAccount::new(0, balance));
+
+        // the state after paying the proposer and the fee of the transaction
+        let expected = MockEthProvider::default();
+        expected.add_account(
+            sender,
+            ExtendedAccount::new(1, balance - U256::from(1000 + 21_000 * 1_000_000_000_u64)),
+        );
+        expected.add_account(fee_recipient, ExtendedAccount::new(0, U256::from(1000)));
+
+        let receipt = Receipt {
+            tx_type: TxType::EIP1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![],
+        }
+        .with_bloom();
+        let header = Header {
+            gas_used: 21_000,
+            transactions_root: proofs::calculate_transaction_root(&[transaction.clone()]),
+            receipts_root: proofs::calculate_receipt_root(&[receipt]),
+            state_root: expected.state_root(PostState::default()).unwrap(),
+            ..block.header.header.clone()
+        }
+        .seal_slow();
+        let block = SealedBlock { header, body: vec![transaction], ..block };
+
+        let mut request = request(&block, 30_000_000);
+        request.request.message.proposer_fee_recipient = fee_recipient;
+        request.request.message.value = U256::from(1000);
+        api.validate_builder_submission_v2_blocking(request.clone()).unwrap();
+
+        // the proposer must be paid at least the value of the bid
+        request.request.message.value = U256::from(1001);
+        assert!(matches!(
+            api.validate_builder_submission_v2_blocking(request),
+            Err(FlashbotsApiError::InsufficientProposerPayment { .. })
+        ));
+    }
+}
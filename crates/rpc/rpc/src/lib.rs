@@ -35,8 +35,10 @@ mod call_guard;
 mod debug;
 mod engine;
 pub mod eth;
+mod flashbots;
 mod layers;
 mod net;
+mod reth;
 mod rpc;
 mod trace;
 mod txpool;
@@ -46,9 +48,11 @@ pub use admin::AdminApi;
 pub use call_guard::TracingCallGuard;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
+pub use eth::{EthApi, EthApiSpec, EthBundle, EthFilter, EthPubSub, EthSubscriptionIdProvider};
+pub use flashbots::{FlashbotsApi, FlashbotsApiError, FlashbotsConfig};
 pub use layers::{AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret};
 pub use net::NetApi;
+pub use reth::RethApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
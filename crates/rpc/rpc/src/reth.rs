@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, server::SubscriptionMessage, PendingSubscriptionSink};
+use reth_rpc_api::RethApiServer;
+use reth_rpc_types::SyncProgress;
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+
+/// `reth` API implementation.
+///
+/// This type provides the functionality for handling `reth` related requests.
+pub struct RethApi {
+    /// The latest progress of the sync pipeline.
+    sync_status: watch::Receiver<SyncProgress>,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl RethApi {
+    /// Creates a new instance of `RethApi`.
+    ///
+    /// Subscription tasks are spawned via [tokio::task::spawn]
+    pub fn new(sync_status: watch::Receiver<SyncProgress>) -> Self {
+        Self::with_spawner(sync_status, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `RethApi` that spawns subscription tasks with the given spawner.
+    pub fn with_spawner(
+        sync_status: watch::Receiver<SyncProgress>,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        RethApi { sync_status, subscription_task_spawner }
+    }
+}
+
+#[async_trait]
+impl RethApiServer for RethApi {
+    /// Handler for `reth_syncStatus`
+    fn sync_status(&self) -> RpcResult<SyncProgress> {
+        Ok(self.sync_status.borrow().clone())
+    }
+
+    /// Handler for `reth_subscribeSyncStatus`
+    async fn subscribe_sync_status(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        // yields the current progress first and then every update
+        let mut updates = WatchStream::new(self.sync_status.clone());
+
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    progress = updates.next() => {
+                        let Some(progress) = progress else { break };
+                        let Ok(msg) = SubscriptionMessage::from_json(&progress) else { break };
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for RethApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethApi").field("sync_status", &self.sync_status).finish_non_exhaustive()
+    }
+}
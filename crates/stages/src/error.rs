@@ -36,6 +36,18 @@ pub enum StageError {
         /// The error that occurred when attempting to attach the header.
         error: Box<consensus::ConsensusError>,
     },
+    /// The stage downloaded a block that is invalid.
+    ///
+    /// Unlike a [StageError::Validation] error, the invalid block and its descendants were not
+    /// written to the database, only its ancestors need to be kept.
+    #[error("Stage downloaded an invalid block {number}: {error}.", number = block.number)]
+    BadBlock {
+        /// The invalid block.
+        block: SealedHeader,
+        /// The underlying consensus error.
+        #[source]
+        error: consensus::ConsensusError,
+    },
     /// The stage encountered a database error.
     #[error("An internal database error occurred: {0}")]
     Database(#[from] DbError),
@@ -65,6 +77,9 @@ pub enum StageError {
     /// Internal error
     #[error(transparent)]
     Internal(#[from] reth_interfaces::Error),
+    /// The stage failed to spill or read back the entries of an [ETL collector][crate::etl].
+    #[error("ETL collector error: {0}")]
+    Etl(#[from] std::io::Error),
     /// The stage encountered a recoverable error.
     ///
     /// These types of errors are caught by the [Pipeline][crate::Pipeline] and trigger a restart
@@ -88,6 +103,7 @@ impl StageError {
                 StageError::DatabaseIntegrity(_) |
                 StageError::StageCheckpoint(_) |
                 StageError::ChannelClosed |
+                StageError::Etl(_) |
                 StageError::Fatal(_)
         )
     }
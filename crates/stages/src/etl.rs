@@ -0,0 +1,284 @@
+//! ETL (extract, transform, load) collector for bulk loading tables in key order.
+//!
+//! Some stages produce table entries in an order that differs from the key order of the table,
+//! e.g. entries keyed by a hash. Inserting them directly results in random writes, while buffering
+//! them all in memory to sort them first results in huge memory spikes.
+//!
+//! A [Collector] buffers the encoded entries up to a memory budget. Once the budget is exceeded,
+//! the buffer is sorted and spilled to a temporary file. All sorted runs are finally merged, which
+//! yields the entries in the order of the database, so that they can be appended to the table.
+
+use rayon::slice::ParallelSliceMut;
+use reth_db::table::{Compress, Encode, Key, Value};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    mem::size_of,
+};
+
+/// The default memory budget of a [Collector], in bytes.
+pub const DEFAULT_ETL_BUFFER_SIZE: usize = 256 * 1024 * 1024;
+
+/// An encoded key and compressed value pair.
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// The estimated bookkeeping overhead of the allocator for every heap allocation, in bytes.
+const ALLOCATION_OVERHEAD: usize = 2 * size_of::<usize>();
+
+/// Collects entries of a table and returns them sorted in the order of the database.
+///
+/// Entries are ordered by their encoded keys and then by their compressed values, which matches
+/// the order of keys and duplicate values in the database.
+pub struct Collector<K, V> {
+    /// Entries that are not yet spilled to disk.
+    buffer: Vec<Entry>,
+    /// The size of the heap allocations of the buffered keys and values, in bytes.
+    buffer_size: usize,
+    /// The memory budget, in bytes.
+    buffer_capacity: usize,
+    /// Temporary files that contain the sorted runs.
+    runs: Vec<File>,
+    /// The total number of collected entries.
+    len: usize,
+    _marker: PhantomData<(K, V)>,
+}
+
+// === impl Collector ===
+
+impl<K: Key, V: Value> Collector<K, V> {
+    /// Creates a new collector that spills its entries to disk once they exceed the given number
+    /// of bytes.
+    pub fn new(buffer_capacity: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            buffer_size: 0,
+            buffer_capacity,
+            runs: Vec::new(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of collected entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no entries were collected.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the memory used by the buffered entries, in bytes.
+    ///
+    /// Besides the encoded keys and values, this accounts for the allocated slots of the buffer
+    /// and the overhead of the allocator for every key and value.
+    fn memory_size(&self) -> usize {
+        self.buffer.capacity() * size_of::<Entry>() + self.buffer_size
+    }
+
+    /// Collects the given entry.
+    ///
+    /// Spills the buffered entries to disk if the memory budget is exceeded.
+    pub fn insert(&mut self, key: K, value: V) -> io::Result<()> {
+        let key = key.encode().as_ref().to_vec();
+        let value = value.compress().as_ref().to_vec();
+        self.buffer_size += key.capacity() + value.capacity() + 2 * ALLOCATION_OVERHEAD;
+        self.buffer.push((key, value));
+        self.len += 1;
+
+        if self.memory_size() >= self.buffer_capacity {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Sorts the buffered entries and writes them to a new temporary file.
+    ///
+    /// The allocated slots of the buffer are released as well, since they count against the
+    /// memory budget.
+    fn spill(&mut self) -> io::Result<()> {
+        self.buffer.par_sort_unstable();
+
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        for (key, value) in self.buffer.drain(..) {
+            writer.write_all(&(key.len() as u32).to_le_bytes())?;
+            writer.write_all(&(value.len() as u32).to_le_bytes())?;
+            writer.write_all(&key)?;
+            writer.write_all(&value)?;
+        }
+        let mut file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+
+        self.runs.push(file);
+        self.buffer = Vec::new();
+        self.buffer_size = 0;
+        Ok(())
+    }
+
+    /// Consumes the collector and returns an iterator over all collected entries in the order of
+    /// the database.
+    ///
+    /// The entries are returned as raw bytes, see [RawKey](reth_db::RawKey) and
+    /// [RawValue](reth_db::RawValue).
+    pub fn into_sorted(mut self) -> io::Result<SortedEntries> {
+        self.buffer.par_sort_unstable();
+
+        let mut runs: Vec<_> =
+            self.runs.into_iter().map(|file| Run::File(BufReader::new(file))).collect();
+        if !self.buffer.is_empty() {
+            runs.push(Run::Memory(self.buffer.into_iter()))
+        }
+        SortedEntries::new(runs)
+    }
+}
+
+impl<K, V> fmt::Debug for Collector<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collector")
+            .field("buffer_size", &self.buffer_size)
+            .field("buffer_capacity", &self.buffer_capacity)
+            .field("runs", &self.runs.len())
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A sorted run of entries.
+enum Run {
+    /// A run that was spilled to disk.
+    File(BufReader<File>),
+    /// The remaining buffer of the collector.
+    Memory(std::vec::IntoIter<Entry>),
+}
+
+impl Run {
+    /// Returns the next entry of the run.
+    fn next_entry(&mut self) -> io::Result<Option<Entry>> {
+        let reader = match self {
+            Run::File(reader) => reader,
+            Run::Memory(entries) => return Ok(entries.next()),
+        };
+
+        let mut lengths = [0u8; 8];
+        match reader.read_exact(&mut lengths) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let (key_len, value_len) = lengths.split_at(4);
+        let mut key = vec![0u8; u32::from_le_bytes(key_len.try_into().unwrap()) as usize];
+        let mut value = vec![0u8; u32::from_le_bytes(value_len.try_into().unwrap()) as usize];
+        reader.read_exact(&mut key)?;
+        reader.read_exact(&mut value)?;
+        Ok(Some((key, value)))
+    }
+}
+
+/// Iterator over the entries of a [Collector] in the order of the database.
+///
+/// Performs a k-way merge of the sorted runs of the collector.
+pub struct SortedEntries {
+    runs: Vec<Run>,
+    /// The next entry of every run that is not exhausted, along with the index of the run.
+    heap: BinaryHeap<Reverse<(Entry, usize)>>,
+}
+
+impl SortedEntries {
+    fn new(mut runs: Vec<Run>) -> io::Result<Self> {
+        let mut heap = BinaryHeap::with_capacity(runs.len());
+        for (idx, run) in runs.iter_mut().enumerate() {
+            if let Some(entry) = run.next_entry()? {
+                heap.push(Reverse((entry, idx)));
+            }
+        }
+        Ok(Self { runs, heap })
+    }
+}
+
+impl Iterator for SortedEntries {
+    type Item = io::Result<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((entry, idx)) = self.heap.pop()?;
+        match self.runs[idx].next_entry() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, idx))),
+            Ok(None) => {}
+            Err(err) => return Some(Err(err)),
+        }
+        Some(Ok(entry))
+    }
+}
+
+impl fmt::Debug for SortedEntries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortedEntries").field("runs", &self.runs.len()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::table::{Decode, Decompress};
+    use reth_primitives::{TxNumber, H256};
+
+    fn collect_sorted(collector: Collector<H256, TxNumber>) -> Vec<(H256, TxNumber)> {
+        collector
+            .into_sorted()
+            .unwrap()
+            .map(|entry| {
+                let (key, value) = entry.unwrap();
+                (H256::decode(key).unwrap(), TxNumber::decompress(value).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sorts_in_memory() {
+        let mut entries = (0..100).map(|i| (H256::random(), i)).collect::<Vec<_>>();
+
+        let mut collector = Collector::new(DEFAULT_ETL_BUFFER_SIZE);
+        for (key, value) in entries.clone() {
+            collector.insert(key, value).unwrap();
+        }
+        assert!(collector.runs.is_empty());
+        assert_eq!(collector.len(), entries.len());
+
+        entries.sort();
+        assert_eq!(collect_sorted(collector), entries);
+    }
+
+    #[test]
+    fn merges_spilled_runs() {
+        let mut entries = (0..1000).map(|i| (H256::random(), i)).collect::<Vec<_>>();
+
+        // spill roughly every five entries
+        let mut collector = Collector::new(800);
+        for (key, value) in entries.clone() {
+            collector.insert(key, value).unwrap();
+        }
+        assert!(collector.runs.len() > 1);
+        assert_eq!(collector.len(), entries.len());
+
+        entries.sort();
+        assert_eq!(collect_sorted(collector), entries);
+    }
+
+    #[test]
+    fn budget_includes_entry_overhead() {
+        // the encoded keys and values alone fit into the budget twice
+        let entries = (0..100).map(|i| (H256::random(), i)).collect::<Vec<_>>();
+        let encoded_size = entries.len() * (size_of::<H256>() + size_of::<TxNumber>());
+
+        let mut collector = Collector::new(encoded_size * 2);
+        for (key, value) in entries {
+            collector.insert(key, value).unwrap();
+            assert!(collector.memory_size() < encoded_size * 2);
+        }
+        assert!(!collector.runs.is_empty());
+    }
+}
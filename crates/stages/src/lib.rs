@@ -76,6 +76,8 @@ pub mod test_utils;
 /// A re-export of common structs and traits.
 pub mod prelude;
 
+pub mod etl;
+
 /// Implementations of stages.
 pub mod stages;
 
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageSet};
 use reth_db::database::Database;
@@ -18,6 +18,10 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// The minimum time between two commits.
+    commit_interval: Duration,
+    /// The size of the dirty pages of the uncommitted transaction above which it is committed.
+    max_dirty_size: Option<usize>,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,11 +71,31 @@ where
         self
     }
 
+    /// Set the minimum time between two commits.
+    ///
+    /// The progress of a stage is committed once the interval has elapsed since the last commit,
+    /// and whenever the stage is done. By default, the progress is committed after every step of
+    /// a stage.
+    pub fn with_commit_interval(mut self, commit_interval: Duration) -> Self {
+        self.commit_interval = commit_interval;
+        self
+    }
+
+    /// Set the maximum size of the pages dirtied by the uncommitted transaction, in bytes.
+    ///
+    /// The progress of a stage is committed once its steps dirtied more pages than this, even if
+    /// the commit interval has not elapsed yet. This bounds the memory held by stages that do not
+    /// track the size of their own writes.
+    pub fn with_max_dirty_size(mut self, max_dirty_size: Option<usize>) -> Self {
+        self.max_dirty_size = max_dirty_size;
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     ///
     /// Note: it's expected that this is either an [Arc](std::sync::Arc) or an Arc wrapper type.
     pub fn build(self, db: DB, chain_spec: Arc<ChainSpec>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, commit_interval, max_dirty_size } = self;
         Pipeline {
             db,
             chain_spec,
@@ -81,13 +105,22 @@ where
             listeners: Default::default(),
             progress: Default::default(),
             metrics_tx,
+            commit_interval,
+            max_dirty_size,
         }
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            commit_interval: Duration::ZERO,
+            max_dirty_size: None,
+        }
     }
 }
 
@@ -96,6 +129,8 @@ impl<DB: Database> std::fmt::Debug for PipelineBuilder<DB> {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("commit_interval", &self.commit_interval)
+            .field("max_dirty_size", &self.max_dirty_size)
             .finish()
     }
 }
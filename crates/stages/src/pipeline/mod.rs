@@ -3,14 +3,18 @@ use crate::{
     UnwindInput,
 };
 use futures_util::Future;
-use reth_db::database::Database;
+use reth_db::{database::Database, transaction::DbTxMut};
 use reth_interfaces::executor::BlockExecutionError;
 use reth_primitives::{
     constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH, listener::EventListeners, stage::StageId,
     BlockNumber, ChainSpec, H256,
 };
 use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
-use std::{pin::Pin, sync::Arc};
+use std::{
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::watch;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
@@ -107,6 +111,10 @@ pub struct Pipeline<DB: Database> {
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// The minimum time between two commits.
+    commit_interval: Duration,
+    /// The size of the dirty pages of the uncommitted transaction above which it is committed.
+    max_dirty_size: Option<usize>,
 }
 
 impl<DB> Pipeline<DB>
@@ -332,6 +340,7 @@ where
 
         let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+        let mut last_commit = Instant::now();
 
         loop {
             let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
@@ -393,9 +402,23 @@ where
                         result: out.clone(),
                     });
 
-                    // TODO: Make the commit interval configurable
-                    provider_rw.commit()?;
-                    provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+                    // Commit if the stage is done, asks for it (e.g. it hit its memory budget), the
+                    // commit interval elapsed or the transaction dirtied too many pages.
+                    let dirty_size_exceeded = match self.max_dirty_size {
+                        Some(max_dirty_size) => {
+                            provider_rw.tx_ref().dirty_size()? >= max_dirty_size
+                        }
+                        None => false,
+                    };
+                    if done ||
+                        stage.should_commit() ||
+                        dirty_size_exceeded ||
+                        last_commit.elapsed() >= self.commit_interval
+                    {
+                        provider_rw.commit()?;
+                        provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+                        last_commit = Instant::now();
+                    }
 
                     if done {
                         let block_number = checkpoint.block_number;
@@ -418,6 +441,21 @@ where
                             .saturating_sub(BEACON_CONSENSUS_REORG_UNWIND_DEPTH)
                             .max(1);
                         Ok(ControlFlow::Unwind { target: unwind_to, bad_block: local_head })
+                    } else if let StageError::BadBlock { block, error } = err {
+                        warn!(
+                            target: "sync::pipeline",
+                            stage = %stage_id,
+                            bad_block = %block.number,
+                            "Stage downloaded an invalid block: {error}"
+                        );
+
+                        // We unwind to the parent of the bad block, which removes the bad block
+                        // and its descendants written by previous stages, and restart the
+                        // execution loop from the beginning.
+                        Ok(ControlFlow::Unwind {
+                            target: block.number.saturating_sub(1),
+                            bad_block: block,
+                        })
                     } else if let StageError::Validation { block, error } = err {
                         warn!(
                             target: "sync::pipeline",
@@ -467,6 +505,9 @@ where
                             stage = %stage_id,
                             "Stage encountered a non-fatal error: {err}. Retrying..."
                         );
+                        drop(provider_rw);
+                        provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+                        last_commit = Instant::now();
                         continue
                     };
                     return out
@@ -482,6 +523,8 @@ impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("listeners", &self.listeners)
+            .field("commit_interval", &self.commit_interval)
+            .field("max_dirty_size", &self.max_dirty_size)
             .finish()
     }
 }
@@ -878,6 +921,177 @@ mod tests {
         );
     }
 
+    /// Runs a pipeline where a stage downloads an invalid block.
+    ///
+    /// The flow is:
+    ///
+    /// - Stage A syncs to block 10
+    /// - Stage B downloads block 8, which is invalid
+    /// - Stage A unwinds to block 7, the parent of the bad block
+    /// - Stage B is skipped, since it has not progressed past block 7
+    /// - Stage A and B sync to block 10
+    #[tokio::test]
+    async fn run_pipeline_with_bad_block() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(7) }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Err(StageError::BadBlock {
+                        block: random_header(&mut generators::rng(), 8, Default::default()),
+                        error: consensus::ConsensusError::BaseFeeMissing,
+                    }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(db, MAINNET.clone());
+        let events = pipeline.events();
+
+        // Run pipeline
+        tokio::spawn(async move {
+            pipeline.run().await.expect("Could not run pipeline");
+        });
+
+        let events = events.collect::<Vec<PipelineEvent>>().await;
+        assert!(events.contains(&PipelineEvent::Unwinding {
+            stage_id: StageId::Other("A"),
+            input: UnwindInput {
+                checkpoint: StageCheckpoint::new(10),
+                unwind_to: 7,
+                bad_block: Some(8)
+            }
+        }));
+        assert!(events.contains(&PipelineEvent::Skipped { stage_id: StageId::Other("B") }));
+        assert_eq!(
+            events.last(),
+            Some(&PipelineEvent::Ran {
+                pipeline_position: 2,
+                pipeline_total: 2,
+                stage_id: StageId::Other("B"),
+                result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+            })
+        );
+    }
+
+    /// Runs a pipeline with a commit interval.
+    ///
+    /// The first step of the stage is not committed, so it is discarded along with the failed
+    /// second step. The last step is committed, since the stage is done.
+    #[tokio::test]
+    async fn run_pipeline_with_commit_interval() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .with_commit_interval(Duration::from_secs(3600))
+            .build(db.clone(), MAINNET.clone());
+        let events = pipeline.events();
+
+        pipeline.run().await.expect("Could not run pipeline");
+        drop(pipeline);
+
+        let running = events
+            .filter_map(|event| match event {
+                PipelineEvent::Running { checkpoint, .. } => Some(checkpoint),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(running, vec![None, Some(StageCheckpoint::new(5)), None]);
+
+        let provider = ProviderFactory::new(&db, MAINNET.clone()).provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Other("A")).unwrap(),
+            Some(StageCheckpoint::new(10))
+        );
+    }
+
+    /// Runs a pipeline with a commit interval and a stage that asks for every step to be committed.
+    ///
+    /// The first step is committed despite the commit interval, so the stage resumes from it after
+    /// the failed second step.
+    #[tokio::test]
+    async fn run_pipeline_with_forced_commit() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .with_should_commit(true),
+            )
+            .with_max_block(10)
+            .with_commit_interval(Duration::from_secs(3600))
+            .build(db.clone(), MAINNET.clone());
+        let events = pipeline.events();
+
+        pipeline.run().await.expect("Could not run pipeline");
+        drop(pipeline);
+
+        let running = events
+            .filter_map(|event| match event {
+                PipelineEvent::Running { checkpoint, .. } => Some(checkpoint),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            running,
+            vec![None, Some(StageCheckpoint::new(5)), Some(StageCheckpoint::new(5))]
+        );
+    }
+
+    /// Runs a pipeline with a commit interval and a maximum dirty size.
+    ///
+    /// Saving the checkpoint of the first step dirties a page, so the first step is committed
+    /// despite the commit interval and the stage resumes from it after the failed second step.
+    #[tokio::test]
+    async fn run_pipeline_with_max_dirty_size() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .with_commit_interval(Duration::from_secs(3600))
+            .with_max_dirty_size(Some(1))
+            .build(db.clone(), MAINNET.clone());
+        let events = pipeline.events();
+
+        pipeline.run().await.expect("Could not run pipeline");
+        drop(pipeline);
+
+        let running = events
+            .filter_map(|event| match event {
+                PipelineEvent::Running { checkpoint, .. } => Some(checkpoint),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            running,
+            vec![None, Some(StageCheckpoint::new(5)), Some(StageCheckpoint::new(5))]
+        );
+    }
+
     /// Checks that the pipeline re-runs stages on non-fatal errors and stops on fatal ones.
     #[tokio::test]
     async fn pipeline_error_handling() {
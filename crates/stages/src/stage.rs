@@ -193,6 +193,14 @@ pub trait Stage<DB: Database>: Send + Sync {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError>;
+
+    /// Whether the changes of the last [Stage::execute] call must be committed right away, e.g.
+    /// because the stage hit its memory budget.
+    ///
+    /// Otherwise, the pipeline commits once the stage is done or the commit interval elapsed.
+    fn should_commit(&self) -> bool {
+        false
+    }
 }
 
 /// Prune target.
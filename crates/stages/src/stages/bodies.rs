@@ -10,7 +10,10 @@ use reth_db::{
 };
 use reth_interfaces::{
     consensus::Consensus,
-    p2p::bodies::{downloader::BodyDownloader, response::BlockResponse},
+    p2p::{
+        bodies::{downloader::BodyDownloader, response::BlockResponse},
+        error::DownloadError,
+    },
 };
 use reth_primitives::stage::{EntitiesCheckpoint, StageCheckpoint, StageId};
 use reth_provider::DatabaseProviderRW;
@@ -94,8 +97,15 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
 
         // Task downloader can return `None` only if the response relaying channel was closed. This
         // is a fatal error to prevent the pipeline from running forever.
-        let downloaded_bodies =
-            self.downloader.try_next().await?.ok_or(StageError::ChannelClosed)?;
+        let downloaded_bodies = match self.downloader.try_next().await {
+            Ok(Some(bodies)) => bodies,
+            Ok(None) => return Err(StageError::ChannelClosed),
+            Err(DownloadError::BadBlock { header, error }) => {
+                error!(target: "sync::stages::bodies", ?error, number = header.number, hash = ?header.hash, "Downloaded invalid block");
+                return Err(StageError::BadBlock { block: *header, error })
+            }
+            Err(error) => return Err(error.into()),
+        };
 
         trace!(target: "sync::stages::bodies", bodies_len = downloaded_bodies.len(), "Writing blocks");
 
@@ -69,12 +69,20 @@ pub struct ExecutionStage<EF: ExecutorFactory> {
     executor_factory: EF,
     /// The commit thresholds of the execution stage.
     thresholds: ExecutionStageThresholds,
+    /// Whether the last batch hit the memory threshold, in which case its changes must be
+    /// committed before the next batch is executed.
+    memory_threshold_hit: bool,
 }
 
 impl<EF: ExecutorFactory> ExecutionStage<EF> {
     /// Create new execution stage with specified config.
     pub fn new(executor_factory: EF, thresholds: ExecutionStageThresholds) -> Self {
-        Self { metrics: ExecutionStageMetrics::default(), executor_factory, thresholds }
+        Self {
+            metrics: ExecutionStageMetrics::default(),
+            executor_factory,
+            thresholds,
+            memory_threshold_hit: false,
+        }
     }
 
     /// Create an execution stage with the provided  executor factory.
@@ -90,8 +98,19 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: ExecInput,
     ) -> Result<ExecOutput, StageError> {
+        self.execute_batch(provider, input).map(|(output, _)| output)
+    }
+
+    /// Executes the next batch of blocks.
+    ///
+    /// Returns the output of the stage and whether the batch hit the memory threshold.
+    fn execute_batch<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        input: ExecInput,
+    ) -> Result<(ExecOutput, bool), StageError> {
         if input.target_reached() {
-            return Ok(ExecOutput::done(input.checkpoint()))
+            return Ok((ExecOutput::done(input.checkpoint()), false))
         }
 
         let start_block = input.next_block();
@@ -108,6 +127,9 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
 
         // Execute block range
         let mut state = PostState::default();
+        // The estimated memory used by the state changes. Changes to the same state are counted
+        // for every block, which overestimates the memory used by the merged state.
+        let mut memory_used = 0;
         for block_number in start_block..=max_block {
             let td = provider
                 .header_td_by_number(block_number)?
@@ -134,13 +156,17 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
                 .increment(block.header.gas_used as f64 / MGAS_TO_GAS as f64);
 
             // Merge state changes
+            memory_used += block_state.memory_size_hint();
             state.extend(block_state);
             stage_progress = block_number;
             stage_checkpoint.progress.processed += block.gas_used;
 
             // Check if we should commit now
-            if self.thresholds.is_end_of_batch(block_number - start_block, state.size_hint() as u64)
-            {
+            if self.thresholds.is_end_of_batch(
+                block_number - start_block,
+                state.size_hint() as u64,
+                memory_used,
+            ) {
                 break
             }
         }
@@ -152,11 +178,12 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         trace!(target: "sync::stages::execution", took = ?start.elapsed(), "Wrote state");
 
         let done = stage_progress == max_block;
-        Ok(ExecOutput {
+        let output = ExecOutput {
             checkpoint: StageCheckpoint::new(stage_progress)
                 .with_execution_stage_checkpoint(stage_checkpoint),
             done,
-        })
+        };
+        Ok((output, self.thresholds.is_memory_exceeded(memory_used)))
     }
 }
 
@@ -274,16 +301,18 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
         // to optimize revm or move data to the heap.
         //
         // See https://github.com/bluealloy/revm/issues/305
-        std::thread::scope(|scope| {
+        let (output, memory_threshold_hit) = std::thread::scope(|scope| {
             let handle = std::thread::Builder::new()
                 .stack_size(BIG_STACK_SIZE)
                 .spawn_scoped(scope, || {
                     // execute and store output to results
-                    self.execute_inner(provider, input)
+                    self.execute_batch(provider, input)
                 })
                 .expect("Expects that thread name is not null");
             handle.join().expect("Expects for thread to not panic")
-        })
+        })?;
+        self.memory_threshold_hit = memory_threshold_hit;
+        Ok(output)
     }
 
     /// Unwind the stage.
@@ -383,32 +412,53 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
 
         Ok(UnwindOutput { checkpoint })
     }
+
+    /// The changes of a batch that hit the memory threshold are committed right away, so that the
+    /// uncommitted writes of the database transaction don't exceed the memory budget.
+    fn should_commit(&self) -> bool {
+        self.memory_threshold_hit
+    }
 }
 
 /// The thresholds at which the execution stage writes state changes to the database.
 ///
-/// If either of the thresholds (`max_blocks` and `max_changes`) are hit, then the execution stage
-/// commits all pending changes to the database.
+/// If any of the thresholds (`max_blocks`, `max_changes` and `max_memory`) are hit, then the
+/// execution stage commits all pending changes to the database.
 #[derive(Debug)]
 pub struct ExecutionStageThresholds {
     /// The maximum number of blocks to process before the execution stage commits.
     pub max_blocks: Option<u64>,
     /// The maximum amount of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The maximum estimated size of the state changes to keep in memory, in bytes, before the
+    /// execution stage commits.
+    pub max_memory: Option<usize>,
 }
 
 impl Default for ExecutionStageThresholds {
     fn default() -> Self {
-        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000) }
+        Self { max_blocks: Some(500_000), max_changes: Some(5_000_000), max_memory: None }
     }
 }
 
 impl ExecutionStageThresholds {
     /// Check if the batch thresholds have been hit.
     #[inline]
-    pub fn is_end_of_batch(&self, blocks_processed: u64, changes_processed: u64) -> bool {
+    pub fn is_end_of_batch(
+        &self,
+        blocks_processed: u64,
+        changes_processed: u64,
+        memory_used: usize,
+    ) -> bool {
         blocks_processed >= self.max_blocks.unwrap_or(u64::MAX) ||
-            changes_processed >= self.max_changes.unwrap_or(u64::MAX)
+            changes_processed >= self.max_changes.unwrap_or(u64::MAX) ||
+            self.is_memory_exceeded(memory_used)
+    }
+
+    /// Check if the memory threshold has been hit.
+    #[inline]
+    pub fn is_memory_exceeded(&self, memory_used: usize) -> bool {
+        memory_used >= self.max_memory.unwrap_or(usize::MAX)
     }
 }
 
@@ -432,7 +482,7 @@ mod tests {
             Factory::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()));
         ExecutionStage::new(
             factory,
-            ExecutionStageThresholds { max_blocks: Some(100), max_changes: None },
+            ExecutionStageThresholds { max_blocks: Some(100), max_changes: None, max_memory: None },
         )
     }
 
@@ -1,12 +1,14 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{
+    etl::{Collector, DEFAULT_ETL_BUFFER_SIZE},
+    ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput,
+};
 use itertools::Itertools;
-use rayon::slice::ParallelSliceMut;
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    RawKey, RawTable,
+    RawKey, RawTable, RawValue,
 };
 use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
@@ -15,6 +17,7 @@ use reth_primitives::{
         AccountHashingCheckpoint, CheckpointBlockRange, EntitiesCheckpoint, StageCheckpoint,
         StageId,
     },
+    Account, Address, H256,
 };
 use reth_provider::{AccountExtReader, DatabaseProviderRW, HashingWriter};
 use std::{
@@ -27,25 +30,59 @@ use tracing::*;
 
 /// Account hashing stage hashes plain account.
 /// This is preparation before generating intermediate hashes and calculating Merkle tree root.
-#[derive(Clone, Debug)]
+///
+/// When hashing all accounts, the hashed accounts are collected with an ETL [Collector] over
+/// multiple executions of the stage, each hashing at most `commit_threshold` accounts. They are
+/// only written once all accounts were hashed, so that they can be appended to the table in key
+/// order.
+///
+/// The collected accounts only live in memory, so a pipeline commit in between only persists the
+/// inner checkpoint. A restarted stage ignores it, clears the table and hashes all accounts again.
+#[derive(Debug)]
 pub struct AccountHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
     /// hashing and full storage hashing.
     pub clean_threshold: u64,
     /// The maximum number of accounts to process before committing.
     pub commit_threshold: u64,
+    /// The memory budget of the collector of hashed accounts, in bytes.
+    pub etl_buffer_size: usize,
+    /// The accounts hashed by the previous executions, if not all accounts were hashed yet.
+    collected: Option<CollectedAccounts>,
+}
+
+/// The hashed accounts collected while hashing all accounts.
+#[derive(Debug)]
+struct CollectedAccounts {
+    /// The range of blocks the accounts are hashed for.
+    block_range: CheckpointBlockRange,
+    /// The next account to hash.
+    next_address: Option<Address>,
+    /// The collected hashed accounts.
+    collector: Collector<H256, RawValue<Account>>,
 }
 
 impl AccountHashingStage {
     /// Create new instance of [AccountHashingStage].
     pub fn new(clean_threshold: u64, commit_threshold: u64) -> Self {
-        Self { clean_threshold, commit_threshold }
+        Self { clean_threshold, commit_threshold, ..Default::default() }
+    }
+
+    /// Sets the memory budget of the collector of hashed accounts, in bytes.
+    pub fn with_etl_buffer_size(mut self, etl_buffer_size: usize) -> Self {
+        self.etl_buffer_size = etl_buffer_size;
+        self
     }
 }
 
 impl Default for AccountHashingStage {
     fn default() -> Self {
-        Self { clean_threshold: 500_000, commit_threshold: 100_000 }
+        Self {
+            clean_threshold: 500_000,
+            commit_threshold: 100_000,
+            etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
+            collected: None,
+        }
     }
 }
 
@@ -81,13 +118,13 @@ impl AccountHashingStage {
     pub fn seed<DB: Database>(
         provider: &DatabaseProviderRW<'_, DB>,
         opts: SeedOpts,
-    ) -> Result<Vec<(reth_primitives::Address, reth_primitives::Account)>, StageError> {
+    ) -> Result<Vec<(Address, Account)>, StageError> {
         use reth_db::models::AccountBeforeTx;
         use reth_interfaces::test_utils::{
             generators,
             generators::{random_block_range, random_eoa_account_range},
         };
-        use reth_primitives::{Account, H256, U256};
+        use reth_primitives::U256;
         use reth_provider::BlockWriter;
 
         let mut rng = generators::rng();
@@ -153,27 +190,37 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
             let stage_checkpoint = input
                 .checkpoint
                 .and_then(|checkpoint| checkpoint.account_hashing_stage_checkpoint());
+            let block_range = CheckpointBlockRange { from: from_block, to: to_block };
 
-            let start_address = match stage_checkpoint {
-                Some(AccountHashingCheckpoint { address: address @ Some(_), block_range: CheckpointBlockRange { from, to }, .. })
+            let mut collected = match (self.collected.take(), stage_checkpoint) {
+                (
+                    Some(collected),
+                    Some(AccountHashingCheckpoint { address: address @ Some(_), block_range: checkpoint_range, .. }),
+                )
                     // Checkpoint is only valid if the range of transitions didn't change.
                     // An already hashed account may have been changed with the new range,
-                    // and therefore should be hashed again.
-                    if from == from_block && to == to_block =>
+                    // and therefore should be hashed again. The hashed accounts are only kept in
+                    // memory, so they are also hashed again after a restart.
+                    if checkpoint_range == block_range &&
+                        collected.block_range == block_range &&
+                        collected.next_address == address =>
                 {
                     debug!(target: "sync::stages::account_hashing::exec", checkpoint = ?stage_checkpoint, "Continuing inner account hashing checkpoint");
 
-                    address
+                    collected
                 }
                 _ => {
                     // clear table, load all accounts and hash it
                     tx.clear::<tables::HashedAccount>()?;
 
-                    None
+                    CollectedAccounts {
+                        block_range,
+                        next_address: None,
+                        collector: Collector::new(self.etl_buffer_size),
+                    }
                 }
-            }
-            .take()
-            .map(RawKey::new);
+            };
+            let start_address = collected.next_address.map(RawKey::new);
 
             let next_address = {
                 let mut accounts_cursor =
@@ -182,7 +229,7 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
                 // channels used to return result of account hashing
                 let mut channels = Vec::new();
                 for chunk in &accounts_cursor
-                    .walk(start_address.clone())?
+                    .walk(start_address)?
                     .take(self.commit_threshold as usize)
                     .chunks(
                         max(self.commit_threshold as usize, rayon::current_num_threads()) /
@@ -198,49 +245,49 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
                     rayon::spawn(move || {
                         for (address, account) in chunk.into_iter() {
                             let address = address.key().unwrap();
-                            let _ = tx.send((RawKey::new(keccak256(address)), account));
+                            let _ = tx.send((keccak256(address), account));
                         }
                     });
                 }
-                let mut hashed_batch = Vec::with_capacity(self.commit_threshold as usize);
 
-                // Iterate over channels and append the hashed accounts.
+                // Iterate over channels and collect the hashed accounts, which are sorted by the
+                // collector.
                 for mut channel in channels {
-                    while let Some(hashed) = channel.recv().await {
-                        hashed_batch.push(hashed);
+                    while let Some((hashed_address, account)) = channel.recv().await {
+                        collected.collector.insert(hashed_address, account)?;
                     }
                 }
-                // sort it all in parallel
-                hashed_batch.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
-
-                let mut hashed_account_cursor =
-                    tx.cursor_write::<RawTable<tables::HashedAccount>>()?;
-
-                // iterate and put presorted hashed accounts
-                if start_address.is_none() {
-                    hashed_batch
-                        .into_iter()
-                        .try_for_each(|(k, v)| hashed_account_cursor.append(k, v))?;
-                } else {
-                    hashed_batch
-                        .into_iter()
-                        .try_for_each(|(k, v)| hashed_account_cursor.insert(k, v))?;
-                }
+
                 // next key of iterator
                 accounts_cursor.next()?
             };
 
             if let Some((next_address, _)) = &next_address {
+                // Nothing is written until all accounts are hashed, so the block of the checkpoint
+                // stays the same.
+                collected.next_address = Some(next_address.key()?);
+                let mut progress = stage_checkpoint_progress(provider)?;
+                progress.processed += collected.collector.len() as u64;
+
                 let checkpoint = input.checkpoint().with_account_hashing_stage_checkpoint(
                     AccountHashingCheckpoint {
-                        address: Some(next_address.key().unwrap()),
-                        block_range: CheckpointBlockRange { from: from_block, to: to_block },
-                        progress: stage_checkpoint_progress(provider)?,
+                        address: collected.next_address,
+                        block_range,
+                        progress,
                     },
                 );
+                self.collected = Some(collected);
 
                 return Ok(ExecOutput { checkpoint, done: false })
             }
+
+            // the table was cleared, so the presorted hashed accounts can be appended
+            let mut hashed_account_cursor = tx.cursor_write::<RawTable<tables::HashedAccount>>()?;
+            for entry in collected.collector.into_sorted()? {
+                let (hashed_address, account) = entry?;
+                hashed_account_cursor
+                    .append(RawKey::from_vec(hashed_address), RawValue::from_vec(account))?;
+            }
         } else {
             // Aggregate all transition changesets and make a list of accounts that have been
             // changed.
@@ -270,6 +317,9 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
+        // the collected accounts may have been changed in blocks that are unwound
+        self.collected = None;
+
         let (range, unwind_progress, _) =
             input.unwind_block_range_with_threshold(self.commit_threshold);
 
@@ -349,6 +399,48 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    #[tokio::test]
+    async fn execute_clean_account_hashing_with_spilling_collector() {
+        let (previous_stage, stage_progress) = (20, 10);
+        // Set up the runner
+        let mut runner = AccountHashingTestRunner::default();
+        runner.set_clean_threshold(1);
+        // force the collector to spill to disk
+        runner.set_etl_buffer_size(64);
+
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        let rx = runner.execute(input);
+        let result = rx.await.unwrap();
+
+        assert_matches!(
+            result,
+            Ok(ExecOutput {
+                checkpoint: StageCheckpoint {
+                    block_number,
+                    stage_checkpoint: Some(StageUnitCheckpoint::Account(AccountHashingCheckpoint {
+                        progress: EntitiesCheckpoint {
+                            processed,
+                            total,
+                        },
+                        ..
+                    })),
+                },
+                done: true,
+            }) if block_number == previous_stage &&
+                processed == total &&
+                total == runner.tx.table::<tables::PlainAccountState>().unwrap().len() as u64
+        );
+
+        // Validate the stage execution
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     #[tokio::test]
     async fn execute_clean_account_hashing_with_commit_threshold() {
         let (previous_stage, stage_progress) = (20, 10);
@@ -365,8 +457,8 @@ mod tests {
         runner.seed_execution(input).expect("failed to seed execution");
 
         // first run, hash first five accounts.
-        let rx = runner.execute(input);
-        let result = rx.await.unwrap();
+        let mut stage = runner.stage();
+        let result = runner.execute_with(&mut stage, input).await;
 
         let fifth_address = runner
             .tx
@@ -401,12 +493,12 @@ mod tests {
             }) if address == fifth_address &&
                 total == runner.tx.table::<tables::PlainAccountState>().unwrap().len() as u64
         );
-        assert_eq!(runner.tx.table::<tables::HashedAccount>().unwrap().len(), 5);
+        // the hashed accounts are only written once all accounts are hashed
+        assert!(runner.tx.table_is_empty::<tables::HashedAccount>().unwrap());
 
         // second run, hash next five accounts.
         input.checkpoint = Some(result.unwrap().checkpoint);
-        let rx = runner.execute(input);
-        let result = rx.await.unwrap();
+        let result = runner.execute_with(&mut stage, input).await;
 
         assert_matches!(
             result,
@@ -434,6 +526,45 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// A new stage hashes all accounts again, since the accounts hashed by the previous one are
+    /// lost
+    #[tokio::test]
+    async fn execute_restarted_clean_account_hashing() {
+        let (previous_stage, stage_progress) = (20, 10);
+        // Set up the runner
+        let mut runner = AccountHashingTestRunner::default();
+        runner.set_clean_threshold(1);
+        runner.set_commit_threshold(5);
+
+        let mut input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        // Hash the first five accounts and drop the stage
+        let result = runner.execute_with(&mut runner.stage(), input).await.unwrap();
+        assert!(!result.done);
+        let first_checkpoint = result.checkpoint;
+
+        // A new stage with the committed checkpoint starts over from the first account
+        let mut stage = runner.stage();
+        input.checkpoint = Some(first_checkpoint);
+        let result = runner.execute_with(&mut stage, input).await.unwrap();
+        assert_eq!(result, ExecOutput { checkpoint: first_checkpoint, done: false });
+        assert!(runner.tx.table_is_empty::<tables::HashedAccount>().unwrap());
+
+        // Hash the last five accounts
+        input.checkpoint = Some(result.checkpoint);
+        let result = runner.execute_with(&mut stage, input).await;
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        assert_eq!(runner.tx.table::<tables::HashedAccount>().unwrap().len(), 10);
+
+        // Validate the stage execution
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     mod test_utils {
         use super::*;
         use crate::{
@@ -448,6 +579,7 @@ mod tests {
             pub(crate) tx: TestTransaction,
             commit_threshold: u64,
             clean_threshold: u64,
+            etl_buffer_size: usize,
         }
 
         impl AccountHashingTestRunner {
@@ -460,6 +592,10 @@ mod tests {
                 self.commit_threshold = threshold;
             }
 
+            pub(crate) fn set_etl_buffer_size(&mut self, etl_buffer_size: usize) {
+                self.etl_buffer_size = etl_buffer_size;
+            }
+
             /// Iterates over PlainAccount table and checks that the accounts match the ones
             /// in the HashedAccount table
             pub(crate) fn check_hashed_accounts(&self) -> Result<(), TestRunnerError> {
@@ -511,6 +647,7 @@ mod tests {
                     tx: TestTransaction::default(),
                     commit_threshold: 1000,
                     clean_threshold: 1000,
+                    etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
                 }
             }
         }
@@ -523,10 +660,8 @@ mod tests {
             }
 
             fn stage(&self) -> Self::S {
-                Self::S {
-                    commit_threshold: self.commit_threshold,
-                    clean_threshold: self.clean_threshold,
-                }
+                AccountHashingStage::new(self.clean_threshold, self.commit_threshold)
+                    .with_etl_buffer_size(self.etl_buffer_size)
             }
         }
 
@@ -1,11 +1,15 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{
+    etl::{Collector, DEFAULT_ETL_BUFFER_SIZE},
+    ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput,
+};
 use num_traits::Zero;
 use reth_db::{
-    cursor::DbDupCursorRO,
+    cursor::{DbDupCursorRO, DbDupCursorRW},
     database::Database,
     models::BlockNumberAddress,
     tables,
     transaction::{DbTx, DbTxMut},
+    RawDupSort, RawKey, RawValue,
 };
 use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
@@ -14,14 +18,21 @@ use reth_primitives::{
         CheckpointBlockRange, EntitiesCheckpoint, StageCheckpoint, StageId,
         StorageHashingCheckpoint,
     },
-    StorageEntry,
+    Address, StorageEntry, H256,
 };
 use reth_provider::{DatabaseProviderRW, HashingWriter, StorageReader};
-use std::{collections::BTreeMap, fmt::Debug};
+use std::fmt::Debug;
 use tracing::*;
 
 /// Storage hashing stage hashes plain storage.
 /// This is preparation before generating intermediate hashes and calculating Merkle tree root.
+///
+/// When hashing all storages, the hashed slots are collected with an ETL [Collector] over multiple
+/// executions of the stage, each hashing at most `commit_threshold` slots. They are only written
+/// once all slots were hashed, so that they can be appended to the table in key order.
+///
+/// The collected slots only live in memory, so a pipeline commit in between only persists the
+/// inner checkpoint. A restarted stage ignores it, clears the table and hashes all slots again.
 #[derive(Debug)]
 pub struct StorageHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
@@ -29,18 +40,46 @@ pub struct StorageHashingStage {
     pub clean_threshold: u64,
     /// The maximum number of slots to process before committing.
     pub commit_threshold: u64,
+    /// The memory budget of the collector of hashed slots, in bytes.
+    pub etl_buffer_size: usize,
+    /// The slots hashed by the previous executions, if not all slots were hashed yet.
+    collected: Option<CollectedStorages>,
+}
+
+/// The hashed slots collected while hashing all storages.
+#[derive(Debug)]
+struct CollectedStorages {
+    /// The range of blocks the slots are hashed for.
+    block_range: CheckpointBlockRange,
+    /// The address of the next slot to hash.
+    next_address: Option<Address>,
+    /// The key of the next slot to hash.
+    next_slot: Option<H256>,
+    /// The collected hashed slots.
+    collector: Collector<H256, StorageEntry>,
 }
 
 impl StorageHashingStage {
     /// Create new instance of [StorageHashingStage].
     pub fn new(clean_threshold: u64, commit_threshold: u64) -> Self {
-        Self { clean_threshold, commit_threshold }
+        Self { clean_threshold, commit_threshold, ..Default::default() }
+    }
+
+    /// Sets the memory budget of the collector of hashed slots, in bytes.
+    pub fn with_etl_buffer_size(mut self, etl_buffer_size: usize) -> Self {
+        self.etl_buffer_size = etl_buffer_size;
+        self
     }
 }
 
 impl Default for StorageHashingStage {
     fn default() -> Self {
-        Self { clean_threshold: 500_000, commit_threshold: 100_000 }
+        Self {
+            clean_threshold: 500_000,
+            commit_threshold: 100_000,
+            etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
+            collected: None,
+        }
     }
 }
 
@@ -72,61 +111,70 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
             let stage_checkpoint = input
                 .checkpoint
                 .and_then(|checkpoint| checkpoint.storage_hashing_stage_checkpoint());
-
-            let (mut current_key, mut current_subkey) = match stage_checkpoint {
-                Some(StorageHashingCheckpoint {
-                         address: address @ Some(_),
-                         storage,
-                         block_range: CheckpointBlockRange { from, to },
-                         ..
-                     })
+            let block_range = CheckpointBlockRange { from: from_block, to: to_block };
+
+            let mut collected = match (self.collected.take(), stage_checkpoint) {
+                (
+                    Some(collected),
+                    Some(StorageHashingCheckpoint {
+                        address: address @ Some(_),
+                        storage,
+                        block_range: checkpoint_range,
+                        ..
+                    }),
+                )
                 // Checkpoint is only valid if the range of transitions didn't change.
                 // An already hashed storage may have been changed with the new range,
-                // and therefore should be hashed again.
-                if from == from_block && to == to_block =>
+                // and therefore should be hashed again. The hashed slots are only kept in memory,
+                // so they are also hashed again after a restart.
+                if checkpoint_range == block_range &&
+                    collected.block_range == block_range &&
+                    (collected.next_address, collected.next_slot) == (address, storage) =>
                     {
                         debug!(target: "sync::stages::storage_hashing::exec", checkpoint = ?stage_checkpoint, "Continuing inner storage hashing checkpoint");
 
-                        (address, storage)
+                        collected
                     }
                 _ => {
                     // clear table, load all accounts and hash it
                     tx.clear::<tables::HashedStorage>()?;
 
-                    (None, None)
+                    CollectedStorages {
+                        block_range,
+                        next_address: None,
+                        next_slot: None,
+                        collector: Collector::new(self.etl_buffer_size),
+                    }
                 }
             };
+            let (mut current_key, mut current_subkey) =
+                (collected.next_address, collected.next_slot);
 
             let mut keccak_address = None;
 
-            let mut hashed_batch = BTreeMap::new();
+            let mut hashed = 0;
             let mut remaining = self.commit_threshold as usize;
             {
                 let mut storage = tx.cursor_dup_read::<tables::PlainStorageState>()?;
                 while !remaining.is_zero() {
-                    hashed_batch.extend(
-                        storage
-                            .walk_dup(current_key, current_subkey)?
-                            .take(remaining)
-                            .map(|res| {
-                                res.map(|(address, slot)| {
-                                    // Address caching for the first iteration when current_key
-                                    // is None
-                                    let keccak_address =
-                                        if let Some(keccak_address) = keccak_address {
-                                            keccak_address
-                                        } else {
-                                            keccak256(address)
-                                        };
-
-                                    // TODO cache map keccak256(slot.key) ?
-                                    ((keccak_address, keccak256(slot.key)), slot.value)
-                                })
-                            })
-                            .collect::<Result<BTreeMap<_, _>, _>>()?,
-                    );
+                    for entry in storage.walk_dup(current_key, current_subkey)?.take(remaining) {
+                        let (address, slot) = entry?;
+                        // Address caching for the first iteration when current_key is None
+                        let keccak_address = if let Some(keccak_address) = keccak_address {
+                            keccak_address
+                        } else {
+                            keccak256(address)
+                        };
+
+                        // TODO cache map keccak256(slot.key) ?
+                        collected.collector.insert(
+                            keccak_address,
+                            StorageEntry { key: keccak256(slot.key), value: slot.value },
+                        )?;
+                        hashed += 1;
+                    }
 
-                    remaining = self.commit_threshold as usize - hashed_batch.len();
+                    remaining = self.commit_threshold as usize - hashed;
 
                     if let Some((address, slot)) = storage.next_dup()? {
                         // There's still some remaining elements on this key, so we need to save
@@ -151,23 +199,34 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
                 }
             }
 
-            // iterate and put presorted hashed slots
-            hashed_batch.into_iter().try_for_each(|((addr, key), value)| {
-                tx.put::<tables::HashedStorage>(addr, StorageEntry { key, value })
-            })?;
-
             if current_key.is_some() {
+                // Nothing is written until all slots are hashed, so the block of the checkpoint
+                // stays the same.
+                (collected.next_address, collected.next_slot) = (current_key, current_subkey);
+                let mut progress = stage_checkpoint_progress(provider)?;
+                progress.processed += collected.collector.len() as u64;
+
                 let checkpoint = input.checkpoint().with_storage_hashing_stage_checkpoint(
                     StorageHashingCheckpoint {
                         address: current_key,
                         storage: current_subkey,
-                        block_range: CheckpointBlockRange { from: from_block, to: to_block },
-                        progress: stage_checkpoint_progress(provider)?,
+                        block_range,
+                        progress,
                     },
                 );
+                self.collected = Some(collected);
 
                 return Ok(ExecOutput { checkpoint, done: false })
             }
+
+            // the table was cleared, so the presorted hashed slots can be appended
+            let mut hashed_storage_cursor =
+                tx.cursor_dup_write::<RawDupSort<tables::HashedStorage>>()?;
+            for entry in collected.collector.into_sorted()? {
+                let (keccak_address, slot) = entry?;
+                hashed_storage_cursor
+                    .append_dup(RawKey::from_vec(keccak_address), RawValue::from_vec(slot))?;
+            }
         } else {
             // Aggregate all changesets and and make list of storages that have been
             // changed.
@@ -196,6 +255,9 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
+        // the collected slots may have been changed in blocks that are unwound
+        self.collected = None;
+
         let (range, unwind_progress, _) =
             input.unwind_block_range_with_threshold(self.commit_threshold);
 
@@ -267,9 +329,76 @@ mod tests {
 
         runner.seed_execution(input).expect("failed to seed execution");
 
+        let mut stage = runner.stage();
         loop {
             if let Ok(result @ ExecOutput { checkpoint, done }) =
-                runner.execute(input).await.unwrap()
+                runner.execute_with(&mut stage, input).await
+            {
+                if !done {
+                    let previous_checkpoint = input
+                        .checkpoint
+                        .and_then(|checkpoint| checkpoint.storage_hashing_stage_checkpoint())
+                        .unwrap_or_default();
+                    assert_matches!(checkpoint.storage_hashing_stage_checkpoint(), Some(StorageHashingCheckpoint {
+                        progress: EntitiesCheckpoint {
+                            processed,
+                            total,
+                        },
+                        ..
+                    }) if processed == previous_checkpoint.progress.processed + 1 &&
+                        total == runner.tx.table::<tables::PlainStorageState>().unwrap().len() as u64);
+
+                    // Continue from checkpoint
+                    input.checkpoint = Some(checkpoint);
+                    continue
+                } else {
+                    assert!(checkpoint.block_number == previous_stage);
+                    assert_matches!(checkpoint.storage_hashing_stage_checkpoint(), Some(StorageHashingCheckpoint {
+                        progress: EntitiesCheckpoint {
+                            processed,
+                            total,
+                        },
+                        ..
+                    }) if processed == total &&
+                        total == runner.tx.table::<tables::PlainStorageState>().unwrap().len() as u64);
+
+                    // Validate the stage execution
+                    assert!(
+                        runner.validate_execution(input, Some(result)).is_ok(),
+                        "execution validation"
+                    );
+
+                    break
+                }
+            }
+            panic!("Failed execution");
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_clean_storage_hashing_with_spilling_collector() {
+        let (previous_stage, stage_progress) = (500, 100);
+
+        // Set up the runner
+        let mut runner = StorageHashingTestRunner::default();
+
+        // set low clean threshold so we hash the whole storage
+        runner.set_clean_threshold(1);
+        // hash all slots in one batch and force the collector to spill to disk
+        runner.set_commit_threshold(u64::MAX);
+        runner.set_etl_buffer_size(1024);
+
+        let mut input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        let mut stage = runner.stage();
+        loop {
+            if let Ok(result @ ExecOutput { checkpoint, done }) =
+                runner.execute_with(&mut stage, input).await
             {
                 if !done {
                     let previous_checkpoint = input
@@ -328,8 +457,8 @@ mod tests {
         runner.seed_execution(input).expect("failed to seed execution");
 
         // first run, hash first half of storages.
-        let rx = runner.execute(input);
-        let result = rx.await.unwrap();
+        let mut stage = runner.stage();
+        let result = runner.execute_with(&mut stage, input).await;
 
         let (progress_address, progress_key) = runner
             .tx
@@ -366,14 +495,14 @@ mod tests {
             }) if address == progress_address && storage == progress_key &&
                 total == runner.tx.table::<tables::PlainStorageState>().unwrap().len() as u64
         );
-        assert_eq!(runner.tx.table::<tables::HashedStorage>().unwrap().len(), 500);
+        // the hashed slots are only written once all slots are hashed
+        assert!(runner.tx.table_is_empty::<tables::HashedStorage>().unwrap());
 
         // second run with commit threshold of 2 to check if subkey is set.
-        runner.set_commit_threshold(2);
+        stage.commit_threshold = 2;
         let result = result.unwrap();
         input.checkpoint = Some(result.checkpoint);
-        let rx = runner.execute(input);
-        let result = rx.await.unwrap();
+        let result = runner.execute_with(&mut stage, input).await;
 
         let (progress_address, progress_key) = runner
             .tx
@@ -412,13 +541,12 @@ mod tests {
             }) if address == progress_address && storage == progress_key &&
                 total == runner.tx.table::<tables::PlainStorageState>().unwrap().len() as u64
         );
-        assert_eq!(runner.tx.table::<tables::HashedStorage>().unwrap().len(), 502);
+        assert!(runner.tx.table_is_empty::<tables::HashedStorage>().unwrap());
 
         // third last run, hash rest of storages.
-        runner.set_commit_threshold(1000);
+        stage.commit_threshold = 1000;
         input.checkpoint = Some(result.unwrap().checkpoint);
-        let rx = runner.execute(input);
-        let result = rx.await.unwrap();
+        let result = runner.execute_with(&mut stage, input).await;
 
         assert_matches!(
             result,
@@ -453,15 +581,63 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// A new stage hashes all slots again, since the slots hashed by the previous one are lost
+    #[tokio::test]
+    async fn execute_restarted_clean_storage_hashing() {
+        let (previous_stage, stage_progress) = (500, 100);
+        // Set up the runner
+        let mut runner = StorageHashingTestRunner::default();
+        runner.set_clean_threshold(1);
+        runner.set_commit_threshold(500);
+
+        let mut input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        // Hash the first half of the storages and drop the stage
+        let result = runner.execute_with(&mut runner.stage(), input).await.unwrap();
+        assert!(!result.done);
+        let first_checkpoint = result.checkpoint;
+
+        // A new stage with the committed checkpoint starts over from the first slot
+        let mut stage = runner.stage();
+        input.checkpoint = Some(first_checkpoint);
+        let result = runner.execute_with(&mut stage, input).await.unwrap();
+        assert_eq!(result, ExecOutput { checkpoint: first_checkpoint, done: false });
+        assert!(runner.tx.table_is_empty::<tables::HashedStorage>().unwrap());
+
+        // Hash the rest of the storages
+        stage.commit_threshold = 1000;
+        input.checkpoint = Some(result.checkpoint);
+        let result = runner.execute_with(&mut stage, input).await;
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        assert_eq!(
+            runner.tx.table::<tables::HashedStorage>().unwrap().len(),
+            runner.tx.table::<tables::PlainStorageState>().unwrap().len()
+        );
+
+        // Validate the stage execution
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     struct StorageHashingTestRunner {
         tx: TestTransaction,
         commit_threshold: u64,
         clean_threshold: u64,
+        etl_buffer_size: usize,
     }
 
     impl Default for StorageHashingTestRunner {
         fn default() -> Self {
-            Self { tx: TestTransaction::default(), commit_threshold: 1000, clean_threshold: 1000 }
+            Self {
+                tx: TestTransaction::default(),
+                commit_threshold: 1000,
+                clean_threshold: 1000,
+                etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
+            }
         }
     }
 
@@ -473,10 +649,8 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            Self::S {
-                commit_threshold: self.commit_threshold,
-                clean_threshold: self.clean_threshold,
-            }
+            StorageHashingStage::new(self.clean_threshold, self.commit_threshold)
+                .with_etl_buffer_size(self.etl_buffer_size)
         }
     }
 
@@ -592,6 +766,10 @@ mod tests {
             self.commit_threshold = threshold;
         }
 
+        fn set_etl_buffer_size(&mut self, etl_buffer_size: usize) {
+            self.etl_buffer_size = etl_buffer_size;
+        }
+
         fn check_hashed_storage(&self) -> Result<(), TestRunnerError> {
             self.tx
                 .query(|tx| {
@@ -227,6 +227,10 @@ where
                 error!(target: "sync::stages::headers", ?error, "Cannot attach header to head");
                 return Err(StageError::DetachedHead { local_head, header, error })
             }
+            Some(Err(HeadersDownloaderError::BadBlock { header, error })) => {
+                error!(target: "sync::stages::headers", ?error, number = header.number, hash = ?header.hash, "Downloaded invalid header");
+                return Err(StageError::BadBlock { block: header, error: *error })
+            }
             None => return Err(StageError::ChannelClosed),
         };
 
@@ -328,7 +332,8 @@ where
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
-        // TODO: handle bad block
+        // The bad block, if any, is above the unwind target and is removed along with its
+        // descendants.
         provider.unwind_table_by_walker::<tables::CanonicalHeaders, tables::HeaderNumbers>(
             input.unwind_to + 1,
         )?;
@@ -324,12 +324,20 @@ impl<DB: Database> Stage<DB> for MerkleStage {
             // Validation passed, apply unwind changes to the database.
             updates.flush(provider.tx_ref())?;
 
-            // TODO(alexey): update entities checkpoint
+            // The trie now reflects all hashed entries as of the unwind target.
+            let total_hashed_entries = (tx.entries::<tables::HashedAccount>()? +
+                tx.entries::<tables::HashedStorage>()?)
+                as u64;
+            entities_checkpoint =
+                EntitiesCheckpoint { processed: total_hashed_entries, total: total_hashed_entries };
         } else {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         }
 
-        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        Ok(UnwindOutput {
+            checkpoint: StageCheckpoint::new(input.unwind_to)
+                .with_entities_stage_checkpoint(entities_checkpoint),
+        })
     }
 }
 
@@ -1,17 +1,19 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{
+    etl::{Collector, DEFAULT_ETL_BUFFER_SIZE},
+    ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput,
+};
 use itertools::Itertools;
-use rayon::prelude::*;
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
+    DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_primitives::{
     keccak256,
     stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
-    TransactionSignedNoHash, TxNumber, H256,
+    BlockNumber, TransactionSignedNoHash, TxNumber, H256,
 };
 use reth_provider::DatabaseProviderRW;
 use tokio::sync::mpsc;
@@ -22,22 +24,57 @@ use tracing::*;
 /// This stage walks over the bodies table, and sets the transaction hash of each transaction in a
 /// block to the corresponding `BlockNumber` at each block. This is written to the
 /// [`tables::TxHashNumber`] This is used for looking up changesets via the transaction hash.
-#[derive(Debug, Clone)]
+///
+/// The hashes of the whole block range are collected with an ETL [Collector] over multiple
+/// executions of the stage, each hashing at most `commit_threshold` transactions. They are only
+/// written once the last batch was hashed, so that they can be appended to the table in key order.
+/// Until then, the checkpoint of the stage does not advance.
+///
+/// The collected hashes only live in memory, so a pipeline commit in between does not persist
+/// them. A restarted stage starts to collect the hashes of the range from its first block again.
+#[derive(Debug)]
 pub struct TransactionLookupStage {
-    /// The number of lookup entries to commit at once
+    /// The number of transactions to hash at once
     commit_threshold: u64,
+    /// The memory budget of the hash collector, in bytes
+    etl_buffer_size: usize,
+    /// The hashes collected by the previous executions, if the block range is not complete yet
+    collected: Option<CollectedHashes>,
 }
 
 impl Default for TransactionLookupStage {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self {
+            commit_threshold: 5_000_000,
+            etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
+            collected: None,
+        }
     }
 }
 
+/// The transaction hashes collected for a block range that was not hashed completely.
+#[derive(Debug)]
+struct CollectedHashes {
+    /// The checkpoint the block range starts after.
+    checkpoint: BlockNumber,
+    /// The last block of the range.
+    target: BlockNumber,
+    /// The last block whose transactions were hashed.
+    last_block: BlockNumber,
+    /// The collected hash -> id entries.
+    collector: Collector<H256, TxNumber>,
+}
+
 impl TransactionLookupStage {
     /// Create new instance of [TransactionLookupStage].
     pub fn new(commit_threshold: u64) -> Self {
-        Self { commit_threshold }
+        Self { commit_threshold, ..Default::default() }
+    }
+
+    /// Sets the memory budget of the hash collector, in bytes.
+    pub fn with_etl_buffer_size(mut self, etl_buffer_size: usize) -> Self {
+        self.etl_buffer_size = etl_buffer_size;
+        self
     }
 }
 
@@ -57,8 +94,30 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         if input.target_reached() {
             return Ok(ExecOutput::done(input.checkpoint()))
         }
-        let (tx_range, block_range, is_final_range) =
-            input.next_block_range_with_transaction_threshold(provider, self.commit_threshold)?;
+
+        // Continue with the hashes collected by the previous executions if they belong to the same
+        // block range.
+        let checkpoint = input.checkpoint().block_number;
+        let mut collected = match self.collected.take() {
+            Some(collected)
+                if collected.checkpoint == checkpoint && collected.target == input.target() =>
+            {
+                collected
+            }
+            _ => CollectedHashes {
+                checkpoint,
+                target: input.target(),
+                last_block: checkpoint,
+                collector: Collector::new(self.etl_buffer_size),
+            },
+        };
+
+        let batch_input = ExecInput {
+            target: input.target,
+            checkpoint: Some(StageCheckpoint::new(collected.last_block)),
+        };
+        let (tx_range, block_range, is_final_range) = batch_input
+            .next_block_range_with_transaction_threshold(provider, self.commit_threshold)?;
         let end_block = *block_range.end();
         let tx_range_size = tx_range.clone().count();
 
@@ -70,7 +129,6 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 
         let chunk_size = (tx_range_size / rayon::current_num_threads()).max(1);
         let mut channels = Vec::with_capacity(chunk_size);
-        let mut transaction_count = 0;
 
         for chunk in &tx_walker.chunks(chunk_size) {
             let (tx, rx) = mpsc::unbounded_channel();
@@ -78,7 +136,6 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 
             // Note: Unfortunate side-effect of how chunk is designed in itertools (it is not Send)
             let chunk: Vec<_> = chunk.collect();
-            transaction_count += chunk.len();
 
             // Spawn the task onto the global rayon pool
             // This task will send the results through the channel after it has calculated the hash.
@@ -91,34 +148,47 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
             });
         }
 
-        let mut tx_list = Vec::with_capacity(transaction_count);
-
-        // Iterate over channels and append the tx hashes to be sorted out later
+        // Iterate over channels and collect the tx hashes, which are sorted by the collector
+        // before inserting the reverse lookup for hash -> tx_id.
         for mut channel in channels {
             while let Some(tx) = channel.recv().await {
                 let (tx_hash, tx_id) = tx.map_err(|boxed| *boxed)?;
-                tx_list.push((tx_hash, tx_id));
+                collected.collector.insert(tx_hash, tx_id)?;
             }
         }
+        collected.last_block = end_block;
+
+        if !is_final_range {
+            // Nothing is written until the whole range is hashed, so the checkpoint stays the same.
+            let mut entities_checkpoint = stage_checkpoint(provider)?;
+            entities_checkpoint.processed += collected.collector.len() as u64;
+            self.collected = Some(collected);
+
+            return Ok(ExecOutput {
+                checkpoint: input.checkpoint().with_entities_stage_checkpoint(entities_checkpoint),
+                done: false,
+            })
+        }
 
-        // Sort before inserting the reverse lookup for hash -> tx_id.
-        tx_list.par_sort_unstable_by(|txa, txb| txa.0.cmp(&txb.0));
+        debug!(target: "sync::stages::transaction_lookup", entries = collected.collector.len(), "Writing transaction lookup");
 
-        let mut txhash_cursor = tx.cursor_write::<tables::TxHashNumber>()?;
+        let mut txhash_cursor = tx.cursor_write::<RawTable<tables::TxHashNumber>>()?;
+        let mut entries = collected.collector.into_sorted()?.peekable();
 
         // If the last inserted element in the database is equal or bigger than the first
         // in our set, then we need to insert inside the DB. If it is smaller then last
         // element in the DB, we can append to the DB.
-        // Append probably only ever happens during sync, on the first table insertion.
-        let insert = tx_list
-            .first()
-            .zip(txhash_cursor.last()?)
-            .map(|((first, _), (last, _))| first <= &last)
-            .unwrap_or_default();
-        // if txhash_cursor.last() is None we will do insert. `zip` would return none if any item is
-        // none. if it is some and if first is smaller than last, we will do append.
-
-        for (tx_hash, id) in tx_list {
+        // The whole range is collected before writing, so during the initial sync the table is
+        // empty and all hashes are appended. Inserts only happen for small ranges on top of it.
+        let last = txhash_cursor.last()?.map(|(last, _)| last.key()).transpose()?;
+        let insert = match (entries.peek(), last) {
+            (Some(Ok((first, _))), Some(last)) => first.as_slice() <= last.as_bytes(),
+            _ => false,
+        };
+
+        for entry in entries {
+            let (tx_hash, id) = entry?;
+            let (tx_hash, id) = (RawKey::from_vec(tx_hash), RawValue::from_vec(id));
             if insert {
                 txhash_cursor.insert(tx_hash, id)?;
             } else {
@@ -129,7 +199,7 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(end_block)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
-            done: is_final_range,
+            done: true,
         })
     }
 
@@ -139,6 +209,9 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
+        // the collected hashes may belong to blocks that are unwound
+        self.collected = None;
+
         let tx = provider.tx_ref();
         let (range, unwind_to, _) = input.unwind_block_range_with_threshold(self.commit_threshold);
 
@@ -275,38 +348,96 @@ mod tests {
 
         let total_txs = runner.tx.table::<tables::Transactions>().unwrap().len() as u64;
 
-        // Execute first time
-        let result = runner.execute(first_input).await.unwrap();
+        // Execute first time, the hashes are only collected
+        let mut stage = runner.stage();
+        let result = runner.execute_with(&mut stage, first_input).await;
         let mut tx_count = 0;
-        let expected_progress = seed
-            .iter()
+        seed.iter()
             .find(|x| {
                 tx_count += x.body.len();
                 tx_count as u64 > threshold
             })
-            .map(|x| x.number)
-            .unwrap_or(previous_stage);
+            .expect("threshold is exceeded");
         assert_matches!(result, Ok(_));
         assert_eq!(
             result.unwrap(),
             ExecOutput {
-                checkpoint: StageCheckpoint::new(expected_progress).with_entities_stage_checkpoint(
-                    EntitiesCheckpoint {
-                        processed: runner.tx.table::<tables::TxHashNumber>().unwrap().len() as u64,
-                        total: total_txs
-                    }
+                checkpoint: StageCheckpoint::new(stage_progress).with_entities_stage_checkpoint(
+                    EntitiesCheckpoint { processed: tx_count as u64, total: total_txs }
                 ),
                 done: false
             }
         );
+        assert!(runner.tx.table_is_empty::<tables::TxHashNumber>().unwrap());
+
+        // Execute the same stage to completion
+        let mut result = runner.execute_with(&mut stage, first_input).await;
+        while let Ok(ExecOutput { done: false, .. }) = result {
+            assert!(runner.tx.table_is_empty::<tables::TxHashNumber>().unwrap());
+            result = runner.execute_with(&mut stage, first_input).await;
+        }
+        assert_matches!(result, Ok(_));
+        assert_eq!(
+            result.as_ref().unwrap(),
+            &ExecOutput {
+                checkpoint: StageCheckpoint::new(previous_stage).with_entities_stage_checkpoint(
+                    EntitiesCheckpoint { processed: total_txs, total: total_txs }
+                ),
+                done: true
+            }
+        );
 
-        // Execute second time to completion
+        assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
+    }
+
+    /// A new stage starts to collect the hashes of an incomplete range from scratch
+    #[tokio::test]
+    async fn execute_restarted_transaction_lookup() {
+        let mut runner = TransactionLookupTestRunner::default();
+        runner.set_threshold(50);
+        let (stage_progress, previous_stage) = (1000, 1100);
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        let mut rng = generators::rng();
+
+        let seed =
+            random_block_range(&mut rng, stage_progress + 1..=previous_stage, H256::zero(), 2..4);
+        runner.tx.insert_blocks(seed.iter(), None).expect("failed to seed execution");
+
+        // Collect the first batch and drop the stage
+        let result = runner.execute_with(&mut runner.stage(), input).await;
+        assert_matches!(result, Ok(ExecOutput { done: false, .. }));
+
+        // All hashes are written by a new stage
         runner.set_threshold(u64::MAX);
-        let second_input = ExecInput {
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "validation failed");
+    }
+
+    /// Execute the stage with a memory budget that forces the collector to spill to disk
+    #[tokio::test]
+    async fn execute_spilling_collector_transaction_lookup() {
+        let mut runner = TransactionLookupTestRunner::default();
+        runner.set_threshold(u64::MAX);
+        runner.set_etl_buffer_size(1024);
+        let (stage_progress, previous_stage) = (1000, 1100);
+        let input = ExecInput {
             target: Some(previous_stage),
-            checkpoint: Some(StageCheckpoint::new(expected_progress)),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
         };
-        let result = runner.execute(second_input).await.unwrap();
+        let mut rng = generators::rng();
+
+        let seed =
+            random_block_range(&mut rng, stage_progress + 1..=previous_stage, H256::zero(), 0..4);
+        runner.tx.insert_blocks(seed.iter(), None).expect("failed to seed execution");
+
+        let total_txs = runner.tx.table::<tables::Transactions>().unwrap().len() as u64;
+
+        // Execute once to completion
+        let result = runner.execute(input).await.unwrap();
         assert_matches!(result, Ok(_));
         assert_eq!(
             result.as_ref().unwrap(),
@@ -318,17 +449,22 @@ mod tests {
             }
         );
 
-        assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "validation failed");
     }
 
     struct TransactionLookupTestRunner {
         tx: TestTransaction,
         threshold: u64,
+        etl_buffer_size: usize,
     }
 
     impl Default for TransactionLookupTestRunner {
         fn default() -> Self {
-            Self { threshold: 1000, tx: TestTransaction::default() }
+            Self {
+                threshold: 1000,
+                etl_buffer_size: DEFAULT_ETL_BUFFER_SIZE,
+                tx: TestTransaction::default(),
+            }
         }
     }
 
@@ -337,6 +473,10 @@ mod tests {
             self.threshold = threshold;
         }
 
+        fn set_etl_buffer_size(&mut self, etl_buffer_size: usize) {
+            self.etl_buffer_size = etl_buffer_size;
+        }
+
         /// # Panics
         ///
         /// 1. If there are any entries in the [tables::TxHashNumber] table above a given block
@@ -372,7 +512,7 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            TransactionLookupStage { commit_threshold: self.threshold }
+            TransactionLookupStage::new(self.threshold).with_etl_buffer_size(self.etl_buffer_size)
         }
     }
 
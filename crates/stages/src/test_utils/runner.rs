@@ -57,6 +57,22 @@ pub(crate) trait ExecuteStageTestRunner: StageTestRunner {
         rx
     }
 
+    /// Run [Stage::execute] on the given stage instance, so that the stage keeps its state
+    /// between executions.
+    async fn execute_with(
+        &self,
+        stage: &mut Self::S,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let db = self.tx().inner_raw();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+
+        let result = stage.execute(&provider, input).await;
+        provider.commit().expect("failed to commit");
+        result
+    }
+
     /// Run a hook after [Stage::execute]. Required for Headers & Bodies stages.
     async fn after_execution(&self, _seed: Self::Seed) -> Result<(), TestRunnerError> {
         Ok(())
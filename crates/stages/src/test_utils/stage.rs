@@ -9,11 +9,17 @@ pub struct TestStage {
     id: StageId,
     exec_outputs: VecDeque<Result<ExecOutput, StageError>>,
     unwind_outputs: VecDeque<Result<UnwindOutput, StageError>>,
+    should_commit: bool,
 }
 
 impl TestStage {
     pub fn new(id: StageId) -> Self {
-        Self { id, exec_outputs: VecDeque::new(), unwind_outputs: VecDeque::new() }
+        Self {
+            id,
+            exec_outputs: VecDeque::new(),
+            unwind_outputs: VecDeque::new(),
+            should_commit: false,
+        }
     }
 
     pub fn with_exec(mut self, exec_outputs: VecDeque<Result<ExecOutput, StageError>>) -> Self {
@@ -38,6 +44,11 @@ impl TestStage {
         self.unwind_outputs.push_back(output);
         self
     }
+
+    pub fn with_should_commit(mut self, should_commit: bool) -> Self {
+        self.should_commit = should_commit;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,4 +76,8 @@ impl<DB: Database> Stage<DB> for TestStage {
             .pop_front()
             .unwrap_or_else(|| panic!("Test stage {} unwound too many times.", self.id))
     }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
 }
@@ -80,6 +80,10 @@ impl<'a> DbTx<'a> for TxMock {
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
         todo!()
     }
+
+    fn id(&self) -> u64 {
+        todo!()
+    }
 }
 
 impl<'a> DbTxMut<'a> for TxMock {
@@ -110,6 +114,10 @@ impl<'a> DbTxMut<'a> for TxMock {
     ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, DatabaseError> {
         todo!()
     }
+
+    fn dirty_size(&self) -> Result<usize, DatabaseError> {
+        todo!()
+    }
 }
 
 impl<'a> TableImporter<'a> for TxMock {}
@@ -49,6 +49,10 @@ pub trait DbTx<'tx>: for<'a> DbTxGAT<'a> {
     ) -> Result<<Self as DbTxGAT<'_>>::DupCursor<T>, DatabaseError>;
     /// Returns number of entries in the table.
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError>;
+    /// Returns the id of the transaction.
+    ///
+    /// Read only transactions with the same id read the same snapshot of the database.
+    fn id(&self) -> u64;
 }
 
 /// Read write transaction that allows writing to database
@@ -68,4 +72,6 @@ pub trait DbTxMut<'tx>: for<'a> DbTxMutGAT<'a> {
     fn cursor_dup_write<T: DupSort>(
         &self,
     ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, DatabaseError>;
+    /// Returns the size of the pages dirtied by the transaction so far, in bytes.
+    fn dirty_size(&self) -> Result<usize, DatabaseError>;
 }
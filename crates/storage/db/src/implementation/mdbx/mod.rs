@@ -66,6 +66,26 @@ impl<E: EnvironmentKind> Env<E> {
         path: &Path,
         kind: EnvKind,
         log_level: Option<LogLevel>,
+    ) -> Result<Env<E>, DatabaseError> {
+        Self::open_with_exclusive(path, kind, false, log_level)
+    }
+
+    /// Opens the database at the specified path in read/write mode, failing if it's already opened
+    /// by another process.
+    ///
+    /// While the environment is open, no other process can open the database.
+    pub fn open_exclusive(
+        path: &Path,
+        log_level: Option<LogLevel>,
+    ) -> Result<Env<E>, DatabaseError> {
+        Self::open_with_exclusive(path, EnvKind::RW, true, log_level)
+    }
+
+    fn open_with_exclusive(
+        path: &Path,
+        kind: EnvKind,
+        exclusive: bool,
+        log_level: Option<LogLevel>,
     ) -> Result<Env<E>, DatabaseError> {
         let mode = match kind {
             EnvKind::RO => Mode::ReadOnly,
@@ -85,6 +105,7 @@ impl<E: EnvironmentKind> Env<E> {
         });
         inner_env.set_flags(EnvironmentFlags {
             mode,
+            exclusive,
             // We disable readahead because it improves performance for linear scans, but
             // worsens it for random access (which is our access pattern outside of sync)
             no_rdahead: true,
@@ -144,6 +165,64 @@ impl<E: EnvironmentKind> Env<E> {
 
         Ok(())
     }
+
+    /// Returns how many pages of the database file are in use and how many are on the free-list.
+    pub fn page_usage(&self) -> Result<PageUsage, DatabaseError> {
+        let stat = self.inner.stat().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let info = self.inner.info().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let free_pages = self.inner.freelist().map_err(|e| DatabaseError::Stats(e.into()))?;
+
+        Ok(PageUsage {
+            page_size: stat.page_size() as usize,
+            // Page numbers are 0 based.
+            total_pages: info.last_pgno() + 1,
+            free_pages,
+        })
+    }
+
+    /// Copies the database into the `dest` directory, omitting all free pages.
+    ///
+    /// The directory must exist, but must not contain a database yet. Only the data file is
+    /// copied, the database version file has to be created separately.
+    pub fn copy_compacted(&self, dest: &Path) -> Result<(), DatabaseError> {
+        self.inner
+            .copy(&dest.join(MDBX_DATA_FILE_NAME), true)
+            .map_err(|e| DatabaseError::Copy(e.into()))
+    }
+}
+
+/// The name of the MDBX data file inside the database directory.
+pub const MDBX_DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Page usage of the database file, see [`Env::page_usage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageUsage {
+    /// Size of a single page in bytes.
+    pub page_size: usize,
+    /// Number of pages allocated in the database file.
+    pub total_pages: usize,
+    /// Number of allocated pages that are on the free-list and can be reused.
+    pub free_pages: usize,
+}
+
+impl PageUsage {
+    /// Size of the allocated part of the database file in bytes.
+    pub fn total_size(&self) -> usize {
+        self.total_pages * self.page_size
+    }
+
+    /// Size of the pages on the free-list in bytes.
+    pub fn free_size(&self) -> usize {
+        self.free_pages * self.page_size
+    }
+
+    /// Share of the allocated pages that are on the free-list, between `0.0` and `1.0`.
+    pub fn free_share(&self) -> f64 {
+        if self.total_pages == 0 {
+            return 0.0
+        }
+        self.free_pages as f64 / self.total_pages as f64
+    }
 }
 
 impl<E: EnvironmentKind> Deref for Env<E> {
@@ -196,6 +275,37 @@ mod tests {
     const ERROR_INIT_TX: &str = "Failed to create a MDBX transaction.";
     const ERROR_ETH_ADDRESS: &str = "Invalid address.";
 
+    #[test]
+    fn db_copy_compacted() {
+        let env = create_test_db::<WriteMap>(EnvKind::RW);
+
+        // Fill and clear a table to move its pages to the free-list.
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        for number in 0..1000 {
+            tx.put::<CanonicalHeaders>(number, H256::random()).expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.clear::<CanonicalHeaders>().expect(ERROR_PUT);
+        tx.put::<CanonicalHeaders>(0, H256::zero()).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let usage = env.page_usage().unwrap();
+        assert!(usage.free_pages > 0);
+
+        let dest = tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        env.copy_compacted(&dest).unwrap();
+
+        let compacted = Env::<WriteMap>::open(&dest, EnvKind::RO, None).expect(ERROR_DB_CREATION);
+        let compacted_usage = compacted.page_usage().unwrap();
+        assert_eq!(compacted_usage.free_pages, 0);
+        assert!(compacted_usage.total_pages < usage.total_pages);
+
+        let tx = compacted.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<CanonicalHeaders>(0).expect(ERROR_GET), Some(H256::zero()));
+        assert_eq!(tx.entries::<CanonicalHeaders>().expect(ERROR_GET), 1);
+    }
+
     #[test]
     fn db_creation() {
         create_test_db::<NoWriteMap>(EnvKind::RW);
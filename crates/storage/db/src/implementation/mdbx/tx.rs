@@ -30,11 +30,6 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
         Self { inner, db_handles: Default::default() }
     }
 
-    /// Gets this transaction ID.
-    pub fn id(&self) -> u64 {
-        self.inner.id()
-    }
-
     /// Gets a table database handle if it exists, otherwise creates it.
     pub fn get_dbi<T: Table>(&self) -> Result<DBI, DatabaseError> {
         let mut handles = self.db_handles.write();
@@ -120,6 +115,10 @@ impl<'tx, K: TransactionKind, E: EnvironmentKind> DbTx<'tx> for Tx<'tx, K, E> {
             .map_err(|e| DatabaseError::Stats(e.into()))?
             .entries())
     }
+
+    fn id(&self) -> u64 {
+        self.inner.id()
+    }
 }
 
 impl<E: EnvironmentKind> DbTxMut<'_> for Tx<'_, RW, E> {
@@ -163,4 +162,8 @@ impl<E: EnvironmentKind> DbTxMut<'_> for Tx<'_, RW, E> {
     ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, DatabaseError> {
         self.new_cursor()
     }
+
+    fn dirty_size(&self) -> Result<usize, DatabaseError> {
+        Ok(self.inner.dirty_space().map_err(|e| DatabaseError::Stats(e.into()))? as usize)
+    }
 }
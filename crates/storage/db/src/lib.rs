@@ -159,6 +159,24 @@ pub fn open_db(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<Databas
     }
 }
 
+/// Opens up an existing database in exclusive Read/Write mode. Fails if the database is opened by
+/// another process, e.g. a running node.
+pub fn open_db_exclusive(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<DatabaseEnv> {
+    #[cfg(feature = "mdbx")]
+    {
+        Env::<WriteMap>::open_exclusive(path, log_level).with_context(|| {
+            format!(
+                "Could not open database at path {} exclusively, is it used by another process?",
+                path.display()
+            )
+        })
+    }
+    #[cfg(not(feature = "mdbx"))]
+    {
+        unimplemented!();
+    }
+}
+
 /// Collection of database test utilities
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils {
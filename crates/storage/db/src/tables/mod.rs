@@ -30,7 +30,7 @@ use crate::{
             accounts::{AccountBeforeTx, BlockNumberAddress},
             blocks::{HeaderHash, StoredBlockOmmers},
             storage_sharded_key::StorageShardedKey,
-            ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+            ChainStateKey, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
         },
     },
 };
@@ -51,7 +51,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 25;
+pub const NUM_TABLES: usize = 27;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -183,7 +183,9 @@ tables!([
     (StoragesTrie, TableType::DupSort),
     (TxSenders, TableType::Table),
     (SyncStage, TableType::Table),
-    (SyncStageProgress, TableType::Table)
+    (SyncStageProgress, TableType::Table),
+    (ChainState, TableType::Table),
+    (PendingBlocks, TableType::Table)
 ]);
 
 #[macro_export]
@@ -415,6 +417,17 @@ table!(
     ( SyncStageProgress ) StageId | Vec<u8>
 );
 
+table!(
+    /// Stores the hashes of the last finalized and safe blocks received from the consensus layer.
+    ( ChainState ) ChainStateKey | BlockHash
+);
+
+table!(
+    /// Stores the RLP encoded blocks of the blockchain tree that are not canonical yet, i.e.
+    /// sidechain and buffered blocks, so they survive restarts.
+    ( PendingBlocks ) BlockHash | Vec<u8>
+);
+
 /// Alias Types
 
 /// List with transaction numbers.
@@ -454,6 +467,8 @@ mod tests {
         (TableType::Table, TxSenders::const_name()),
         (TableType::Table, SyncStage::const_name()),
         (TableType::Table, SyncStageProgress::const_name()),
+        (TableType::Table, ChainState::const_name()),
+        (TableType::Table, PendingBlocks::const_name()),
     ];
 
     #[test]
@@ -8,6 +8,7 @@ use reth_primitives::{
     trie::{StoredNibbles, StoredNibblesSubKey},
     Address, H256,
 };
+use serde::{Deserialize, Serialize};
 
 pub mod accounts;
 pub mod blocks;
@@ -61,6 +62,36 @@ impl Decode for Vec<u8> {
     }
 }
 
+/// Keys of the [`ChainState`](crate::tables::ChainState) table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ChainStateKey {
+    /// The last block that was finalized by the consensus layer.
+    LastFinalizedBlock,
+    /// The last block that was declared safe by the consensus layer.
+    LastSafeBlock,
+}
+
+impl Encode for ChainStateKey {
+    type Encoded = [u8; 1];
+
+    fn encode(self) -> Self::Encoded {
+        match self {
+            Self::LastFinalizedBlock => [0],
+            Self::LastSafeBlock => [1],
+        }
+    }
+}
+
+impl Decode for ChainStateKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            [1] => Ok(Self::LastSafeBlock),
+            _ => Err(DatabaseError::DecodeError),
+        }
+    }
+}
+
 impl Encode for Address {
     type Encoded = [u8; 20];
     fn encode(self) -> Self::Encoded {
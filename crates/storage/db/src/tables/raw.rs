@@ -50,6 +50,10 @@ impl<K: Key> RawKey<K> {
     pub fn new(key: K) -> Self {
         Self { key: K::encode(key).as_ref().to_vec(), _phantom: std::marker::PhantomData }
     }
+    /// Create new raw key from already encoded bytes.
+    pub fn from_vec(key: Vec<u8>) -> Self {
+        Self { key, _phantom: std::marker::PhantomData }
+    }
     /// Returns the raw key.
     pub fn key(&self) -> Result<K, DatabaseError> {
         K::decode(&self.key)
@@ -96,6 +100,10 @@ impl<V: Value> RawValue<V> {
     pub fn new(value: V) -> Self {
         Self { value: V::compress(value).as_ref().to_vec(), _phantom: std::marker::PhantomData }
     }
+    /// Create new raw value from already compressed bytes.
+    pub fn from_vec(value: Vec<u8>) -> Self {
+        Self { value, _phantom: std::marker::PhantomData }
+    }
     /// Returns the raw value.
     pub fn value(&self) -> Result<V, DatabaseError> {
         V::decompress(&self.value)
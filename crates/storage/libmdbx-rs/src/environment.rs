@@ -213,6 +213,39 @@ where
 
         Ok(freelist)
     }
+
+    /// Copies the environment into a new data file at `dest`, which must not exist yet.
+    ///
+    /// With `compact`, free pages are omitted and all pages are renumbered sequentially, so the
+    /// copy only occupies as much space as the live data. The copy is always resizable.
+    ///
+    /// Note:
+    ///
+    /// * The copy is made within a read transaction, so running write transactions in parallel can
+    ///   grow the source file significantly. Prefer copying while no writer is active.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = CString::new(path_to_bytes(dest)).map_err(|_| Error::Invalid)?;
+        let mut flags = ffi::MDBX_CP_FORCE_DYNAMIC_SIZE;
+        if compact {
+            flags |= ffi::MDBX_CP_COMPACT;
+        }
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
 }
 
 /// Environment statistics.
@@ -470,20 +503,6 @@ where
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
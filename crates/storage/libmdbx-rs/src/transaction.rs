@@ -10,8 +10,12 @@ use indexmap::IndexSet;
 use libc::{c_uint, c_void};
 use parking_lot::Mutex;
 use std::{
-    fmt, fmt::Debug, marker::PhantomData, mem::size_of, ptr, rc::Rc, result, slice,
-    sync::mpsc::sync_channel,
+    fmt,
+    fmt::Debug,
+    marker::PhantomData,
+    mem::{self, size_of},
+    ptr, result, slice,
+    sync::{mpsc::sync_channel, Arc},
 };
 
 mod private {
@@ -53,7 +57,7 @@ where
     K: TransactionKind,
     E: EnvironmentKind,
 {
-    txn: Rc<Mutex<*mut ffi::MDBX_txn>>,
+    txn: Arc<Mutex<*mut ffi::MDBX_txn>>,
     primed_dbis: Mutex<IndexSet<ffi::MDBX_dbi>>,
     committed: bool,
     env: &'env Environment<E>,
@@ -79,9 +83,14 @@ where
         }
     }
 
+    // `Transaction` and `Cursor` are `Send` and `Sync`, and every cursor holds a clone of the
+    // handle, so cursors of a transaction that is shared across threads clone and drop the handle
+    // concurrently, which is a data race on the counter of an `Rc`. The raw pointer is not `Send`,
+    // hence the lint, but it is only ever accessed through the mutex.
+    #[allow(clippy::arc_with_non_send_sync)]
     pub(crate) fn new_from_ptr(env: &'env Environment<E>, txn: *mut ffi::MDBX_txn) -> Self {
         Self {
-            txn: Rc::new(Mutex::new(txn)),
+            txn: Arc::new(Mutex::new(txn)),
             primed_dbis: Mutex::new(IndexSet::new()),
             committed: false,
             env,
@@ -93,7 +102,7 @@ where
     ///
     /// The caller **must** ensure that the pointer is not used after the
     /// lifetime of the transaction.
-    pub(crate) fn txn_mutex(&self) -> Rc<Mutex<*mut ffi::MDBX_txn>> {
+    pub(crate) fn txn_mutex(&self) -> Arc<Mutex<*mut ffi::MDBX_txn>> {
         self.txn.clone()
     }
 
@@ -235,6 +244,15 @@ impl<'env, E> Transaction<'env, RW, E>
 where
     E: EnvironmentKind,
 {
+    /// Returns the size of the pages dirtied by the transaction so far, in bytes.
+    pub fn dirty_space(&self) -> Result<u64> {
+        unsafe {
+            let mut info: ffi::MDBX_txn_info = mem::zeroed();
+            mdbx_result(txn_execute(&self.txn, |txn| ffi::mdbx_txn_info(txn, &mut info, false)))?;
+            Ok(info.txn_space_dirty)
+        }
+    }
+
     fn open_db_with_flags<'txn>(
         &'txn self,
         name: Option<&str>,
@@ -156,3 +156,36 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::new().open(dir.path()).unwrap();
+
+    // Write a few values and clear all but the last one to fill the freelist.
+    for i in 0..64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        let db = tx.open_db(None).unwrap();
+        if i < 63 {
+            tx.clear_db(db.dbi()).expect("clear");
+        }
+        tx.put(db.dbi(), value, value, WriteFlags::default()).expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    assert!(env.freelist().unwrap() > 0);
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+
+    let copy = Environment::new().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() <= env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    let mut value = [0u8; 8];
+    LittleEndian::write_u64(&mut value, 63);
+    assert_eq!(tx.get(db.dbi(), &value).unwrap(), Some(value));
+}
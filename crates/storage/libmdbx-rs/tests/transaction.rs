@@ -325,6 +325,20 @@ fn test_stat() {
     }
 }
 
+#[test]
+fn test_dirty_space() {
+    let dir = tempdir().unwrap();
+    let env = Environment::new().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_txn().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    let before = txn.dirty_space().unwrap();
+    for i in 0..1000u32 {
+        txn.put(db.dbi(), i.to_be_bytes(), [0u8; 128], WriteFlags::empty()).unwrap();
+    }
+    assert!(txn.dirty_space().unwrap() > before);
+}
+
 #[test]
 fn test_stat_dupsort() {
     let dir = tempdir().unwrap();
@@ -25,18 +25,19 @@ pub use traits::{
     BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
     BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
     CanonStateNotificationSender, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, EvmEnvProvider, ExecutorFactory, HashingWriter, HeaderProvider,
-    HistoryWriter, PostStateDataProvider, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StageCheckpointWriter, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StorageReader, TransactionsProvider,
+    ChainSpecProvider, ChainStateReader, ChainStateWriter, EvmEnvProvider, ExecutorFactory,
+    HashingWriter, HeaderProvider, HistoryWriter, PostStateDataProvider, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StageCheckpointWriter, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider, StorageReader, TransactionsProvider,
     WithdrawalsProvider,
 };
 
 /// Provider trait implementations.
 pub mod providers;
 pub use providers::{
-    DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
-    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    ConsistencyFix, ConsistencyReport, DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW,
+    HistoricalStateProvider, HistoricalStateProviderRef, LatestStateProvider,
+    LatestStateProviderRef, ProviderFactory,
 };
 
 /// Execution result
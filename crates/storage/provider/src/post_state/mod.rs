@@ -1,6 +1,7 @@
 //! Output of execution.
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
     models::{AccountBeforeTx, BlockNumberAddress},
     tables,
     transaction::{DbTx, DbTxMut},
@@ -12,7 +13,7 @@ use reth_primitives::{
 };
 use reth_trie::{
     hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
-    StateRoot, StateRootError,
+    ParallelStateRoot, StateRoot, StateRootError,
 };
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -119,6 +120,40 @@ impl PostState {
             + self.storage_changes.size
     }
 
+    /// Return an estimate of the memory used by the poststate, in bytes.
+    ///
+    /// Only the changes, bytecodes and receipts are accounted for, not the overhead of the
+    /// collections holding them.
+    pub fn memory_size_hint(&self) -> usize {
+        const ACCOUNT_SIZE: usize = std::mem::size_of::<(Address, Option<Account>)>();
+        const SLOT_SIZE: usize = std::mem::size_of::<(U256, U256)>();
+
+        let storage_slots: usize = self.storage.values().map(|storage| storage.storage.len()).sum();
+        let bytecodes: usize = self.bytecode.values().map(|bytecode| bytecode.0.len()).sum();
+        let receipts: usize = self
+            .receipts
+            .values()
+            .flatten()
+            .map(|receipt| {
+                std::mem::size_of::<Receipt>() +
+                    receipt
+                        .logs
+                        .iter()
+                        .map(|log| {
+                            std::mem::size_of::<Log>() +
+                                log.topics.len() * std::mem::size_of::<H256>() +
+                                log.data.len()
+                        })
+                        .sum::<usize>()
+            })
+            .sum();
+
+        (self.accounts.len() + self.account_changes.size) * ACCOUNT_SIZE +
+            (storage_slots + self.storage_changes.size) * SLOT_SIZE +
+            bytecodes +
+            receipts
+    }
+
     /// Get the latest state of all changed accounts.
     pub fn accounts(&self) -> &BTreeMap<Address, Option<Account>> {
         &self.accounts
@@ -258,6 +293,20 @@ impl PostState {
             .root()
     }
 
+    /// Calculate the state root for this [PostState], computing the storage roots of the changed
+    /// accounts in parallel.
+    ///
+    /// Every worker reads through its own read transaction of the given database, see
+    /// [ParallelStateRoot].
+    ///
+    /// # Returns
+    ///
+    /// The state root for this [PostState].
+    pub fn state_root_parallel<DB: Database>(&self, db: &DB) -> Result<H256, StateRootError> {
+        let hashed_post_state = self.hash_state_slow();
+        ParallelStateRoot::new(db).with_hashed_post_state(&hashed_post_state).root()
+    }
+
     // todo: note overwrite behavior, i.e. changes in `other` take precedent
     /// Extend this [PostState] with the changes in another [PostState].
     pub fn extend(&mut self, mut other: PostState) {
@@ -1816,6 +1865,26 @@ mod tests {
         assert_eq!(post_state.account_changes().get(&2).and_then(|ch| ch.get(&address)), None);
     }
 
+    #[test]
+    fn memory_size_hint() {
+        let mut post_state = PostState::default();
+        assert_eq!(post_state.memory_size_hint(), 0);
+
+        // The created account and its changeset
+        post_state.create_account(1, Address::random(), Account::default());
+        let account_size = post_state.memory_size_hint();
+        assert!(account_size > 0);
+
+        post_state.add_bytecode(H256::random(), Bytecode::new_raw(vec![0x60, 0x00].into()));
+        assert_eq!(post_state.memory_size_hint(), account_size + 2);
+
+        post_state.add_receipt(1, Receipt::default());
+        assert_eq!(
+            post_state.memory_size_hint(),
+            account_size + 2 + std::mem::size_of::<Receipt>()
+        );
+    }
+
     #[test]
     fn empty_post_state_state_root() {
         let db: Arc<DatabaseEnv> = create_test_rw_db();
@@ -0,0 +1,757 @@
+//! Consistency checks between stage checkpoints and the tables each stage writes.
+
+use crate::{BlockReader, DatabaseProvider, HeaderProvider, StageCheckpointReader};
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    table::Table,
+    tables,
+    transaction::DbTx,
+};
+use reth_interfaces::Result;
+use reth_primitives::{
+    keccak256,
+    stage::{StageCheckpoint, StageId},
+    Account, Address, BlockNumber, PruneMode, TxNumber, H256, U256,
+};
+use reth_trie::StateRoot;
+use std::fmt;
+
+/// Stages that are verified by [`DatabaseProvider::check_consistency`], in pipeline order.
+pub const CONSISTENCY_CHECKED_STAGES: [StageId; 12] = [
+    StageId::Headers,
+    StageId::TotalDifficulty,
+    StageId::Bodies,
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::TransactionLookup,
+    StageId::IndexStorageHistory,
+    StageId::IndexAccountHistory,
+    StageId::Finish,
+];
+
+/// The maximum number of mismatching entries reported for a hashed state table.
+///
+/// The walk stops at this number of mismatches, the stage has to be rerun either way.
+const MAX_HASHED_MISMATCHES: usize = 100;
+
+/// The kind of disagreement between a stage checkpoint and the table contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InconsistencyKind {
+    /// The highest key of the table is above the highest key implied by the checkpoint.
+    AheadOfCheckpoint {
+        /// Highest key implied by the checkpoint, `None` if the table should be empty.
+        expected: Option<u64>,
+        /// Highest key found in the table.
+        found: u64,
+    },
+    /// The table does not cover every key up to the checkpoint.
+    BehindCheckpoint {
+        /// Highest key implied by the checkpoint.
+        expected: u64,
+        /// Highest key found in the table, `None` if the table is empty.
+        found: Option<u64>,
+    },
+    /// The number of entries differs from the number implied by the checkpoint.
+    EntryCount {
+        /// Number of entries implied by the checkpoint.
+        expected: usize,
+        /// Number of entries found in the table.
+        found: usize,
+    },
+    /// The stage checkpoint is ahead of the checkpoint of the stage it depends on.
+    CheckpointAhead {
+        /// The stage this stage depends on.
+        dependency: StageId,
+        /// Checkpoint of the dependency.
+        dependency_block: BlockNumber,
+    },
+    /// The hashed account does not match the plain account state.
+    HashedAccount {
+        /// Plain account address.
+        address: Address,
+        /// The account in plain state.
+        plain: Option<Account>,
+        /// The account in hashed state.
+        hashed: Option<Account>,
+    },
+    /// The hashed storage slot does not match the plain storage state.
+    HashedStorage {
+        /// Plain account address.
+        address: Address,
+        /// Plain storage slot.
+        slot: H256,
+        /// The value in plain state.
+        plain: U256,
+        /// The value in hashed state.
+        hashed: Option<U256>,
+    },
+    /// The state root computed from the trie tables does not match the header.
+    StateRoot {
+        /// The block whose header was compared against.
+        block: BlockNumber,
+        /// State root in the header.
+        expected: H256,
+        /// State root computed from the trie tables.
+        got: H256,
+    },
+    /// The header of a block below the checkpoint is missing.
+    MissingHeader {
+        /// The block number of the missing header.
+        block: BlockNumber,
+    },
+}
+
+/// A single disagreement between a stage checkpoint and the contents of the tables it writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// The stage whose output is inconsistent.
+    pub stage: StageId,
+    /// The checkpoint of the stage at the time of the check.
+    pub checkpoint: StageCheckpoint,
+    /// The name of the table the inconsistency was found in.
+    pub table: &'static str,
+    /// What exactly is inconsistent.
+    pub kind: InconsistencyKind,
+}
+
+impl Inconsistency {
+    /// Returns the action that brings the tables back in line with the checkpoints.
+    pub fn suggested_fix(&self) -> ConsistencyFix {
+        match (&self.stage, &self.kind) {
+            // Downloaded data can only be rolled back, there's no plain source to rebuild it from.
+            (StageId::Headers | StageId::Bodies, InconsistencyKind::AheadOfCheckpoint { .. }) => {
+                ConsistencyFix::Unwind { to: self.checkpoint.block_number }
+            }
+            (StageId::Headers, _) => {
+                ConsistencyFix::Unwind { to: self.checkpoint.block_number.saturating_sub(1) }
+            }
+            (stage, _) => ConsistencyFix::Drop(*stage),
+        }
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (checkpoint {}), table {}: {:?}",
+            self.stage, self.checkpoint.block_number, self.table, self.kind
+        )
+    }
+}
+
+/// Action that resolves an [`Inconsistency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyFix {
+    /// Unwind the chain down to the given block.
+    Unwind {
+        /// The block to unwind to.
+        to: BlockNumber,
+    },
+    /// Drop all data of the stage and rerun it.
+    Drop(StageId),
+}
+
+/// The outcome of [`DatabaseProvider::check_consistency`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// All inconsistencies found, in the order of [`CONSISTENCY_CHECKED_STAGES`].
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    /// Returns the deduplicated fixes for all found inconsistencies, in pipeline order.
+    pub fn fixes(&self) -> Vec<ConsistencyFix> {
+        let mut fixes = Vec::new();
+        for fix in self.inconsistencies.iter().map(Inconsistency::suggested_fix) {
+            if !fixes.contains(&fix) {
+                fixes.push(fix);
+            }
+        }
+        fixes
+    }
+}
+
+impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
+    /// Walks the output tables of every stage in [`CONSISTENCY_CHECKED_STAGES`] and verifies them
+    /// against the stage checkpoint and, for derived tables, against their plain state source.
+    ///
+    /// The transaction lookup entries of blocks pruned by the given prune mode may or may not be
+    /// removed already.
+    pub fn check_consistency(
+        &self,
+        tx_lookup_prune_mode: Option<PruneMode>,
+    ) -> Result<ConsistencyReport> {
+        self.check_stages_consistency(CONSISTENCY_CHECKED_STAGES, tx_lookup_prune_mode)
+    }
+
+    /// Same as [`DatabaseProvider::check_consistency`], but only for the given stages.
+    ///
+    /// Stages that don't write any tables are ignored.
+    pub fn check_stages_consistency(
+        &self,
+        stages: impl IntoIterator<Item = StageId>,
+        tx_lookup_prune_mode: Option<PruneMode>,
+    ) -> Result<ConsistencyReport> {
+        let mut report = ConsistencyReport::default();
+        for stage in stages {
+            let checkpoint = self.get_stage_checkpoint(stage)?.unwrap_or_default();
+            let mut checker = StageChecker {
+                provider: self,
+                stage,
+                checkpoint,
+                tx_lookup_prune_mode,
+                report: &mut report,
+            };
+            match stage {
+                StageId::Headers => checker.check_headers()?,
+                StageId::TotalDifficulty => checker.check_total_difficulty()?,
+                StageId::Bodies => checker.check_bodies()?,
+                StageId::SenderRecovery => checker.check_senders()?,
+                StageId::Execution => checker.check_execution()?,
+                StageId::AccountHashing => checker.check_account_hashing()?,
+                StageId::StorageHashing => checker.check_storage_hashing()?,
+                StageId::MerkleExecute => checker.check_merkle()?,
+                StageId::TransactionLookup => checker.check_transaction_lookup()?,
+                StageId::IndexAccountHistory => checker.check_account_history()?,
+                StageId::IndexStorageHistory => checker.check_storage_history()?,
+                StageId::Finish => checker.check_finish()?,
+                StageId::MerkleUnwind | StageId::Other(_) => {}
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Runs the checks of a single stage and records the findings in the report.
+struct StageChecker<'a, 'this, TX> {
+    provider: &'a DatabaseProvider<'this, TX>,
+    stage: StageId,
+    checkpoint: StageCheckpoint,
+    tx_lookup_prune_mode: Option<PruneMode>,
+    report: &'a mut ConsistencyReport,
+}
+
+impl<'a, 'this, TX: DbTx<'this>> StageChecker<'a, 'this, TX> {
+    fn tx(&self) -> &'a TX {
+        self.provider.tx_ref()
+    }
+
+    fn push<T: Table>(&mut self, kind: InconsistencyKind) {
+        self.report.inconsistencies.push(Inconsistency {
+            stage: self.stage,
+            checkpoint: self.checkpoint,
+            table: T::NAME,
+            kind,
+        });
+    }
+
+    /// The highest transaction number at the checkpoint, `None` if there were no transactions.
+    fn last_tx_at_checkpoint(&self) -> Result<Option<TxNumber>> {
+        Ok(self
+            .provider
+            .block_body_indices(self.checkpoint.block_number)?
+            .map(|indices| indices.next_tx_num())
+            .and_then(|next| next.checked_sub(1)))
+    }
+
+    /// Returns `true` if the checkpoint of `dependency` is behind this stage's checkpoint, in
+    /// which case it's recorded as an inconsistency of the given table.
+    fn is_ahead_of<T: Table>(&mut self, dependency: StageId) -> Result<bool> {
+        let dependency_block =
+            self.provider.get_stage_checkpoint(dependency)?.unwrap_or_default().block_number;
+        if self.checkpoint.block_number > dependency_block {
+            self.push::<T>(InconsistencyKind::CheckpointAhead { dependency, dependency_block });
+            return Ok(true)
+        }
+        Ok(false)
+    }
+
+    /// Returns `true` if this stage has caught up with `dependency` and has no intermediate
+    /// progress, i.e. its tables must fully reflect the dependency's output.
+    fn is_synced_with(&self, dependency: StageId) -> Result<bool> {
+        let dependency_block =
+            self.provider.get_stage_checkpoint(dependency)?.unwrap_or_default().block_number;
+        Ok(self.checkpoint.block_number == dependency_block &&
+            self.checkpoint.stage_checkpoint.is_none())
+    }
+
+    /// Compares the highest key of the table with the expected highest key.
+    fn check_last_key<T: Table<Key = u64>>(&mut self, expected: Option<u64>) -> Result<()> {
+        let found = self.tx().cursor_read::<T>()?.last()?.map(|(key, _)| key);
+        match (expected, found) {
+            (expected, Some(found)) if expected.map_or(true, |expected| found > expected) => {
+                self.push::<T>(InconsistencyKind::AheadOfCheckpoint { expected, found })
+            }
+            (Some(expected), found) if found.map_or(true, |found| found < expected) => {
+                self.push::<T>(InconsistencyKind::BehindCheckpoint { expected, found })
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Compares the number of entries in the table with the expected count.
+    fn check_entries<T: Table>(&mut self, expected: usize) -> Result<()> {
+        let found = self.tx().entries::<T>()?;
+        if found != expected {
+            self.push::<T>(InconsistencyKind::EntryCount { expected, found });
+        }
+        Ok(())
+    }
+
+    /// Records the header of the given block as missing if the table has no entry for it.
+    fn check_header_exists<T: Table<Key = BlockNumber>>(
+        &mut self,
+        block: BlockNumber,
+    ) -> Result<()> {
+        if self.tx().get::<T>(block)?.is_none() {
+            self.push::<T>(InconsistencyKind::MissingHeader { block });
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the headers stage is in the middle of filling the gap between its
+    /// checkpoint and the sync target.
+    fn is_headers_sync_in_progress(&self) -> bool {
+        self.checkpoint.headers_stage_checkpoint().map_or(false, |stage_checkpoint| {
+            stage_checkpoint.block_range.from == self.checkpoint.block_number &&
+                stage_checkpoint.block_range.to > self.checkpoint.block_number
+        })
+    }
+
+    fn check_headers(&mut self) -> Result<()> {
+        let checkpoint = self.checkpoint.block_number;
+        if self.is_headers_sync_in_progress() {
+            // The headers are downloaded in reverse from the sync target and written before the
+            // checkpoint advances, so only the headers up to the checkpoint can be verified.
+            self.check_header_exists::<tables::CanonicalHeaders>(checkpoint)?;
+            self.check_header_exists::<tables::Headers>(checkpoint)?;
+            let found = self.tx().entries::<tables::HeaderNumbers>()?;
+            if found <= checkpoint as usize {
+                self.push::<tables::HeaderNumbers>(InconsistencyKind::EntryCount {
+                    expected: checkpoint as usize + 1,
+                    found,
+                });
+            }
+            return Ok(())
+        }
+
+        let expected = Some(checkpoint);
+        self.check_last_key::<tables::CanonicalHeaders>(expected)?;
+        self.check_last_key::<tables::Headers>(expected)?;
+        self.check_entries::<tables::HeaderNumbers>(checkpoint as usize + 1)
+    }
+
+    fn check_total_difficulty(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::HeaderTD>(StageId::Headers)? {
+            return Ok(())
+        }
+        self.check_last_key::<tables::HeaderTD>(Some(self.checkpoint.block_number))
+    }
+
+    fn check_bodies(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::BlockBodyIndices>(StageId::Headers)? {
+            return Ok(())
+        }
+        self.check_last_key::<tables::BlockBodyIndices>(Some(self.checkpoint.block_number))?;
+        let last_tx = self.last_tx_at_checkpoint()?;
+        self.check_last_key::<tables::Transactions>(last_tx)?;
+        self.check_last_key::<tables::TransactionBlock>(last_tx)
+    }
+
+    fn check_senders(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::TxSenders>(StageId::Bodies)? {
+            return Ok(())
+        }
+        let last_tx = self.last_tx_at_checkpoint()?;
+        self.check_last_key::<tables::TxSenders>(last_tx)?;
+        self.check_entries::<tables::TxSenders>(last_tx.map_or(0, |tx| tx as usize + 1))
+    }
+
+    fn check_execution(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::Receipts>(StageId::SenderRecovery)? {
+            return Ok(())
+        }
+        self.check_last_key::<tables::Receipts>(self.last_tx_at_checkpoint()?)?;
+
+        // Changesets are only written for blocks that changed state, so they may end below the
+        // checkpoint, but never above it.
+        let checkpoint = self.checkpoint.block_number;
+        if let Some((found, _)) = self.tx().cursor_read::<tables::AccountChangeSet>()?.last()? {
+            if found > checkpoint {
+                self.push::<tables::AccountChangeSet>(InconsistencyKind::AheadOfCheckpoint {
+                    expected: Some(checkpoint),
+                    found,
+                });
+            }
+        }
+        if let Some((key, _)) = self.tx().cursor_read::<tables::StorageChangeSet>()?.last()? {
+            if key.block_number() > checkpoint {
+                self.push::<tables::StorageChangeSet>(InconsistencyKind::AheadOfCheckpoint {
+                    expected: Some(checkpoint),
+                    found: key.block_number(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_account_hashing(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::HashedAccount>(StageId::Execution)? ||
+            !self.is_synced_with(StageId::Execution)?
+        {
+            return Ok(())
+        }
+
+        let mut hashed_cursor = self.tx().cursor_read::<tables::HashedAccount>()?;
+        let (mut plain_entries, mut mismatches) = (0, 0);
+        for entry in self.tx().cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+            let (address, plain) = entry?;
+            plain_entries += 1;
+            let hashed = hashed_cursor.seek_exact(keccak256(address))?.map(|(_, account)| account);
+            if hashed != Some(plain) {
+                self.push::<tables::HashedAccount>(InconsistencyKind::HashedAccount {
+                    address,
+                    plain: Some(plain),
+                    hashed,
+                });
+                mismatches += 1;
+                if mismatches == MAX_HASHED_MISMATCHES {
+                    return Ok(())
+                }
+            }
+        }
+        self.check_entries::<tables::HashedAccount>(plain_entries)
+    }
+
+    fn check_storage_hashing(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::HashedStorage>(StageId::Execution)? ||
+            !self.is_synced_with(StageId::Execution)?
+        {
+            return Ok(())
+        }
+
+        let mut hashed_cursor = self.tx().cursor_dup_read::<tables::HashedStorage>()?;
+        let (mut plain_entries, mut mismatches) = (0, 0);
+        for entry in self.tx().cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+            let (address, plain) = entry?;
+            plain_entries += 1;
+            let hashed_slot = keccak256(plain.key);
+            let hashed = hashed_cursor
+                .seek_by_key_subkey(keccak256(address), hashed_slot)?
+                .filter(|entry| entry.key == hashed_slot)
+                .map(|entry| entry.value);
+            if hashed != Some(plain.value) {
+                self.push::<tables::HashedStorage>(InconsistencyKind::HashedStorage {
+                    address,
+                    slot: plain.key,
+                    plain: plain.value,
+                    hashed,
+                });
+                mismatches += 1;
+                if mismatches == MAX_HASHED_MISMATCHES {
+                    return Ok(())
+                }
+            }
+        }
+        self.check_entries::<tables::HashedStorage>(plain_entries)
+    }
+
+    fn check_merkle(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::AccountsTrie>(StageId::AccountHashing)? ||
+            self.is_ahead_of::<tables::StoragesTrie>(StageId::StorageHashing)? ||
+            !self.is_synced_with(StageId::AccountHashing)?
+        {
+            return Ok(())
+        }
+
+        let block = self.checkpoint.block_number;
+        let Some(header) = self.provider.header_by_number(block)? else {
+            self.push::<tables::Headers>(InconsistencyKind::MissingHeader { block });
+            return Ok(())
+        };
+        let got = StateRoot::new(self.tx()).root().map_err(reth_db::DatabaseError::from)?;
+        if got != header.state_root {
+            self.push::<tables::AccountsTrie>(InconsistencyKind::StateRoot {
+                block,
+                expected: header.state_root,
+                got,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_transaction_lookup(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::TxHashNumber>(StageId::Bodies)? {
+            return Ok(())
+        }
+        let expected = self.last_tx_at_checkpoint()?.map_or(0, |tx| tx as usize + 1);
+        let Some(prune_mode) = self.tx_lookup_prune_mode else {
+            return self.check_entries::<tables::TxHashNumber>(expected)
+        };
+
+        // The lookups of pruned blocks may or may not be removed yet, so only the lookups of the
+        // retained blocks are required.
+        let checkpoint = self.checkpoint.block_number;
+        let first_retained_block = match prune_mode {
+            PruneMode::Full => None,
+            PruneMode::Distance(distance) => Some(checkpoint.saturating_sub(distance)),
+            PruneMode::Before(block) => Some(block),
+        };
+        let first_retained_tx = match first_retained_block {
+            Some(block) if block <= checkpoint => self
+                .provider
+                .block_body_indices(block)?
+                .map_or(expected, |indices| indices.first_tx_num as usize),
+            _ => expected,
+        };
+
+        let found = self.tx().entries::<tables::TxHashNumber>()?;
+        if found > expected {
+            self.push::<tables::TxHashNumber>(InconsistencyKind::EntryCount { expected, found });
+        } else if found < expected - first_retained_tx {
+            self.push::<tables::TxHashNumber>(InconsistencyKind::EntryCount {
+                expected: expected - first_retained_tx,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_account_history(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::AccountHistory>(StageId::Execution)? {
+            return Ok(())
+        }
+        let mut highest = None;
+        for entry in self.tx().cursor_read::<tables::AccountHistory>()?.walk(None)? {
+            let (_, list) = entry?;
+            highest = highest.max(list.iter(0).max().map(|block| block as u64));
+        }
+        self.check_highest_indexed::<tables::AccountHistory>(highest);
+        Ok(())
+    }
+
+    fn check_storage_history(&mut self) -> Result<()> {
+        if self.is_ahead_of::<tables::StorageHistory>(StageId::Execution)? {
+            return Ok(())
+        }
+        let mut highest = None;
+        for entry in self.tx().cursor_read::<tables::StorageHistory>()?.walk(None)? {
+            let (_, list) = entry?;
+            highest = highest.max(list.iter(0).max().map(|block| block as u64));
+        }
+        self.check_highest_indexed::<tables::StorageHistory>(highest);
+        Ok(())
+    }
+
+    /// History indices may end below the checkpoint if the last blocks didn't change state, but
+    /// must never reference blocks above it.
+    fn check_highest_indexed<T: Table>(&mut self, highest: Option<BlockNumber>) {
+        let expected = self.checkpoint.block_number;
+        if let Some(found) = highest.filter(|found| *found > expected) {
+            self.push::<T>(InconsistencyKind::AheadOfCheckpoint {
+                expected: Some(expected),
+                found,
+            });
+        }
+    }
+
+    fn check_finish(&mut self) -> Result<()> {
+        // The finish checkpoint must not claim more than the slowest stage has processed.
+        for dependency in StageId::ALL.into_iter().filter(|stage| !stage.is_finish()) {
+            if self.is_ahead_of::<tables::SyncStage>(dependency)? {
+                break
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderFactory;
+    use reth_db::{
+        models::StoredBlockBodyIndices, test_utils::create_test_rw_db, transaction::DbTxMut,
+    };
+    use reth_primitives::{
+        stage::{CheckpointBlockRange, EntitiesCheckpoint, HeadersCheckpoint},
+        Header, MAINNET,
+    };
+
+    #[test]
+    fn headers_above_checkpoint_during_sync() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        // the headers of the gap 2..=4 are not downloaded yet
+        for number in [0, 1, 5, 6] {
+            let hash = H256::from_low_u64_be(number);
+            tx.put::<tables::CanonicalHeaders>(number, hash).unwrap();
+            tx.put::<tables::Headers>(number, Header { number, ..Default::default() }).unwrap();
+            tx.put::<tables::HeaderNumbers>(hash, number).unwrap();
+        }
+
+        let headers_checkpoint = HeadersCheckpoint {
+            block_range: CheckpointBlockRange { from: 1, to: 6 },
+            progress: EntitiesCheckpoint { processed: 4, total: 6 },
+        };
+        tx.put::<tables::SyncStage>(
+            StageId::Headers.to_string(),
+            StageCheckpoint::new(1).with_headers_stage_checkpoint(headers_checkpoint),
+        )
+        .unwrap();
+        assert!(provider
+            .check_stages_consistency([StageId::Headers], None)
+            .unwrap()
+            .is_consistent());
+
+        // without the progress of the sync, the headers above the checkpoint are inconsistent
+        tx.put::<tables::SyncStage>(StageId::Headers.to_string(), StageCheckpoint::new(1)).unwrap();
+        let report = provider.check_stages_consistency([StageId::Headers], None).unwrap();
+        assert_eq!(
+            report.inconsistencies.iter().map(|i| i.kind.clone()).collect::<Vec<_>>(),
+            vec![
+                InconsistencyKind::AheadOfCheckpoint { expected: Some(1), found: 6 },
+                InconsistencyKind::AheadOfCheckpoint { expected: Some(1), found: 6 },
+                InconsistencyKind::EntryCount { expected: 2, found: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn pruned_transaction_lookup() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        for block in 0..3 {
+            tx.put::<tables::BlockBodyIndices>(
+                block,
+                StoredBlockBodyIndices { first_tx_num: block * 2, tx_count: 2 },
+            )
+            .unwrap();
+        }
+        for stage in [StageId::Bodies, StageId::TransactionLookup] {
+            tx.put::<tables::SyncStage>(stage.to_string(), StageCheckpoint::new(2)).unwrap();
+        }
+        // only the lookups of the last block are left
+        for tx_number in 4..6 {
+            tx.put::<tables::TxHashNumber>(H256::from_low_u64_be(tx_number), tx_number).unwrap();
+        }
+
+        let check = |prune_mode| {
+            provider
+                .check_stages_consistency([StageId::TransactionLookup], prune_mode)
+                .unwrap()
+                .inconsistencies
+                .into_iter()
+                .map(|i| i.kind)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(check(None), vec![InconsistencyKind::EntryCount { expected: 6, found: 2 }]);
+        assert!(check(Some(PruneMode::Full)).is_empty());
+        assert!(check(Some(PruneMode::Distance(0))).is_empty());
+        assert!(check(Some(PruneMode::Before(2))).is_empty());
+        assert_eq!(
+            check(Some(PruneMode::Before(1))),
+            vec![InconsistencyKind::EntryCount { expected: 4, found: 2 }]
+        );
+
+        // the lookups of pruned blocks may still be present
+        for tx_number in 0..4 {
+            tx.put::<tables::TxHashNumber>(H256::from_low_u64_be(tx_number), tx_number).unwrap();
+        }
+        assert!(check(Some(PruneMode::Before(2))).is_empty());
+    }
+
+    #[test]
+    fn hashed_mismatches_are_capped() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        for _ in 0..MAX_HASHED_MISMATCHES * 2 {
+            tx.put::<tables::PlainAccountState>(Address::random(), Account::default()).unwrap();
+        }
+
+        let report = provider.check_stages_consistency([StageId::AccountHashing], None).unwrap();
+        assert_eq!(report.inconsistencies.len(), MAX_HASHED_MISMATCHES);
+        assert_eq!(report.fixes(), vec![ConsistencyFix::Drop(StageId::AccountHashing)]);
+    }
+
+    #[test]
+    fn senders_behind_checkpoint() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        tx.put::<tables::BlockBodyIndices>(
+            1,
+            StoredBlockBodyIndices { first_tx_num: 0, tx_count: 3 },
+        )
+        .unwrap();
+        tx.put::<tables::SyncStage>(StageId::Bodies.to_string(), StageCheckpoint::new(1)).unwrap();
+        tx.put::<tables::SyncStage>(StageId::SenderRecovery.to_string(), StageCheckpoint::new(1))
+            .unwrap();
+        tx.put::<tables::TxSenders>(0, Address::random()).unwrap();
+        tx.put::<tables::TxSenders>(1, Address::random()).unwrap();
+
+        let report = provider.check_stages_consistency([StageId::SenderRecovery], None).unwrap();
+        assert_eq!(
+            report.inconsistencies.iter().map(|i| i.kind.clone()).collect::<Vec<_>>(),
+            vec![
+                InconsistencyKind::BehindCheckpoint { expected: 2, found: Some(1) },
+                InconsistencyKind::EntryCount { expected: 3, found: 2 },
+            ]
+        );
+        assert_eq!(report.fixes(), vec![ConsistencyFix::Drop(StageId::SenderRecovery)]);
+    }
+
+    #[test]
+    fn hashed_state_mismatch() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let address = Address::random();
+        let account = Account { nonce: 1, ..Default::default() };
+        tx.put::<tables::PlainAccountState>(address, account).unwrap();
+        tx.put::<tables::HashedAccount>(
+            keccak256(address),
+            Account { nonce: 2, ..Default::default() },
+        )
+        .unwrap();
+
+        let report = provider.check_stages_consistency([StageId::AccountHashing], None).unwrap();
+        assert_eq!(
+            report.inconsistencies.iter().map(|i| i.kind.clone()).collect::<Vec<_>>(),
+            vec![InconsistencyKind::HashedAccount {
+                address,
+                plain: Some(account),
+                hashed: Some(Account { nonce: 2, ..Default::default() }),
+            }]
+        );
+
+        tx.put::<tables::HashedAccount>(keccak256(address), account).unwrap();
+        assert!(provider
+            .check_stages_consistency([StageId::AccountHashing], None)
+            .unwrap()
+            .is_consistent());
+    }
+}
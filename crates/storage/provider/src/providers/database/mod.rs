@@ -19,6 +19,11 @@ use tracing::trace;
 
 mod provider;
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+
+mod consistency;
+pub use consistency::{
+    ConsistencyFix, ConsistencyReport, Inconsistency, InconsistencyKind, CONSISTENCY_CHECKED_STAGES,
+};
 use reth_interfaces::db::LogLevel;
 
 /// A common provider that fetches data from a database.
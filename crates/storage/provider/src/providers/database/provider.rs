@@ -2,8 +2,9 @@ use crate::{
     post_state::StorageChangeset,
     traits::{AccountExtReader, BlockSource, ReceiptProvider, StageCheckpointWriter},
     AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    EvmEnvProvider, HashingWriter, HeaderProvider, HistoryWriter, PostState, ProviderError,
-    StageCheckpointReader, StorageReader, TransactionsProvider, WithdrawalsProvider,
+    ChainStateReader, ChainStateWriter, EvmEnvProvider, HashingWriter, HeaderProvider,
+    HistoryWriter, PostState, ProviderError, StageCheckpointReader, StorageReader,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -12,7 +13,8 @@ use reth_db::{
     database::{Database, DatabaseGAT},
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        ChainStateKey, ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers,
+        StoredBlockWithdrawals,
     },
     table::Table,
     tables,
@@ -33,6 +35,7 @@ use reth_revm_primitives::{
     env::{fill_block_env, fill_cfg_and_block_env, fill_cfg_env},
     primitives::{BlockEnv, CfgEnv, SpecId},
 };
+use reth_rlp::{Decodable, Encodable};
 use reth_trie::StateRoot;
 use std::{
     collections::{btree_map::Entry, BTreeMap, BTreeSet},
@@ -1228,6 +1231,61 @@ impl<'this, TX: DbTxMut<'this>> StageCheckpointWriter for DatabaseProvider<'this
     }
 }
 
+impl<'this, TX: DbTx<'this>> ChainStateReader for DatabaseProvider<'this, TX> {
+    fn last_finalized_block_hash(&self) -> Result<Option<BlockHash>> {
+        Ok(self.tx.get::<tables::ChainState>(ChainStateKey::LastFinalizedBlock)?)
+    }
+
+    fn last_safe_block_hash(&self) -> Result<Option<BlockHash>> {
+        Ok(self.tx.get::<tables::ChainState>(ChainStateKey::LastSafeBlock)?)
+    }
+
+    fn pending_blocks(&self) -> Result<Vec<SealedBlock>> {
+        self.tx
+            .cursor_read::<tables::PendingBlocks>()?
+            .walk(None)?
+            .map(|entry| {
+                let (_, encoded) = entry?;
+                let block = SealedBlock::decode(&mut encoded.as_slice())
+                    .map_err(|_| DatabaseError::DecodeError)?;
+                Ok(block)
+            })
+            .collect()
+    }
+}
+
+impl<'this, TX: DbTxMut<'this>> ChainStateWriter for DatabaseProvider<'this, TX> {
+    fn save_finalized_and_safe_block_hashes(
+        &self,
+        finalized: Option<BlockHash>,
+        safe: Option<BlockHash>,
+    ) -> Result<()> {
+        if let Some(hash) = finalized {
+            self.tx.put::<tables::ChainState>(ChainStateKey::LastFinalizedBlock, hash)?;
+        }
+        if let Some(hash) = safe {
+            self.tx.put::<tables::ChainState>(ChainStateKey::LastSafeBlock, hash)?;
+        }
+        Ok(())
+    }
+
+    fn update_pending_blocks(
+        &self,
+        inserted: Vec<SealedBlock>,
+        removed: Vec<BlockHash>,
+    ) -> Result<()> {
+        for hash in removed {
+            self.tx.delete::<tables::PendingBlocks>(hash, None)?;
+        }
+        for block in inserted {
+            let mut encoded = Vec::with_capacity(block.length());
+            block.encode(&mut encoded);
+            self.tx.put::<tables::PendingBlocks>(block.hash, encoded)?;
+        }
+        Ok(())
+    }
+}
+
 impl<'this, TX: DbTx<'this>> StorageReader for DatabaseProvider<'this, TX> {
     fn plainstate_storages(
         &self,
@@ -1,8 +1,8 @@
 use crate::{
     BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
-    PostStateDataProvider, ProviderError, ReceiptProvider, ReceiptProviderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChainStateReader, ChainStateWriter, EvmEnvProvider,
+    HeaderProvider, PostStateDataProvider, ProviderError, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateProviderBox, StateProviderFactory, TransactionsProvider,
     WithdrawalsProvider,
 };
@@ -72,16 +72,32 @@ where
 {
     /// Create a new provider using only the database and the tree, fetching the latest header from
     /// the database to initialize the provider.
+    ///
+    /// The finalized and safe blocks are restored from the last forkchoice state persisted in the
+    /// database, if any.
     pub fn new(database: ProviderFactory<DB>, tree: Tree) -> Result<Self> {
         let provider = database.provider()?;
         let best: ChainInfo = provider.chain_info()?;
-        match provider.header_by_number(best.best_number)? {
-            Some(header) => {
-                drop(provider);
-                Ok(Self::with_latest(database, tree, header.seal(best.best_hash)))
-            }
-            None => Err(Error::Provider(ProviderError::HeaderNotFound(best.best_number.into()))),
+        let Some(header) = provider.header_by_number(best.best_number)? else {
+            return Err(Error::Provider(ProviderError::HeaderNotFound(best.best_number.into())))
+        };
+
+        let sealed_header = |hash: Option<BlockHash>| -> Result<Option<SealedHeader>> {
+            let Some(hash) = hash else { return Ok(None) };
+            Ok(provider.header(&hash)?.map(|header| header.seal(hash)))
+        };
+        let finalized = sealed_header(provider.last_finalized_block_hash()?)?;
+        let safe = sealed_header(provider.last_safe_block_hash()?)?;
+        drop(provider);
+
+        let this = Self::with_latest(database, tree, header.seal(best.best_hash));
+        if let Some(finalized) = finalized {
+            this.chain_info.set_finalized(finalized);
         }
+        if let Some(safe) = safe {
+            this.chain_info.set_safe(safe);
+        }
+        Ok(this)
     }
 }
 
@@ -393,6 +409,52 @@ where
     }
 }
 
+impl<DB, Tree> ChainStateReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn last_finalized_block_hash(&self) -> Result<Option<BlockHash>> {
+        self.database.provider()?.last_finalized_block_hash()
+    }
+
+    fn last_safe_block_hash(&self) -> Result<Option<BlockHash>> {
+        self.database.provider()?.last_safe_block_hash()
+    }
+
+    fn pending_blocks(&self) -> Result<Vec<SealedBlock>> {
+        self.database.provider()?.pending_blocks()
+    }
+}
+
+impl<DB, Tree> ChainStateWriter for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn save_finalized_and_safe_block_hashes(
+        &self,
+        finalized: Option<BlockHash>,
+        safe: Option<BlockHash>,
+    ) -> Result<()> {
+        let provider = self.database.provider_rw()?;
+        provider.save_finalized_and_safe_block_hashes(finalized, safe)?;
+        provider.commit()?;
+        Ok(())
+    }
+
+    fn update_pending_blocks(
+        &self,
+        inserted: Vec<SealedBlock>,
+        removed: Vec<BlockHash>,
+    ) -> Result<()> {
+        let provider = self.database.provider_rw()?;
+        provider.update_pending_blocks(inserted, removed)?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
 impl<DB, Tree> EvmEnvProvider for BlockchainProvider<DB, Tree>
 where
     DB: Database,
@@ -1,21 +1,25 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    EvmEnvProvider, HeaderProvider, PostState, PostStateDataProvider, ReceiptProviderIdExt,
-    StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider, TransactionsProvider,
-    WithdrawalsProvider,
+    ChainSpecProvider, EvmEnvProvider, HeaderProvider, PostState, PostStateDataProvider,
+    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{
-    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, ChainInfo, Header, Receipt, SealedBlock, SealedHeader,
-    StorageKey, StorageValue, TransactionMeta, TransactionSigned, TxHash, TxNumber, H256, U256,
+    keccak256, proofs::genesis_state_root, Account, Address, Block, BlockHash, BlockHashOrNumber,
+    BlockId, BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, GenesisAccount,
+    Header, Receipt, SealedBlock, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    TransactionSigned, TxHash, TxNumber, H256, MAINNET, U256,
+};
+use reth_revm_primitives::{
+    env::fill_cfg_and_block_env,
+    primitives::{BlockEnv, CfgEnv},
 };
-use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::RangeBounds,
     sync::Arc,
 };
@@ -29,6 +33,8 @@ pub struct MockEthProvider {
     pub headers: Arc<Mutex<HashMap<H256, Header>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Blocks besides the best block whose state is the local account store
+    pub historical_states: Arc<Mutex<HashSet<H256>>>,
 }
 
 /// An extended account for local store
@@ -100,6 +106,12 @@ impl MockEthProvider {
         self.accounts.lock().insert(address, account);
     }
 
+    /// Serves the local account store as the state of the given block as well, e.g. as the parent
+    /// state of a block whose transactions are replayed.
+    pub fn add_historical_state(&self, hash: H256) {
+        self.historical_states.lock().insert(hash);
+    }
+
     /// Add account to local account store
     pub fn extend_accounts(&self, iter: impl IntoIterator<Item = (Address, ExtendedAccount)>) {
         for (address, account) in iter.into_iter() {
@@ -341,6 +353,12 @@ impl BlockReader for MockEthProvider {
     }
 }
 
+impl ChainSpecProvider for MockEthProvider {
+    fn chain_spec(&self) -> Arc<ChainSpec> {
+        MAINNET.clone()
+    }
+}
+
 impl BlockReaderIdExt for MockEthProvider {
     fn block_by_id(&self, id: BlockId) -> Result<Option<Block>> {
         match id {
@@ -375,8 +393,59 @@ impl AccountReader for MockEthProvider {
 }
 
 impl StateRootProvider for MockEthProvider {
-    fn state_root(&self, _post_state: PostState) -> Result<H256> {
-        todo!()
+    fn state_root(&self, post_state: PostState) -> Result<H256> {
+        let mut accounts = self
+            .accounts
+            .lock()
+            .iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, H256(value.to_be_bytes())))
+                    .collect::<HashMap<_, _>>();
+                (*address, (account.account, storage))
+            })
+            .collect::<HashMap<_, _>>();
+
+        for (address, storage) in post_state.storage() {
+            let (_, account_storage) = accounts.entry(*address).or_default();
+            if storage.wiped() {
+                account_storage.clear();
+            }
+            account_storage.extend(
+                storage
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (H256(slot.to_be_bytes()), H256(value.to_be_bytes()))),
+            );
+        }
+        for (address, account) in post_state.accounts() {
+            match account {
+                Some(account) => accounts.entry(*address).or_default().0 = *account,
+                None => {
+                    accounts.remove(address);
+                }
+            }
+        }
+
+        let mut alloc = HashMap::with_capacity(accounts.len());
+        for (address, (account, storage)) in accounts {
+            let code = match account.bytecode_hash {
+                Some(code_hash) => match post_state.bytecode(&code_hash) {
+                    Some(bytecode) => Some(bytecode.clone()),
+                    None => self.bytecode_by_hash(code_hash)?,
+                },
+                None => None,
+            };
+            let account = GenesisAccount::default()
+                .with_nonce(Some(account.nonce))
+                .with_balance(account.balance)
+                .with_code(code.map(|code| code.original_bytes().into()))
+                .with_storage(Some(storage));
+            alloc.insert(address, account);
+        }
+        Ok(genesis_state_root(&alloc))
     }
 }
 
@@ -410,40 +479,43 @@ impl StateProvider for MockEthProvider {
 impl EvmEnvProvider for MockEthProvider {
     fn fill_env_at(
         &self,
-        _cfg: &mut CfgEnv,
-        _block_env: &mut BlockEnv,
-        _at: BlockHashOrNumber,
+        cfg: &mut CfgEnv,
+        block_env: &mut BlockEnv,
+        at: BlockHashOrNumber,
     ) -> Result<()> {
-        unimplemented!()
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_env_with_header(cfg, block_env, &header)
     }
 
     fn fill_env_with_header(
         &self,
-        _cfg: &mut CfgEnv,
-        _block_env: &mut BlockEnv,
-        _header: &Header,
+        cfg: &mut CfgEnv,
+        block_env: &mut BlockEnv,
+        header: &Header,
     ) -> Result<()> {
-        unimplemented!()
+        let total_difficulty = self
+            .header_td_by_number(header.number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
+        fill_cfg_and_block_env(cfg, block_env, &self.chain_spec(), header, total_difficulty);
+        Ok(())
     }
 
-    fn fill_block_env_at(&self, _block_env: &mut BlockEnv, _at: BlockHashOrNumber) -> Result<()> {
-        unimplemented!()
+    fn fill_block_env_at(&self, block_env: &mut BlockEnv, at: BlockHashOrNumber) -> Result<()> {
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_block_env_with_header(block_env, &header)
     }
 
-    fn fill_block_env_with_header(
-        &self,
-        _block_env: &mut BlockEnv,
-        _header: &Header,
-    ) -> Result<()> {
-        unimplemented!()
+    fn fill_block_env_with_header(&self, block_env: &mut BlockEnv, header: &Header) -> Result<()> {
+        self.fill_env_with_header(&mut CfgEnv::default(), block_env, header)
     }
 
-    fn fill_cfg_env_at(&self, _cfg: &mut CfgEnv, _at: BlockHashOrNumber) -> Result<()> {
-        unimplemented!()
+    fn fill_cfg_env_at(&self, cfg: &mut CfgEnv, at: BlockHashOrNumber) -> Result<()> {
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_cfg_env_with_header(cfg, &header)
     }
 
-    fn fill_cfg_env_with_header(&self, _cfg: &mut CfgEnv, _header: &Header) -> Result<()> {
-        unimplemented!()
+    fn fill_cfg_env_with_header(&self, cfg: &mut CfgEnv, header: &Header) -> Result<()> {
+        self.fill_env_with_header(cfg, &mut BlockEnv::default(), header)
     }
 }
 
@@ -452,16 +524,22 @@ impl StateProviderFactory for MockEthProvider {
         Ok(Box::new(self.clone()))
     }
 
-    fn history_by_block_number(&self, _block: BlockNumber) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn history_by_block_number(&self, block: BlockNumber) -> Result<StateProviderBox<'_>> {
+        let hash = self.block_hash(block)?.ok_or(ProviderError::HeaderNotFound(block.into()))?;
+        self.history_by_block_hash(hash)
     }
 
-    fn history_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn history_by_block_hash(&self, block: BlockHash) -> Result<StateProviderBox<'_>> {
+        // only the latest state is tracked
+        if self.chain_info()?.best_hash != block && !self.historical_states.lock().contains(&block)
+        {
+            return Err(ProviderError::StateForHashNotFound(block).into())
+        }
+        Ok(Box::new(self.clone()))
     }
 
-    fn state_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn state_by_block_hash(&self, block: BlockHash) -> Result<StateProviderBox<'_>> {
+        self.history_by_block_hash(block)
     }
 
     fn pending(&self) -> Result<StateProviderBox<'_>> {
@@ -485,16 +563,16 @@ impl StateProviderFactory for Arc<MockEthProvider> {
         Ok(Box::new(self.clone()))
     }
 
-    fn history_by_block_number(&self, _block: BlockNumber) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn history_by_block_number(&self, block: BlockNumber) -> Result<StateProviderBox<'_>> {
+        StateProviderFactory::history_by_block_number(&**self, block)
     }
 
-    fn history_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn history_by_block_hash(&self, block: BlockHash) -> Result<StateProviderBox<'_>> {
+        StateProviderFactory::history_by_block_hash(&**self, block)
     }
 
-    fn state_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+    fn state_by_block_hash(&self, block: BlockHash) -> Result<StateProviderBox<'_>> {
+        StateProviderFactory::state_by_block_hash(&**self, block)
     }
 
     fn pending(&self) -> Result<StateProviderBox<'_>> {
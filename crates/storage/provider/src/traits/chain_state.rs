@@ -0,0 +1,35 @@
+use reth_interfaces::Result;
+use reth_primitives::{BlockHash, SealedBlock};
+
+/// The trait for fetching the forkchoice state received from the consensus layer and the
+/// non-canonical blocks of the blockchain tree, as persisted by the previous run.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ChainStateReader: Send + Sync {
+    /// Fetch the hash of the last finalized block.
+    fn last_finalized_block_hash(&self) -> Result<Option<BlockHash>>;
+
+    /// Fetch the hash of the last safe block.
+    fn last_safe_block_hash(&self) -> Result<Option<BlockHash>>;
+
+    /// Fetch the persisted sidechain and buffered blocks of the blockchain tree.
+    fn pending_blocks(&self) -> Result<Vec<SealedBlock>>;
+}
+
+/// The trait for persisting the forkchoice state received from the consensus layer and the
+/// non-canonical blocks of the blockchain tree.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ChainStateWriter: Send + Sync {
+    /// Save the hashes of the last finalized and safe blocks, skipping the ones that are `None`.
+    fn save_finalized_and_safe_block_hashes(
+        &self,
+        finalized: Option<BlockHash>,
+        safe: Option<BlockHash>,
+    ) -> Result<()>;
+
+    /// Insert and remove persisted sidechain and buffered blocks of the blockchain tree.
+    fn update_pending_blocks(
+        &self,
+        inserted: Vec<SealedBlock>,
+        removed: Vec<BlockHash>,
+    ) -> Result<()>;
+}
@@ -21,6 +21,9 @@ pub use evm_env::EvmEnvProvider;
 mod chain_info;
 pub use chain_info::CanonChainTracker;
 
+mod chain_state;
+pub use chain_state::{ChainStateReader, ChainStateWriter};
+
 mod header;
 pub use header::HeaderProvider;
 
@@ -90,9 +90,9 @@
 //!
 //! - `serde` (default): Enable serde support
 //! - `test-utils`: Export utilities for testing
-use crate::pool::PoolInner;
+use crate::{error::PoolError, pool::PoolInner};
 use aquamarine as _;
-use reth_primitives::{Address, TxHash, U256};
+use reth_primitives::{Address, BlockNumber, TxHash, H256, U256};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::Receiver;
@@ -108,8 +108,9 @@ pub use crate::{
     pool::{AllTransactionsEvents, PoolTransactionEvent, TransactionEvent, TransactionEvents},
     traits::{
         AllPoolTransactions, BestTransactions, BlockInfo, CanonicalStateUpdate, ChangedAccount,
-        NewTransactionEvent, PoolSize, PoolTransaction, PooledTransaction, PropagateKind,
-        PropagatedTransactions, TransactionOrigin, TransactionPool, TransactionPoolExt,
+        NewTransactionEvent, PoolBundle, PoolSize, PoolTransaction, PooledTransaction,
+        PropagateKind, PropagatedTransactions, TransactionOrigin, TransactionPool,
+        TransactionPoolExt,
     },
     validate::{
         EthTransactionValidator, TransactionValidationOutcome, TransactionValidator,
@@ -284,6 +285,48 @@ where
         Ok(transactions)
     }
 
+    async fn add_private_transactions(
+        &self,
+        transactions: Vec<Self::Transaction>,
+        max_block_number: BlockNumber,
+    ) -> PoolResult<Vec<PoolResult<TxHash>>> {
+        let validated = self.validate_all(TransactionOrigin::Private, transactions).await?;
+
+        let transactions =
+            self.pool.add_private_transactions(max_block_number, validated.into_values());
+        Ok(transactions)
+    }
+
+    async fn add_bundle(&self, mut bundle: PoolBundle<Self::Transaction>) -> PoolResult<H256> {
+        // the transactions are validated in order, so the first invalid one is reported
+        let outcomes = futures_util::future::join_all(
+            std::mem::take(&mut bundle.transactions)
+                .into_iter()
+                .map(|tx| self.validate(TransactionOrigin::Private, tx)),
+        )
+        .await;
+
+        for (_, outcome) in outcomes {
+            match outcome {
+                TransactionValidationOutcome::Valid { transaction, .. } => {
+                    bundle.transactions.push(transaction)
+                }
+                TransactionValidationOutcome::Invalid(transaction, err) => {
+                    return Err(PoolError::InvalidTransaction(*transaction.hash(), err))
+                }
+                TransactionValidationOutcome::Error(hash, err) => {
+                    return Err(PoolError::Other(hash, err))
+                }
+            }
+        }
+
+        Ok(self.pool.add_bundle(bundle))
+    }
+
+    fn bundles(&self, block_number: BlockNumber) -> Vec<Arc<PoolBundle<Self::Transaction>>> {
+        self.pool.bundles(block_number)
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
@@ -5,11 +5,11 @@
 
 use crate::{
     error::PoolError, AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo,
-    NewTransactionEvent, PoolResult, PoolSize, PoolTransaction, PooledTransaction,
+    NewTransactionEvent, PoolBundle, PoolResult, PoolSize, PoolTransaction, PooledTransaction,
     PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
     TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
-use reth_primitives::{Address, TxHash};
+use reth_primitives::{Address, BlockNumber, TxHash, H256};
 use std::{marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
@@ -69,6 +69,28 @@ impl TransactionPool for NoopTransactionPool {
             .collect())
     }
 
+    async fn add_private_transactions(
+        &self,
+        transactions: Vec<Self::Transaction>,
+        _max_block_number: BlockNumber,
+    ) -> PoolResult<Vec<PoolResult<TxHash>>> {
+        self.add_transactions(TransactionOrigin::Private, transactions).await
+    }
+
+    async fn add_bundle(&self, bundle: PoolBundle<Self::Transaction>) -> PoolResult<H256> {
+        let hash = bundle.hash;
+        Err(match bundle.transactions.into_iter().next() {
+            Some(transaction) => {
+                PoolError::Other(hash, Box::new(NoopInsertError::new(transaction)))
+            }
+            None => PoolError::DiscardedOnInsert(hash),
+        })
+    }
+
+    fn bundles(&self, _block_number: BlockNumber) -> Vec<Arc<PoolBundle<Self::Transaction>>> {
+        vec![]
+    }
+
     fn transaction_event_listener(&self, _tx_hash: TxHash) -> Option<TransactionEvents> {
         None
     }
@@ -74,7 +74,7 @@ use crate::{
         txpool::{SenderInfo, TxPool},
     },
     traits::{
-        AllPoolTransactions, BlockInfo, NewTransactionEvent, PoolSize, PoolTransaction,
+        AllPoolTransactions, BlockInfo, NewTransactionEvent, PoolBundle, PoolSize, PoolTransaction,
         PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
@@ -82,9 +82,9 @@ use crate::{
 };
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock};
-use reth_primitives::{Address, TxHash, H256};
+use reth_primitives::{Address, BlockNumber, TxHash, H256};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     sync::Arc,
     time::Instant,
@@ -122,6 +122,10 @@ pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
     pending_transaction_listener: Mutex<Vec<mpsc::Sender<TxHash>>>,
     /// Listeners for new transactions added to the pool.
     transaction_listener: Mutex<Vec<mpsc::Sender<NewTransactionEvent<T::Transaction>>>>,
+    /// Tracks the private transactions in the pool and the last block they're valid for.
+    private_transactions: RwLock<HashMap<TxHash, BlockNumber>>,
+    /// The bundles in the pool by the number of the block they target.
+    bundles: RwLock<BTreeMap<BlockNumber, Vec<Arc<PoolBundle<T::Transaction>>>>>,
 }
 
 // === impl PoolInner ===
@@ -140,6 +144,8 @@ where
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
+            private_transactions: Default::default(),
+            bundles: Default::default(),
             config,
         }
     }
@@ -227,16 +233,16 @@ where
         self.event_listener.write().subscribe_all()
     }
 
-    /// Returns hashes of _all_ transactions in the pool.
+    /// Returns hashes of _all_ transactions in the pool that can be propagated.
     pub(crate) fn pooled_transactions_hashes(&self) -> Vec<TxHash> {
         let pool = self.pool.read();
-        pool.all().hashes_iter().collect()
+        pool.all().transactions_iter().filter(|tx| tx.propagate).map(|tx| *tx.hash()).collect()
     }
 
-    /// Returns _all_ transactions in the pool.
+    /// Returns _all_ transactions in the pool that can be propagated.
     pub(crate) fn pooled_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let pool = self.pool.read();
-        pool.all().transactions_iter().collect()
+        pool.all().transactions_iter().filter(|tx| tx.propagate).collect()
     }
 
     /// Updates the entire pool after a new block was executed.
@@ -260,6 +266,49 @@ where
             changed_senders,
         );
         self.notify_on_new_state(outcome);
+        self.remove_expired_private_transactions(number);
+        self.remove_expired_bundles(number);
+    }
+
+    /// Removes all bundles that target a block up to the given block number, since the chain
+    /// already advanced past them.
+    fn remove_expired_bundles(&self, block_number: BlockNumber) {
+        let mut bundles = self.bundles.write();
+        let pending = bundles.split_off(&(block_number + 1));
+        let expired = std::mem::replace(&mut *bundles, pending);
+        if !expired.is_empty() {
+            debug!(target: "txpool", count = expired.values().map(Vec::len).sum::<usize>(), block_number, "removing expired bundles");
+        }
+    }
+
+    /// Removes all private transactions that can no longer be included, because the chain
+    /// advanced past their max block number.
+    fn remove_expired_private_transactions(&self, block_number: BlockNumber) {
+        let expired = {
+            let mut private = self.private_transactions.write();
+            if private.is_empty() {
+                return
+            }
+            let pool = self.pool.read();
+            let mut expired = Vec::new();
+            private.retain(|hash, max_block_number| {
+                if !pool.contains(hash) {
+                    // already mined or discarded
+                    return false
+                }
+                if *max_block_number <= block_number {
+                    expired.push(*hash);
+                    return false
+                }
+                true
+            });
+            expired
+        };
+
+        if !expired.is_empty() {
+            debug!(target: "txpool", count = expired.len(), block_number, "removing expired private transactions");
+            self.remove_transactions(expired);
+        }
     }
 
     /// Add a single validated transaction into the pool.
@@ -280,7 +329,7 @@ where
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
-                    propagate: false,
+                    propagate: !origin.is_private(),
                     timestamp: Instant::now(),
                     origin,
                     encoded_length,
@@ -289,9 +338,11 @@ where
                 let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
                 let hash = *added.hash();
 
-                // Notify about new pending transactions
+                // Notify about new pending transactions, private transactions are not announced
                 if let Some(pending_hash) = added.as_pending() {
-                    self.on_new_pending_transaction(pending_hash);
+                    if !origin.is_private() {
+                        self.on_new_pending_transaction(pending_hash);
+                    }
                 }
 
                 // Notify tx event listeners
@@ -358,6 +409,42 @@ where
             .collect()
     }
 
+    /// Adds all transactions in the iterator to the pool as [TransactionOrigin::Private]
+    /// transactions that are valid until the block with the given number, returning a list of
+    /// results.
+    pub fn add_private_transactions(
+        &self,
+        max_block_number: BlockNumber,
+        transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
+    ) -> Vec<PoolResult<TxHash>> {
+        let added = self.add_transactions(TransactionOrigin::Private, transactions);
+
+        let mut private = self.private_transactions.write();
+        for hash in added.iter().flatten() {
+            private.insert(*hash, max_block_number);
+        }
+
+        added
+    }
+
+    /// Adds the validated bundle to the pool, returning the hash of the bundle.
+    ///
+    /// A bundle that is already known is not added again.
+    pub fn add_bundle(&self, bundle: PoolBundle<T::Transaction>) -> H256 {
+        let hash = bundle.hash;
+        let mut bundles = self.bundles.write();
+        let target = bundles.entry(bundle.block_number).or_default();
+        if !target.iter().any(|known| known.hash == hash) {
+            target.push(Arc::new(bundle));
+        }
+        hash
+    }
+
+    /// Returns all bundles that target the block with the given number.
+    pub fn bundles(&self, block_number: BlockNumber) -> Vec<Arc<PoolBundle<T::Transaction>>> {
+        self.bundles.read().get(&block_number).cloned().unwrap_or_default()
+    }
+
     /// Notify all listeners about a new pending transaction.
     fn on_new_pending_transaction(&self, ready: &TxHash) {
         let mut transaction_listeners = self.pending_transaction_listener.lock();
@@ -432,22 +519,22 @@ where
         self.pool.read().best_transactions()
     }
 
-    /// Returns all transactions from the pending sub-pool
+    /// Returns all non-private transactions from the pending sub-pool
     pub(crate) fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.pool.read().pending_transactions()
+        without_private(self.pool.read().pending_transactions())
     }
 
-    /// Returns all transactions from parked pools
+    /// Returns all non-private transactions from parked pools
     pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.pool.read().queued_transactions()
+        without_private(self.pool.read().queued_transactions())
     }
 
-    /// Returns all transactions in the pool
+    /// Returns all non-private transactions in the pool
     pub(crate) fn all_transactions(&self) -> AllPoolTransactions<T::Transaction> {
         let pool = self.pool.read();
         AllPoolTransactions {
-            pending: pool.pending_transactions(),
-            queued: pool.queued_transactions(),
+            pending: without_private(pool.pending_transactions()),
+            queued: without_private(pool.queued_transactions()),
         }
     }
 
@@ -596,3 +683,104 @@ pub(crate) struct OnNewCanonicalStateOutcome {
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<TxHash>,
 }
+
+/// Removes all [TransactionOrigin::Private] transactions, which must not be exposed via RPC.
+fn without_private<T: PoolTransaction>(
+    mut transactions: Vec<Arc<ValidPoolTransaction<T>>>,
+) -> Vec<Arc<ValidPoolTransaction<T>>> {
+    transactions.retain(|tx| !tx.origin.is_private());
+    transactions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noop::NoopTransactionValidator,
+        test_utils::{MockOrdering, MockTransaction},
+    };
+    use reth_primitives::U256;
+
+    #[test]
+    fn private_transactions_are_not_exposed_and_expire() {
+        let pool = PoolInner::new(
+            NoopTransactionValidator::default(),
+            MockOrdering::default(),
+            Default::default(),
+        );
+        let mut pending = pool.add_pending_listener();
+
+        let tx = MockTransaction::legacy();
+        let outcome = TransactionValidationOutcome::Valid {
+            balance: U256::ZERO,
+            state_nonce: 0,
+            transaction: tx,
+        };
+        let hash =
+            pool.add_private_transactions(2, std::iter::once(outcome)).pop().unwrap().unwrap();
+
+        // private transactions are available for block production, but not for peers
+        assert!(pending.try_recv().is_err());
+        assert!(pool.pooled_transactions().is_empty());
+        assert!(pool.pooled_transactions_hashes().is_empty());
+        // nor for the `txpool` RPC namespace
+        assert!(pool.pending_transactions().is_empty());
+        assert!(pool.all_transactions().pending.is_empty());
+        assert_eq!(pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>(), vec![hash]);
+
+        let update = |number| CanonicalStateUpdate {
+            hash: H256::random(),
+            number,
+            pending_block_base_fee: 0,
+            changed_accounts: vec![],
+            mined_transactions: vec![],
+        };
+
+        pool.on_canonical_state_change(update(1));
+        assert!(pool.get(&hash).is_some());
+
+        pool.on_canonical_state_change(update(2));
+        assert!(pool.get(&hash).is_none());
+        assert!(pool.private_transactions.read().is_empty());
+    }
+
+    #[test]
+    fn bundles_are_not_pooled_and_expire() {
+        let pool = PoolInner::new(
+            NoopTransactionValidator::default(),
+            MockOrdering::default(),
+            Default::default(),
+        );
+
+        let bundle =
+            PoolBundle::new(vec![MockTransaction::legacy(), MockTransaction::eip1559()], 2);
+        let hash = pool.add_bundle(bundle.clone());
+        assert_eq!(hash, bundle.hash);
+        // a known bundle is not added twice
+        assert_eq!(pool.add_bundle(bundle), hash);
+
+        // bundles are only available to the payload builder of the block they target
+        assert_eq!(
+            pool.bundles(2).iter().map(|bundle| bundle.hash).collect::<Vec<_>>(),
+            vec![hash]
+        );
+        assert!(pool.bundles(1).is_empty());
+        assert!(pool.best_transactions().next().is_none());
+        assert!(pool.pooled_transactions().is_empty());
+
+        let update = |number| CanonicalStateUpdate {
+            hash: H256::random(),
+            number,
+            pending_block_base_fee: 0,
+            changed_accounts: vec![],
+            mined_transactions: vec![],
+        };
+
+        pool.on_canonical_state_change(update(1));
+        assert_eq!(pool.bundles(2).len(), 1);
+
+        pool.on_canonical_state_change(update(2));
+        assert!(pool.bundles(2).is_empty());
+        assert!(pool.bundles.read().is_empty());
+    }
+}
@@ -5,8 +5,9 @@ use crate::{
     AllTransactionsEvents,
 };
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, H256, U256,
+    keccak256, Address, BlockNumber, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId,
+    Transaction, TransactionKind, TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, H256,
+    U256,
 };
 use reth_rlp::Encodable;
 use std::{collections::HashMap, fmt, sync::Arc};
@@ -90,6 +91,61 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> PoolResult<Vec<PoolResult<TxHash>>>;
 
+    /// Adds an _unvalidated_ private transaction into the pool.
+    ///
+    /// This is the same as [TransactionPool::add_private_transactions] but for a single
+    /// transaction.
+    ///
+    /// Consumer: RPC
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: BlockNumber,
+    ) -> PoolResult<TxHash> {
+        self.add_private_transactions(vec![transaction], max_block_number)
+            .await?
+            .pop()
+            .expect("exists; qed")
+    }
+
+    /// Adds the given _unvalidated_ transactions into the pool with a
+    /// [TransactionOrigin::Private] origin.
+    ///
+    /// Private transactions are never announced or propagated to peers, but are still yielded by
+    /// [TransactionPool::best_transactions] and can therefore be included by the payload builder.
+    /// They are evicted from the pool once the canonical chain advanced past `max_block_number`.
+    ///
+    /// Returns a list of results.
+    ///
+    /// Consumer: RPC
+    async fn add_private_transactions(
+        &self,
+        transactions: Vec<Self::Transaction>,
+        max_block_number: BlockNumber,
+    ) -> PoolResult<Vec<PoolResult<TxHash>>>;
+
+    /// Adds the _unvalidated_ transactions of the given bundle to the pool.
+    ///
+    /// Unlike private transactions, the transactions of a bundle are not yielded by
+    /// [TransactionPool::best_transactions]: the payload builder includes a bundle atomically and
+    /// in order at the top of the block it targets, see [TransactionPool::bundles]. The bundle is
+    /// never announced or propagated to peers and is evicted once the canonical chain reached its
+    /// block number.
+    ///
+    /// Either all transactions of the bundle are valid and the bundle is added, or the bundle is
+    /// rejected with the error of the first invalid transaction.
+    ///
+    /// Returns the hash of the bundle.
+    ///
+    /// Consumer: RPC
+    async fn add_bundle(&self, bundle: PoolBundle<Self::Transaction>) -> PoolResult<H256>;
+
+    /// Returns all bundles that target the block with the given number, in the order they were
+    /// added.
+    ///
+    /// Consumer: Block production
+    fn bundles(&self, block_number: BlockNumber) -> Vec<Arc<PoolBundle<Self::Transaction>>>;
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -100,13 +156,15 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Returns a new Stream that yields transactions hashes for new ready transactions.
     ///
+    /// Note: this does not yield [TransactionOrigin::Private] transactions.
+    ///
     /// Consumer: RPC
     fn pending_transactions_listener(&self) -> Receiver<TxHash>;
 
     /// Returns a new stream that yields new valid transactions added to the pool.
     fn new_transactions_listener(&self) -> Receiver<NewTransactionEvent<Self::Transaction>>;
 
-    /// Returns the _hashes_ of all transactions in the pool.
+    /// Returns the _hashes_ of all transactions in the pool that can be propagated.
     ///
     /// Note: This returns a `Vec` but should guarantee that all hashes are unique.
     ///
@@ -118,7 +176,7 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: P2P
     fn pooled_transaction_hashes_max(&self, max: usize) -> Vec<TxHash>;
 
-    /// Returns the _full_ transaction objects all transactions in the pool.
+    /// Returns the _full_ transaction objects all transactions in the pool that can be propagated.
     ///
     /// Note: This returns a `Vec` but should guarantee that all transactions are unique.
     ///
@@ -144,6 +202,8 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// This is primarily used for the `txpool_` RPC namespace: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool> which distinguishes between `pending` and `queued` transactions, where `pending` are transactions ready for inclusion in the next block and `queued` are transactions that are ready for inclusion in future blocks.
     ///
+    /// Note: this does not include [TransactionOrigin::Private] transactions.
+    ///
     /// Consumer: RPC
     fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
@@ -151,6 +211,8 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// This and [Self::pending_transactions] are mutually exclusive.
     ///
+    /// Note: this does not include [TransactionOrigin::Private] transactions.
+    ///
     /// Consumer: RPC
     fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
@@ -159,6 +221,8 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// This is primarily used for the `txpool_` namespace: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool>
     ///
+    /// Note: this does not include [TransactionOrigin::Private] transactions.
+    ///
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
@@ -254,6 +318,73 @@ impl<T: PoolTransaction> Default for AllPoolTransactions<T> {
     }
 }
 
+/// A bundle of transactions that is included atomically and in order at the top of the block it
+/// targets, see [TransactionPool::add_bundle].
+#[derive(Debug, Clone)]
+pub struct PoolBundle<T> {
+    /// Hash of the bundle: keccak256 of the concatenated transaction hashes.
+    pub hash: H256,
+    /// The transactions of the bundle, in execution order.
+    pub transactions: Vec<T>,
+    /// Number of the block the bundle targets.
+    pub block_number: BlockNumber,
+    /// The minimum timestamp of the block the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of the block the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// Hashes of the transactions of the bundle that are allowed to revert.
+    pub reverting_tx_hashes: Vec<TxHash>,
+}
+
+// === impl PoolBundle ===
+
+impl<T: PoolTransaction> PoolBundle<T> {
+    /// Creates a new bundle of the given transactions that targets the block with the given
+    /// number.
+    pub fn new(transactions: Vec<T>, block_number: BlockNumber) -> Self {
+        let hash_bytes =
+            transactions.iter().flat_map(|tx| tx.hash().to_fixed_bytes()).collect::<Vec<_>>();
+        Self {
+            hash: keccak256(hash_bytes),
+            transactions,
+            block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+        }
+    }
+}
+
+impl<T> PoolBundle<T> {
+    /// Sets the timestamp bounds of the block the bundle can be included in.
+    pub fn with_timestamps(
+        mut self,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+    ) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<TxHash>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Returns `true` if the bundle can be included in a block with the given timestamp.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    pub fn may_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+}
+
 /// Represents a transaction that was propagated over the network.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct PropagatedTransactions(pub HashMap<TxHash, Vec<PropagateKind>>);
@@ -319,6 +450,12 @@ pub enum TransactionOrigin {
     /// This is usually considered an "untrusted" source, for example received from another in the
     /// network.
     External,
+    /// Transaction has been submitted privately from a local source.
+    ///
+    /// Private transactions are treated like local transactions, but are never propagated to
+    /// peers, for example transactions submitted via `eth_sendPrivateRawTransaction` or
+    /// `eth_sendBundle`.
+    Private,
 }
 
 // === impl TransactionOrigin ===
@@ -326,7 +463,12 @@ pub enum TransactionOrigin {
 impl TransactionOrigin {
     /// Whether the transaction originates from a local source.
     pub fn is_local(&self) -> bool {
-        matches!(self, TransactionOrigin::Local)
+        matches!(self, TransactionOrigin::Local | TransactionOrigin::Private)
+    }
+
+    /// Whether the transaction was submitted privately and must not be propagated.
+    pub fn is_private(&self) -> bool {
+        matches!(self, TransactionOrigin::Private)
     }
 }
 
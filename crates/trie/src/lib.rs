@@ -42,6 +42,10 @@ pub use errors::{StateRootError, StorageRootError};
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
 
+/// The parallel computation of the state root.
+mod parallel;
+pub use parallel::ParallelStateRoot;
+
 /// Buffer for trie updates.
 pub mod updates;
 
@@ -0,0 +1,272 @@
+use crate::{
+    hashed_cursor::{HashedCursorFactory, HashedPostState, HashedPostStateCursorFactory},
+    prefix_set::PrefixSet,
+    updates::TrieUpdates,
+    StateRoot, StateRootError, StorageRoot, StorageRootError,
+};
+use rayon::prelude::*;
+use reth_db::{database::Database, transaction::DbTx};
+use reth_primitives::H256;
+use std::collections::HashMap;
+
+/// ParallelStateRoot is used to compute the state root with the changed storage roots computed
+/// concurrently.
+///
+/// The storage roots of all accounts with changed storage are computed on the global rayon pool,
+/// each worker with its own read transaction. The precomputed roots are then fed into the walk of
+/// the account trie, which yields the same root and trie updates as [StateRoot].
+///
+/// All storage roots are computed against the snapshot of the database read by the walk of the
+/// account trie: the storage roots that a worker computed on a newer snapshot, because a change was
+/// committed in the meantime, are recomputed on the transaction of the account trie.
+#[derive(Debug)]
+pub struct ParallelStateRoot<'a, DB> {
+    /// The database to open read transactions from.
+    db: &'a DB,
+    /// The hashed post state on top of the database state, if any.
+    hashed_state: Option<&'a HashedPostState>,
+    /// A set of account prefixes that have changed.
+    changed_account_prefixes: PrefixSet,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<H256, PrefixSet>,
+}
+
+impl<'a, DB: Database> ParallelStateRoot<'a, DB> {
+    /// Create a new [ParallelStateRoot] instance.
+    pub fn new(db: &'a DB) -> Self {
+        Self {
+            db,
+            hashed_state: None,
+            changed_account_prefixes: PrefixSet::default(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+
+    /// Set the hashed post state on top of the database state.
+    ///
+    /// The changed account and storage prefixes are set to the ones of the post state.
+    pub fn with_hashed_post_state(mut self, hashed_state: &'a HashedPostState) -> Self {
+        let (account_prefixes, storage_prefixes) = hashed_state.construct_prefix_sets();
+        self.hashed_state = Some(hashed_state);
+        self.changed_account_prefixes = account_prefixes;
+        self.changed_storage_prefixes = storage_prefixes;
+        self
+    }
+
+    /// Set the changed account prefixes.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSet) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<H256, PrefixSet>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Computes the state root.
+    ///
+    /// # Returns
+    ///
+    /// The state root hash.
+    pub fn root(self) -> Result<H256, StateRootError> {
+        let (root, _) = self.calculate(false)?;
+        Ok(root)
+    }
+
+    /// Computes the state root collecting the updates in the process.
+    ///
+    /// # Returns
+    ///
+    /// The state root and the trie updates.
+    pub fn root_with_updates(self) -> Result<(H256, TrieUpdates), StateRootError> {
+        self.calculate(true)
+    }
+
+    fn calculate(self, retain_updates: bool) -> Result<(H256, TrieUpdates), StateRootError> {
+        tracing::debug!(target: "trie::parallel_state_root", accounts = self.changed_storage_prefixes.len(), "calculating storage roots");
+
+        // the transaction of the account trie, opened first to pin the snapshot of the computation
+        let tx = self.db.tx()?;
+        let snapshot = tx.id();
+
+        let worker_roots = self
+            .changed_storage_prefixes
+            .par_iter()
+            .map_init(
+                || self.db.tx(),
+                |worker_tx, (hashed_address, prefixes)| {
+                    let worker_tx =
+                        worker_tx.as_ref().map_err(|err| StateRootError::DB(err.clone()))?;
+                    if worker_tx.id() != snapshot {
+                        return Ok((*hashed_address, None))
+                    }
+                    let storage_root = self.storage_root(
+                        worker_tx,
+                        *hashed_address,
+                        prefixes.clone(),
+                        retain_updates,
+                    )?;
+                    Ok((*hashed_address, Some(storage_root)))
+                },
+            )
+            .collect::<Result<Vec<_>, StateRootError>>()?;
+
+        let mut storage_roots = HashMap::with_capacity(worker_roots.len());
+        let mut recomputed = 0usize;
+        for (hashed_address, storage_root) in worker_roots {
+            let storage_root = match storage_root {
+                Some(storage_root) => storage_root,
+                None => {
+                    recomputed += 1;
+                    let prefixes = self.changed_storage_prefixes[&hashed_address].clone();
+                    self.storage_root(&tx, hashed_address, prefixes, retain_updates)?
+                }
+            };
+            storage_roots.insert(hashed_address, storage_root);
+        }
+
+        tracing::debug!(target: "trie::parallel_state_root", recomputed, "calculating state root");
+
+        let state_root = StateRoot::new(&tx)
+            .with_changed_account_prefixes(self.changed_account_prefixes)
+            .with_changed_storage_prefixes(self.changed_storage_prefixes)
+            .with_precomputed_storage_roots(storage_roots);
+        let (root, updates) = match self.hashed_state {
+            Some(hashed_state) => {
+                let hashed_cursor_factory = HashedPostStateCursorFactory::new(&tx, hashed_state);
+                calculate_state_root(
+                    state_root.with_hashed_cursor_factory(&hashed_cursor_factory),
+                    retain_updates,
+                )?
+            }
+            None => calculate_state_root(state_root, retain_updates)?,
+        };
+
+        Ok((root, updates))
+    }
+
+    /// Computes the storage root of the given account with the given changed prefixes.
+    fn storage_root<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        hashed_address: H256,
+        prefixes: PrefixSet,
+        retain_updates: bool,
+    ) -> Result<(H256, usize, TrieUpdates), StorageRootError> {
+        let storage_root =
+            StorageRoot::new_hashed(tx, hashed_address).with_changed_prefixes(prefixes);
+        match self.hashed_state {
+            Some(hashed_state) => {
+                let hashed_cursor_factory = HashedPostStateCursorFactory::new(tx, hashed_state);
+                calculate_storage_root(
+                    storage_root.with_hashed_cursor_factory(&hashed_cursor_factory),
+                    retain_updates,
+                )
+            }
+            None => calculate_storage_root(storage_root, retain_updates),
+        }
+    }
+}
+
+fn calculate_state_root<'a, 'b, 'tx, TX, H>(
+    state_root: StateRoot<'a, 'b, TX, H>,
+    retain_updates: bool,
+) -> Result<(H256, TrieUpdates), StateRootError>
+where
+    TX: DbTx<'tx>,
+    H: HashedCursorFactory<'b>,
+{
+    if retain_updates {
+        state_root.root_with_updates()
+    } else {
+        Ok((state_root.root()?, TrieUpdates::default()))
+    }
+}
+
+fn calculate_storage_root<'a, 'b, 'tx, TX, H>(
+    storage_root: StorageRoot<'a, 'b, TX, H>,
+    retain_updates: bool,
+) -> Result<(H256, usize, TrieUpdates), StorageRootError>
+where
+    TX: DbTx<'tx>,
+    H: HashedCursorFactory<'b>,
+{
+    if retain_updates {
+        storage_root.root_with_updates()
+    } else {
+        Ok((storage_root.root()?, 0, TrieUpdates::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashed_cursor::HashedStorage;
+    use reth_db::{tables, test_utils::create_test_rw_db, transaction::DbTxMut};
+    use reth_primitives::{Account, StorageEntry, U256};
+
+    #[test]
+    fn parallel_root_matches_sequential_root() {
+        let db = create_test_rw_db();
+
+        // Seed the database and build the intermediate nodes of the tries.
+        let mut hashed_addresses = Vec::new();
+        {
+            let tx = db.tx_mut().unwrap();
+            for nonce in 0..100 {
+                let hashed_address = H256::random();
+                tx.put::<tables::HashedAccount>(
+                    hashed_address,
+                    Account { nonce, ..Default::default() },
+                )
+                .unwrap();
+                for value in 1..=nonce {
+                    tx.put::<tables::HashedStorage>(
+                        hashed_address,
+                        StorageEntry { key: H256::random(), value: U256::from(value) },
+                    )
+                    .unwrap();
+                }
+                hashed_addresses.push(hashed_address);
+            }
+            let (_, updates) = StateRoot::new(&tx).root_with_updates().unwrap();
+            updates.flush(&tx).unwrap();
+            tx.commit().unwrap();
+        }
+
+        // Change every other account and its storage on top of the database state.
+        let mut hashed_state = HashedPostState::default();
+        for (nonce, hashed_address) in hashed_addresses.iter().enumerate().step_by(2) {
+            hashed_state.accounts.insert(
+                *hashed_address,
+                Some(Account { nonce: nonce as u64 + 1, ..Default::default() }),
+            );
+            hashed_state.storages.insert(
+                *hashed_address,
+                HashedStorage {
+                    wiped: false,
+                    storage: (1..=10).map(|value| (H256::random(), U256::from(value))).collect(),
+                },
+            );
+        }
+
+        let tx = db.tx().unwrap();
+        let (account_prefixes, storage_prefixes) = hashed_state.construct_prefix_sets();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(&tx, &hashed_state);
+        let expected = StateRoot::new(&tx)
+            .with_hashed_cursor_factory(&hashed_cursor_factory)
+            .with_changed_account_prefixes(account_prefixes)
+            .with_changed_storage_prefixes(storage_prefixes)
+            .root_with_updates()
+            .unwrap();
+
+        let parallel = ParallelStateRoot::new(db.as_ref()).with_hashed_post_state(&hashed_state);
+        assert_eq!(parallel.root_with_updates().unwrap(), expected);
+
+        let parallel = ParallelStateRoot::new(db.as_ref()).with_hashed_post_state(&hashed_state);
+        assert_eq!(parallel.root().unwrap(), expected.0);
+    }
+}
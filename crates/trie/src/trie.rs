@@ -29,6 +29,9 @@ pub struct StateRoot<'a, 'b, TX, H> {
     /// A map containing storage changes with the hashed address as key and a set of storage key
     /// prefixes as the value.
     pub changed_storage_prefixes: HashMap<H256, PrefixSet>,
+    /// Storage roots of accounts that were computed ahead of the account trie walk, along with
+    /// the number of walked storage slots and the storage trie updates.
+    precomputed_storage_roots: HashMap<H256, (H256, usize, TrieUpdates)>,
     /// Previous intermediate state.
     previous_state: Option<IntermediateStateRootState>,
     /// The number of updates after which the intermediate progress should be returned.
@@ -48,6 +51,17 @@ impl<'a, 'b, TX, H> StateRoot<'a, 'b, TX, H> {
         self
     }
 
+    /// Set the storage roots that were computed ahead of the account trie walk.
+    ///
+    /// The storage roots of accounts that are not in the map are computed during the walk.
+    pub fn with_precomputed_storage_roots(
+        mut self,
+        storage_roots: HashMap<H256, (H256, usize, TrieUpdates)>,
+    ) -> Self {
+        self.precomputed_storage_roots = storage_roots;
+        self
+    }
+
     /// Set the threshold.
     pub fn with_threshold(mut self, threshold: u64) -> Self {
         self.threshold = threshold;
@@ -75,6 +89,7 @@ impl<'a, 'b, TX, H> StateRoot<'a, 'b, TX, H> {
             tx: self.tx,
             changed_account_prefixes: self.changed_account_prefixes,
             changed_storage_prefixes: self.changed_storage_prefixes,
+            precomputed_storage_roots: self.precomputed_storage_roots,
             threshold: self.threshold,
             previous_state: self.previous_state,
             hashed_cursor_factory,
@@ -92,6 +107,7 @@ where
             tx,
             changed_account_prefixes: PrefixSet::default(),
             changed_storage_prefixes: HashMap::default(),
+            precomputed_storage_roots: HashMap::default(),
             previous_state: None,
             threshold: 100_000,
             hashed_cursor_factory: tx,
@@ -205,6 +221,7 @@ where
     fn calculate(self, retain_updates: bool) -> Result<StateRootProgress, StateRootError> {
         tracing::debug!(target: "loader", "calculating state root");
         let mut trie_updates = TrieUpdates::default();
+        let mut precomputed_storage_roots = self.precomputed_storage_roots;
 
         let mut hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let mut trie_cursor =
@@ -278,23 +295,33 @@ where
                 // progress.
                 // TODO: We can consider introducing the TrieProgress::Progress/Complete
                 // abstraction inside StorageRoot, but let's give it a try as-is for now.
-                let storage_root_calculator = StorageRoot::new_hashed(self.tx, hashed_address)
-                    .with_hashed_cursor_factory(self.hashed_cursor_factory)
-                    .with_changed_prefixes(
-                        self.changed_storage_prefixes
-                            .get(&hashed_address)
-                            .cloned()
-                            .unwrap_or_default(),
-                    );
-
-                let storage_root = if retain_updates {
-                    let (root, storage_slots_walked, updates) =
-                        storage_root_calculator.root_with_updates()?;
-                    hashed_entries_walked += storage_slots_walked;
-                    trie_updates.extend(updates.into_iter());
+                let storage_root = if let Some((root, storage_slots_walked, updates)) =
+                    precomputed_storage_roots.remove(&hashed_address)
+                {
+                    if retain_updates {
+                        hashed_entries_walked += storage_slots_walked;
+                        trie_updates.extend(updates.into_iter());
+                    }
                     root
                 } else {
-                    storage_root_calculator.root()?
+                    let storage_root_calculator = StorageRoot::new_hashed(self.tx, hashed_address)
+                        .with_hashed_cursor_factory(self.hashed_cursor_factory)
+                        .with_changed_prefixes(
+                            self.changed_storage_prefixes
+                                .get(&hashed_address)
+                                .cloned()
+                                .unwrap_or_default(),
+                        );
+
+                    if retain_updates {
+                        let (root, storage_slots_walked, updates) =
+                            storage_root_calculator.root_with_updates()?;
+                        hashed_entries_walked += storage_slots_walked;
+                        trie_updates.extend(updates.into_iter());
+                        root
+                    } else {
+                        storage_root_calculator.root()?
+                    }
                 };
 
                 let account = EthAccount::from(account).with_storage_root(storage_root);
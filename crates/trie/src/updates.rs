@@ -22,7 +22,7 @@ pub enum TrieKey {
 }
 
 /// The operation to perform on the trie.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TrieOp {
     /// Delete the node entry.
     Delete,
@@ -38,7 +38,7 @@ impl TrieOp {
 }
 
 /// The aggregation of trie updates.
-#[derive(Debug, Default, Clone, Deref)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deref)]
 pub struct TrieUpdates {
     trie_operations: HashMap<TrieKey, TrieOp>,
 }
@@ -15,12 +15,19 @@ use std::{collections::BTreeMap, ffi::OsStr, fs, path::Path, sync::Arc};
 #[derive(Debug)]
 pub struct BlockchainTests {
     suite: String,
+    parallel_execution: bool,
 }
 
 impl BlockchainTests {
     /// Create a new handler for a subset of the blockchain test suite.
     pub fn new(suite: String) -> Self {
-        Self { suite }
+        Self { suite, parallel_execution: false }
+    }
+
+    /// Execute the blocks of all test cases optimistically in parallel.
+    pub fn with_parallel_execution(mut self) -> Self {
+        self.parallel_execution = true;
+        self
     }
 }
 
@@ -30,6 +37,12 @@ impl Suite for BlockchainTests {
     fn suite_name(&self) -> String {
         format!("BlockchainTests/{}", self.suite)
     }
+
+    fn load_case(&self, path: &Path) -> Result<Self::Case, Error> {
+        let mut case = BlockchainTestCase::load(path)?;
+        case.parallel_execution = self.parallel_execution;
+        Ok(case)
+    }
 }
 
 /// An Ethereum blockchain test.
@@ -37,6 +50,7 @@ impl Suite for BlockchainTests {
 pub struct BlockchainTestCase {
     tests: BTreeMap<String, BlockchainTest>,
     skip: bool,
+    parallel_execution: bool,
 }
 
 impl Case for BlockchainTestCase {
@@ -51,6 +65,7 @@ impl Case for BlockchainTestCase {
                     })
                 })?,
             skip: should_skip(path),
+            parallel_execution: false,
         })
     }
 
@@ -95,9 +110,12 @@ impl Case for BlockchainTestCase {
 
             // Call execution stage
             {
-                let mut stage = ExecutionStage::new_with_factory(reth_revm::Factory::new(
-                    Arc::new(case.network.clone().into()),
-                ));
+                let mut executor_factory =
+                    reth_revm::Factory::new(Arc::new(case.network.clone().into()));
+                if self.parallel_execution {
+                    executor_factory = executor_factory.with_parallel_execution();
+                }
+                let mut stage = ExecutionStage::new_with_factory(executor_factory);
 
                 tokio::runtime::Builder::new_current_thread()
                     .build()
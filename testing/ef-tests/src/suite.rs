@@ -2,7 +2,7 @@
 
 use crate::{
     case::{Case, Cases},
-    result::assert_tests_pass,
+    result::{assert_tests_pass, Error},
 };
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
@@ -21,6 +21,11 @@ pub trait Suite {
     /// - `BlockchainTests/TransitionTests`
     fn suite_name(&self) -> String;
 
+    /// Load the test case at the given path.
+    fn load_case(&self, path: &Path) -> Result<Self::Case, Error> {
+        Self::Case::load(path)
+    }
+
     /// Load an run each contained test case.
     ///
     /// # Note
@@ -35,7 +40,7 @@ pub trait Suite {
         let test_cases = find_all_files_with_extension(&suite_path, ".json")
             .into_iter()
             .map(|test_case_path| {
-                let case = Self::Case::load(&test_case_path).expect("test case should load");
+                let case = self.load_case(&test_case_path).expect("test case should load");
                 (test_case_path, case)
             })
             .collect();
@@ -76,4 +76,11 @@ mod general_state_tests {
     general_state_test!(vm_tests, VMTests);
 }
 
+/// Executes the blocks of all general state tests optimistically in parallel, which must yield the
+/// same post state as the sequential execution above.
+#[test]
+fn general_state_tests_parallel() {
+    BlockchainTests::new("GeneralStateTests".to_string()).with_parallel_execution().run();
+}
+
 // TODO: Add ValidBlocks and InvalidBlocks tests